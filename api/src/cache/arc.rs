@@ -0,0 +1,386 @@
+//! In-process L1 cache tier using the Adaptive Replacement Cache (ARC)
+//! eviction policy (Megiddo & Modha, FAST '03).
+//!
+//! ARC keeps four lists: `T1` (keys seen once recently), `T2` (keys seen at
+//! least twice), and the ghost lists `B1`/`B2` which hold only the keys of
+//! entries recently evicted from `T1`/`T2`. A target `p` splits the `c`-entry
+//! cache budget between `T1` and `T2`; hits in the ghost lists nudge `p`
+//! toward whichever of recency/frequency is currently paying off. This stops
+//! a burst of one-off framework listings (recency-heavy) from evicting
+//! frequently read requirement rows (frequency-heavy), which a plain LRU would
+//! not resist.
+//!
+//! All lists are intrusive doubly-linked lists over a single node arena, so
+//! `get`, `put` and `remove` are O(1); `remove_matching` scans every indexed
+//! key. Values live only in `T1`/`T2`; ghost nodes carry
+//! just their key.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Sentinel for "no node" in the arena-backed links.
+const NIL: usize = usize::MAX;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ListId {
+    /// Not currently on any list (a free arena slot).
+    Free,
+    T1,
+    T2,
+    B1,
+    B2,
+}
+
+struct Node {
+    key: String,
+    /// Present for resident (`T1`/`T2`) entries, `None` for ghosts.
+    value: Option<String>,
+    list: ListId,
+    prev: usize,
+    next: usize,
+}
+
+/// An intrusive doubly-linked list; `head` is the MRU end, `tail` the LRU end.
+#[derive(Clone, Copy)]
+struct List {
+    head: usize,
+    tail: usize,
+    len: usize,
+}
+
+impl List {
+    const fn new() -> Self {
+        List {
+            head: NIL,
+            tail: NIL,
+            len: 0,
+        }
+    }
+}
+
+struct Inner {
+    capacity: usize,
+    /// ARC target size for `T1`.
+    p: usize,
+    nodes: Vec<Node>,
+    free: Vec<usize>,
+    index: HashMap<String, usize>,
+    t1: List,
+    t2: List,
+    b1: List,
+    b2: List,
+}
+
+impl Inner {
+    fn list_mut(&mut self, id: ListId) -> &mut List {
+        match id {
+            ListId::T1 => &mut self.t1,
+            ListId::T2 => &mut self.t2,
+            ListId::B1 => &mut self.b1,
+            ListId::B2 => &mut self.b2,
+            ListId::Free => unreachable!("Free is not a tracked list"),
+        }
+    }
+
+    /// Unlink `idx` from whatever list currently owns it.
+    fn unlink(&mut self, idx: usize) {
+        let (prev, next, list) = {
+            let n = &self.nodes[idx];
+            (n.prev, n.next, n.list)
+        };
+        if prev != NIL {
+            self.nodes[prev].next = next;
+        }
+        if next != NIL {
+            self.nodes[next].prev = prev;
+        }
+        let l = self.list_mut(list);
+        if l.head == idx {
+            l.head = next;
+        }
+        if l.tail == idx {
+            l.tail = prev;
+        }
+        l.len -= 1;
+        let n = &mut self.nodes[idx];
+        n.prev = NIL;
+        n.next = NIL;
+        n.list = ListId::Free;
+    }
+
+    /// Push `idx` onto the MRU (head) end of `id`.
+    fn push_front(&mut self, idx: usize, id: ListId) {
+        let old_head = self.list_mut(id).head;
+        {
+            let n = &mut self.nodes[idx];
+            n.prev = NIL;
+            n.next = old_head;
+            n.list = id;
+        }
+        if old_head != NIL {
+            self.nodes[old_head].prev = idx;
+        }
+        let l = self.list_mut(id);
+        l.head = idx;
+        if l.tail == NIL {
+            l.tail = idx;
+        }
+        l.len += 1;
+    }
+
+    /// Move an existing node to the MRU end of `id`.
+    fn move_to_front(&mut self, idx: usize, id: ListId) {
+        self.unlink(idx);
+        self.push_front(idx, id);
+    }
+
+    fn alloc(&mut self, key: String, value: Option<String>) -> usize {
+        if let Some(idx) = self.free.pop() {
+            let n = &mut self.nodes[idx];
+            n.key = key;
+            n.value = value;
+            n.prev = NIL;
+            n.next = NIL;
+            n.list = ListId::Free;
+            idx
+        } else {
+            self.nodes.push(Node {
+                key,
+                value,
+                list: ListId::Free,
+                prev: NIL,
+                next: NIL,
+            });
+            self.nodes.len() - 1
+        }
+    }
+
+    /// Drop a node entirely: unlink, forget its key, and recycle the slot.
+    fn discard(&mut self, idx: usize) {
+        self.unlink(idx);
+        let key = std::mem::take(&mut self.nodes[idx].key);
+        self.index.remove(&key);
+        self.nodes[idx].value = None;
+        self.free.push(idx);
+    }
+
+    /// Turn the LRU entry of `from` into a ghost on `to`, dropping its value.
+    fn demote_lru_to_ghost(&mut self, from: ListId, to: ListId) {
+        let lru = self.list_mut(from).tail;
+        if lru == NIL {
+            return;
+        }
+        self.nodes[lru].value = None;
+        self.move_to_front(lru, to);
+    }
+
+    /// ARC `REPLACE`: evict one resident entry, demoting it to the matching
+    /// ghost list. `in_b2` is true when the triggering key was found in `B2`.
+    fn replace(&mut self, in_b2: bool) {
+        let t1_len = self.t1.len;
+        if t1_len >= 1 && ((in_b2 && t1_len == self.p) || t1_len > self.p) {
+            self.demote_lru_to_ghost(ListId::T1, ListId::B1);
+        } else {
+            self.demote_lru_to_ghost(ListId::T2, ListId::B2);
+        }
+    }
+}
+
+/// Thread-safe ARC cache mapping string keys to serialized string payloads.
+pub struct ArcCache {
+    inner: Mutex<Inner>,
+}
+
+impl ArcCache {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        ArcCache {
+            inner: Mutex::new(Inner {
+                capacity,
+                p: 0,
+                nodes: Vec::with_capacity(capacity),
+                free: Vec::new(),
+                index: HashMap::with_capacity(capacity * 2),
+                t1: List::new(),
+                t2: List::new(),
+                b1: List::new(),
+                b2: List::new(),
+            }),
+        }
+    }
+
+    /// Look up `key`, promoting a resident hit toward `T2`'s MRU end. Returns
+    /// the stored payload, or `None` for a miss (including ghost-only hits).
+    pub fn get(&self, key: &str) -> Option<String> {
+        let mut g = self.inner.lock().unwrap();
+        let idx = *g.index.get(key)?;
+        match g.nodes[idx].list {
+            ListId::T1 | ListId::T2 => {
+                // Seen again: it belongs in the frequency list.
+                g.move_to_front(idx, ListId::T2);
+                g.nodes[idx].value.clone()
+            }
+            _ => None,
+        }
+    }
+
+    /// Insert or refresh `key` with `value`, running the ARC bookkeeping that
+    /// adapts `p` on ghost hits and evicts to stay within capacity.
+    pub fn put(&self, key: &str, value: String) {
+        let mut g = self.inner.lock().unwrap();
+
+        if let Some(&idx) = g.index.get(key) {
+            match g.nodes[idx].list {
+                ListId::T1 | ListId::T2 => {
+                    g.nodes[idx].value = Some(value);
+                    g.move_to_front(idx, ListId::T2);
+                    return;
+                }
+                ListId::B1 => {
+                    // Ghost hit in B1: favor recency.
+                    let delta = (g.b2.len / g.b1.len.max(1)).max(1);
+                    g.p = (g.p + delta).min(g.capacity);
+                    g.replace(false);
+                    g.nodes[idx].value = Some(value);
+                    g.move_to_front(idx, ListId::T2);
+                    return;
+                }
+                ListId::B2 => {
+                    // Ghost hit in B2: favor frequency.
+                    let delta = (g.b1.len / g.b2.len.max(1)).max(1);
+                    g.p = g.p.saturating_sub(delta);
+                    g.replace(true);
+                    g.nodes[idx].value = Some(value);
+                    g.move_to_front(idx, ListId::T2);
+                    return;
+                }
+                ListId::Free => unreachable!("indexed node cannot be free"),
+            }
+        }
+
+        // Genuine miss: make room per ARC case IV, then insert into T1.
+        let c = g.capacity;
+        let l1 = g.t1.len + g.b1.len;
+        if l1 == c {
+            if g.t1.len < c {
+                // Drop the LRU ghost of B1, then evict a resident.
+                let lru = g.b1.tail;
+                if lru != NIL {
+                    g.discard(lru);
+                }
+                g.replace(false);
+            } else {
+                // B1 empty: evict the LRU resident of T1 outright.
+                let lru = g.t1.tail;
+                if lru != NIL {
+                    g.discard(lru);
+                }
+            }
+        } else if l1 < c {
+            let total = g.t1.len + g.t2.len + g.b1.len + g.b2.len;
+            if total >= c {
+                if total == 2 * c {
+                    let lru = g.b2.tail;
+                    if lru != NIL {
+                        g.discard(lru);
+                    }
+                }
+                g.replace(false);
+            }
+        }
+
+        let idx = g.alloc(key.to_string(), Some(value));
+        g.index.insert(key.to_string(), idx);
+        g.push_front(idx, ListId::T1);
+    }
+
+    /// Purge a single key from the resident and ghost tiers.
+    pub fn remove(&self, key: &str) {
+        let mut g = self.inner.lock().unwrap();
+        if let Some(&idx) = g.index.get(key) {
+            g.discard(idx);
+        }
+    }
+
+    /// Purge every resident/ghost key matching a simple glob (a literal prefix
+    /// optionally terminated by `*`), mirroring the remote `delete_pattern`.
+    pub fn remove_matching(&self, pattern: &str) {
+        let prefix = pattern.strip_suffix('*').unwrap_or(pattern);
+        let exact = !pattern.ends_with('*');
+        let victims: Vec<String> = {
+            let g = self.inner.lock().unwrap();
+            g.index
+                .keys()
+                .filter(|k| {
+                    if exact {
+                        k.as_str() == pattern
+                    } else {
+                        k.starts_with(prefix)
+                    }
+                })
+                .cloned()
+                .collect()
+        };
+        let mut g = self.inner.lock().unwrap();
+        for k in victims {
+            if let Some(&idx) = g.index.get(&k) {
+                g.discard(idx);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hit_then_miss() {
+        let cache = ArcCache::new(2);
+        cache.put("a", "1".into());
+        cache.put("b", "2".into());
+        assert_eq!(cache.get("a").as_deref(), Some("1"));
+        assert_eq!(cache.get("b").as_deref(), Some("2"));
+        assert_eq!(cache.get("missing"), None);
+    }
+
+    #[test]
+    fn evicts_lru_once_over_capacity() {
+        let cache = ArcCache::new(2);
+        cache.put("a", "1".into());
+        cache.put("b", "2".into());
+        cache.put("c", "3".into()); // T1 is full and B1 empty: "a" (LRU of T1) is dropped outright
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(cache.get("b").as_deref(), Some("2"));
+        assert_eq!(cache.get("c").as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn ghost_hit_grows_target_p() {
+        let cache = ArcCache::new(2);
+        cache.put("a", "1".into());
+        cache.put("b", "2".into());
+        assert_eq!(cache.get("a").as_deref(), Some("1")); // "a" -> T2
+        cache.put("c", "3".into()); // "b" (LRU of T1) -> B1
+        assert_eq!(cache.get("b"), None);
+        // Re-requesting the ghost key should adapt p toward recency and
+        // re-admit the key into the frequency list.
+        cache.put("b", "2b".into());
+        assert_eq!(cache.inner.lock().unwrap().p, 1);
+        assert_eq!(cache.get("b").as_deref(), Some("2b"));
+    }
+
+    #[test]
+    fn remove_and_pattern_purge() {
+        let cache = ArcCache::new(8);
+        cache.put("framework:1", "x".into());
+        cache.put("framework:2", "y".into());
+        cache.put("framework_reqs:1", "z".into());
+        cache.remove("framework:1");
+        assert_eq!(cache.get("framework:1"), None);
+        cache.remove_matching("framework:*");
+        assert_eq!(cache.get("framework:2"), None);
+        // A different prefix is untouched.
+        assert_eq!(cache.get("framework_reqs:1").as_deref(), Some("z"));
+    }
+}
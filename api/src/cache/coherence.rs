@@ -0,0 +1,158 @@
+//! Cross-node cache coherence over Redis pub/sub.
+//!
+//! The in-process L1 tier ([`super::arc::ArcCache`]) is per-process, so an
+//! invalidation on one API node would otherwise leave every other node serving
+//! a stale copy until TTL expiry. Each node subscribes to the
+//! [`INVALIDATION_CHANNEL`] and purges the named keys/patterns from its own L1
+//! tier; local invalidations are buffered and flushed as one batched message
+//! per tick so editing a framework with many requirements does not produce an
+//! invalidation storm. Messages carry the originating node id, which the
+//! publisher skips on receipt so it never re-processes its own writes.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::StreamExt;
+use redis::{aio::ConnectionManager, AsyncCommands, Client};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::arc::ArcCache;
+use crate::utils::AppResult;
+
+/// Channel every node publishes invalidations to and subscribes on.
+pub const INVALIDATION_CHANNEL: &str = "opengrc:cache:invalidate";
+
+/// How long rapid local invalidations are coalesced before a broadcast.
+const FLUSH_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct InvalidationMessage {
+    /// Id of the node that originated the invalidation.
+    node: String,
+    #[serde(default)]
+    keys: Vec<String>,
+    #[serde(default)]
+    patterns: Vec<String>,
+}
+
+#[derive(Default)]
+struct Pending {
+    keys: Vec<String>,
+    patterns: Vec<String>,
+}
+
+impl Pending {
+    fn is_empty(&self) -> bool {
+        self.keys.is_empty() && self.patterns.is_empty()
+    }
+}
+
+/// Owns this node's identity and the buffer of not-yet-broadcast invalidations.
+pub struct CoherenceBus {
+    node_id: Arc<str>,
+    pending: Arc<Mutex<Pending>>,
+}
+
+impl CoherenceBus {
+    /// Start the bus: spawn a subscriber that purges `l1` on remote
+    /// invalidations and a publisher that flushes the local buffer each tick.
+    pub fn start(client: Client, manager: ConnectionManager, l1: Arc<ArcCache>) -> Self {
+        let node_id: Arc<str> = Arc::from(Uuid::new_v4().to_string());
+        let pending = Arc::new(Mutex::new(Pending::default()));
+
+        {
+            let node_id = node_id.clone();
+            let l1 = l1.clone();
+            tokio::spawn(async move {
+                if let Err(e) = run_subscriber(client, l1, node_id).await {
+                    tracing::warn!("cache coherence subscriber stopped: {}", e);
+                }
+            });
+        }
+        {
+            let node_id = node_id.clone();
+            let pending = pending.clone();
+            tokio::spawn(async move {
+                run_publisher(manager, pending, node_id).await;
+            });
+        }
+
+        CoherenceBus { node_id, pending }
+    }
+
+    /// Queue one or more keys for broadcast to peer nodes.
+    pub fn note_keys<I: IntoIterator<Item = String>>(&self, keys: I) {
+        let mut p = self.pending.lock().unwrap();
+        p.keys.extend(keys);
+    }
+
+    /// Queue a glob pattern for broadcast to peer nodes.
+    pub fn note_pattern(&self, pattern: String) {
+        let mut p = self.pending.lock().unwrap();
+        p.patterns.push(pattern);
+    }
+}
+
+async fn run_publisher(mut conn: ConnectionManager, pending: Arc<Mutex<Pending>>, node_id: Arc<str>) {
+    let mut ticker = tokio::time::interval(FLUSH_INTERVAL);
+    loop {
+        ticker.tick().await;
+        let batch = {
+            let mut p = pending.lock().unwrap();
+            if p.is_empty() {
+                continue;
+            }
+            std::mem::take(&mut *p)
+        };
+
+        let msg = InvalidationMessage {
+            node: node_id.to_string(),
+            keys: batch.keys,
+            patterns: batch.patterns,
+        };
+        match serde_json::to_string(&msg) {
+            Ok(payload) => {
+                if let Err(e) = conn.publish::<_, _, ()>(INVALIDATION_CHANNEL, payload).await {
+                    tracing::warn!("failed to publish cache invalidation: {}", e);
+                }
+            }
+            Err(e) => tracing::warn!("failed to encode cache invalidation: {}", e),
+        }
+    }
+}
+
+async fn run_subscriber(client: Client, l1: Arc<ArcCache>, node_id: Arc<str>) -> AppResult<()> {
+    let mut pubsub = client.get_async_connection().await?.into_pubsub();
+    pubsub.subscribe(INVALIDATION_CHANNEL).await?;
+    let mut stream = pubsub.on_message();
+
+    while let Some(message) = stream.next().await {
+        let payload: String = match message.get_payload() {
+            Ok(p) => p,
+            Err(e) => {
+                tracing::warn!("malformed cache invalidation payload: {}", e);
+                continue;
+            }
+        };
+        let parsed: InvalidationMessage = match serde_json::from_str(&payload) {
+            Ok(m) => m,
+            Err(e) => {
+                tracing::warn!("undecodable cache invalidation message: {}", e);
+                continue;
+            }
+        };
+        // Skip our own broadcasts — the local tier was already purged inline.
+        if parsed.node.as_str() == &*node_id {
+            continue;
+        }
+        for key in &parsed.keys {
+            l1.remove(key);
+        }
+        for pattern in &parsed.patterns {
+            l1.remove_matching(pattern);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,199 @@
+//! Operator-friendly configuration for the cache layer.
+//!
+//! Capacity and expiry limits are expressed in human-readable units — byte
+//! suffixes for sizes (`256MB`, `2GB`) and duration suffixes for TTLs (`15m`,
+//! `2h`) — and parsed here into `u64` byte counts and [`Duration`]s that feed
+//! the L1 capacity, GC size budget, and the per-prefix TTLs applied on write.
+
+use crate::utils::{AppError, AppResult};
+use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use super::GcConfig;
+
+/// Assumed average serialized entry size, used to translate a byte-denominated
+/// size budget into the entry-counted L1 capacity / GC ceiling.
+const AVG_ENTRY_BYTES: u64 = 4 * 1024;
+
+/// Parsed cache settings, sourced from the environment via [`from_env`].
+///
+/// [`from_env`]: CacheConfig::from_env
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    /// Total size budget for cached data, in bytes. Drives the L1 capacity and
+    /// the GC eviction ceiling.
+    pub max_size: u64,
+    /// TTL for list entries (framework/requirement listings).
+    pub list_ttl: Duration,
+    /// TTL for individual framework entries.
+    pub framework_ttl: Duration,
+    /// TTL for individual requirement entries.
+    pub requirement_ttl: Duration,
+    /// Entries younger than this are never evicted by the collector.
+    pub min_age: Duration,
+    /// How often the background collector runs.
+    pub gc_interval: Duration,
+    /// When set, enables the hybrid disk tier rooted at this directory; large
+    /// payloads spill here instead of occupying the in-memory tier.
+    pub disk_path: Option<PathBuf>,
+    /// Serialized-payload weight (bytes) at or above which an entry is routed
+    /// to the disk tier rather than kept in RAM.
+    pub spill_threshold: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig {
+            max_size: 256 * 1024 * 1024,
+            list_ttl: Duration::from_secs(900),
+            framework_ttl: Duration::from_secs(3600),
+            requirement_ttl: Duration::from_secs(3600),
+            min_age: Duration::from_secs(3600),
+            gc_interval: Duration::from_secs(600),
+            disk_path: None,
+            spill_threshold: 64 * 1024,
+        }
+    }
+}
+
+impl CacheConfig {
+    /// Read cache settings from the environment, falling back to the defaults
+    /// for any unset variable. Malformed values surface as [`AppError`].
+    pub fn from_env() -> AppResult<Self> {
+        let d = CacheConfig::default();
+        Ok(CacheConfig {
+            max_size: env_size("CACHE_MAX_SIZE", d.max_size)?,
+            list_ttl: env_duration("CACHE_LIST_TTL", d.list_ttl)?,
+            framework_ttl: env_duration("CACHE_FRAMEWORK_TTL", d.framework_ttl)?,
+            requirement_ttl: env_duration("CACHE_REQUIREMENT_TTL", d.requirement_ttl)?,
+            min_age: env_duration("CACHE_GC_MIN_AGE", d.min_age)?,
+            gc_interval: env_duration("CACHE_GC_INTERVAL", d.gc_interval)?,
+            disk_path: env::var("CACHE_DISK_PATH").ok().map(PathBuf::from),
+            spill_threshold: env_size("CACHE_SPILL_THRESHOLD", d.spill_threshold)?,
+        })
+    }
+
+    /// Number of entries the in-process L1 tier should hold, derived from the
+    /// byte budget using an assumed average entry size.
+    pub fn l1_capacity(&self) -> usize {
+        ((self.max_size / AVG_ENTRY_BYTES).max(1)) as usize
+    }
+
+    /// Build the [`GcConfig`] implied by this configuration.
+    pub fn gc_config(&self) -> GcConfig {
+        GcConfig {
+            max_entries: Some(self.l1_capacity()),
+            min_age: self.min_age,
+            retention_window: Some(Duration::from_secs(86_400)),
+            interval: self.gc_interval,
+        }
+    }
+}
+
+fn env_size(var: &str, default: u64) -> AppResult<u64> {
+    match env::var(var) {
+        Ok(v) => parse_size(&v),
+        Err(_) => Ok(default),
+    }
+}
+
+fn env_duration(var: &str, default: Duration) -> AppResult<Duration> {
+    match env::var(var) {
+        Ok(v) => parse_duration(&v),
+        Err(_) => Ok(default),
+    }
+}
+
+/// Parse a human-readable size into bytes.
+///
+/// Accepts a `KB`/`MB`/`GB` suffix (case-insensitive, base 1024); a bare number
+/// is treated as bytes. Returns a [`AppError::BadRequest`] on malformed input.
+pub fn parse_size(input: &str) -> AppResult<u64> {
+    let s = input.trim();
+    if s.is_empty() {
+        return Err(bad(input, "size"));
+    }
+    let lower = s.to_lowercase();
+    let (num, mult) = if let Some(n) = lower.strip_suffix("gb") {
+        (n, 1024 * 1024 * 1024)
+    } else if let Some(n) = lower.strip_suffix("mb") {
+        (n, 1024 * 1024)
+    } else if let Some(n) = lower.strip_suffix("kb") {
+        (n, 1024)
+    } else if let Some(n) = lower.strip_suffix('b') {
+        (n, 1)
+    } else {
+        (lower.as_str(), 1)
+    };
+
+    num.trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(mult))
+        .ok_or_else(|| bad(input, "size"))
+}
+
+/// Parse a human-readable duration into a [`Duration`].
+///
+/// Accepts an `s`/`m`/`h`/`d` suffix (case-insensitive); a bare number is
+/// treated as seconds. Returns a [`AppError::BadRequest`] on malformed input.
+pub fn parse_duration(input: &str) -> AppResult<Duration> {
+    let s = input.trim();
+    if s.is_empty() {
+        return Err(bad(input, "duration"));
+    }
+    let lower = s.to_lowercase();
+    let (num, mult) = if let Some(n) = lower.strip_suffix('d') {
+        (n, 86_400)
+    } else if let Some(n) = lower.strip_suffix('h') {
+        (n, 3_600)
+    } else if let Some(n) = lower.strip_suffix('m') {
+        (n, 60)
+    } else if let Some(n) = lower.strip_suffix('s') {
+        (n, 1)
+    } else {
+        (lower.as_str(), 1)
+    };
+
+    num.trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(mult))
+        .map(Duration::from_secs)
+        .ok_or_else(|| bad(input, "duration"))
+}
+
+fn bad(input: &str, kind: &str) -> AppError {
+    AppError::BadRequest(format!("invalid cache {}: {:?}", kind, input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sizes() {
+        assert_eq!(parse_size("1024").unwrap(), 1024);
+        assert_eq!(parse_size("1KB").unwrap(), 1024);
+        assert_eq!(parse_size("256mb").unwrap(), 256 * 1024 * 1024);
+        assert_eq!(parse_size(" 2GB ").unwrap(), 2 * 1024 * 1024 * 1024);
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("30").unwrap(), Duration::from_secs(30));
+        assert_eq!(parse_duration("15m").unwrap(), Duration::from_secs(900));
+        assert_eq!(parse_duration("2H").unwrap(), Duration::from_secs(7200));
+        assert_eq!(parse_duration("1d").unwrap(), Duration::from_secs(86_400));
+    }
+
+    #[test]
+    fn rejects_malformed() {
+        assert!(parse_size("").is_err());
+        assert!(parse_size("lots").is_err());
+        assert!(parse_size("12xb").is_err());
+        assert!(parse_duration("soon").is_err());
+        assert!(parse_duration("5y").is_err());
+    }
+}
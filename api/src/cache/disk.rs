@@ -0,0 +1,124 @@
+//! Optional on-disk spill tier for large serialized payloads.
+//!
+//! Bulky `with_reqs:{framework_id}` graphs are expensive to rebuild but waste
+//! RAM in the in-process [`ArcCache`](super::arc::ArcCache). When a hybrid
+//! cache is configured, entries whose serialized weight reaches a threshold are
+//! routed here instead of the memory tier: the payload is written to a file in
+//! a dedicated directory and streamed back on demand. An in-memory index maps
+//! each cache key to its backing file so the tier participates in the same
+//! `delete`/tag/GC purge paths as the memory tier.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// A local-filesystem tier for large cache payloads.
+pub struct DiskTier {
+    dir: PathBuf,
+    /// Maps cache key -> backing file path (a file inside `dir`).
+    index: Mutex<HashMap<String, PathBuf>>,
+}
+
+impl DiskTier {
+    /// Open (creating if necessary) a disk tier rooted at `dir`.
+    pub fn open(dir: impl AsRef<Path>) -> std::io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)?;
+        Ok(DiskTier {
+            dir,
+            index: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Fetch a spilled payload, streaming it back from disk. Returns `None` on
+    /// a miss or if the backing file has disappeared.
+    pub fn get(&self, key: &str) -> Option<String> {
+        let path = self.index.lock().unwrap().get(key).cloned()?;
+        std::fs::read_to_string(path).ok()
+    }
+
+    /// Spill `value` to disk under `key`, replacing any prior payload.
+    pub fn put(&self, key: &str, value: &str) {
+        let path = self.dir.join(Self::file_name(key));
+        if std::fs::write(&path, value).is_ok() {
+            self.index.lock().unwrap().insert(key.to_string(), path);
+        }
+    }
+
+    /// Remove a single key's spilled payload, if present.
+    pub fn remove(&self, key: &str) {
+        if let Some(path) = self.index.lock().unwrap().remove(key) {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    /// Remove every spilled key matching a simple glob (a literal prefix
+    /// optionally terminated by `*`), mirroring the remote `delete_pattern`.
+    pub fn remove_matching(&self, pattern: &str) {
+        let prefix = pattern.strip_suffix('*').unwrap_or(pattern);
+        let exact = !pattern.ends_with('*');
+        let victims: Vec<(String, PathBuf)> = {
+            let index = self.index.lock().unwrap();
+            index
+                .iter()
+                .filter(|(k, _)| {
+                    if exact {
+                        k.as_str() == pattern
+                    } else {
+                        k.starts_with(prefix)
+                    }
+                })
+                .map(|(k, p)| (k.clone(), p.clone()))
+                .collect()
+        };
+        let mut index = self.index.lock().unwrap();
+        for (key, path) in victims {
+            index.remove(&key);
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    /// Map a cache key to a filesystem-safe file name via a stable hash, so
+    /// arbitrary `:`-delimited keys never collide with path separators.
+    fn file_name(key: &str) -> String {
+        // FNV-1a over the key bytes: cheap, stable, no external dependency.
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for b in key.as_bytes() {
+            hash ^= *b as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        format!("{:016x}.blob", hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("opengrc-disktier-{}", name));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn round_trips_and_removes() {
+        let tier = DiskTier::open(scratch("round")).unwrap();
+        tier.put("framework:with_reqs:1", "bulky");
+        assert_eq!(tier.get("framework:with_reqs:1").as_deref(), Some("bulky"));
+        tier.remove("framework:with_reqs:1");
+        assert_eq!(tier.get("framework:with_reqs:1"), None);
+    }
+
+    #[test]
+    fn pattern_purge_is_prefix_scoped() {
+        let tier = DiskTier::open(scratch("pattern")).unwrap();
+        tier.put("framework:with_reqs:1", "a");
+        tier.put("framework:with_reqs:2", "b");
+        tier.put("requirement:9", "c");
+        tier.remove_matching("framework:*");
+        assert_eq!(tier.get("framework:with_reqs:1"), None);
+        assert_eq!(tier.get("framework:with_reqs:2"), None);
+        assert_eq!(tier.get("requirement:9").as_deref(), Some("c"));
+    }
+}
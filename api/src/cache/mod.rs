@@ -1,35 +1,263 @@
 use crate::utils::AppResult;
 use redis::{aio::ConnectionManager, AsyncCommands, Client};
 use serde::{de::DeserializeOwned, Serialize};
-use std::time::Duration;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+mod arc;
+mod coherence;
+mod config;
+mod disk;
+
+use arc::ArcCache;
+use coherence::CoherenceBus;
+use disk::DiskTier;
+pub use config::{parse_duration, parse_size, CacheConfig};
+
+/// Capacity (in entries) of the in-process L1 tier that fronts Redis.
+const L1_CAPACITY: usize = 1024;
+
+/// Sorted set scoring every data key by its last-access unix timestamp.
+const LASTUSE_ZSET: &str = "cache:lastuse";
+
+/// Policy driving background garbage collection of aged-out data keys.
+#[derive(Debug, Clone, Copy)]
+pub struct GcConfig {
+    /// Hard ceiling on the number of tracked data keys; `None` is unlimited.
+    pub max_entries: Option<usize>,
+    /// Entries younger than this are never evicted, even when over budget.
+    pub min_age: Duration,
+    /// Entries idle longer than this are evicted regardless of budget; `None`
+    /// disables idle-based eviction.
+    pub retention_window: Option<Duration>,
+    /// How often the background collector runs.
+    pub interval: Duration,
+}
+
+impl Default for GcConfig {
+    fn default() -> Self {
+        GcConfig {
+            max_entries: None,
+            min_age: Duration::from_secs(3600),
+            retention_window: Some(Duration::from_secs(86_400)),
+            interval: Duration::from_secs(600),
+        }
+    }
+}
+
+/// In-memory buffer of last-access timestamps, flushed to Redis in one batch to
+/// keep the read path free of extra round-trips.
+type DeferredLastUse = Mutex<HashMap<String, i64>>;
 
 #[derive(Clone)]
 pub struct CacheClient {
     manager: ConnectionManager,
+    /// In-process ARC tier; serialized payloads are shared verbatim with Redis.
+    l1: Arc<ArcCache>,
+    /// Broadcasts local invalidations so peer nodes purge their own L1 tiers.
+    coherence: Arc<CoherenceBus>,
+    /// Optional hybrid disk tier holding payloads above `spill_threshold`.
+    disk: Option<Arc<DiskTier>>,
+    /// Serialized weight (bytes) at/above which a value spills to `disk`.
+    spill_threshold: usize,
+    /// Buffered last-use timestamps awaiting a batched flush.
+    last_use: Arc<DeferredLastUse>,
+    gc: GcConfig,
+    /// Operator-configured TTLs applied when entries are written.
+    ttls: CacheTtls,
+}
+
+/// Per-prefix TTLs, tuned independently via [`CacheConfig`].
+#[derive(Debug, Clone, Copy)]
+struct CacheTtls {
+    list: Duration,
+    framework: Duration,
+    requirement: Duration,
+}
+
+impl Default for CacheTtls {
+    fn default() -> Self {
+        CacheTtls {
+            list: Duration::from_secs(900),
+            framework: Duration::from_secs(3600),
+            requirement: Duration::from_secs(3600),
+        }
+    }
 }
 
 impl CacheClient {
     pub async fn new(redis_url: &str) -> AppResult<Self> {
+        Self::with_l1_capacity(redis_url, L1_CAPACITY).await
+    }
+
+    /// Build a client with an explicitly sized L1 tier.
+    pub async fn with_l1_capacity(redis_url: &str, l1_capacity: usize) -> AppResult<Self> {
+        Self::build(redis_url, l1_capacity, GcConfig::default()).await
+    }
+
+    /// Build a client from an operator-supplied [`CacheConfig`], deriving the
+    /// L1 capacity, GC budget, and per-prefix TTLs from its human-readable
+    /// size/duration settings.
+    pub async fn with_config(redis_url: &str, config: &CacheConfig) -> AppResult<Self> {
+        let ttls = CacheTtls {
+            list: config.list_ttl,
+            framework: config.framework_ttl,
+            requirement: config.requirement_ttl,
+        };
+
+        // Enable the hybrid disk tier only when an operator configured a path.
+        let (disk, spill_threshold) = match &config.disk_path {
+            Some(path) => {
+                let tier = DiskTier::open(path).map_err(|e| {
+                    crate::utils::AppError::InternalServerError(format!(
+                        "Failed to open cache disk tier at {}: {}",
+                        path.display(),
+                        e
+                    ))
+                })?;
+                (Some(Arc::new(tier)), config.spill_threshold as usize)
+            }
+            None => (None, usize::MAX),
+        };
+
+        Self::assemble(
+            redis_url,
+            config.l1_capacity(),
+            config.gc_config(),
+            ttls,
+            disk,
+            spill_threshold,
+        )
+        .await
+    }
+
+    /// Build a client with an explicit L1 size and GC policy.
+    pub async fn build(redis_url: &str, l1_capacity: usize, gc: GcConfig) -> AppResult<Self> {
+        Self::assemble(
+            redis_url,
+            l1_capacity,
+            gc,
+            CacheTtls::default(),
+            None,
+            usize::MAX,
+        )
+        .await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn assemble(
+        redis_url: &str,
+        l1_capacity: usize,
+        gc: GcConfig,
+        ttls: CacheTtls,
+        disk: Option<Arc<DiskTier>>,
+        spill_threshold: usize,
+    ) -> AppResult<Self> {
         let client = Client::open(redis_url)?;
-        let manager = ConnectionManager::new(client).await?;
+        let manager = ConnectionManager::new(client.clone()).await?;
+        let l1 = Arc::new(ArcCache::new(l1_capacity));
+        let coherence = Arc::new(CoherenceBus::start(client, manager.clone(), l1.clone()));
+
+        let cache = Self {
+            manager,
+            l1,
+            coherence,
+            disk,
+            spill_threshold,
+            last_use: Arc::new(Mutex::new(HashMap::new())),
+            gc,
+            ttls,
+        };
 
-        Ok(Self { manager })
+        cache.spawn_gc();
+        Ok(cache)
+    }
+
+    /// TTL to apply to list entries (framework/requirement listings).
+    pub fn list_ttl(&self) -> Duration {
+        self.ttls.list
+    }
+
+    /// TTL to apply to individual framework entries.
+    pub fn framework_ttl(&self) -> Duration {
+        self.ttls.framework
+    }
+
+    /// TTL to apply to individual requirement entries.
+    pub fn requirement_ttl(&self) -> Duration {
+        self.ttls.requirement
     }
 
     pub async fn get<T: DeserializeOwned>(&self, key: &str) -> AppResult<Option<T>> {
+        // Consult the local tiers (RAM, then any disk spill) before the network.
+        if let Some(hit) = self.local_get(key) {
+            self.touch(key);
+            let deserialized = serde_json::from_str(&hit)
+                .map_err(|e| crate::utils::AppError::InternalServerError(format!("Failed to deserialize cache value: {}", e)))?;
+            return Ok(Some(deserialized));
+        }
+
         let mut conn = self.manager.clone();
         let value: Option<String> = conn.get(key).await?;
 
         match value {
             Some(v) => {
+                self.touch(key);
                 let deserialized = serde_json::from_str(&v)
                     .map_err(|e| crate::utils::AppError::InternalServerError(format!("Failed to deserialize cache value: {}", e)))?;
+                // Backfill the local tier so the next read stays off the network,
+                // routing bulky payloads to disk rather than RAM.
+                self.local_put(key, v);
                 Ok(Some(deserialized))
             }
             None => Ok(None),
         }
     }
 
+    /// Read a key from the local tiers: RAM first, then any disk spill.
+    fn local_get(&self, key: &str) -> Option<String> {
+        if let Some(hit) = self.l1.get(key) {
+            return Some(hit);
+        }
+        self.disk.as_ref().and_then(|d| d.get(key))
+    }
+
+    /// Store a payload in the local tier, routing by weight: entries at or
+    /// above `spill_threshold` go to the disk tier (when configured), smaller
+    /// ones stay in the in-memory ARC tier. The other tier is purged so a key
+    /// never lingers in both.
+    fn local_put(&self, key: &str, value: String) {
+        match &self.disk {
+            Some(disk) if value.len() >= self.spill_threshold => {
+                self.l1.remove(key);
+                disk.put(key, &value);
+            }
+            _ => {
+                if let Some(disk) = &self.disk {
+                    disk.remove(key);
+                }
+                self.l1.put(key, value);
+            }
+        }
+    }
+
+    /// Purge a key from every local tier.
+    fn local_remove(&self, key: &str) {
+        self.l1.remove(key);
+        if let Some(disk) = &self.disk {
+            disk.remove(key);
+        }
+    }
+
+    /// Purge every local-tier key matching a glob from both RAM and disk.
+    fn local_remove_matching(&self, pattern: &str) {
+        self.l1.remove_matching(pattern);
+        if let Some(disk) = &self.disk {
+            disk.remove_matching(pattern);
+        }
+    }
+
     pub async fn set<T: Serialize>(
         &self,
         key: &str,
@@ -41,20 +269,100 @@ impl CacheClient {
             .map_err(|e| crate::utils::AppError::InternalServerError(format!("Failed to serialize cache value: {}", e)))?;
 
         if let Some(ttl) = ttl {
-            conn.set_ex::<_, _, ()>(key, serialized, ttl.as_secs()).await?;
+            conn.set_ex::<_, _, ()>(key, &serialized, ttl.as_secs()).await?;
         } else {
-            conn.set::<_, _, ()>(key, serialized).await?;
+            conn.set::<_, _, ()>(key, &serialized).await?;
         }
 
+        self.local_put(key, serialized);
+        self.touch(key);
         Ok(())
     }
 
     pub async fn delete(&self, key: &str) -> AppResult<()> {
         let mut conn = self.manager.clone();
-        conn.del::<_, ()>(key).await?;
+        self.last_use.lock().unwrap().remove(key);
+        redis::pipe()
+            .del(key)
+            .ignore()
+            .zrem(LASTUSE_ZSET, key)
+            .ignore()
+            .del(Self::key_tags_key(key))
+            .ignore()
+            .query_async::<_, ()>(&mut conn)
+            .await?;
+        self.local_remove(key);
+        self.coherence.note_keys([key.to_string()]);
         Ok(())
     }
 
+    /// Store a value and register it under one or more invalidation tags.
+    ///
+    /// Each tag is backed by a Redis Set (`tag:<tag>`) holding the member keys,
+    /// so [`invalidate_tag`](Self::invalidate_tag) can bust a whole logical
+    /// group in a bounded, membership-driven sweep instead of an O(N) key scan.
+    pub async fn set_tagged<T: Serialize>(
+        &self,
+        key: &str,
+        value: &T,
+        ttl: Option<Duration>,
+        tags: &[String],
+    ) -> AppResult<()> {
+        self.set(key, value, ttl).await?;
+
+        if tags.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = self.manager.clone();
+        let mut pipe = redis::pipe();
+        for tag in tags {
+            pipe.sadd(Self::tag_key(tag), key).ignore();
+            // Reverse index so GC can clean dangling memberships when it
+            // evicts a data key out from under its tags.
+            pipe.sadd(Self::key_tags_key(key), tag).ignore();
+        }
+        pipe.query_async::<_, ()>(&mut conn).await?;
+        Ok(())
+    }
+
+    /// Invalidate every key registered under `tag`, then drop the tag set.
+    ///
+    /// `SMEMBERS` yields the member keys, a single pipelined `DEL` removes them
+    /// all, and a final `DEL` discards the now-empty tag set.
+    pub async fn invalidate_tag(&self, tag: &str) -> AppResult<()> {
+        let mut conn = self.manager.clone();
+        let tag_key = Self::tag_key(tag);
+        let members: Vec<String> = conn.smembers(&tag_key).await?;
+
+        let mut pipe = redis::pipe();
+        if !members.is_empty() {
+            pipe.del(&members).ignore();
+            pipe.zrem(LASTUSE_ZSET, &members).ignore();
+            for member in &members {
+                pipe.del(Self::key_tags_key(member)).ignore();
+            }
+        }
+        pipe.del(&tag_key).ignore();
+        pipe.query_async::<_, ()>(&mut conn).await?;
+
+        // Drop the same members from the in-process tier so this node stops
+        // serving them immediately, and broadcast so peers do too.
+        for member in &members {
+            self.local_remove(member);
+        }
+        self.coherence.note_keys(members);
+        Ok(())
+    }
+
+    fn tag_key(tag: &str) -> String {
+        format!("tag:{}", tag)
+    }
+
+    fn key_tags_key(key: &str) -> String {
+        format!("keytags:{}", key)
+    }
+
     pub async fn delete_pattern(&self, pattern: &str) -> AppResult<()> {
         let mut conn = self.manager.clone();
         let keys: Vec<String> = conn.keys(pattern).await?;
@@ -63,6 +371,8 @@ impl CacheClient {
             conn.del::<_, ()>(keys).await?;
         }
 
+        self.local_remove_matching(pattern);
+        self.coherence.note_pattern(pattern.to_string());
         Ok(())
     }
 
@@ -83,6 +393,130 @@ impl CacheClient {
         conn.expire::<_, ()>(key, ttl.as_secs() as i64).await?;
         Ok(())
     }
+
+    // ==================== Last-use tracking & GC ====================
+
+    /// Record a deferred last-access timestamp for `key`. Cheap and in-memory;
+    /// the value is persisted later by [`flush_last_use`](Self::flush_last_use).
+    fn touch(&self, key: &str) {
+        self.last_use.lock().unwrap().insert(key.to_string(), now_unix());
+    }
+
+    /// Flush buffered last-use timestamps to the `cache:lastuse` sorted set in
+    /// a single pipelined `ZADD`, clearing the buffer.
+    pub async fn flush_last_use(&self) -> AppResult<()> {
+        let pending: Vec<(String, i64)> = {
+            let mut map = self.last_use.lock().unwrap();
+            if map.is_empty() {
+                return Ok(());
+            }
+            map.drain().collect()
+        };
+
+        let mut conn = self.manager.clone();
+        let mut pipe = redis::pipe();
+        for (key, ts) in &pending {
+            pipe.zadd(LASTUSE_ZSET, key, *ts).ignore();
+        }
+        pipe.query_async::<_, ()>(&mut conn).await?;
+        Ok(())
+    }
+
+    /// Evict aged-out data keys per the configured [`GcConfig`], returning the
+    /// number of keys evicted. Idle entries past the retention window go first,
+    /// then the oldest entries above `max_entries` (never those younger than
+    /// `min_age`).
+    pub async fn gc(&self) -> AppResult<usize> {
+        // Persist pending timestamps so eviction decisions see fresh scores.
+        self.flush_last_use().await?;
+
+        let now = now_unix();
+        let mut conn = self.manager.clone();
+        let mut victims: Vec<String> = Vec::new();
+
+        // Idle-based eviction: anything last used before the retention cutoff.
+        if let Some(window) = self.gc.retention_window {
+            let cutoff = now - window.as_secs() as i64;
+            let idle: Vec<String> = conn
+                .zrangebyscore(LASTUSE_ZSET, "-inf", cutoff)
+                .await?;
+            victims.extend(idle);
+        }
+
+        // Budget-based eviction: trim the oldest entries above the ceiling,
+        // but never evict anything younger than `min_age`.
+        if let Some(max) = self.gc.max_entries {
+            let total: usize = conn.zcard(LASTUSE_ZSET).await?;
+            if total > max {
+                let overflow = total - max;
+                let age_cutoff = now - self.gc.min_age.as_secs() as i64;
+                let oldest: Vec<String> = conn
+                    .zrangebyscore_limit(LASTUSE_ZSET, "-inf", age_cutoff, 0, overflow as isize)
+                    .await?;
+                victims.extend(oldest);
+            }
+        }
+
+        if victims.is_empty() {
+            return Ok(0);
+        }
+
+        victims.sort();
+        victims.dedup();
+        for key in &victims {
+            self.purge_key(key).await?;
+        }
+        Ok(victims.len())
+    }
+
+    /// Fully retire a single data key: drop the value, its last-use score, and
+    /// every tag-set membership recorded in the reverse index.
+    async fn purge_key(&self, key: &str) -> AppResult<()> {
+        let mut conn = self.manager.clone();
+        let key_tags = Self::key_tags_key(key);
+        let tags: Vec<String> = conn.smembers(&key_tags).await?;
+
+        let mut pipe = redis::pipe();
+        pipe.del(key).ignore();
+        pipe.zrem(LASTUSE_ZSET, key).ignore();
+        for tag in &tags {
+            pipe.srem(Self::tag_key(tag), key).ignore();
+        }
+        pipe.del(&key_tags).ignore();
+        pipe.query_async::<_, ()>(&mut conn).await?;
+
+        self.local_remove(key);
+        self.coherence.note_keys([key.to_string()]);
+        Ok(())
+    }
+
+    /// Spawn the background collector: flush last-use each interval and run a
+    /// GC sweep. A zero interval (or fully unbounded policy) leaves it idle.
+    fn spawn_gc(&self) {
+        let cache = self.clone();
+        let interval = self.gc.interval;
+        let unbounded = self.gc.max_entries.is_none() && self.gc.retention_window.is_none();
+        if interval.is_zero() || unbounded {
+            return;
+        }
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = cache.gc().await {
+                    tracing::warn!("cache GC sweep failed: {}", e);
+                }
+            }
+        });
+    }
+}
+
+/// Current wall-clock time as whole unix seconds.
+fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
 }
 
 pub fn cache_key(prefix: &str, id: &str) -> String {
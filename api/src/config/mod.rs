@@ -12,6 +12,7 @@ pub struct Config {
     pub s3: S3Config,
     pub meilisearch: MeilisearchConfig,
     pub encryption: EncryptionConfig,
+    pub vendor_portal: VendorPortalConfig,
     pub environment: String,
 }
 
@@ -66,6 +67,25 @@ pub struct StorageConfig {
     pub access_key_id: Option<String>,
     /// AWS secret key
     pub secret_access_key: Option<String>,
+    /// Enable envelope encryption at rest for evidence blobs.
+    pub encrypt_at_rest: bool,
+    /// Hex-encoded 256-bit master key used to wrap per-object data keys.
+    /// Required when `encrypt_at_rest` is true.
+    pub encryption_master_key: Option<String>,
+    /// Secret used to sign local-storage download tokens. When unset, local
+    /// download URLs are unsigned (legacy behavior).
+    pub download_token_secret: Option<String>,
+    /// Per-org egress quota in bytes per rolling hour. Zero/absent means
+    /// unlimited (backward compatible).
+    pub download_limit: u64,
+    /// IAM role ARN to assume (STS AssumeRole / cross-account access).
+    pub role_arn: Option<String>,
+    /// Path to an OIDC web-identity token file (EKS IRSA / web-identity flow).
+    pub web_identity_token_file: Option<String>,
+    /// External ID required by the trust policy of the assumed role.
+    pub external_id: Option<String>,
+    /// Session name attached to assumed-role credentials.
+    pub session_name: Option<String>,
 }
 
 impl StorageConfig {
@@ -95,6 +115,12 @@ pub struct EncryptionConfig {
     pub key: String,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct VendorPortalConfig {
+    /// Secret used to sign vendor-portal access JWTs (HS256).
+    pub jwt_secret: String,
+}
+
 impl Config {
     pub fn from_env() -> Result<Self> {
         dotenvy::dotenv().ok();
@@ -158,6 +184,19 @@ impl Config {
                 endpoint: env::var("S3_ENDPOINT").ok(),
                 access_key_id: env::var("AWS_ACCESS_KEY_ID").ok(),
                 secret_access_key: env::var("AWS_SECRET_ACCESS_KEY").ok(),
+                encrypt_at_rest: env::var("STORAGE_ENCRYPT_AT_REST")
+                    .map(|v| v.to_lowercase() == "true")
+                    .unwrap_or(false),
+                encryption_master_key: env::var("STORAGE_ENCRYPTION_KEY").ok(),
+                download_token_secret: env::var("STORAGE_DOWNLOAD_TOKEN_SECRET").ok(),
+                download_limit: env::var("STORAGE_DOWNLOAD_LIMIT")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(0),
+                role_arn: env::var("S3_ROLE_ARN").ok(),
+                web_identity_token_file: env::var("AWS_WEB_IDENTITY_TOKEN_FILE").ok(),
+                external_id: env::var("S3_ROLE_EXTERNAL_ID").ok(),
+                session_name: env::var("S3_ROLE_SESSION_NAME").ok(),
             },
             meilisearch: MeilisearchConfig {
                 host: env::var("MEILISEARCH_HOST")
@@ -178,6 +217,15 @@ impl Config {
                     key
                 }),
             },
+            vendor_portal: VendorPortalConfig {
+                jwt_secret: env::var("VENDOR_PORTAL_JWT_SECRET").unwrap_or_else(|_| {
+                    let secret = crate::utils::EncryptionService::generate_key();
+                    tracing::warn!(
+                        "VENDOR_PORTAL_JWT_SECRET not set, using generated secret. Set VENDOR_PORTAL_JWT_SECRET in production!"
+                    );
+                    secret
+                }),
+            },
             environment: env::var("ENVIRONMENT").unwrap_or_else(|_| "development".to_string()),
         })
     }
@@ -94,6 +94,65 @@ pub struct AwsSyncOptions {
     /// Whether to sync inactive/terminated resources
     #[serde(default)]
     pub include_inactive: bool,
+
+    /// Remediation SLA thresholds (in days) per Security Hub severity.
+    #[serde(default)]
+    pub remediation_sla: RemediationSla,
+}
+
+/// Per-severity remediation SLA thresholds, in days. A finding older than its
+/// severity's threshold (by first-observed age) is flagged as overdue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemediationSla {
+    #[serde(default = "default_critical_sla")]
+    pub critical_days: i64,
+    #[serde(default = "default_high_sla")]
+    pub high_days: i64,
+    #[serde(default = "default_medium_sla")]
+    pub medium_days: i64,
+    #[serde(default = "default_low_sla")]
+    pub low_days: i64,
+}
+
+fn default_critical_sla() -> i64 {
+    7
+}
+
+fn default_high_sla() -> i64 {
+    30
+}
+
+fn default_medium_sla() -> i64 {
+    90
+}
+
+fn default_low_sla() -> i64 {
+    180
+}
+
+impl Default for RemediationSla {
+    fn default() -> Self {
+        Self {
+            critical_days: default_critical_sla(),
+            high_days: default_high_sla(),
+            medium_days: default_medium_sla(),
+            low_days: default_low_sla(),
+        }
+    }
+}
+
+impl RemediationSla {
+    /// SLA threshold in days for a severity label, or `None` if the severity is
+    /// not subject to an SLA (e.g. INFORMATIONAL).
+    pub fn threshold_for(&self, severity_label: &str) -> Option<i64> {
+        match severity_label.to_uppercase().as_str() {
+            "CRITICAL" => Some(self.critical_days),
+            "HIGH" => Some(self.high_days),
+            "MEDIUM" => Some(self.medium_days),
+            "LOW" => Some(self.low_days),
+            _ => None,
+        }
+    }
 }
 
 fn default_cloudtrail_hours() -> u32 {
@@ -110,6 +169,7 @@ impl Default for AwsSyncOptions {
             cloudtrail_hours: default_cloudtrail_hours(),
             max_findings: default_max_findings(),
             include_inactive: false,
+            remediation_sla: RemediationSla::default(),
         }
     }
 }
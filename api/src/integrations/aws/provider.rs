@@ -1,5 +1,7 @@
 use super::client::AwsClient;
 use super::config::AwsConfig;
+use super::services::iam::InMemoryEscalationSnapshotStore;
+use super::services::securityhub::InMemoryFindingSnapshotStore;
 use crate::integrations::{
     IntegrationCapability, IntegrationProvider, SyncContext, SyncResult, TestConnectionDetails,
 };
@@ -7,11 +9,22 @@ use async_trait::async_trait;
 use serde_json::Value;
 
 /// AWS integration provider
-pub struct AwsProvider;
+pub struct AwsProvider {
+    /// Prior-sync snapshots used to compute Security Hub finding deltas across
+    /// runs. Held on the provider so the registry's single instance retains
+    /// history for the process lifetime.
+    snapshots: InMemoryFindingSnapshotStore,
+    /// Prior-sync escalation-capable principal set, used to compute IAM
+    /// privilege-escalation drift across runs.
+    escalation_snapshots: InMemoryEscalationSnapshotStore,
+}
 
 impl AwsProvider {
     pub fn new() -> Self {
-        Self
+        Self {
+            snapshots: InMemoryFindingSnapshotStore::new(),
+            escalation_snapshots: InMemoryEscalationSnapshotStore::new(),
+        }
     }
 }
 
@@ -122,7 +135,14 @@ impl IntegrationProvider for AwsProvider {
         );
 
         // Run the sync orchestrator
-        let result = super::sync::run_sync(&client, &aws_config, &context).await?;
+        let result = super::sync::run_sync(
+            &client,
+            &aws_config,
+            &context,
+            &self.snapshots,
+            &self.escalation_snapshots,
+        )
+        .await?;
 
         tracing::info!(
             organization_id = %context.organization_id,
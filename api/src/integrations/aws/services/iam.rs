@@ -3,7 +3,8 @@ use crate::integrations::provider::{CollectedEvidence, SyncContext, SyncResult};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
 
 /// IAM User data collected from AWS
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +21,8 @@ pub struct AwsIamUser {
     pub groups: Vec<String>,
     pub attached_policies: Vec<AwsAttachedPolicy>,
     pub inline_policy_names: Vec<String>,
+    /// Parsed documents for `inline_policy_names`, same order.
+    pub inline_policy_documents: Vec<Option<serde_json::Value>>,
     pub tags: HashMap<String, String>,
 }
 
@@ -61,6 +64,8 @@ pub struct AwsIamRole {
     pub create_date: Option<DateTime<Utc>>,
     pub attached_policies: Vec<AwsAttachedPolicy>,
     pub inline_policy_names: Vec<String>,
+    /// Parsed documents for `inline_policy_names`, same order.
+    pub inline_policy_documents: Vec<Option<serde_json::Value>>,
     pub last_used_date: Option<DateTime<Utc>>,
     pub last_used_region: Option<String>,
     pub tags: HashMap<String, String>,
@@ -88,12 +93,223 @@ pub struct AwsIamPolicy {
     pub tags: HashMap<String, String>,
 }
 
+/// Individually high-impact IAM/resource actions. Any principal whose
+/// effective permissions (after wildcard expansion) cover one of these is
+/// worth surfacing in evidence, independent of whether it forms a full
+/// escalation chain.
+pub const SENSITIVE_ACTIONS: &[&str] = &[
+    "iam:*",
+    "iam:CreatePolicyVersion",
+    "iam:SetDefaultPolicyVersion",
+    "iam:PassRole",
+    "iam:CreateAccessKey",
+    "iam:UpdateAssumeRolePolicy",
+    "iam:AttachUserPolicy",
+    "iam:AttachRolePolicy",
+    "iam:PutUserPolicy",
+    "iam:PutRolePolicy",
+    "s3:DeleteBucket",
+    "kms:ScheduleKeyDeletion",
+];
+
+/// A known IAM privilege-escalation technique: a principal whose effective
+/// action set covers every action in `actions` can chain them to obtain
+/// broader access than it started with.
+pub struct PrivilegeEscalationChain {
+    pub name: &'static str,
+    pub actions: &'static [&'static str],
+    pub description: &'static str,
+}
+
+/// Catalog of publicly-documented IAM privilege-escalation chains. Detection
+/// is action-presence based - it does not evaluate resource ARNs or policy
+/// conditions, so it over-approximates (a principal scoped to one resource
+/// is still flagged).
+pub const ESCALATION_CHAINS: &[PrivilegeEscalationChain] = &[
+    PrivilegeEscalationChain {
+        name: "CreatePolicyVersion+AttachUserPolicy",
+        actions: &["iam:CreatePolicyVersion", "iam:AttachUserPolicy"],
+        description: "Can publish a new default policy version and attach it to a user, granting arbitrary permissions.",
+    },
+    PrivilegeEscalationChain {
+        name: "PassRole+CreateFunction",
+        actions: &["iam:PassRole", "lambda:CreateFunction"],
+        description: "Can pass a privileged role to a new Lambda function and invoke it to assume that role's permissions.",
+    },
+    PrivilegeEscalationChain {
+        name: "PassRole+RunInstances",
+        actions: &["iam:PassRole", "ec2:RunInstances"],
+        description: "Can pass a privileged role to a new EC2 instance and use its instance profile credentials.",
+    },
+    PrivilegeEscalationChain {
+        name: "CreateAccessKey",
+        actions: &["iam:CreateAccessKey"],
+        description: "Can mint long-lived credentials for another principal (this check is not scoped to \"self only\").",
+    },
+    PrivilegeEscalationChain {
+        name: "UpdateAssumeRolePolicy+AssumeRole",
+        actions: &["iam:UpdateAssumeRolePolicy", "sts:AssumeRole"],
+        description: "Can rewrite a role's trust policy to allow itself to assume it.",
+    },
+];
+
+/// An IAM principal whose effective permissions satisfy one or more
+/// [`ESCALATION_CHAINS`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscalationFinding {
+    pub principal_type: String, // "user" | "role"
+    pub principal_name: String,
+    pub arn: String,
+    pub chains: Vec<String>,
+    pub sensitive_actions_held: Vec<String>,
+}
+
+/// Persisted view of which principals were escalation-capable as of the
+/// prior sync, used to compute drift for freshness SLAs.
+pub trait EscalationSnapshotStore: Send + Sync {
+    /// Load the set of escalation-capable principal ARNs from the prior run.
+    fn load(&self) -> HashSet<String>;
+    /// Persist the current set for the next run.
+    fn save(&self, capable: HashSet<String>);
+}
+
+/// In-process [`EscalationSnapshotStore`]; persists for the lifetime of the owner.
+#[derive(Default)]
+pub struct InMemoryEscalationSnapshotStore {
+    capable: Mutex<HashSet<String>>,
+}
+
+impl InMemoryEscalationSnapshotStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl EscalationSnapshotStore for InMemoryEscalationSnapshotStore {
+    fn load(&self) -> HashSet<String> {
+        self.capable.lock().unwrap().clone()
+    }
+
+    fn save(&self, capable: HashSet<String>) {
+        *self.capable.lock().unwrap() = capable;
+    }
+}
+
+/// Extract every action named in an `Effect: Allow` statement, wildcards and
+/// all (e.g. `"iam:*"`, `"*"`).
+fn extract_allowed_actions(policy_doc: &Option<serde_json::Value>) -> HashSet<String> {
+    let mut actions = HashSet::new();
+    let Some(doc) = policy_doc else {
+        return actions;
+    };
+    let Some(statements) = doc.get("Statement").and_then(|s| s.as_array()) else {
+        return actions;
+    };
+
+    for statement in statements {
+        if statement.get("Effect").and_then(|e| e.as_str()) != Some("Allow") {
+            continue;
+        }
+        match statement.get("Action") {
+            Some(serde_json::Value::String(s)) => {
+                actions.insert(s.clone());
+            }
+            Some(serde_json::Value::Array(arr)) => {
+                actions.extend(arr.iter().filter_map(|v| v.as_str()).map(str::to_string));
+            }
+            _ => {}
+        }
+    }
+
+    actions
+}
+
+/// Expand a raw action set (which may contain `"*"` or `"service:*"`
+/// wildcards) against `catalog`, returning the subset of catalog actions the
+/// principal effectively holds.
+fn expand_against_catalog(raw_actions: &HashSet<String>, catalog: &[&str]) -> HashSet<String> {
+    let mut held = HashSet::new();
+    for &catalog_action in catalog {
+        let service = catalog_action.split(':').next().unwrap_or("");
+        let service_wildcard = format!("{}:*", service);
+        let matches = raw_actions.contains("*")
+            || raw_actions.contains(&service_wildcard)
+            || raw_actions
+                .iter()
+                .any(|a| a.eq_ignore_ascii_case(catalog_action));
+        if matches {
+            held.insert(catalog_action.to_string());
+        }
+    }
+    held
+}
+
+/// Check whether `held` (catalog actions the principal effectively holds)
+/// satisfies every action required by `chain`.
+fn satisfies_chain(held: &HashSet<String>, chain: &PrivilegeEscalationChain) -> bool {
+    chain.actions.iter().all(|a| held.contains(*a))
+}
+
+/// Evaluate one principal's escalation exposure from its attached managed
+/// policies and inline policy documents.
+fn evaluate_principal(
+    principal_type: &str,
+    principal_name: &str,
+    arn: &str,
+    attached: &[AwsAttachedPolicy],
+    inline_documents: &[Option<serde_json::Value>],
+    policies_by_arn: &HashMap<String, AwsIamPolicy>,
+) -> Option<EscalationFinding> {
+    let mut raw_actions = HashSet::new();
+
+    for policy in attached {
+        if let Some(p) = policies_by_arn.get(&policy.policy_arn) {
+            raw_actions.extend(extract_allowed_actions(&p.policy_document));
+        }
+    }
+    for doc in inline_documents {
+        raw_actions.extend(extract_allowed_actions(doc));
+    }
+
+    let held = expand_against_catalog(&raw_actions, SENSITIVE_ACTIONS);
+    if held.is_empty() {
+        return None;
+    }
+
+    // Chains may need actions outside the sensitive catalog (e.g.
+    // `lambda:CreateFunction`), so expand against each chain's own actions.
+    let chains: Vec<String> = ESCALATION_CHAINS
+        .iter()
+        .filter(|c| satisfies_chain(&expand_against_catalog(&raw_actions, c.actions), c))
+        .map(|c| c.name.to_string())
+        .collect();
+
+    if chains.is_empty() {
+        return None;
+    }
+
+    let mut sensitive_actions_held: Vec<String> = held.into_iter().collect();
+    sensitive_actions_held.sort();
+
+    Some(EscalationFinding {
+        principal_type: principal_type.to_string(),
+        principal_name: principal_name.to_string(),
+        arn: arn.to_string(),
+        chains,
+        sensitive_actions_held,
+    })
+}
+
 /// IAM Collector for syncing IAM data
 pub struct IamCollector;
 
 impl IamCollector {
     /// Sync IAM data from AWS
-    pub async fn sync(client: &AwsClient, _context: &SyncContext) -> Result<SyncResult, String> {
+    pub async fn sync(
+        client: &AwsClient,
+        _context: &SyncContext,
+        escalation_snapshots: &dyn EscalationSnapshotStore,
+    ) -> Result<SyncResult, String> {
         let mut result = SyncResult::default();
         let iam_client = client.iam_client();
 
@@ -252,6 +468,92 @@ impl IamCollector {
             control_codes: vec!["CC6.1".to_string(), "CC6.2".to_string()],
         });
 
+        // Privilege-escalation analysis: expand each principal's attached +
+        // inline policy documents against the sensitive-action catalog, then
+        // check for full escalation chains.
+        let policies_by_arn: HashMap<String, AwsIamPolicy> = policies
+            .iter()
+            .map(|p| (p.arn.clone(), p.clone()))
+            .collect();
+
+        let mut escalation_findings: Vec<EscalationFinding> = users
+            .iter()
+            .filter_map(|u| {
+                evaluate_principal(
+                    "user",
+                    &u.user_name,
+                    &u.arn,
+                    &u.attached_policies,
+                    &u.inline_policy_documents,
+                    &policies_by_arn,
+                )
+            })
+            .chain(roles.iter().filter_map(|r| {
+                evaluate_principal(
+                    "role",
+                    &r.role_name,
+                    &r.arn,
+                    &r.attached_policies,
+                    &r.inline_policy_documents,
+                    &policies_by_arn,
+                )
+            }))
+            .collect();
+        escalation_findings.sort_by(|a, b| a.arn.cmp(&b.arn));
+
+        if !escalation_findings.is_empty() {
+            result.evidence_collected.push(CollectedEvidence {
+                title: "IAM Privilege Escalation Risk Report".to_string(),
+                description: Some(format!(
+                    "{} principals can complete a known privilege-escalation chain",
+                    escalation_findings.len()
+                )),
+                evidence_type: "automated".to_string(),
+                source: "aws".to_string(),
+                source_reference: Some("iam:privilege-escalation".to_string()),
+                data: json!({
+                    "escalation_capable_principals": escalation_findings,
+                    "collected_at": Utc::now().to_rfc3339(),
+                }),
+                control_codes: vec!["CC6.1".to_string(), "CC6.3".to_string(), "CC6.8".to_string()],
+            });
+        }
+
+        // Diff the escalation-capable principal set against the prior sync so
+        // drift (new or no-longer-capable principals) triggers freshness SLAs.
+        let current_capable: HashSet<String> =
+            escalation_findings.iter().map(|f| f.arn.clone()).collect();
+        let prior_capable = escalation_snapshots.load();
+        let newly_capable: Vec<&EscalationFinding> = escalation_findings
+            .iter()
+            .filter(|f| !prior_capable.contains(&f.arn))
+            .collect();
+        let no_longer_capable: Vec<&String> = prior_capable
+            .iter()
+            .filter(|arn| !current_capable.contains(*arn))
+            .collect();
+        escalation_snapshots.save(current_capable);
+
+        if !newly_capable.is_empty() || !no_longer_capable.is_empty() {
+            result.evidence_collected.push(CollectedEvidence {
+                title: "IAM Privilege Escalation Drift Since Last Sync".to_string(),
+                description: Some(format!(
+                    "{} principals newly escalation-capable, {} no longer escalation-capable",
+                    newly_capable.len(),
+                    no_longer_capable.len()
+                )),
+                evidence_type: "automated".to_string(),
+                source: "aws".to_string(),
+                source_reference: Some("iam:privilege-escalation:delta".to_string()),
+                data: json!({
+                    "newly_capable": newly_capable,
+                    "no_longer_capable": no_longer_capable,
+                    "collected_at": Utc::now().to_rfc3339(),
+                }),
+                control_codes: vec!["CC6.1".to_string(), "CC6.8".to_string()],
+            });
+        }
+
         result.records_created = result.records_processed;
         Ok(result)
     }
@@ -294,6 +596,11 @@ impl IamCollector {
                 let inline_policy_names =
                     Self::get_user_inline_policies(iam_client, &user_name).await?;
 
+                // Get inline policy documents (for privilege-escalation analysis)
+                let inline_policy_documents =
+                    Self::get_user_inline_policy_documents(iam_client, &user_name, &inline_policy_names)
+                        .await?;
+
                 // Get tags
                 let tags = Self::get_user_tags(iam_client, &user_name).await?;
 
@@ -318,6 +625,7 @@ impl IamCollector {
                     groups,
                     attached_policies,
                     inline_policy_names,
+                    inline_policy_documents,
                     tags,
                 });
             }
@@ -465,6 +773,35 @@ impl IamCollector {
         Ok(response.policy_names().to_vec())
     }
 
+    /// Fetch and parse the document for each named inline user policy.
+    async fn get_user_inline_policy_documents(
+        iam_client: &aws_sdk_iam::Client,
+        user_name: &str,
+        policy_names: &[String],
+    ) -> Result<Vec<Option<serde_json::Value>>, String> {
+        let mut documents = Vec::with_capacity(policy_names.len());
+        for policy_name in policy_names {
+            let response = iam_client
+                .get_user_policy()
+                .user_name(user_name)
+                .policy_name(policy_name)
+                .send()
+                .await
+                .map_err(|e| {
+                    format!(
+                        "Failed to get inline policy {} for user {}: {}",
+                        policy_name, user_name, e
+                    )
+                })?;
+
+            let doc = urlencoding::decode(response.policy_document())
+                .ok()
+                .and_then(|decoded| serde_json::from_str(&decoded).ok());
+            documents.push(doc);
+        }
+        Ok(documents)
+    }
+
     async fn get_user_tags(
         iam_client: &aws_sdk_iam::Client,
         user_name: &str,
@@ -512,6 +849,11 @@ impl IamCollector {
                 let inline_policy_names =
                     Self::get_role_inline_policies(iam_client, &role_name).await?;
 
+                // Get inline policy documents (for privilege-escalation analysis)
+                let inline_policy_documents =
+                    Self::get_role_inline_policy_documents(iam_client, &role_name, &inline_policy_names)
+                        .await?;
+
                 // Get role last used
                 let (last_used_date, last_used_region) = role
                     .role_last_used()
@@ -554,6 +896,7 @@ impl IamCollector {
                     create_date,
                     attached_policies,
                     inline_policy_names,
+                    inline_policy_documents,
                     last_used_date,
                     last_used_region,
                     tags,
@@ -605,6 +948,35 @@ impl IamCollector {
         Ok(response.policy_names().to_vec())
     }
 
+    /// Fetch and parse the document for each named inline role policy.
+    async fn get_role_inline_policy_documents(
+        iam_client: &aws_sdk_iam::Client,
+        role_name: &str,
+        policy_names: &[String],
+    ) -> Result<Vec<Option<serde_json::Value>>, String> {
+        let mut documents = Vec::with_capacity(policy_names.len());
+        for policy_name in policy_names {
+            let response = iam_client
+                .get_role_policy()
+                .role_name(role_name)
+                .policy_name(policy_name)
+                .send()
+                .await
+                .map_err(|e| {
+                    format!(
+                        "Failed to get inline policy {} for role {}: {}",
+                        policy_name, role_name, e
+                    )
+                })?;
+
+            let doc = urlencoding::decode(response.policy_document())
+                .ok()
+                .and_then(|decoded| serde_json::from_str(&decoded).ok());
+            documents.push(doc);
+        }
+        Ok(documents)
+    }
+
     async fn get_role_tags(
         iam_client: &aws_sdk_iam::Client,
         role_name: &str,
@@ -828,3 +1200,93 @@ impl IamCollector {
         (allows_admin, wildcard_actions, wildcard_resources, risk_score)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy_doc(actions: &[&str]) -> Option<serde_json::Value> {
+        Some(json!({
+            "Version": "2012-10-17",
+            "Statement": [{
+                "Effect": "Allow",
+                "Action": actions,
+                "Resource": "*",
+            }]
+        }))
+    }
+
+    #[test]
+    fn extract_allowed_actions_ignores_deny_statements() {
+        let doc = Some(json!({
+            "Statement": [
+                {"Effect": "Allow", "Action": "iam:CreateAccessKey"},
+                {"Effect": "Deny", "Action": "iam:PassRole"},
+            ]
+        }));
+        let actions = extract_allowed_actions(&doc);
+        assert!(actions.contains("iam:CreateAccessKey"));
+        assert!(!actions.contains("iam:PassRole"));
+    }
+
+    #[test]
+    fn expand_against_catalog_matches_service_wildcard() {
+        let raw: HashSet<String> = ["iam:*".to_string()].into_iter().collect();
+        let held = expand_against_catalog(&raw, SENSITIVE_ACTIONS);
+        assert!(held.contains("iam:CreateAccessKey"));
+        assert!(held.contains("iam:PutRolePolicy"));
+    }
+
+    #[test]
+    fn satisfies_chain_requires_every_action() {
+        let chain = &ESCALATION_CHAINS[0];
+        let mut held = HashSet::new();
+        held.insert(chain.actions[0].to_string());
+        assert!(!satisfies_chain(&held, chain));
+
+        held.insert(chain.actions[1].to_string());
+        assert!(satisfies_chain(&held, chain));
+    }
+
+    #[test]
+    fn evaluate_principal_flags_create_access_key_chain() {
+        let inline = vec![policy_doc(&["iam:CreateAccessKey"])];
+        let finding = evaluate_principal(
+            "user",
+            "alice",
+            "arn:aws:iam::123456789012:user/alice",
+            &[],
+            &inline,
+            &HashMap::new(),
+        );
+        let finding = finding.expect("CreateAccessKey alone should be flagged");
+        assert_eq!(finding.principal_type, "user");
+        assert!(finding.chains.contains(&"CreateAccessKey".to_string()));
+    }
+
+    #[test]
+    fn evaluate_principal_returns_none_without_sensitive_actions() {
+        let inline = vec![policy_doc(&["s3:GetObject"])];
+        let finding = evaluate_principal(
+            "user",
+            "bob",
+            "arn:aws:iam::123456789012:user/bob",
+            &[],
+            &inline,
+            &HashMap::new(),
+        );
+        assert!(finding.is_none());
+    }
+
+    #[test]
+    fn in_memory_escalation_snapshot_store_round_trips() {
+        let store = InMemoryEscalationSnapshotStore::new();
+        assert!(store.load().is_empty());
+
+        let capable: HashSet<String> = ["arn:aws:iam::123456789012:user/alice".to_string()]
+            .into_iter()
+            .collect();
+        store.save(capable.clone());
+        assert_eq!(store.load(), capable);
+    }
+}
@@ -1,8 +1,11 @@
 use crate::integrations::aws::client::AwsClient;
+use crate::integrations::aws::config::RemediationSla;
 use crate::integrations::provider::{CollectedEvidence, SyncContext, SyncResult};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Mutex;
 
 /// Security Hub finding
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,26 +32,131 @@ pub struct AwsSecurityHubFinding {
     pub last_observed_at: Option<DateTime<Utc>>,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
+    /// Key of the Jira issue opened for this finding, once bridged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jira_issue_key: Option<String>,
+}
+
+/// State of a single finding as recorded at the end of a sync.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotEntry {
+    pub updated_at: Option<DateTime<Utc>>,
+    pub workflow_status: String,
+    pub severity_label: String,
+    /// Whether the finding was considered resolved at snapshot time.
+    pub resolved: bool,
+}
+
+/// Persisted view of the prior Security Hub sync for one region, keyed by
+/// finding `id`. Comparing the current active set against this snapshot yields
+/// the NEW / RESOLVED / REOPENED deltas.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FindingSnapshot {
+    pub findings: HashMap<String, SnapshotEntry>,
+    /// Newest `updated_at` observed, used as the next high-water mark.
+    pub high_water_mark: Option<DateTime<Utc>>,
+}
+
+/// Storage for per-region [`FindingSnapshot`]s across syncs.
+pub trait FindingSnapshotStore: Send + Sync {
+    /// Load the snapshot for a key, or an empty snapshot if none exists.
+    fn load(&self, key: &str) -> FindingSnapshot;
+    /// Persist the snapshot for a key.
+    fn save(&self, key: &str, snapshot: FindingSnapshot);
+}
+
+/// In-process [`FindingSnapshotStore`]; persists for the lifetime of the owner.
+#[derive(Default)]
+pub struct InMemoryFindingSnapshotStore {
+    snapshots: Mutex<HashMap<String, FindingSnapshot>>,
+}
+
+impl InMemoryFindingSnapshotStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl FindingSnapshotStore for InMemoryFindingSnapshotStore {
+    fn load(&self, key: &str) -> FindingSnapshot {
+        self.snapshots
+            .lock()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn save(&self, key: &str, snapshot: FindingSnapshot) {
+        self.snapshots
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), snapshot);
+    }
+}
+
+/// Delta between a prior snapshot and the current active finding set.
+#[derive(Debug, Default)]
+pub struct FindingDelta {
+    /// Findings whose id was not present in the prior snapshot.
+    pub new: Vec<AwsSecurityHubFinding>,
+    /// Findings (id + last known state) that were previously active and have
+    /// since been resolved.
+    pub resolved: Vec<(String, SnapshotEntry)>,
+    /// Findings that were resolved before and are active again.
+    pub reopened: Vec<AwsSecurityHubFinding>,
+    /// Snapshot to persist for the next run.
+    pub snapshot: FindingSnapshot,
 }
 
 /// Security Hub collector
 pub struct SecurityHubCollector;
 
 impl SecurityHubCollector {
-    /// Sync Security Hub findings for a region
+    /// Sync Security Hub findings for a region.
+    ///
+    /// Findings are diffed against the prior run's snapshot (loaded from
+    /// `snapshots`) to emit NEW / RESOLVED / REOPENED evidence. When
+    /// `context.last_sync_token` carries a high-water mark (and
+    /// neither `context.full_sync` nor `context.full_resync` is set) the
+    /// `get_findings` query is narrowed to
+    /// records updated since then; resolution is only
+    /// inferred from absence during a full scan so incremental re-runs stay
+    /// idempotent.
     pub async fn sync(
         client: &AwsClient,
-        _context: &SyncContext,
+        context: &SyncContext,
         region: &str,
+        snapshots: &dyn FindingSnapshotStore,
+        sla: &RemediationSla,
     ) -> Result<SyncResult, String> {
         let mut result = SyncResult::default();
 
         let sh_client = client.securityhub_client(region).await?;
 
+        // A high-water mark from the prior run narrows the server-side query,
+        // except on a requested full sync or resync.
+        let updated_since = if context.full_sync || context.full_resync {
+            None
+        } else {
+            context
+                .last_sync_token
+                .as_deref()
+                .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                .map(|d| d.with_timezone(&Utc))
+        };
+        let full_scan = updated_since.is_none();
+
         // Get findings
-        let findings = Self::collect_findings(&sh_client, region).await?;
+        let findings = Self::collect_findings(&sh_client, region, updated_since.as_ref()).await?;
         result.records_processed = findings.len() as i32;
 
+        // Diff against the prior snapshot and persist the new one.
+        let snapshot_key = format!("securityhub:{}", region);
+        let prior = snapshots.load(&snapshot_key);
+        let delta = Self::compute_delta(&prior, &findings, full_scan);
+        snapshots.save(&snapshot_key, delta.snapshot.clone());
+
         // Group findings by severity
         let critical: Vec<_> = findings
             .iter()
@@ -123,30 +231,302 @@ impl SecurityHubCollector {
             }
         }
 
+        // Delta evidence: one entry per non-empty bucket.
+        if !delta.new.is_empty() {
+            let (crit, high) = Self::count_severities(&delta.new);
+            result.evidence_collected.push(CollectedEvidence {
+                title: format!("New Findings Since Last Sync - {}", region),
+                description: Some(format!(
+                    "{} new findings ({} critical, {} high)",
+                    delta.new.len(),
+                    crit,
+                    high
+                )),
+                evidence_type: "automated".to_string(),
+                source: "aws".to_string(),
+                source_reference: Some(format!("securityhub:{}:delta:new", region)),
+                data: json!({
+                    "region": region,
+                    "findings": delta.new.iter().map(Self::delta_finding_json).collect::<Vec<_>>(),
+                    "collected_at": Utc::now().to_rfc3339(),
+                }),
+                control_codes: vec!["CC7.1".to_string(), "CC7.2".to_string()],
+            });
+        }
+
+        if !delta.resolved.is_empty() {
+            result.evidence_collected.push(CollectedEvidence {
+                title: format!("Resolved Findings Since Last Sync - {}", region),
+                description: Some(format!("{} findings resolved", delta.resolved.len())),
+                evidence_type: "automated".to_string(),
+                source: "aws".to_string(),
+                source_reference: Some(format!("securityhub:{}:delta:resolved", region)),
+                data: json!({
+                    "region": region,
+                    "findings": delta.resolved.iter().map(|(id, entry)| json!({
+                        "id": id,
+                        "severity": entry.severity_label,
+                        "last_workflow_status": entry.workflow_status,
+                    })).collect::<Vec<_>>(),
+                    "collected_at": Utc::now().to_rfc3339(),
+                }),
+                control_codes: vec!["CC7.1".to_string()],
+            });
+        }
+
+        if !delta.reopened.is_empty() {
+            result.evidence_collected.push(CollectedEvidence {
+                title: format!("Reopened Findings Since Last Sync - {}", region),
+                description: Some(format!("{} findings reopened", delta.reopened.len())),
+                evidence_type: "automated".to_string(),
+                source: "aws".to_string(),
+                source_reference: Some(format!("securityhub:{}:delta:reopened", region)),
+                data: json!({
+                    "region": region,
+                    "findings": delta.reopened.iter().map(Self::delta_finding_json).collect::<Vec<_>>(),
+                    "collected_at": Utc::now().to_rfc3339(),
+                }),
+                control_codes: vec!["CC7.1".to_string(), "CC3.2".to_string()],
+            });
+        }
+
+        // Finding-age / remediation-SLA analysis.
+        if let Some(evidence) = Self::analyze_aging(&findings, sla, region, Utc::now()) {
+            result.evidence_collected.push(evidence);
+        }
+
         result.records_created = result.records_processed;
         Ok(result)
     }
 
+    /// Timestamp used for age math, preferring `first_observed_at` then
+    /// `created_at` then `updated_at`.
+    fn age_anchor(finding: &AwsSecurityHubFinding) -> Option<DateTime<Utc>> {
+        finding
+            .first_observed_at
+            .or(finding.created_at)
+            .or(finding.updated_at)
+    }
+
+    /// Render a day count as a human-readable relative age.
+    fn humanize_age(days: i64) -> String {
+        match days {
+            0 => "first observed today".to_string(),
+            1 => "first observed 1 day ago".to_string(),
+            _ => format!("first observed {} days ago", days),
+        }
+    }
+
+    /// Build the "Overdue Remediations" evidence when any active finding has
+    /// breached its severity's SLA. The payload also carries summary aging
+    /// statistics (oldest unresolved critical, mean time-to-resolve).
+    fn analyze_aging(
+        findings: &[AwsSecurityHubFinding],
+        sla: &RemediationSla,
+        region: &str,
+        now: DateTime<Utc>,
+    ) -> Option<CollectedEvidence> {
+        let mut overdue = Vec::new();
+        let mut oldest_critical_days: Option<i64> = None;
+        let mut resolve_durations: Vec<i64> = Vec::new();
+
+        for finding in findings {
+            let resolved = finding.workflow_status == "RESOLVED"
+                || finding.record_state == "ARCHIVED";
+            let Some(anchor) = Self::age_anchor(finding) else {
+                continue;
+            };
+            let age_days = (now - anchor).num_days();
+
+            if resolved {
+                // Best-effort time-to-resolve for findings still visible in the
+                // active set but marked resolved.
+                if let Some(updated) = finding.updated_at {
+                    resolve_durations.push((updated - anchor).num_days().max(0));
+                }
+                continue;
+            }
+
+            if finding.severity_label == "CRITICAL" {
+                oldest_critical_days =
+                    Some(oldest_critical_days.map_or(age_days, |cur| cur.max(age_days)));
+            }
+
+            if let Some(threshold) = sla.threshold_for(&finding.severity_label) {
+                if age_days > threshold {
+                    overdue.push(json!({
+                        "id": finding.id,
+                        "title": finding.title,
+                        "severity": finding.severity_label,
+                        "age_days": age_days,
+                        "age": Self::humanize_age(age_days),
+                        "sla_days": threshold,
+                        "first_observed_at": finding.first_observed_at,
+                        "workflow_status": finding.workflow_status,
+                    }));
+                }
+            }
+        }
+
+        if overdue.is_empty() {
+            return None;
+        }
+
+        let mean_time_to_resolve = if resolve_durations.is_empty() {
+            None
+        } else {
+            Some(resolve_durations.iter().sum::<i64>() / resolve_durations.len() as i64)
+        };
+
+        Some(CollectedEvidence {
+            title: format!("Overdue Remediations - {}", region),
+            description: Some(format!(
+                "{} findings past their remediation SLA",
+                overdue.len()
+            )),
+            evidence_type: "automated".to_string(),
+            source: "aws".to_string(),
+            source_reference: Some(format!("securityhub:{}:overdue", region)),
+            data: json!({
+                "region": region,
+                "overdue_count": overdue.len(),
+                "overdue": overdue,
+                "oldest_unresolved_critical_days": oldest_critical_days,
+                "mean_time_to_resolve_days": mean_time_to_resolve,
+                "collected_at": now.to_rfc3339(),
+            }),
+            control_codes: vec!["CC7.1".to_string(), "CC3.2".to_string()],
+        })
+    }
+
+    /// Compute the NEW / RESOLVED / REOPENED delta and the snapshot to persist.
+    ///
+    /// `full_scan` indicates the current finding set is the complete active set
+    /// (not an incremental `updated_since` slice); resolution-by-absence is
+    /// only trusted then, otherwise unchanged findings would look resolved.
+    pub fn compute_delta(
+        prior: &FindingSnapshot,
+        findings: &[AwsSecurityHubFinding],
+        full_scan: bool,
+    ) -> FindingDelta {
+        let mut delta = FindingDelta::default();
+        let current_ids: std::collections::HashSet<&str> =
+            findings.iter().map(|f| f.id.as_str()).collect();
+
+        // Start the next snapshot from the prior one so incremental runs retain
+        // findings that were not re-fetched this pass.
+        let mut snapshot = prior.clone();
+
+        for finding in findings {
+            let is_resolved = finding.workflow_status == "RESOLVED"
+                || finding.record_state == "ARCHIVED";
+            match prior.findings.get(&finding.id) {
+                None => {
+                    if !is_resolved {
+                        delta.new.push(finding.clone());
+                    }
+                }
+                Some(prev) if prev.resolved && !is_resolved => {
+                    delta.reopened.push(finding.clone());
+                }
+                _ => {}
+            }
+
+            snapshot.findings.insert(
+                finding.id.clone(),
+                SnapshotEntry {
+                    updated_at: finding.updated_at,
+                    workflow_status: finding.workflow_status.clone(),
+                    severity_label: finding.severity_label.clone(),
+                    resolved: is_resolved,
+                },
+            );
+
+            // Advance the high-water mark.
+            if let Some(updated) = finding.updated_at {
+                snapshot.high_water_mark = Some(match snapshot.high_water_mark {
+                    Some(hwm) if hwm >= updated => hwm,
+                    _ => updated,
+                });
+            }
+        }
+
+        // Previously-active findings now RESOLVED (seen this run) or, on a full
+        // scan, absent from the active set.
+        for (id, prev) in &prior.findings {
+            if prev.resolved {
+                continue;
+            }
+            // `snapshot` starts as a copy of `prior`, so absence has to be
+            // judged against the ids fetched this run.
+            let now_resolved = if current_ids.contains(id.as_str()) {
+                snapshot.findings.get(id).map_or(false, |entry| entry.resolved)
+            } else {
+                full_scan
+            };
+            if now_resolved {
+                delta.resolved.push((id.clone(), prev.clone()));
+                if let Some(entry) = snapshot.findings.get_mut(id) {
+                    entry.resolved = true;
+                } else {
+                    let mut resolved_entry = prev.clone();
+                    resolved_entry.resolved = true;
+                    snapshot.findings.insert(id.clone(), resolved_entry);
+                }
+            }
+        }
+
+        delta.snapshot = snapshot;
+        delta
+    }
+
+    fn count_severities(findings: &[AwsSecurityHubFinding]) -> (usize, usize) {
+        let crit = findings.iter().filter(|f| f.severity_label == "CRITICAL").count();
+        let high = findings.iter().filter(|f| f.severity_label == "HIGH").count();
+        (crit, high)
+    }
+
+    fn delta_finding_json(f: &AwsSecurityHubFinding) -> serde_json::Value {
+        json!({
+            "id": f.id,
+            "title": f.title,
+            "severity": f.severity_label,
+            "workflow_status": f.workflow_status,
+            "first_observed": f.first_observed_at,
+            "updated_at": f.updated_at,
+        })
+    }
+
     async fn collect_findings(
         sh_client: &aws_sdk_securityhub::Client,
         region: &str,
+        updated_since: Option<&DateTime<Utc>>,
     ) -> Result<Vec<AwsSecurityHubFinding>, String> {
         let mut findings = Vec::new();
         let mut next_token: Option<String> = None;
 
         loop {
+            let mut filters = aws_sdk_securityhub::types::AwsSecurityFindingFilters::builder()
+                .record_state(
+                    aws_sdk_securityhub::types::StringFilter::builder()
+                        .comparison(aws_sdk_securityhub::types::StringFilterComparison::Equals)
+                        .value("ACTIVE")
+                        .build(),
+                );
+
+            // Narrow to findings updated since the prior high-water mark.
+            if let Some(since) = updated_since {
+                filters = filters.updated_at(
+                    aws_sdk_securityhub::types::DateFilter::builder()
+                        .start(since.to_rfc3339())
+                        .end(Utc::now().to_rfc3339())
+                        .build(),
+                );
+            }
+
             let mut request = sh_client
                 .get_findings()
-                .filters(
-                    aws_sdk_securityhub::types::AwsSecurityFindingFilters::builder()
-                        .record_state(
-                            aws_sdk_securityhub::types::StringFilter::builder()
-                                .comparison(aws_sdk_securityhub::types::StringFilterComparison::Equals)
-                                .value("ACTIVE")
-                                .build(),
-                        )
-                        .build(),
-                )
+                .filters(filters.build())
                 .max_results(100);
 
             if let Some(token) = &next_token {
@@ -228,6 +608,7 @@ impl SecurityHubCollector {
                     updated_at: finding.updated_at().and_then(|s| {
                         DateTime::parse_from_rfc3339(s).ok().map(|d| d.with_timezone(&Utc))
                     }),
+                    jira_issue_key: None,
                 });
             }
 
@@ -240,3 +621,130 @@ impl SecurityHubCollector {
         Ok(findings)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(id: &str, workflow_status: &str, record_state: &str) -> AwsSecurityHubFinding {
+        AwsSecurityHubFinding {
+            id: id.to_string(),
+            product_arn: String::new(),
+            product_name: String::new(),
+            generator_id: String::new(),
+            aws_account_id: String::new(),
+            region: "us-east-1".to_string(),
+            types: vec![],
+            title: String::new(),
+            description: String::new(),
+            severity_label: "CRITICAL".to_string(),
+            severity_normalized: 90,
+            workflow_status: workflow_status.to_string(),
+            record_state: record_state.to_string(),
+            compliance_status: None,
+            compliance_standards: vec![],
+            related_resources: vec![],
+            remediation_text: None,
+            remediation_url: None,
+            first_observed_at: None,
+            last_observed_at: None,
+            created_at: None,
+            updated_at: None,
+            jira_issue_key: None,
+        }
+    }
+
+    #[test]
+    fn first_run_marks_every_active_finding_new() {
+        let delta = SecurityHubCollector::compute_delta(
+            &FindingSnapshot::default(),
+            &[finding("a", "NEW", "ACTIVE"), finding("b", "NEW", "ACTIVE")],
+            true,
+        );
+        assert_eq!(delta.new.len(), 2);
+        assert!(delta.resolved.is_empty());
+        assert!(delta.reopened.is_empty());
+    }
+
+    #[test]
+    fn resolved_detected_by_absence_only_on_full_scan() {
+        let first = SecurityHubCollector::compute_delta(
+            &FindingSnapshot::default(),
+            &[finding("a", "NEW", "ACTIVE")],
+            true,
+        );
+
+        // Full scan with "a" absent -> resolved.
+        let resolved = SecurityHubCollector::compute_delta(&first.snapshot, &[], true);
+        assert_eq!(resolved.resolved.len(), 1);
+
+        // Incremental slice with "a" absent -> not inferred as resolved.
+        let incremental = SecurityHubCollector::compute_delta(&first.snapshot, &[], false);
+        assert!(incremental.resolved.is_empty());
+    }
+
+    #[test]
+    fn reopened_detected_after_resolution() {
+        let first = SecurityHubCollector::compute_delta(
+            &FindingSnapshot::default(),
+            &[finding("a", "NEW", "ACTIVE")],
+            true,
+        );
+        let resolved = SecurityHubCollector::compute_delta(
+            &first.snapshot,
+            &[finding("a", "RESOLVED", "ACTIVE")],
+            true,
+        );
+        assert_eq!(resolved.resolved.len(), 1);
+
+        let reopened = SecurityHubCollector::compute_delta(
+            &resolved.snapshot,
+            &[finding("a", "NEW", "ACTIVE")],
+            true,
+        );
+        assert_eq!(reopened.reopened.len(), 1);
+        assert!(reopened.new.is_empty());
+    }
+
+    #[test]
+    fn aging_flags_findings_past_sla() {
+        let now = Utc::now();
+        let mut f = finding("a", "NEW", "ACTIVE");
+        f.first_observed_at = Some(now - chrono::Duration::days(10));
+        let sla = RemediationSla::default(); // critical = 7 days
+
+        let evidence = SecurityHubCollector::analyze_aging(&[f], &sla, "us-east-1", now)
+            .expect("critical finding 10 days old should breach the 7-day SLA");
+        assert_eq!(evidence.title, "Overdue Remediations - us-east-1");
+        assert_eq!(evidence.data["overdue_count"], 1);
+        assert_eq!(evidence.data["oldest_unresolved_critical_days"], 10);
+    }
+
+    #[test]
+    fn aging_tolerates_missing_first_observed() {
+        let now = Utc::now();
+        let mut f = finding("a", "NEW", "ACTIVE");
+        f.first_observed_at = None;
+        f.created_at = Some(now - chrono::Duration::days(8));
+        let sla = RemediationSla::default();
+
+        assert!(SecurityHubCollector::analyze_aging(&[f], &sla, "us-east-1", now).is_some());
+    }
+
+    #[test]
+    fn reruns_are_idempotent() {
+        let first = SecurityHubCollector::compute_delta(
+            &FindingSnapshot::default(),
+            &[finding("a", "NEW", "ACTIVE")],
+            true,
+        );
+        let second = SecurityHubCollector::compute_delta(
+            &first.snapshot,
+            &[finding("a", "NEW", "ACTIVE")],
+            true,
+        );
+        assert!(second.new.is_empty());
+        assert!(second.resolved.is_empty());
+        assert!(second.reopened.is_empty());
+    }
+}
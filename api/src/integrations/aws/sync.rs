@@ -2,26 +2,43 @@ use super::client::AwsClient;
 use super::config::AwsConfig;
 use super::services::{
     cloudtrail::CloudTrailCollector, config_service::ConfigCollector, ec2::Ec2Collector,
-    iam::IamCollector, rds::RdsCollector, s3::S3Collector, securityhub::SecurityHubCollector,
+    iam::{EscalationSnapshotStore, IamCollector},
+    rds::RdsCollector, s3::S3Collector,
+    securityhub::{FindingSnapshotStore, SecurityHubCollector},
 };
+use crate::integrations::telemetry;
 use crate::integrations::{SyncContext, SyncResult};
+use std::time::Instant;
 
 /// Run the full AWS sync across all enabled services
 pub async fn run_sync(
     client: &AwsClient,
     config: &AwsConfig,
     context: &SyncContext,
+    snapshots: &dyn FindingSnapshotStore,
+    escalation_snapshots: &dyn EscalationSnapshotStore,
 ) -> Result<SyncResult, String> {
+    let span = tracing::info_span!(
+        "integration_sync",
+        integration = "aws",
+        integration_id = %context.integration_id,
+    );
+    let _guard = span.enter();
     let mut result = SyncResult::default();
 
     // Sync global services (IAM)
     if config.services.iam {
+        let _svc = tracing::info_span!("aws_service", service = "iam").entered();
         tracing::info!(
             integration_id = %context.integration_id,
             "Syncing AWS IAM"
         );
-        match IamCollector::sync(client, context).await {
-            Ok(iam_result) => result.merge(iam_result),
+        let started = Instant::now();
+        match IamCollector::sync(client, context, escalation_snapshots).await {
+            Ok(iam_result) => {
+                telemetry::record_service("aws", "iam", started, &iam_result);
+                result.merge(iam_result)
+            }
             Err(e) => {
                 tracing::error!(error = %e, "Failed to sync IAM");
                 result = result.with_error(crate::integrations::provider::SyncError::new(
@@ -36,6 +53,7 @@ pub async fn run_sync(
     let regions = config.all_regions();
 
     for region in &regions {
+        let _region_span = tracing::info_span!("aws_region", region = %region).entered();
         tracing::info!(
             integration_id = %context.integration_id,
             region = %region,
@@ -44,8 +62,20 @@ pub async fn run_sync(
 
         // Security Hub
         if config.services.securityhub {
-            match SecurityHubCollector::sync(client, context, region).await {
-                Ok(sh_result) => result.merge(sh_result),
+            let started = Instant::now();
+            match SecurityHubCollector::sync(
+                client,
+                context,
+                region,
+                snapshots,
+                &config.sync_options.remediation_sla,
+            )
+            .await
+            {
+                Ok(sh_result) => {
+                    telemetry::record_service("aws", "securityhub", started, &sh_result);
+                    result.merge(sh_result)
+                }
                 Err(e) => {
                     tracing::warn!(error = %e, region = %region, "Failed to sync Security Hub");
                     result = result.with_error(
@@ -58,8 +88,12 @@ pub async fn run_sync(
 
         // AWS Config
         if config.services.config {
+            let started = Instant::now();
             match ConfigCollector::sync(client, context, region).await {
-                Ok(cfg_result) => result.merge(cfg_result),
+                Ok(cfg_result) => {
+                    telemetry::record_service("aws", "config", started, &cfg_result);
+                    result.merge(cfg_result)
+                }
                 Err(e) => {
                     tracing::warn!(error = %e, region = %region, "Failed to sync AWS Config");
                     result = result.with_error(
@@ -72,8 +106,12 @@ pub async fn run_sync(
 
         // CloudTrail
         if config.services.cloudtrail {
+            let started = Instant::now();
             match CloudTrailCollector::sync(client, context, region, config.sync_options.cloudtrail_hours).await {
-                Ok(ct_result) => result.merge(ct_result),
+                Ok(ct_result) => {
+                    telemetry::record_service("aws", "cloudtrail", started, &ct_result);
+                    result.merge(ct_result)
+                }
                 Err(e) => {
                     tracing::warn!(error = %e, region = %region, "Failed to sync CloudTrail");
                     result = result.with_error(
@@ -86,8 +124,12 @@ pub async fn run_sync(
 
         // EC2
         if config.services.ec2 {
+            let started = Instant::now();
             match Ec2Collector::sync(client, context, region).await {
-                Ok(ec2_result) => result.merge(ec2_result),
+                Ok(ec2_result) => {
+                    telemetry::record_service("aws", "ec2", started, &ec2_result);
+                    result.merge(ec2_result)
+                }
                 Err(e) => {
                     tracing::warn!(error = %e, region = %region, "Failed to sync EC2");
                     result = result.with_error(
@@ -100,8 +142,12 @@ pub async fn run_sync(
 
         // RDS
         if config.services.rds {
+            let started = Instant::now();
             match RdsCollector::sync(client, context, region).await {
-                Ok(rds_result) => result.merge(rds_result),
+                Ok(rds_result) => {
+                    telemetry::record_service("aws", "rds", started, &rds_result);
+                    result.merge(rds_result)
+                }
                 Err(e) => {
                     tracing::warn!(error = %e, region = %region, "Failed to sync RDS");
                     result = result.with_error(
@@ -115,12 +161,17 @@ pub async fn run_sync(
 
     // S3 (global list, but bucket locations vary)
     if config.services.s3 {
+        let _svc = tracing::info_span!("aws_service", service = "s3").entered();
         tracing::info!(
             integration_id = %context.integration_id,
             "Syncing AWS S3"
         );
+        let started = Instant::now();
         match S3Collector::sync(client, context).await {
-            Ok(s3_result) => result.merge(s3_result),
+            Ok(s3_result) => {
+                telemetry::record_service("aws", "s3", started, &s3_result);
+                result.merge(s3_result)
+            }
             Err(e) => {
                 tracing::error!(error = %e, "Failed to sync S3");
                 result = result.with_error(crate::integrations::provider::SyncError::new(
@@ -0,0 +1,243 @@
+//! Cross-integration finding-to-ticket bridge.
+//!
+//! Wires the AWS Security Hub collector to the Jira client so that severe
+//! findings surfaced during an AWS sync are opened as remediation issues and
+//! kept in step on subsequent syncs. The bridge is deliberately provider
+//! agnostic on the ticket side (it only needs a [`JiraClient`]) and keys every
+//! ticket to the stable Security Hub finding `id` so re-observed findings
+//! update the existing issue instead of spawning duplicates.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use super::aws::services::securityhub::AwsSecurityHubFinding;
+use super::jira::client::JiraClient;
+
+/// Configuration for the Security Hub → Jira bridge.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FindingBridgeConfig {
+    /// Whether the bridge is active.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Jira project key that remediation issues are created under.
+    pub project_key: String,
+    /// Jira issue type to create (e.g. `Bug`, `Task`).
+    #[serde(default = "default_issue_type")]
+    pub issue_type: String,
+    /// Lowest severity label that triggers a ticket, inclusive.
+    #[serde(default = "default_threshold")]
+    pub severity_threshold: String,
+    /// Labels applied to every issue the bridge creates.
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Per-severity label applied in addition to [`FindingBridgeConfig::labels`].
+    #[serde(default)]
+    pub severity_labels: HashMap<String, String>,
+    /// Transition target used to close an issue once its finding is resolved.
+    #[serde(default = "default_resolved_status")]
+    pub resolved_status: String,
+}
+
+fn default_issue_type() -> String {
+    "Bug".to_string()
+}
+
+fn default_threshold() -> String {
+    "CRITICAL".to_string()
+}
+
+fn default_resolved_status() -> String {
+    "Done".to_string()
+}
+
+impl FindingBridgeConfig {
+    /// Normalized rank for a Security Hub severity label; higher is more severe.
+    fn severity_rank(label: &str) -> i32 {
+        match label.to_uppercase().as_str() {
+            "CRITICAL" => 4,
+            "HIGH" => 3,
+            "MEDIUM" => 2,
+            "LOW" => 1,
+            _ => 0,
+        }
+    }
+
+    /// Whether a finding with the given severity label clears the threshold.
+    fn meets_threshold(&self, severity_label: &str) -> bool {
+        Self::severity_rank(severity_label) >= Self::severity_rank(&self.severity_threshold)
+    }
+
+    /// Labels to attach to an issue for a finding of the given severity.
+    fn labels_for(&self, severity_label: &str) -> Vec<String> {
+        let mut labels = self.labels.clone();
+        if let Some(mapped) = self.severity_labels.get(&severity_label.to_uppercase()) {
+            labels.push(mapped.clone());
+        }
+        labels
+    }
+}
+
+/// Link between a Security Hub finding and the Jira issue tracking it.
+///
+/// Persisted out of band so a later sync can find the ticket it opened for a
+/// given finding `id` rather than creating a new one.
+pub trait FindingTicketStore: Send + Sync {
+    /// Issue key previously linked to this finding, if any.
+    fn get(&self, finding_id: &str) -> Option<String>;
+    /// Record the issue key opened for a finding.
+    fn put(&self, finding_id: &str, issue_key: &str);
+    /// Forget a link once its finding is resolved.
+    fn remove(&self, finding_id: &str);
+}
+
+/// In-memory [`FindingTicketStore`] suitable for a single-process deployment.
+#[derive(Default)]
+pub struct InMemoryFindingTicketStore {
+    links: Mutex<HashMap<String, String>>,
+}
+
+impl InMemoryFindingTicketStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl FindingTicketStore for InMemoryFindingTicketStore {
+    fn get(&self, finding_id: &str) -> Option<String> {
+        self.links.lock().unwrap().get(finding_id).cloned()
+    }
+
+    fn put(&self, finding_id: &str, issue_key: &str) {
+        self.links
+            .lock()
+            .unwrap()
+            .insert(finding_id.to_string(), issue_key.to_string());
+    }
+
+    fn remove(&self, finding_id: &str) {
+        self.links.lock().unwrap().remove(finding_id);
+    }
+}
+
+/// Summary of what a bridge reconciliation pass did.
+#[derive(Debug, Clone, Default)]
+pub struct BridgeReport {
+    pub created: u32,
+    pub updated: u32,
+    pub resolved: u32,
+}
+
+/// Bridges Security Hub findings to Jira issues.
+pub struct FindingBridge<'a, S: FindingTicketStore> {
+    client: &'a JiraClient,
+    config: &'a FindingBridgeConfig,
+    store: &'a S,
+}
+
+impl<'a, S: FindingTicketStore> FindingBridge<'a, S> {
+    pub fn new(client: &'a JiraClient, config: &'a FindingBridgeConfig, store: &'a S) -> Self {
+        Self {
+            client,
+            config,
+            store,
+        }
+    }
+
+    /// Create, update, or close Jira issues to match the given findings.
+    ///
+    /// The `jira_issue_key` field of each linked finding is populated in place
+    /// so callers can persist it alongside the finding record.
+    pub async fn reconcile(
+        &self,
+        findings: &mut [AwsSecurityHubFinding],
+    ) -> Result<BridgeReport, String> {
+        let mut report = BridgeReport::default();
+
+        for finding in findings.iter_mut() {
+            if !self.config.meets_threshold(&finding.severity_label) {
+                continue;
+            }
+
+            let resolved =
+                finding.workflow_status == "RESOLVED" || finding.record_state == "ARCHIVED";
+            let existing = self.store.get(&finding.id);
+
+            match (resolved, existing) {
+                (true, Some(issue_key)) => {
+                    self.client
+                        .add_comment(
+                            &issue_key,
+                            &format!(
+                                "Security Hub finding resolved (workflow_status={}, record_state={}).",
+                                finding.workflow_status, finding.record_state
+                            ),
+                        )
+                        .await?;
+                    self.client
+                        .transition_issue(&issue_key, &self.config.resolved_status)
+                        .await?;
+                    self.store.remove(&finding.id);
+                    finding.jira_issue_key = Some(issue_key);
+                    report.resolved += 1;
+                }
+                (true, None) => {
+                    // Already resolved and never ticketed; nothing to do.
+                }
+                (false, Some(issue_key)) => {
+                    self.client
+                        .add_comment(&issue_key, &self.comment_body(finding))
+                        .await?;
+                    finding.jira_issue_key = Some(issue_key);
+                    report.updated += 1;
+                }
+                (false, None) => {
+                    let key = self
+                        .client
+                        .create_issue(
+                            &self.config.project_key,
+                            &self.config.issue_type,
+                            &format!("[{}] {}", finding.severity_label, finding.title),
+                            &self.issue_body(finding),
+                            &self.config.labels_for(&finding.severity_label),
+                        )
+                        .await?;
+                    self.store.put(&finding.id, &key);
+                    finding.jira_issue_key = Some(key);
+                    report.created += 1;
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Issue description linking back to the AWS finding.
+    fn issue_body(&self, finding: &AwsSecurityHubFinding) -> String {
+        let mut body = format!(
+            "{}\n\nFinding ID: {}\nProduct ARN: {}\nAccount: {} ({})\nSeverity: {}",
+            finding.description,
+            finding.id,
+            finding.product_arn,
+            finding.aws_account_id,
+            finding.region,
+            finding.severity_label,
+        );
+        if let Some(text) = &finding.remediation_text {
+            body.push_str(&format!("\n\nRemediation: {}", text));
+        }
+        if let Some(url) = &finding.remediation_url {
+            body.push_str(&format!("\nRemediation guide: {}", url));
+        }
+        body
+    }
+
+    /// Comment left when a still-open finding is re-observed.
+    fn comment_body(&self, finding: &AwsSecurityHubFinding) -> String {
+        format!(
+            "Finding re-observed during sync (workflow_status={}, severity={}).",
+            finding.workflow_status, finding.severity_label
+        )
+    }
+}
@@ -388,6 +388,44 @@ impl GitHubClient {
         self.get(&url).await
     }
 
+    /// Fetch a file's raw contents from the default branch, returning `None`
+    /// when the file is absent.
+    pub async fn get_file_contents(
+        &self,
+        owner: &str,
+        repo: &str,
+        path: &str,
+    ) -> Result<Option<String>, String> {
+        let url = format!(
+            "{}/repos/{}/{}/contents/{}",
+            GITHUB_API_URL, owner, repo, path
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Accept", "application/vnd.github.raw")
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        let status = response.status();
+        if status.as_u16() == 404 {
+            return Ok(None);
+        }
+
+        let body = response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read response: {}", e))?;
+
+        if !status.is_success() {
+            return Err(format!("GitHub API error ({}): {}", status, body));
+        }
+
+        Ok(Some(body))
+    }
+
     async fn get<T: for<'de> Deserialize<'de>>(&self, url: &str) -> Result<T, String> {
         let response = self.client
             .get(url)
@@ -13,6 +13,9 @@ pub struct GitHubConfig {
     /// Services to enable
     #[serde(default)]
     pub services: GitHubServicesConfig,
+    /// Threshold-based alerting relay for high-severity findings
+    #[serde(default)]
+    pub notifications: Option<super::notifier::NotifierConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -32,6 +35,9 @@ pub struct GitHubServicesConfig {
     /// Sync secret scanning alerts
     #[serde(default = "default_true")]
     pub secret_scanning: bool,
+    /// Cross-reference dependencies against OSV/RustSec beyond GitHub's set
+    #[serde(default = "default_true")]
+    pub osv_alerts: bool,
     /// Sync organization members
     #[serde(default = "default_true")]
     pub members: bool,
@@ -1,5 +1,6 @@
 pub mod client;
 pub mod config;
+pub mod notifier;
 pub mod provider;
 pub mod services;
 pub mod sync;
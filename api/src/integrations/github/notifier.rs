@@ -0,0 +1,487 @@
+//! Threshold-based alerting relay for security sync findings.
+//!
+//! The collectors only accumulate evidence; a `critical` Dependabot advisory or
+//! a bypassed secret push-protection is otherwise invisible until someone reads
+//! the report. This module inspects a finished [`SyncResult`], extracts the
+//! findings that cross the configured thresholds, and relays them to the
+//! enabled channels (generic webhook, Slack, email relay). Dependabot and secret
+//! findings are keyed by `kind:repo#number`, the critical code-scanning total by
+//! its count, and every key is remembered in a persisted set so the same alert
+//! is not re-sent on every sync cycle.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use crate::integrations::provider::SyncResult;
+use crate::utils::hmac_sha256;
+
+/// Notification configuration, parsed from the integration config.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotifierConfig {
+    /// Generic webhook endpoint; payloads are HMAC-signed when `webhook_secret`
+    /// is set.
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    #[serde(default)]
+    pub webhook_secret: Option<String>,
+    /// Slack incoming-webhook URL.
+    #[serde(default)]
+    pub slack_webhook_url: Option<String>,
+    /// Email relay endpoint and recipient (JSON `{to, subject, body}` POST).
+    #[serde(default)]
+    pub email_relay_url: Option<String>,
+    #[serde(default)]
+    pub email_to: Option<String>,
+    /// Directory used to persist the last-notified set between syncs.
+    #[serde(default)]
+    pub state_dir: Option<String>,
+    #[serde(default)]
+    pub thresholds: AlertThresholds,
+}
+
+/// When a finding is severe enough to escalate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertThresholds {
+    /// Minimum Dependabot severity to notify on (default `critical`).
+    #[serde(default = "default_min_severity")]
+    pub dependabot_min_severity: String,
+    /// Notify when a secret has push protection bypassed (default `true`).
+    #[serde(default = "default_true")]
+    pub notify_secret_bypass: bool,
+    /// Notify when the critical code-scanning count exceeds this (default 0).
+    #[serde(default)]
+    pub code_scanning_critical_max: i64,
+}
+
+impl Default for AlertThresholds {
+    fn default() -> Self {
+        Self {
+            dependabot_min_severity: default_min_severity(),
+            notify_secret_bypass: true,
+            code_scanning_critical_max: 0,
+        }
+    }
+}
+
+fn default_min_severity() -> String {
+    "critical".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// A single escalation candidate derived from the sync evidence.
+pub struct Alert {
+    pub dedup_key: String,
+    pub kind: String,
+    pub severity: String,
+    pub repository: String,
+    pub title: String,
+    pub summary: String,
+    pub url: Option<String>,
+}
+
+impl Alert {
+    fn to_json(&self) -> Value {
+        json!({
+            "kind": self.kind,
+            "severity": self.severity,
+            "repository": self.repository,
+            "title": self.title,
+            "summary": self.summary,
+            "url": self.url,
+        })
+    }
+
+    /// A short human-readable line for chat/email channels.
+    fn headline(&self) -> String {
+        format!(
+            "[{}] {} in {}: {}",
+            self.severity.to_uppercase(),
+            self.kind,
+            self.repository,
+            self.title
+        )
+    }
+}
+
+/// A channel that can deliver an [`Alert`].
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    fn channel(&self) -> &'static str;
+    async fn send(&self, alert: &Alert) -> Result<(), String>;
+}
+
+/// Generic webhook channel; signs the payload with HMAC-SHA256 when a secret is
+/// configured so receivers can verify authenticity.
+struct WebhookNotifier {
+    url: String,
+    secret: Option<String>,
+    client: reqwest::Client,
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    fn channel(&self) -> &'static str {
+        "webhook"
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), String> {
+        let body = serde_json::to_vec(&alert.to_json())
+            .map_err(|e| format!("Failed to encode webhook payload: {}", e))?;
+
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header("Content-Type", "application/json");
+        if let Some(secret) = &self.secret {
+            let signature = hex_encode(&hmac_sha256(secret.as_bytes(), &body));
+            request = request.header("X-OpenGRC-Signature", format!("sha256={}", signature));
+        }
+
+        let response = request
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| format!("Webhook request failed: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("Webhook returned {}", response.status()));
+        }
+        Ok(())
+    }
+}
+
+/// Slack incoming-webhook channel.
+struct SlackNotifier {
+    url: String,
+    client: reqwest::Client,
+}
+
+#[async_trait]
+impl Notifier for SlackNotifier {
+    fn channel(&self) -> &'static str {
+        "slack"
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), String> {
+        let text = match &alert.url {
+            Some(url) => format!("{}\n{}", alert.headline(), url),
+            None => alert.headline(),
+        };
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&json!({ "text": text }))
+            .send()
+            .await
+            .map_err(|e| format!("Slack request failed: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("Slack returned {}", response.status()));
+        }
+        Ok(())
+    }
+}
+
+/// Email relay channel; POSTs `{to, subject, body}` to a relay endpoint.
+struct EmailNotifier {
+    url: String,
+    to: String,
+    client: reqwest::Client,
+}
+
+#[async_trait]
+impl Notifier for EmailNotifier {
+    fn channel(&self) -> &'static str {
+        "email"
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), String> {
+        let payload = json!({
+            "to": self.to,
+            "subject": alert.headline(),
+            "body": format!("{}\n\n{}", alert.summary, alert.url.as_deref().unwrap_or("")),
+        });
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| format!("Email relay request failed: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("Email relay returned {}", response.status()));
+        }
+        Ok(())
+    }
+}
+
+/// Drives threshold evaluation, deduplication and fan-out to the channels.
+pub struct NotificationRelay {
+    channels: Vec<Box<dyn Notifier>>,
+    thresholds: AlertThresholds,
+    store: FileNotificationStore,
+    notified: HashSet<String>,
+}
+
+impl NotificationRelay {
+    /// Build a relay from config for `integration_id`, loading the last-notified
+    /// set. Returns `None` when no channels are configured.
+    pub fn from_config(config: &NotifierConfig, integration_id: &str) -> Option<Self> {
+        let client = reqwest::Client::new();
+        let mut channels: Vec<Box<dyn Notifier>> = Vec::new();
+
+        if let Some(url) = &config.webhook_url {
+            channels.push(Box::new(WebhookNotifier {
+                url: url.clone(),
+                secret: config.webhook_secret.clone(),
+                client: client.clone(),
+            }));
+        }
+        if let Some(url) = &config.slack_webhook_url {
+            channels.push(Box::new(SlackNotifier {
+                url: url.clone(),
+                client: client.clone(),
+            }));
+        }
+        if let (Some(url), Some(to)) = (&config.email_relay_url, &config.email_to) {
+            channels.push(Box::new(EmailNotifier {
+                url: url.clone(),
+                to: to.clone(),
+                client: client.clone(),
+            }));
+        }
+
+        if channels.is_empty() {
+            return None;
+        }
+
+        let store = FileNotificationStore::new(config.state_dir.as_deref(), integration_id);
+        let notified = store.load();
+        Some(Self {
+            channels,
+            thresholds: config.thresholds.clone(),
+            store,
+            notified,
+        })
+    }
+
+    /// Evaluate a collector's result, notifying on any fresh threshold crossing.
+    pub async fn process(&mut self, result: &SyncResult) {
+        let alerts = self.extract_alerts(result);
+        let mut dirty = false;
+
+        for alert in alerts {
+            if self.notified.contains(&alert.dedup_key) {
+                continue;
+            }
+
+            let mut delivered = false;
+            for channel in &self.channels {
+                match channel.send(&alert).await {
+                    Ok(()) => delivered = true,
+                    Err(e) => {
+                        tracing::warn!(
+                            channel = channel.channel(),
+                            key = %alert.dedup_key,
+                            error = %e,
+                            "Failed to relay security alert"
+                        );
+                    }
+                }
+            }
+
+            if delivered {
+                self.notified.insert(alert.dedup_key.clone());
+                dirty = true;
+            }
+        }
+
+        if dirty {
+            self.store.save(&self.notified);
+        }
+    }
+
+    /// Pull threshold-crossing findings out of the collected evidence.
+    fn extract_alerts(&self, result: &SyncResult) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        let min_rank = severity_rank(&self.thresholds.dependabot_min_severity);
+
+        for evidence in &result.evidence_collected {
+            match evidence.source_reference.as_deref() {
+                Some("github:dependabot-alerts") => {
+                    let by_repo = evidence
+                        .data
+                        .get("alerts_by_repository")
+                        .and_then(|v| v.as_object());
+                    if let Some(by_repo) = by_repo {
+                        for (repo, repo_alerts) in by_repo {
+                            for alert in repo_alerts.as_array().into_iter().flatten() {
+                                let severity = alert
+                                    .get("severity")
+                                    .and_then(|v| v.as_str())
+                                    .unwrap_or("unknown");
+                                if severity_rank(severity) < min_rank {
+                                    continue;
+                                }
+                                let number =
+                                    alert.get("number").and_then(|v| v.as_i64()).unwrap_or(0);
+                                let package = alert
+                                    .get("package")
+                                    .and_then(|p| p.get("name"))
+                                    .and_then(|v| v.as_str())
+                                    .unwrap_or("unknown");
+                                let summary = alert
+                                    .get("advisory")
+                                    .and_then(|a| a.get("summary"))
+                                    .and_then(|v| v.as_str())
+                                    .unwrap_or("")
+                                    .to_string();
+                                alerts.push(Alert {
+                                    dedup_key: format!("dependabot:{}#{}", repo, number),
+                                    kind: "dependabot".to_string(),
+                                    severity: severity.to_string(),
+                                    repository: repo.clone(),
+                                    title: package.to_string(),
+                                    summary,
+                                    url: alert
+                                        .get("html_url")
+                                        .and_then(|v| v.as_str())
+                                        .map(String::from),
+                                });
+                            }
+                        }
+                    }
+                }
+                Some("github:secret-scanning-alerts") if self.thresholds.notify_secret_bypass => {
+                    for alert in evidence
+                        .data
+                        .get("alerts")
+                        .and_then(|v| v.as_array())
+                        .into_iter()
+                        .flatten()
+                    {
+                        let bypassed = alert
+                            .get("push_protection_bypassed")
+                            .and_then(|v| v.as_bool())
+                            .unwrap_or(false);
+                        if !bypassed {
+                            continue;
+                        }
+                        let repo = alert
+                            .get("repository")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("unknown");
+                        let number = alert.get("number").and_then(|v| v.as_i64()).unwrap_or(0);
+                        let kind_name = alert
+                            .get("secret_type_display_name")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("secret");
+                        alerts.push(Alert {
+                            dedup_key: format!("secret:{}#{}", repo, number),
+                            kind: "secret_scanning".to_string(),
+                            severity: "critical".to_string(),
+                            repository: repo.to_string(),
+                            title: format!("{} with push protection bypassed", kind_name),
+                            summary: "A detected secret was pushed with protection bypassed."
+                                .to_string(),
+                            url: alert
+                                .get("html_url")
+                                .and_then(|v| v.as_str())
+                                .map(String::from),
+                        });
+                    }
+                }
+                Some("github:code-scanning-alerts") => {
+                    let critical = evidence
+                        .data
+                        .get("by_severity")
+                        .and_then(|v| v.get("critical"))
+                        .and_then(|v| v.as_i64())
+                        .unwrap_or(0);
+                    if critical > self.thresholds.code_scanning_critical_max {
+                        // Key on the count so a rising total re-escalates.
+                        alerts.push(Alert {
+                            dedup_key: format!("code_scanning:critical:{}", critical),
+                            kind: "code_scanning".to_string(),
+                            severity: "critical".to_string(),
+                            repository: "organization".to_string(),
+                            title: format!("{} critical code-scanning alerts", critical),
+                            summary: format!(
+                                "Critical code-scanning alerts ({}) exceed the configured threshold of {}.",
+                                critical, self.thresholds.code_scanning_critical_max
+                            ),
+                            url: None,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        alerts
+    }
+}
+
+/// File-backed persistence for the last-notified dedup set, one JSON file per
+/// integration.
+struct FileNotificationStore {
+    path: PathBuf,
+}
+
+impl FileNotificationStore {
+    fn new(state_dir: Option<&str>, integration_id: &str) -> Self {
+        let dir = state_dir
+            .map(PathBuf::from)
+            .unwrap_or_else(|| std::env::temp_dir().join("opengrc-notifications"));
+        let safe_id: String = integration_id
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        Self {
+            path: dir.join(format!("{}.json", safe_id)),
+        }
+    }
+
+    fn load(&self) -> HashSet<String> {
+        match std::fs::read(&self.path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_default(),
+            Err(_) => HashSet::new(),
+        }
+    }
+
+    fn save(&self, notified: &HashSet<String>) {
+        if let Some(parent) = self.path.parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                tracing::warn!(error = %e, "Failed to create notification state dir");
+                return;
+            }
+        }
+        match serde_json::to_vec(notified) {
+            Ok(bytes) => {
+                if let Err(e) = std::fs::write(&self.path, bytes) {
+                    tracing::warn!(error = %e, "Failed to persist notification state");
+                }
+            }
+            Err(e) => tracing::warn!(error = %e, "Failed to encode notification state"),
+        }
+    }
+}
+
+/// Order severities so thresholds can be compared numerically.
+fn severity_rank(severity: &str) -> u8 {
+    match severity.to_lowercase().as_str() {
+        "critical" => 4,
+        "high" => 3,
+        "medium" | "moderate" => 2,
+        "low" => 1,
+        _ => 0,
+    }
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
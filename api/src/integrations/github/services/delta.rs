@@ -0,0 +1,157 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+
+/// State of a single alert recorded at the end of a sync. Entries live in an
+/// [`AlertSnapshot`] keyed by `provider:repo#number`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertStateEntry {
+    /// When the alert was opened upstream (RFC3339), the preferred start of the
+    /// remediation clock.
+    pub created_at: Option<String>,
+    /// When this integration first observed the alert, used as the clock start
+    /// when the provider does not expose `created_at`.
+    pub first_seen_at: String,
+    pub severity: String,
+}
+
+/// Snapshot of the open-alert set persisted between syncs, keyed by
+/// `provider:repo#number`. Carried in and out via `SyncContext`.
+pub type AlertSnapshot = HashMap<String, AlertStateEntry>;
+
+/// A single open alert observed during the current sync.
+pub struct AlertObservation {
+    /// Stable key `provider:repo#number`.
+    pub key: String,
+    pub created_at: Option<String>,
+    pub severity: String,
+}
+
+/// A previously-open alert no longer present in the current sync.
+#[derive(Debug, Clone)]
+pub struct ResolvedAlert {
+    pub key: String,
+    pub severity: String,
+    /// Days between the alert opening and this sync observing its resolution.
+    pub time_to_remediate_days: Option<i64>,
+}
+
+/// Velocity delta between the prior snapshot and the current open-alert set for
+/// one provider.
+#[derive(Debug, Default)]
+pub struct AlertDelta {
+    /// Keys present now but absent from the prior snapshot.
+    pub newly_opened: Vec<String>,
+    /// Alerts open in the prior snapshot but gone now, with their remediation
+    /// time.
+    pub resolved: Vec<ResolvedAlert>,
+    /// Snapshot to persist for the next run (this provider's entries only).
+    pub snapshot: AlertSnapshot,
+}
+
+/// Decode the persisted snapshot carried in `SyncContext.prior_alert_state`,
+/// falling back to an empty snapshot when absent or unparseable.
+pub fn snapshot_from_value(value: Option<&serde_json::Value>) -> AlertSnapshot {
+    value
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// Compute the newly-opened / resolved delta for a single provider.
+///
+/// `prior` is filtered to `prefix` (e.g. `"dependabot:"`) so resolution-by-
+/// absence only considers alerts this provider owns; unrelated entries in the
+/// shared snapshot are preserved untouched. The alert's `created_at` (or, when
+/// missing, the earliest observation) anchors the mean-time-to-remediate math.
+pub fn compute_delta(
+    prior: &AlertSnapshot,
+    prefix: &str,
+    current: &[AlertObservation],
+    now: DateTime<Utc>,
+) -> AlertDelta {
+    let mut delta = AlertDelta::default();
+    let current_keys: std::collections::HashSet<&str> =
+        current.iter().map(|o| o.key.as_str()).collect();
+
+    for obs in current {
+        if !prior.contains_key(&obs.key) {
+            delta.newly_opened.push(obs.key.clone());
+        }
+        // Preserve the original first-seen timestamp across re-observations.
+        let first_seen_at = prior
+            .get(&obs.key)
+            .map(|e| e.first_seen_at.clone())
+            .unwrap_or_else(|| now.to_rfc3339());
+        delta.snapshot.insert(
+            obs.key.clone(),
+            AlertStateEntry {
+                created_at: obs.created_at.clone(),
+                first_seen_at,
+                severity: obs.severity.clone(),
+            },
+        );
+    }
+
+    for (key, entry) in prior {
+        if !key.starts_with(prefix) || current_keys.contains(key.as_str()) {
+            continue;
+        }
+        let opened = entry
+            .created_at
+            .as_deref()
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|d| d.with_timezone(&Utc))
+            .or_else(|| {
+                DateTime::parse_from_rfc3339(&entry.first_seen_at)
+                    .ok()
+                    .map(|d| d.with_timezone(&Utc))
+            });
+        delta.resolved.push(ResolvedAlert {
+            key: key.clone(),
+            severity: entry.severity.clone(),
+            time_to_remediate_days: opened.map(|o| (now - o).num_days().max(0)),
+        });
+    }
+
+    delta
+}
+
+impl AlertDelta {
+    /// Mean remediation time across the resolved alerts that carry a duration.
+    pub fn mean_time_to_remediate_days(&self) -> Option<i64> {
+        let durations: Vec<i64> = self
+            .resolved
+            .iter()
+            .filter_map(|r| r.time_to_remediate_days)
+            .collect();
+        if durations.is_empty() {
+            None
+        } else {
+            Some(durations.iter().sum::<i64>() / durations.len() as i64)
+        }
+    }
+
+    /// Compact summary embedded in each collector's `CollectedEvidence.data`.
+    pub fn summary_json(&self) -> serde_json::Value {
+        json!({
+            "newly_opened": self.newly_opened.len(),
+            "resolved_since_last_sync": self.resolved.len(),
+            "mean_time_to_remediate_days": self.mean_time_to_remediate_days(),
+        })
+    }
+
+    /// Detailed resolved-alert listing for the dedicated velocity evidence.
+    pub fn resolved_json(&self) -> Vec<serde_json::Value> {
+        self.resolved
+            .iter()
+            .map(|r| {
+                json!({
+                    "alert": r.key,
+                    "severity": r.severity,
+                    "time_to_remediate_days": r.time_to_remediate_days,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,595 @@
+//! Dependency-graph reconstruction from repository lockfiles.
+//!
+//! Dependabot tells us *which* package is vulnerable but not whether it sits at
+//! the top of the tree or is dragged in transitively, nor which direct
+//! dependency owns the fix. This module parses the lockfiles a repository
+//! commits (`Cargo.lock`, `package-lock.json`, `yarn.lock`, `poetry.lock`),
+//! builds an in-memory graph keyed by `(ecosystem, name)`, and walks it upward
+//! from a vulnerable package to the project roots so the collector can attach
+//! the shortest dependency paths and a direct/transitive classification.
+//!
+//! Parsing is deliberately forgiving: an unrecognised or truncated lockfile
+//! contributes nothing rather than aborting the build, so attribution silently
+//! degrades to "unknown" instead of failing the collection run.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// `(ecosystem, name)`; the ecosystem is normalised to match the strings
+/// Dependabot reports (`rust`, `npm`, `pip`).
+type NodeKey = (String, String);
+
+/// Result of attributing a vulnerable package against the graph.
+#[derive(Default)]
+pub struct Attribution {
+    /// The package was located in the graph.
+    pub found: bool,
+    /// `"direct"` or `"transitive"` when known.
+    pub classification: Option<&'static str>,
+    /// Rendered shortest paths, e.g. `my-app -> tokio -> mio@0.8.0`.
+    pub paths: Vec<String>,
+    /// Top-level dependencies that pull the package in (deduplicated).
+    pub direct_dependencies: Vec<String>,
+}
+
+/// A dependency graph assembled from one or more lockfiles.
+#[derive(Default)]
+pub struct DependencyGraph {
+    versions: HashMap<NodeKey, String>,
+    dependents: HashMap<NodeKey, Vec<NodeKey>>,
+    nodes: HashSet<NodeKey>,
+    roots: HashSet<NodeKey>,
+    /// Ecosystems whose roots were declared explicitly by the lockfile (so we
+    /// don't additionally treat in-degree-0 nodes as roots for them).
+    explicit_root_ecos: HashSet<String>,
+}
+
+/// An intermediate package record produced by a lockfile parser.
+#[derive(Default)]
+struct RawPkg {
+    name: String,
+    version: String,
+    deps: Vec<String>,
+}
+
+/// Lockfiles recognised by the graph builder, in the order we prefer to fetch
+/// them. The paths are repository-root relative.
+pub const LOCKFILE_PATHS: &[&str] = &[
+    "Cargo.lock",
+    "package-lock.json",
+    "yarn.lock",
+    "poetry.lock",
+];
+
+impl DependencyGraph {
+    /// Build a graph from `(path, contents)` pairs. Unknown filenames and
+    /// unparseable contents are ignored.
+    pub fn build(lockfiles: &[(String, String)]) -> Self {
+        let mut graph = DependencyGraph::default();
+
+        for (path, content) in lockfiles {
+            let base = path.rsplit('/').next().unwrap_or(path);
+            match base {
+                "Cargo.lock" => graph.ingest("rust", parse_cargo_lock(content), None),
+                "poetry.lock" => graph.ingest("pip", parse_poetry_lock(content), None),
+                "yarn.lock" => graph.ingest("npm", parse_yarn_lock(content), None),
+                "package-lock.json" => {
+                    let (pkgs, root) = parse_package_lock(content);
+                    graph.ingest("npm", pkgs, root);
+                }
+                _ => {}
+            }
+        }
+
+        graph.finalize_roots();
+        graph
+    }
+
+    fn ingest(&mut self, eco: &str, pkgs: Vec<RawPkg>, explicit_root: Option<String>) {
+        for pkg in pkgs {
+            if pkg.name.is_empty() {
+                continue;
+            }
+            let key = (eco.to_string(), pkg.name);
+            self.nodes.insert(key.clone());
+            if !pkg.version.is_empty() {
+                self.versions.insert(key.clone(), pkg.version);
+            }
+            for dep in pkg.deps {
+                if dep.is_empty() {
+                    continue;
+                }
+                let child = (eco.to_string(), dep);
+                self.nodes.insert(child.clone());
+                self.dependents
+                    .entry(child)
+                    .or_default()
+                    .push(key.clone());
+            }
+        }
+
+        if let Some(root) = explicit_root {
+            if !root.is_empty() {
+                let key = (eco.to_string(), root);
+                self.nodes.insert(key.clone());
+                self.roots.insert(key);
+            }
+            self.explicit_root_ecos.insert(eco.to_string());
+        }
+    }
+
+    /// For ecosystems without declared roots, treat nodes that nothing depends
+    /// on as the project roots.
+    fn finalize_roots(&mut self) {
+        for node in &self.nodes {
+            if self.explicit_root_ecos.contains(&node.0) {
+                continue;
+            }
+            let has_dependents = self
+                .dependents
+                .get(node)
+                .map(|d| !d.is_empty())
+                .unwrap_or(false);
+            if !has_dependents {
+                self.roots.insert(node.clone());
+            }
+        }
+    }
+
+    /// Every known package as `(ecosystem, name, version)`, using the graph's
+    /// normalised ecosystem keys. Packages without a resolved version (such as
+    /// the synthetic project root) are omitted.
+    pub fn packages(&self) -> Vec<(String, String, String)> {
+        self.versions
+            .iter()
+            .map(|((eco, name), version)| (eco.clone(), name.clone(), version.clone()))
+            .collect()
+    }
+
+    /// Walk upward from a vulnerable package to every reachable root, returning
+    /// the shortest path to each (breadth-first, so first reach is shortest)
+    /// and a direct/transitive verdict. Cycles are bounded by a visited set.
+    pub fn attribute(&self, ecosystem: &str, name: &str) -> Attribution {
+        let eco = normalize_eco(ecosystem);
+        let start: NodeKey = (eco, name.to_string());
+        if !self.nodes.contains(&start) {
+            return Attribution::default();
+        }
+
+        // A root package is itself a direct dependency of the project.
+        if self.roots.contains(&start) {
+            return Attribution {
+                found: true,
+                classification: Some("direct"),
+                paths: vec![self.render_node(&start, true)],
+                direct_dependencies: vec![start.1.clone()],
+            };
+        }
+
+        let mut visited: HashSet<NodeKey> = HashSet::new();
+        let mut pred: HashMap<NodeKey, NodeKey> = HashMap::new();
+        let mut queue: VecDeque<NodeKey> = VecDeque::new();
+        visited.insert(start.clone());
+        queue.push_back(start.clone());
+
+        let mut reached_roots: Vec<NodeKey> = Vec::new();
+        while let Some(cur) = queue.pop_front() {
+            if self.roots.contains(&cur) {
+                reached_roots.push(cur.clone());
+            }
+            if let Some(parents) = self.dependents.get(&cur) {
+                for parent in parents {
+                    if visited.insert(parent.clone()) {
+                        pred.insert(parent.clone(), cur.clone());
+                        queue.push_back(parent.clone());
+                    }
+                }
+            }
+        }
+
+        const MAX_PATHS: usize = 8;
+        let mut paths = Vec::new();
+        let mut direct = Vec::new();
+        let mut is_direct = false;
+
+        for root in reached_roots.iter().take(MAX_PATHS) {
+            // Reconstruct root -> ... -> vulnerable by following predecessors.
+            let mut chain: Vec<NodeKey> = vec![root.clone()];
+            let mut node = root.clone();
+            while node != start {
+                match pred.get(&node) {
+                    Some(next) => {
+                        chain.push(next.clone());
+                        node = next.clone();
+                    }
+                    None => break,
+                }
+            }
+            if chain.last() != Some(&start) {
+                continue;
+            }
+
+            // chain == [root, direct_dep, ..., vuln]; length 2 means the
+            // vulnerable package is itself a direct dependency of the root.
+            if chain.len() == 2 {
+                is_direct = true;
+            }
+            if let Some(dep) = chain.get(1) {
+                if !direct.contains(&dep.1) {
+                    direct.push(dep.1.clone());
+                }
+            }
+
+            let rendered: Vec<String> = chain
+                .iter()
+                .enumerate()
+                .map(|(i, key)| self.render_node(key, i == chain.len() - 1))
+                .collect();
+            paths.push(rendered.join(" -> "));
+        }
+
+        let classification = if is_direct { "direct" } else { "transitive" };
+        Attribution {
+            found: true,
+            classification: Some(classification),
+            paths,
+            direct_dependencies: direct,
+        }
+    }
+
+    /// Render a node as `name` or, for the terminal vulnerable node, `name@ver`.
+    fn render_node(&self, key: &NodeKey, with_version: bool) -> String {
+        match (with_version, self.versions.get(key)) {
+            (true, Some(version)) => format!("{}@{}", key.1, version),
+            _ => key.1.clone(),
+        }
+    }
+}
+
+/// Map a Dependabot ecosystem string onto the graph's keys.
+fn normalize_eco(ecosystem: &str) -> String {
+    match ecosystem.to_lowercase().as_str() {
+        "cargo" | "rust" => "rust".to_string(),
+        "pip" | "pypi" | "poetry" => "pip".to_string(),
+        "npm" | "yarn" => "npm".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Extract the value of a `key = "value"` TOML line, if present.
+fn toml_string_value(line: &str, key: &str) -> Option<String> {
+    let trimmed = line.trim();
+    let rest = trimmed.strip_prefix(key)?;
+    let rest = rest.trim_start();
+    let rest = rest.strip_prefix('=')?.trim();
+    Some(rest.trim_matches('"').to_string())
+}
+
+/// Parse `Cargo.lock`'s `[[package]]` blocks. Dependency entries look like
+/// `name`, `name version`, or `name version (source)`; we keep the name.
+fn parse_cargo_lock(content: &str) -> Vec<RawPkg> {
+    let mut pkgs = Vec::new();
+    let mut cur: Option<RawPkg> = None;
+    let mut in_deps = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed == "[[package]]" {
+            if let Some(pkg) = cur.take() {
+                pkgs.push(pkg);
+            }
+            cur = Some(RawPkg::default());
+            in_deps = false;
+            continue;
+        }
+        if trimmed.starts_with('[') {
+            in_deps = false;
+            continue;
+        }
+
+        let Some(pkg) = cur.as_mut() else { continue };
+
+        if in_deps {
+            if trimmed.starts_with(']') {
+                in_deps = false;
+                continue;
+            }
+            if let Some(dep) = cargo_dep_name(trimmed) {
+                pkg.deps.push(dep);
+            }
+            continue;
+        }
+
+        if let Some(name) = toml_string_value(trimmed, "name") {
+            pkg.name = name;
+        } else if let Some(version) = toml_string_value(trimmed, "version") {
+            pkg.version = version;
+        } else if trimmed.starts_with("dependencies") && trimmed.contains('[') {
+            in_deps = true;
+            // Handle an inline array: `dependencies = ["a", "b 1.0"]`.
+            if let Some(start) = trimmed.find('[') {
+                if let Some(end) = trimmed[start + 1..].find(']') {
+                    let inner = &trimmed[start + 1..start + 1 + end];
+                    for part in inner.split(',') {
+                        if let Some(dep) = cargo_dep_name(part.trim()) {
+                            pkg.deps.push(dep);
+                        }
+                    }
+                    in_deps = false;
+                }
+            }
+        }
+    }
+
+    if let Some(pkg) = cur.take() {
+        pkgs.push(pkg);
+    }
+    pkgs
+}
+
+fn cargo_dep_name(entry: &str) -> Option<String> {
+    let cleaned = entry.trim().trim_matches(|c| c == ',' || c == '"');
+    let name = cleaned.split_whitespace().next().unwrap_or("");
+    if name.is_empty() {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
+
+/// Parse `poetry.lock`'s `[[package]]` blocks plus their
+/// `[package.dependencies]` subtables.
+fn parse_poetry_lock(content: &str) -> Vec<RawPkg> {
+    let mut pkgs = Vec::new();
+    let mut cur: Option<RawPkg> = None;
+    let mut in_deps = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed == "[[package]]" {
+            if let Some(pkg) = cur.take() {
+                pkgs.push(pkg);
+            }
+            cur = Some(RawPkg::default());
+            in_deps = false;
+            continue;
+        }
+        if trimmed == "[package.dependencies]" {
+            in_deps = cur.is_some();
+            continue;
+        }
+        if trimmed.starts_with('[') {
+            in_deps = false;
+            continue;
+        }
+
+        let Some(pkg) = cur.as_mut() else { continue };
+
+        if in_deps {
+            if let Some((name, _)) = trimmed.split_once('=') {
+                let name = name.trim().trim_matches('"');
+                if !name.is_empty() {
+                    pkg.deps.push(name.to_string());
+                }
+            }
+            continue;
+        }
+
+        if let Some(name) = toml_string_value(trimmed, "name") {
+            pkg.name = name;
+        } else if let Some(version) = toml_string_value(trimmed, "version") {
+            pkg.version = version;
+        }
+    }
+
+    if let Some(pkg) = cur.take() {
+        pkgs.push(pkg);
+    }
+    pkgs
+}
+
+/// Parse `package-lock.json` (v1 `dependencies` tree or v2/v3 `packages` map),
+/// returning the packages and the project root name when discoverable.
+fn parse_package_lock(content: &str) -> (Vec<RawPkg>, Option<String>) {
+    let Ok(root): Result<serde_json::Value, _> = serde_json::from_str(content) else {
+        return (Vec::new(), None);
+    };
+
+    // Lockfile v2/v3: the "packages" object, keyed by install path.
+    if let Some(packages) = root.get("packages").and_then(|v| v.as_object()) {
+        let mut pkgs = Vec::new();
+        let mut project_root = root
+            .get("name")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+        let mut root_deps: Vec<String> = Vec::new();
+
+        for (key, entry) in packages {
+            let name = if key.is_empty() {
+                entry
+                    .get("name")
+                    .and_then(|v| v.as_str())
+                    .map(String::from)
+            } else {
+                key.rsplit("node_modules/").next().map(String::from)
+            };
+
+            let deps = npm_dep_names(entry, key.is_empty());
+
+            if key.is_empty() {
+                root_deps = deps;
+                if project_root.is_none() {
+                    project_root = name.clone();
+                }
+                continue;
+            }
+
+            if let Some(name) = name {
+                pkgs.push(RawPkg {
+                    name,
+                    version: entry
+                        .get("version")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("")
+                        .to_string(),
+                    deps,
+                });
+            }
+        }
+
+        let root_name = project_root.unwrap_or_else(|| "project-root".to_string());
+        // The synthetic project root depends on the declared direct packages.
+        pkgs.push(RawPkg {
+            name: root_name.clone(),
+            version: String::new(),
+            deps: root_deps,
+        });
+        return (pkgs, Some(root_name));
+    }
+
+    // Lockfile v1: a recursive "dependencies" tree.
+    if let Some(deps) = root.get("dependencies").and_then(|v| v.as_object()) {
+        let mut pkgs = Vec::new();
+        collect_v1_dependencies(deps, &mut pkgs);
+        let root_name = root
+            .get("name")
+            .and_then(|v| v.as_str())
+            .unwrap_or("project-root")
+            .to_string();
+        pkgs.push(RawPkg {
+            name: root_name.clone(),
+            version: String::new(),
+            deps: deps.keys().cloned().collect(),
+        });
+        return (pkgs, Some(root_name));
+    }
+
+    (Vec::new(), None)
+}
+
+fn npm_dep_names(entry: &serde_json::Value, include_dev: bool) -> Vec<String> {
+    let mut names = Vec::new();
+    for field in ["dependencies", "optionalDependencies"] {
+        if let Some(obj) = entry.get(field).and_then(|v| v.as_object()) {
+            names.extend(obj.keys().cloned());
+        }
+    }
+    if include_dev {
+        if let Some(obj) = entry.get("devDependencies").and_then(|v| v.as_object()) {
+            names.extend(obj.keys().cloned());
+        }
+    }
+    names
+}
+
+fn collect_v1_dependencies(deps: &serde_json::Map<String, serde_json::Value>, out: &mut Vec<RawPkg>) {
+    for (name, entry) in deps {
+        let version = entry
+            .get("version")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string();
+        let mut child_names: Vec<String> = Vec::new();
+        if let Some(requires) = entry.get("requires").and_then(|v| v.as_object()) {
+            child_names.extend(requires.keys().cloned());
+        }
+        if let Some(nested) = entry.get("dependencies").and_then(|v| v.as_object()) {
+            child_names.extend(nested.keys().cloned());
+            collect_v1_dependencies(nested, out);
+        }
+        out.push(RawPkg {
+            name: name.clone(),
+            version,
+            deps: child_names,
+        });
+    }
+}
+
+/// Parse `yarn.lock` (classic v1 format). Each block begins with one or more
+/// comma-separated spec headers and carries an indented `version` and optional
+/// `dependencies:` section.
+fn parse_yarn_lock(content: &str) -> Vec<RawPkg> {
+    let mut pkgs = Vec::new();
+    let mut cur: Option<RawPkg> = None;
+    let mut in_deps = false;
+
+    for line in content.lines() {
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+
+        let indented = line.starts_with(' ') || line.starts_with('\t');
+        if !indented {
+            if let Some(pkg) = cur.take() {
+                pkgs.push(pkg);
+            }
+            in_deps = false;
+            let header = line.trim_end_matches(':');
+            let name = header
+                .split(',')
+                .next()
+                .and_then(yarn_spec_name)
+                .unwrap_or_default();
+            cur = Some(RawPkg {
+                name,
+                ..RawPkg::default()
+            });
+            continue;
+        }
+
+        let Some(pkg) = cur.as_mut() else { continue };
+        let trimmed = line.trim();
+
+        if trimmed == "dependencies:" || trimmed == "optionalDependencies:" {
+            in_deps = true;
+            continue;
+        }
+        if trimmed.ends_with(':') && !trimmed.contains(' ') {
+            in_deps = false;
+            continue;
+        }
+
+        if let Some(rest) = trimmed.strip_prefix("version ") {
+            pkg.version = rest.trim().trim_matches('"').to_string();
+            continue;
+        }
+        if let Some(rest) = trimmed.strip_prefix("version\"") {
+            pkg.version = rest.trim().trim_matches('"').to_string();
+            continue;
+        }
+
+        if in_deps {
+            if let Some((name, _)) = trimmed.split_once(' ') {
+                let name = name.trim().trim_matches('"');
+                if !name.is_empty() {
+                    pkg.deps.push(name.to_string());
+                }
+            }
+        }
+    }
+
+    if let Some(pkg) = cur.take() {
+        pkgs.push(pkg);
+    }
+    pkgs
+}
+
+/// Strip the version range off a yarn spec, e.g. `"@scope/pkg@^1.0.0"` ->
+/// `@scope/pkg` and `lodash@~4.17.0` -> `lodash`.
+fn yarn_spec_name(spec: &str) -> Option<String> {
+    let spec = spec.trim().trim_matches('"');
+    if spec.is_empty() {
+        return None;
+    }
+    let (search_from, scoped) = if let Some(stripped) = spec.strip_prefix('@') {
+        (stripped, true)
+    } else {
+        (spec, false)
+    };
+    match search_from.find('@') {
+        Some(idx) => {
+            let end = if scoped { idx + 1 } else { idx };
+            Some(spec[..end].to_string())
+        }
+        None => Some(spec.to_string()),
+    }
+}
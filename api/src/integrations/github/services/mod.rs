@@ -0,0 +1,8 @@
+pub mod branch_protection;
+pub mod delta;
+pub mod dependency_graph;
+pub mod members;
+pub mod osv;
+pub mod remediation_risk;
+pub mod repositories;
+pub mod security_alerts;
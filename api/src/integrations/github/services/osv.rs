@@ -0,0 +1,290 @@
+//! Supplementary vulnerability lookups against OSV.dev and the registries.
+//!
+//! Dependabot only reports advisories GitHub has ingested. To close the gap we
+//! take the dependency set reconstructed from the repository lockfiles and
+//! batch-query [OSV.dev](https://osv.dev) — which aggregates RustSec, the GitHub
+//! Advisory Database, PyPA and others — for each resolved version. We also ask
+//! the registries whether a pinned version has been *yanked* (crates.io) or
+//! *deprecated* (npm), since those are compliance findings in their own right
+//! even without an associated CVE.
+
+use serde_json::{json, Value};
+
+/// Maximum packages per OSV `querybatch` request.
+const OSV_BATCH_SIZE: usize = 100;
+/// Upper bound on advisory detail look-ups per run, to bound fan-out.
+const MAX_DETAIL_LOOKUPS: usize = 200;
+
+/// An advisory returned by OSV for a specific dependency version.
+pub struct OsvFinding {
+    pub ecosystem: String,
+    pub package: String,
+    pub version: String,
+    pub id: String,
+    pub aliases: Vec<String>,
+    pub summary: Option<String>,
+    pub severity: Option<String>,
+}
+
+impl OsvFinding {
+    /// All identifiers this finding is known by (its OSV id and any aliases).
+    pub fn identifiers(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.id).chain(self.aliases.iter())
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "type": "advisory",
+            "source": "osv",
+            "ecosystem": self.ecosystem,
+            "package": self.package,
+            "version": self.version,
+            "osv_id": self.id,
+            "aliases": self.aliases,
+            "summary": self.summary,
+            "severity": self.severity,
+        })
+    }
+}
+
+/// A package version that has been withdrawn from its registry.
+pub struct AvailabilityFinding {
+    pub ecosystem: String,
+    pub package: String,
+    pub version: String,
+    /// `"yanked"` (crates.io) or `"deprecated"` (npm).
+    pub status: &'static str,
+    pub detail: Option<String>,
+}
+
+impl AvailabilityFinding {
+    pub fn to_json(&self) -> Value {
+        json!({
+            "type": self.status,
+            "source": "registry",
+            "ecosystem": self.ecosystem,
+            "package": self.package,
+            "version": self.version,
+            "detail": self.detail,
+        })
+    }
+}
+
+/// Query OSV for every `(ecosystem, name, version)` in `packages`, returning one
+/// finding per advisory/version pair. Network and decode errors yield no
+/// findings for the affected batch rather than aborting.
+pub async fn query_osv(
+    client: &reqwest::Client,
+    packages: &[(String, String, String)],
+) -> Vec<OsvFinding> {
+    let mut findings = Vec::new();
+    let mut detail_cache: std::collections::HashMap<String, Value> =
+        std::collections::HashMap::new();
+    let mut lookups = 0usize;
+
+    for batch in packages.chunks(OSV_BATCH_SIZE) {
+        let queries: Vec<Value> = batch
+            .iter()
+            .map(|(eco, name, version)| {
+                json!({
+                    "package": { "name": name, "ecosystem": osv_ecosystem(eco) },
+                    "version": version,
+                })
+            })
+            .collect();
+
+        let response: Option<Value> = client
+            .post("https://api.osv.dev/v1/querybatch")
+            .json(&json!({ "queries": queries }))
+            .send()
+            .await
+            .ok()
+            .and_then(|r| r.error_for_status().ok());
+
+        let body = match response {
+            Some(r) => r.json::<Value>().await.ok(),
+            None => None,
+        };
+        let Some(results) = body.as_ref().and_then(|b| b.get("results")).and_then(|r| r.as_array())
+        else {
+            continue;
+        };
+
+        for (idx, entry) in results.iter().enumerate() {
+            let Some((eco, name, version)) = batch.get(idx) else {
+                continue;
+            };
+            let Some(vulns) = entry.get("vulns").and_then(|v| v.as_array()) else {
+                continue;
+            };
+
+            for vuln in vulns {
+                let Some(id) = vuln.get("id").and_then(|v| v.as_str()) else {
+                    continue;
+                };
+
+                let detail = if let Some(cached) = detail_cache.get(id) {
+                    Some(cached.clone())
+                } else if lookups < MAX_DETAIL_LOOKUPS {
+                    lookups += 1;
+                    let fetched = fetch_vuln_detail(client, id).await;
+                    if let Some(ref d) = fetched {
+                        detail_cache.insert(id.to_string(), d.clone());
+                    }
+                    fetched
+                } else {
+                    None
+                };
+
+                let (aliases, summary, severity) = match detail {
+                    Some(d) => (
+                        extract_aliases(&d),
+                        d.get("summary").and_then(|v| v.as_str()).map(String::from),
+                        extract_severity(&d),
+                    ),
+                    None => (Vec::new(), None, None),
+                };
+
+                findings.push(OsvFinding {
+                    ecosystem: eco.clone(),
+                    package: name.clone(),
+                    version: version.clone(),
+                    id: id.to_string(),
+                    aliases,
+                    summary,
+                    severity,
+                });
+            }
+        }
+    }
+
+    findings
+}
+
+/// Check each package version for yanked/deprecated status on its registry.
+pub async fn check_availability(
+    client: &reqwest::Client,
+    packages: &[(String, String, String)],
+) -> Vec<AvailabilityFinding> {
+    let mut findings = Vec::new();
+
+    for (eco, name, version) in packages {
+        let finding = match eco.as_str() {
+            "rust" => check_crates_yanked(client, name, version).await,
+            "npm" => check_npm_deprecated(client, name, version).await,
+            _ => None,
+        };
+        if let Some(f) = finding {
+            findings.push(f);
+        }
+    }
+
+    findings
+}
+
+async fn check_crates_yanked(
+    client: &reqwest::Client,
+    name: &str,
+    version: &str,
+) -> Option<AvailabilityFinding> {
+    let url = format!("https://crates.io/api/v1/crates/{}/{}", name, version);
+    let body = fetch_json(client, &url).await?;
+    let yanked = body
+        .get("version")
+        .and_then(|v| v.get("yanked"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    if yanked {
+        Some(AvailabilityFinding {
+            ecosystem: "rust".to_string(),
+            package: name.to_string(),
+            version: version.to_string(),
+            status: "yanked",
+            detail: None,
+        })
+    } else {
+        None
+    }
+}
+
+async fn check_npm_deprecated(
+    client: &reqwest::Client,
+    name: &str,
+    version: &str,
+) -> Option<AvailabilityFinding> {
+    let url = format!("https://registry.npmjs.org/{}/{}", name, version);
+    let body = fetch_json(client, &url).await?;
+    let deprecated = body.get("deprecated")?;
+    let detail = deprecated.as_str().map(String::from);
+    // The field is present (string message, or `true`) only when deprecated.
+    if detail.is_some() || deprecated.as_bool().unwrap_or(false) {
+        Some(AvailabilityFinding {
+            ecosystem: "npm".to_string(),
+            package: name.to_string(),
+            version: version.to_string(),
+            status: "deprecated",
+            detail,
+        })
+    } else {
+        None
+    }
+}
+
+async fn fetch_vuln_detail(client: &reqwest::Client, id: &str) -> Option<Value> {
+    fetch_json(client, &format!("https://api.osv.dev/v1/vulns/{}", id)).await
+}
+
+fn extract_aliases(detail: &Value) -> Vec<String> {
+    detail
+        .get("aliases")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Prefer the database-specific severity label; fall back to the first CVSS
+/// score string OSV provides.
+fn extract_severity(detail: &Value) -> Option<String> {
+    if let Some(sev) = detail
+        .get("database_specific")
+        .and_then(|d| d.get("severity"))
+        .and_then(|v| v.as_str())
+    {
+        return Some(sev.to_lowercase());
+    }
+    detail
+        .get("severity")
+        .and_then(|v| v.as_array())
+        .and_then(|arr| arr.first())
+        .and_then(|s| s.get("score"))
+        .and_then(|v| v.as_str())
+        .map(String::from)
+}
+
+/// Map the graph's ecosystem key onto OSV's ecosystem name.
+fn osv_ecosystem(eco: &str) -> &'static str {
+    match eco {
+        "rust" => "crates.io",
+        "npm" => "npm",
+        "pip" => "PyPI",
+        _ => "",
+    }
+}
+
+async fn fetch_json(client: &reqwest::Client, url: &str) -> Option<Value> {
+    client
+        .get(url)
+        .header("User-Agent", "opengrc-dependency-analysis")
+        .send()
+        .await
+        .ok()?
+        .error_for_status()
+        .ok()?
+        .json()
+        .await
+        .ok()
+}
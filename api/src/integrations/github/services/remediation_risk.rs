@@ -0,0 +1,372 @@
+//! Dependency-update risk analysis for Dependabot findings.
+//!
+//! For a vulnerable package with a known first-patched version, this resolves
+//! both versions in their registry, downloads the source tarballs, and diffs
+//! them to estimate how risky the upgrade is. The result is attached to the
+//! Dependabot evidence as a `remediation_risk` object so a GRC reviewer can
+//! prioritise low-risk patches and schedule high-risk ones.
+
+use std::collections::HashMap;
+use std::io::Read;
+
+use flate2::read::GzDecoder;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::{json, Value};
+use tar::Archive;
+
+/// Files larger than this (decompressed) are skipped when unpacking, so they
+/// are never line-diffed; this bounds memory on vendored assets.
+const MAX_FILE_BYTES: usize = 512 * 1024;
+
+static PUBLIC_API_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?x)
+        \bpub(\s*\([^)]*\))?\s+(fn|struct|enum|trait|type|const|static)\b   # Rust public items
+        | \bexport\s+(default\s+)?(function|class|const|let|var)\b          # ES modules
+        | \bmodule\.exports\b                                              # CommonJS
+        ",
+    )
+    .expect("valid public-API regex")
+});
+
+/// Aggregated risk signals for a single dependency upgrade.
+pub struct RemediationRisk {
+    from_version: String,
+    to_version: String,
+    lines_changed: usize,
+    api_signature_changed: bool,
+    build_scripts_added: bool,
+    native_or_unsafe_added: bool,
+    maintainers_changed: Option<bool>,
+    major_version_bump: bool,
+}
+
+impl RemediationRisk {
+    fn high_upgrade_risk(&self) -> bool {
+        self.major_version_bump || self.build_scripts_added
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "from_version": self.from_version,
+            "to_version": self.to_version,
+            "lines_changed": self.lines_changed,
+            "api_signature_changed": self.api_signature_changed,
+            "build_scripts_added": self.build_scripts_added,
+            "native_or_unsafe_added": self.native_or_unsafe_added,
+            "maintainers_changed": self.maintainers_changed,
+            "major_version_bump": self.major_version_bump,
+            "high_upgrade_risk": self.high_upgrade_risk(),
+        })
+    }
+}
+
+/// Analyze the upgrade from the installed vulnerable version to the patched
+/// version for a Dependabot alert. Returns `None` when the ecosystem is not
+/// resolvable or the source could not be fetched.
+pub async fn analyze(
+    client: &reqwest::Client,
+    ecosystem: &str,
+    name: &str,
+    patched_version: &str,
+) -> Option<RemediationRisk> {
+    match ecosystem.to_lowercase().as_str() {
+        "cargo" | "rust" => analyze_cargo(client, name, patched_version).await,
+        "npm" => analyze_npm(client, name, patched_version).await,
+        _ => None,
+    }
+}
+
+async fn analyze_cargo(
+    client: &reqwest::Client,
+    name: &str,
+    patched_version: &str,
+) -> Option<RemediationRisk> {
+    let meta: Value = fetch_json(client, &format!("https://crates.io/api/v1/crates/{}", name))
+        .await?;
+
+    let versions: Vec<String> = meta
+        .get("versions")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.get("num").and_then(|n| n.as_str()).map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let from_version = newest_below(&versions, patched_version)?;
+
+    let from_tar = download(
+        client,
+        &format!(
+            "https://crates.io/api/v1/crates/{}/{}/download",
+            name, from_version
+        ),
+    )
+    .await?;
+    let to_tar = download(
+        client,
+        &format!(
+            "https://crates.io/api/v1/crates/{}/{}/download",
+            name, patched_version
+        ),
+    )
+    .await?;
+
+    let from_files = extract_tar_gz(&from_tar);
+    let to_files = extract_tar_gz(&to_tar);
+
+    let mut risk = diff_sources(&from_version, patched_version, &from_files, &to_files);
+    // crates.io does not version ownership; report unknown rather than guess.
+    risk.maintainers_changed = None;
+    Some(risk)
+}
+
+async fn analyze_npm(
+    client: &reqwest::Client,
+    name: &str,
+    patched_version: &str,
+) -> Option<RemediationRisk> {
+    let meta: Value = fetch_json(client, &format!("https://registry.npmjs.org/{}", name)).await?;
+    let versions_obj = meta.get("versions").and_then(|v| v.as_object())?;
+
+    let versions: Vec<String> = versions_obj.keys().cloned().collect();
+    let from_version = newest_below(&versions, patched_version)?;
+
+    let from_meta = versions_obj.get(&from_version)?;
+    let to_meta = versions_obj.get(patched_version)?;
+
+    let from_tar = download(client, tarball_url(from_meta)?).await?;
+    let to_tar = download(client, tarball_url(to_meta)?).await?;
+
+    let from_files = extract_tar_gz(&from_tar);
+    let to_files = extract_tar_gz(&to_tar);
+
+    let mut risk = diff_sources(&from_version, patched_version, &from_files, &to_files);
+    risk.maintainers_changed = Some(maintainers_changed(from_meta, to_meta));
+    Some(risk)
+}
+
+fn tarball_url(version_meta: &Value) -> Option<&str> {
+    version_meta
+        .get("dist")
+        .and_then(|d| d.get("tarball"))
+        .and_then(|v| v.as_str())
+}
+
+fn maintainers_changed(from_meta: &Value, to_meta: &Value) -> bool {
+    let names = |m: &Value| -> Vec<String> {
+        m.get("maintainers")
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                let mut v: Vec<String> = arr
+                    .iter()
+                    .filter_map(|x| x.get("name").and_then(|n| n.as_str()).map(String::from))
+                    .collect();
+                v.sort();
+                v
+            })
+            .unwrap_or_default()
+    };
+    names(from_meta) != names(to_meta)
+}
+
+/// Diff two sets of source files and aggregate the risk signals.
+fn diff_sources(
+    from_version: &str,
+    to_version: &str,
+    from_files: &HashMap<String, String>,
+    to_files: &HashMap<String, String>,
+) -> RemediationRisk {
+    let mut lines_changed = 0usize;
+    let mut api_signature_changed = false;
+    let mut build_scripts_added = false;
+    let mut native_or_unsafe_added = false;
+
+    let mut paths: Vec<&String> = from_files.keys().chain(to_files.keys()).collect();
+    paths.sort();
+    paths.dedup();
+
+    for path in paths {
+        let old = from_files.get(path).map(String::as_str).unwrap_or("");
+        let new = to_files.get(path).map(String::as_str).unwrap_or("");
+        if old == new {
+            continue;
+        }
+
+        let (added, removed) = line_delta(old, new);
+        lines_changed += added.len() + removed.len();
+
+        let changed_lines = added.iter().chain(removed.iter());
+        if changed_lines.clone().any(|l| PUBLIC_API_RE.is_match(l)) {
+            api_signature_changed = true;
+        }
+
+        // A build script or install hook appearing only in the patched version.
+        let base = basename(path);
+        let newly_present = from_files.get(path).is_none() && to_files.contains_key(path);
+        if newly_present && (base == "build.rs" || base == "binding.gyp" || base == "install.js") {
+            build_scripts_added = true;
+        }
+        if base == "package.json" && added.iter().any(|l| {
+            l.contains("\"postinstall\"") || l.contains("\"preinstall\"") || l.contains("\"install\"")
+        }) {
+            build_scripts_added = true;
+        }
+
+        if added
+            .iter()
+            .any(|l| l.contains("unsafe ") || l.trim_start().starts_with("unsafe"))
+        {
+            native_or_unsafe_added = true;
+        }
+        if newly_present && is_native_source(path) {
+            native_or_unsafe_added = true;
+        }
+    }
+
+    RemediationRisk {
+        from_version: from_version.to_string(),
+        to_version: to_version.to_string(),
+        lines_changed,
+        api_signature_changed,
+        build_scripts_added,
+        native_or_unsafe_added,
+        maintainers_changed: None,
+        major_version_bump: is_major_bump(from_version, to_version),
+    }
+}
+
+/// Lines present in `new` but not `old` (added) and vice versa (removed), as a
+/// multiset difference. This is a cheap approximation of a real diff but is
+/// stable enough for aggregate counts and signal detection.
+fn line_delta(old: &str, new: &str) -> (Vec<String>, Vec<String>) {
+    let mut counts: HashMap<&str, i64> = HashMap::new();
+    for line in old.lines() {
+        *counts.entry(line).or_insert(0) -= 1;
+    }
+    for line in new.lines() {
+        *counts.entry(line).or_insert(0) += 1;
+    }
+
+    let mut added = Vec::new();
+    let mut removed = Vec::new();
+    for (line, count) in counts {
+        if count > 0 {
+            for _ in 0..count {
+                added.push(line.to_string());
+            }
+        } else if count < 0 {
+            for _ in 0..(-count) {
+                removed.push(line.to_string());
+            }
+        }
+    }
+    (added, removed)
+}
+
+fn is_native_source(path: &str) -> bool {
+    matches!(
+        path.rsplit('.').next(),
+        Some("c") | Some("cc") | Some("cpp") | Some("h") | Some("hpp") | Some("m") | Some("go")
+    )
+}
+
+fn basename(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// The newest published version strictly below `ceiling` — the most likely
+/// installed vulnerable version.
+fn newest_below(versions: &[String], ceiling: &str) -> Option<String> {
+    let ceil = parse_semver(ceiling);
+    versions
+        .iter()
+        .filter(|v| parse_semver(v) < ceil)
+        .max_by(|a, b| parse_semver(a).cmp(&parse_semver(b)))
+        .cloned()
+}
+
+fn is_major_bump(from: &str, to: &str) -> bool {
+    parse_semver(to).0 > parse_semver(from).0
+}
+
+/// Parse the leading `major.minor.patch` numbers, ignoring pre-release and build
+/// metadata. Missing components default to zero.
+fn parse_semver(version: &str) -> (u64, u64, u64) {
+    let core = version
+        .trim_start_matches(['v', '=', '^', '~', ' '])
+        .split(['-', '+'])
+        .next()
+        .unwrap_or("");
+    let mut parts = core.split('.').map(|p| p.parse::<u64>().unwrap_or(0));
+    (
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+    )
+}
+
+async fn fetch_json(client: &reqwest::Client, url: &str) -> Option<Value> {
+    client
+        .get(url)
+        .header("User-Agent", "opengrc-dependency-analysis")
+        .send()
+        .await
+        .ok()?
+        .json()
+        .await
+        .ok()
+}
+
+async fn download(client: &reqwest::Client, url: &str) -> Option<Vec<u8>> {
+    let bytes = client
+        .get(url)
+        .header("User-Agent", "opengrc-dependency-analysis")
+        .send()
+        .await
+        .ok()?
+        .bytes()
+        .await
+        .ok()?;
+    Some(bytes.to_vec())
+}
+
+/// Decompress a gzipped tarball into a map of path → UTF-8 contents, skipping
+/// binary and oversized entries.
+fn extract_tar_gz(bytes: &[u8]) -> HashMap<String, String> {
+    let mut files = HashMap::new();
+    let mut archive = Archive::new(GzDecoder::new(bytes));
+
+    let entries = match archive.entries() {
+        Ok(entries) => entries,
+        Err(_) => return files,
+    };
+
+    for entry in entries.flatten() {
+        let mut entry = entry;
+        let path = match entry.path() {
+            Ok(p) => p.to_string_lossy().into_owned(),
+            Err(_) => continue,
+        };
+        let size = entry.header().size().unwrap_or(0) as usize;
+        if size == 0 || size > MAX_FILE_BYTES {
+            continue;
+        }
+
+        let mut buf = Vec::with_capacity(size);
+        if entry.read_to_end(&mut buf).is_err() {
+            continue;
+        }
+        if let Ok(text) = String::from_utf8(buf) {
+            // Strip the leading "<name>-<version>/" component so equivalent files
+            // line up across the two versions.
+            let normalized = path.splitn(2, '/').nth(1).unwrap_or(&path).to_string();
+            files.insert(normalized, text);
+        }
+    }
+
+    files
+}
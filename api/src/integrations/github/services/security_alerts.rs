@@ -1,4 +1,9 @@
 use crate::integrations::github::client::{GitHubClient, GitHubRepository};
+use crate::integrations::github::services::delta::{self, AlertObservation};
+use crate::integrations::github::services::dependency_graph::{DependencyGraph, LOCKFILE_PATHS};
+use crate::integrations::github::services::osv;
+use crate::integrations::github::services::remediation_risk;
+use std::collections::HashSet;
 use crate::integrations::provider::{CollectedEvidence, SyncContext, SyncResult};
 use chrono::Utc;
 use serde_json::json;
@@ -12,16 +17,17 @@ impl SecurityAlertsCollector {
     pub async fn sync(
         client: &GitHubClient,
         repos: &[GitHubRepository],
-        _context: &SyncContext,
+        context: &SyncContext,
         collect_dependabot: bool,
         collect_code_scanning: bool,
         collect_secret_scanning: bool,
+        collect_osv: bool,
     ) -> Result<SyncResult, String> {
         let mut result = SyncResult::default();
 
         // Collect Dependabot alerts
         if collect_dependabot {
-            match Self::collect_dependabot_alerts(client, repos).await {
+            match Self::collect_dependabot_alerts(client, repos, context).await {
                 Ok(dependabot_result) => result.merge(dependabot_result),
                 Err(e) => {
                     tracing::warn!(error = %e, "Failed to collect Dependabot alerts");
@@ -31,7 +37,7 @@ impl SecurityAlertsCollector {
 
         // Collect Code Scanning alerts
         if collect_code_scanning {
-            match Self::collect_code_scanning_alerts(client, repos).await {
+            match Self::collect_code_scanning_alerts(client, repos, context).await {
                 Ok(code_scanning_result) => result.merge(code_scanning_result),
                 Err(e) => {
                     tracing::warn!(error = %e, "Failed to collect Code Scanning alerts");
@@ -41,7 +47,7 @@ impl SecurityAlertsCollector {
 
         // Collect Secret Scanning alerts
         if collect_secret_scanning {
-            match Self::collect_secret_scanning_alerts(client, repos).await {
+            match Self::collect_secret_scanning_alerts(client, repos, context).await {
                 Ok(secret_scanning_result) => result.merge(secret_scanning_result),
                 Err(e) => {
                     tracing::warn!(error = %e, "Failed to collect Secret Scanning alerts");
@@ -49,17 +55,35 @@ impl SecurityAlertsCollector {
             }
         }
 
+        // Cross-reference against OSV/RustSec beyond GitHub's advisory set
+        if collect_osv {
+            match Self::collect_osv_alerts(client, repos).await {
+                Ok(osv_result) => result.merge(osv_result),
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to collect supplementary OSV alerts");
+                }
+            }
+        }
+
         Ok(result)
     }
 
     async fn collect_dependabot_alerts(
         client: &GitHubClient,
         repos: &[GitHubRepository],
+        context: &SyncContext,
     ) -> Result<SyncResult, String> {
         let mut result = SyncResult::default();
         let mut all_alerts = Vec::new();
         let mut alerts_by_severity: HashMap<String, i32> = HashMap::new();
         let mut alerts_by_repo: HashMap<String, Vec<serde_json::Value>> = HashMap::new();
+        // Direct/transitive breakdown aggregated across every repository.
+        let mut direct_vs_transitive: HashMap<String, i32> = HashMap::new();
+        // Open alerts observed this run, for velocity delta against the prior sync.
+        let mut observations: Vec<AlertObservation> = Vec::new();
+
+        // Shared client for registry lookups during remediation-risk analysis.
+        let registry_client = reqwest::Client::new();
 
         for repo in repos {
             if repo.archived || repo.disabled {
@@ -72,6 +96,21 @@ impl SecurityAlertsCollector {
             }
             let owner = parts[0];
 
+            // Reconstruct the dependency graph from whatever lockfiles the repo
+            // commits; attribution gracefully degrades to "unknown" when none
+            // are present or parseable.
+            let mut lockfiles = Vec::new();
+            for path in LOCKFILE_PATHS {
+                match client.get_file_contents(owner, &repo.name, path).await {
+                    Ok(Some(contents)) => lockfiles.push((path.to_string(), contents)),
+                    Ok(None) => {}
+                    Err(e) => {
+                        tracing::debug!(repo = %repo.full_name, path, error = %e, "Failed to fetch lockfile");
+                    }
+                }
+            }
+            let graph = DependencyGraph::build(&lockfiles);
+
             match client.list_dependabot_alerts(owner, &repo.name).await {
                 Ok(alerts) => {
                     result.records_processed += alerts.len() as i32;
@@ -80,6 +119,47 @@ impl SecurityAlertsCollector {
                         let severity = alert.security_advisory.severity.to_lowercase();
                         *alerts_by_severity.entry(severity.clone()).or_insert(0) += 1;
 
+                        observations.push(AlertObservation {
+                            key: format!("dependabot:{}#{}", repo.full_name, alert.number),
+                            created_at: Some(alert.created_at.clone()),
+                            severity: severity.clone(),
+                        });
+
+                        let first_patched = alert
+                            .security_vulnerability
+                            .first_patched_version
+                            .as_ref()
+                            .map(|v| v.identifier.clone());
+
+                        // Follow-on upgrade-risk analysis: diff the vulnerable and
+                        // patched sources to tell reviewers how risky the bump is.
+                        let remediation_risk = match &first_patched {
+                            Some(patched) => remediation_risk::analyze(
+                                &registry_client,
+                                &alert.dependency.package.ecosystem,
+                                &alert.dependency.package.name,
+                                patched,
+                            )
+                            .await
+                            .map(|r| r.to_json()),
+                            None => None,
+                        };
+
+                        // Attribute the vulnerable package to the direct
+                        // dependencies that pull it in, via the lockfile graph.
+                        let attribution = graph.attribute(
+                            &alert.dependency.package.ecosystem,
+                            &alert.dependency.package.name,
+                        );
+                        let classification = if attribution.found {
+                            attribution.classification.unwrap_or("unknown")
+                        } else {
+                            "unknown"
+                        };
+                        *direct_vs_transitive
+                            .entry(classification.to_string())
+                            .or_insert(0) += 1;
+
                         let alert_json = json!({
                             "number": alert.number,
                             "state": alert.state,
@@ -88,6 +168,9 @@ impl SecurityAlertsCollector {
                                 "ecosystem": alert.dependency.package.ecosystem,
                                 "name": alert.dependency.package.name,
                             },
+                            "dependency_relationship": classification,
+                            "dependency_paths": attribution.paths,
+                            "pulled_in_by": attribution.direct_dependencies,
                             "manifest_path": alert.dependency.manifest_path,
                             "advisory": {
                                 "ghsa_id": alert.security_advisory.ghsa_id,
@@ -95,7 +178,8 @@ impl SecurityAlertsCollector {
                                 "summary": alert.security_advisory.summary,
                             },
                             "vulnerable_version_range": alert.security_vulnerability.vulnerable_version_range,
-                            "first_patched_version": alert.security_vulnerability.first_patched_version.as_ref().map(|v| &v.identifier),
+                            "first_patched_version": first_patched,
+                            "remediation_risk": remediation_risk,
                             "created_at": alert.created_at,
                             "html_url": alert.html_url,
                         });
@@ -113,6 +197,11 @@ impl SecurityAlertsCollector {
             }
         }
 
+        // Velocity delta against the prior sync's open-alert set.
+        let prior = delta::snapshot_from_value(context.prior_alert_state.as_ref());
+        let alert_delta =
+            delta::compute_delta(&prior, "dependabot:", &observations, Utc::now());
+
         if !all_alerts.is_empty() {
             let critical_count = *alerts_by_severity.get("critical").unwrap_or(&0);
             let high_count = *alerts_by_severity.get("high").unwrap_or(&0);
@@ -131,8 +220,159 @@ impl SecurityAlertsCollector {
                 data: json!({
                     "total_alerts": all_alerts.len(),
                     "by_severity": alerts_by_severity,
+                    "direct_vs_transitive": direct_vs_transitive,
                     "repositories_affected": alerts_by_repo.len(),
                     "alerts_by_repository": alerts_by_repo,
+                    "delta": alert_delta.summary_json(),
+                    "collected_at": Utc::now().to_rfc3339(),
+                }),
+                control_codes: vec![
+                    "CC3.2".to_string(),
+                    "CC7.1".to_string(),
+                    "CC7.2".to_string(),
+                ],
+            });
+        }
+
+        // Dedicated remediation-velocity evidence demonstrating a functioning
+        // process rather than a point-in-time count.
+        if let Some(evidence) = Self::velocity_evidence(
+            "dependabot",
+            "github:dependabot-alerts:velocity",
+            &alert_delta,
+        ) {
+            result.evidence_collected.push(evidence);
+        }
+
+        result.records_created = result.records_processed;
+        Ok(result)
+    }
+
+    /// Build "Vulnerability Remediation Velocity" evidence from an alert delta,
+    /// or `None` when nothing opened or resolved since the last sync. The
+    /// payload carries the snapshot to persist in `SyncContext` for the next run.
+    fn velocity_evidence(
+        provider: &str,
+        source_reference: &str,
+        alert_delta: &delta::AlertDelta,
+    ) -> Option<CollectedEvidence> {
+        if alert_delta.newly_opened.is_empty() && alert_delta.resolved.is_empty() {
+            return None;
+        }
+
+        let snapshot = serde_json::to_value(&alert_delta.snapshot).unwrap_or_default();
+        Some(CollectedEvidence {
+            title: format!("Vulnerability Remediation Velocity - {}", provider),
+            description: Some(format!(
+                "{} newly opened, {} resolved since last sync",
+                alert_delta.newly_opened.len(),
+                alert_delta.resolved.len()
+            )),
+            evidence_type: "automated".to_string(),
+            source: "github".to_string(),
+            source_reference: Some(source_reference.to_string()),
+            data: json!({
+                "newly_opened": alert_delta.newly_opened,
+                "resolved_since_last_sync": alert_delta.resolved_json(),
+                "mean_time_to_remediate_days": alert_delta.mean_time_to_remediate_days(),
+                "alert_state_snapshot": snapshot,
+                "collected_at": Utc::now().to_rfc3339(),
+            }),
+            control_codes: vec!["CC7.1".to_string(), "CC7.2".to_string()],
+        })
+    }
+
+    /// Enumerate the dependency set from each repository's lockfiles and
+    /// cross-reference it against OSV.dev (and, through OSV, RustSec), emitting a
+    /// "Supplementary Vulnerability Report" of advisories GitHub has not already
+    /// surfaced plus any yanked/deprecated versions.
+    async fn collect_osv_alerts(
+        client: &GitHubClient,
+        repos: &[GitHubRepository],
+    ) -> Result<SyncResult, String> {
+        let mut result = SyncResult::default();
+        let mut supplementary: Vec<serde_json::Value> = Vec::new();
+        let mut by_type: HashMap<String, i32> = HashMap::new();
+
+        let osv_client = reqwest::Client::new();
+
+        for repo in repos {
+            if repo.archived || repo.disabled {
+                continue;
+            }
+
+            let parts: Vec<&str> = repo.full_name.split('/').collect();
+            if parts.len() != 2 {
+                continue;
+            }
+            let owner = parts[0];
+
+            // Rebuild the dependency set from committed lockfiles.
+            let mut lockfiles = Vec::new();
+            for path in LOCKFILE_PATHS {
+                if let Ok(Some(contents)) = client.get_file_contents(owner, &repo.name, path).await {
+                    lockfiles.push((path.to_string(), contents));
+                }
+            }
+            let graph = DependencyGraph::build(&lockfiles);
+            let packages = graph.packages();
+            if packages.is_empty() {
+                continue;
+            }
+            result.records_processed += packages.len() as i32;
+
+            // Advisory ids GitHub already reported, so we only keep the delta.
+            let mut known_ids: HashSet<String> = HashSet::new();
+            if let Ok(alerts) = client.list_dependabot_alerts(owner, &repo.name).await {
+                for alert in &alerts {
+                    known_ids.insert(alert.security_advisory.ghsa_id.to_lowercase());
+                    if let Some(cve) = &alert.security_advisory.cve_id {
+                        known_ids.insert(cve.to_lowercase());
+                    }
+                }
+            }
+
+            for finding in osv::query_osv(&osv_client, &packages).await {
+                let already_known = finding
+                    .identifiers()
+                    .any(|id| known_ids.contains(&id.to_lowercase()));
+                if already_known {
+                    continue;
+                }
+                let mut entry = finding.to_json();
+                entry["repository"] = serde_json::json!(repo.full_name);
+                *by_type.entry("advisory".to_string()).or_insert(0) += 1;
+                supplementary.push(entry);
+            }
+
+            for finding in osv::check_availability(&osv_client, &packages).await {
+                let mut entry = finding.to_json();
+                entry["repository"] = serde_json::json!(repo.full_name);
+                *by_type.entry(finding.status.to_string()).or_insert(0) += 1;
+                supplementary.push(entry);
+            }
+        }
+
+        if !supplementary.is_empty() {
+            let advisory_count = *by_type.get("advisory").unwrap_or(&0);
+            let yanked_count =
+                *by_type.get("yanked").unwrap_or(&0) + *by_type.get("deprecated").unwrap_or(&0);
+
+            result.evidence_collected.push(CollectedEvidence {
+                title: "Supplementary Vulnerability Report".to_string(),
+                description: Some(format!(
+                    "{} findings not surfaced by Dependabot ({} advisories, {} yanked/deprecated)",
+                    supplementary.len(),
+                    advisory_count,
+                    yanked_count
+                )),
+                evidence_type: "automated".to_string(),
+                source: "github".to_string(),
+                source_reference: Some("github:osv-alerts".to_string()),
+                data: json!({
+                    "total_findings": supplementary.len(),
+                    "by_type": by_type,
+                    "findings": supplementary,
                     "collected_at": Utc::now().to_rfc3339(),
                 }),
                 control_codes: vec![
@@ -150,11 +390,13 @@ impl SecurityAlertsCollector {
     async fn collect_code_scanning_alerts(
         client: &GitHubClient,
         repos: &[GitHubRepository],
+        context: &SyncContext,
     ) -> Result<SyncResult, String> {
         let mut result = SyncResult::default();
         let mut all_alerts = Vec::new();
         let mut alerts_by_severity: HashMap<String, i32> = HashMap::new();
         let mut alerts_by_tool: HashMap<String, i32> = HashMap::new();
+        let mut observations: Vec<AlertObservation> = Vec::new();
 
         for repo in repos {
             if repo.archived || repo.disabled {
@@ -183,6 +425,12 @@ impl SecurityAlertsCollector {
                         *alerts_by_severity.entry(severity.clone()).or_insert(0) += 1;
                         *alerts_by_tool.entry(alert.tool.name.clone()).or_insert(0) += 1;
 
+                        observations.push(AlertObservation {
+                            key: format!("code-scanning:{}#{}", repo.full_name, alert.number),
+                            created_at: Some(alert.created_at.clone()),
+                            severity: severity.clone(),
+                        });
+
                         all_alerts.push(json!({
                             "repository": repo.full_name,
                             "number": alert.number,
@@ -213,6 +461,10 @@ impl SecurityAlertsCollector {
             }
         }
 
+        let prior = delta::snapshot_from_value(context.prior_alert_state.as_ref());
+        let alert_delta =
+            delta::compute_delta(&prior, "code-scanning:", &observations, Utc::now());
+
         if !all_alerts.is_empty() {
             let critical_count = *alerts_by_severity.get("critical").unwrap_or(&0);
             let high_count = *alerts_by_severity.get("high").unwrap_or(&0);
@@ -233,6 +485,7 @@ impl SecurityAlertsCollector {
                     "by_severity": alerts_by_severity,
                     "by_tool": alerts_by_tool,
                     "alerts": all_alerts,
+                    "delta": alert_delta.summary_json(),
                     "collected_at": Utc::now().to_rfc3339(),
                 }),
                 control_codes: vec![
@@ -243,6 +496,14 @@ impl SecurityAlertsCollector {
             });
         }
 
+        if let Some(evidence) = Self::velocity_evidence(
+            "code-scanning",
+            "github:code-scanning-alerts:velocity",
+            &alert_delta,
+        ) {
+            result.evidence_collected.push(evidence);
+        }
+
         result.records_created = result.records_processed;
         Ok(result)
     }
@@ -250,10 +511,12 @@ impl SecurityAlertsCollector {
     async fn collect_secret_scanning_alerts(
         client: &GitHubClient,
         repos: &[GitHubRepository],
+        context: &SyncContext,
     ) -> Result<SyncResult, String> {
         let mut result = SyncResult::default();
         let mut all_alerts = Vec::new();
         let mut alerts_by_type: HashMap<String, i32> = HashMap::new();
+        let mut observations: Vec<AlertObservation> = Vec::new();
 
         for repo in repos {
             if repo.archived || repo.disabled {
@@ -275,6 +538,12 @@ impl SecurityAlertsCollector {
                             .entry(alert.secret_type_display_name.clone())
                             .or_insert(0) += 1;
 
+                        observations.push(AlertObservation {
+                            key: format!("secret-scanning:{}#{}", repo.full_name, alert.number),
+                            created_at: Some(alert.created_at.clone()),
+                            severity: alert.secret_type_display_name.clone(),
+                        });
+
                         all_alerts.push(json!({
                             "repository": repo.full_name,
                             "number": alert.number,
@@ -293,6 +562,10 @@ impl SecurityAlertsCollector {
             }
         }
 
+        let prior = delta::snapshot_from_value(context.prior_alert_state.as_ref());
+        let alert_delta =
+            delta::compute_delta(&prior, "secret-scanning:", &observations, Utc::now());
+
         if !all_alerts.is_empty() {
             result.evidence_collected.push(CollectedEvidence {
                 title: "Secret Scanning Alert Report".to_string(),
@@ -307,6 +580,7 @@ impl SecurityAlertsCollector {
                     "total_alerts": all_alerts.len(),
                     "by_secret_type": alerts_by_type,
                     "alerts": all_alerts,
+                    "delta": alert_delta.summary_json(),
                     "collected_at": Utc::now().to_rfc3339(),
                 }),
                 control_codes: vec![
@@ -317,6 +591,14 @@ impl SecurityAlertsCollector {
             });
         }
 
+        if let Some(evidence) = Self::velocity_evidence(
+            "secret-scanning",
+            "github:secret-scanning-alerts:velocity",
+            &alert_delta,
+        ) {
+            result.evidence_collected.push(evidence);
+        }
+
         result.records_created = result.records_processed;
         Ok(result)
     }
@@ -1,5 +1,6 @@
 use super::client::GitHubClient;
 use super::config::GitHubConfig;
+use super::notifier::NotificationRelay;
 use super::services::{
     branch_protection::BranchProtectionCollector, members::MembersCollector,
     repositories::RepositoryCollector, security_alerts::SecurityAlertsCollector,
@@ -15,6 +16,13 @@ pub async fn run_sync(
 ) -> Result<SyncResult, String> {
     let mut result = SyncResult::default();
 
+    // Optional threshold-based alerting relay, escalating high-severity findings
+    // as each collector returns.
+    let mut relay = config
+        .notifications
+        .as_ref()
+        .and_then(|cfg| NotificationRelay::from_config(cfg, &context.integration_id.to_string()));
+
     // First, collect repositories (needed for other collectors)
     let repos = if config.services.repositories
         || config.services.branch_protection
@@ -80,7 +88,8 @@ pub async fn run_sync(
     // Sync security alerts
     if (config.services.dependabot_alerts
         || config.services.code_scanning
-        || config.services.secret_scanning)
+        || config.services.secret_scanning
+        || config.services.osv_alerts)
         && !repos.is_empty()
     {
         tracing::info!(
@@ -89,6 +98,7 @@ pub async fn run_sync(
             dependabot = config.services.dependabot_alerts,
             code_scanning = config.services.code_scanning,
             secret_scanning = config.services.secret_scanning,
+            osv = config.services.osv_alerts,
             "Syncing GitHub security alerts"
         );
 
@@ -99,10 +109,16 @@ pub async fn run_sync(
             config.services.dependabot_alerts,
             config.services.code_scanning,
             config.services.secret_scanning,
+            config.services.osv_alerts,
         )
         .await
         {
-            Ok(alerts_result) => result.merge(alerts_result),
+            Ok(alerts_result) => {
+                if let Some(relay) = relay.as_mut() {
+                    relay.process(&alerts_result).await;
+                }
+                result.merge(alerts_result);
+            }
             Err(e) => {
                 tracing::error!(error = %e, "Failed to sync security alerts");
                 result = result.with_error(SyncError::new("security_alerts_sync_failed", e));
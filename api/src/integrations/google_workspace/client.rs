@@ -264,6 +264,24 @@ impl GoogleWorkspaceClient {
         self.list_activities("admin", &start_time).await
     }
 
+    /// Get login audit activities newer than an explicit high-water mark
+    /// (RFC3339), used by incremental syncs to avoid re-pulling a full window.
+    pub async fn list_login_activities_since(
+        &self,
+        start_time: &str,
+    ) -> Result<Vec<GoogleActivity>, String> {
+        self.list_activities("login", start_time).await
+    }
+
+    /// Get admin audit activities newer than an explicit high-water mark
+    /// (RFC3339).
+    pub async fn list_admin_activities_since(
+        &self,
+        start_time: &str,
+    ) -> Result<Vec<GoogleActivity>, String> {
+        self.list_activities("admin", start_time).await
+    }
+
     async fn list_activities(&self, application_name: &str, start_time: &str) -> Result<Vec<GoogleActivity>, String> {
         let mut all_activities = Vec::new();
         let mut page_token: Option<String> = None;
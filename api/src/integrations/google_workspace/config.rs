@@ -23,6 +23,12 @@ pub struct GoogleWorkspaceConfig {
     /// Services to enable
     #[serde(default)]
     pub services: GoogleWorkspaceServicesConfig,
+    /// Customer-supplied audit event classification rules, keyed by
+    /// collector (`"login"`, `"admin"`), layered ahead of the built-in
+    /// defaults so new Workspace event types can be mapped to a category and
+    /// control framework without recompiling. See
+    /// [`crate::integrations::google_workspace::services::classifier`].
+    pub audit_classification_rules: Option<Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -126,7 +126,16 @@ impl IntegrationProvider for GoogleWorkspaceProvider {
     }
 
     fn optional_fields(&self) -> Vec<&'static str> {
-        vec!["service_account_key", "access_token", "refresh_token", "customer_id", "domain", "admin_email", "services"]
+        vec![
+            "service_account_key",
+            "access_token",
+            "refresh_token",
+            "customer_id",
+            "domain",
+            "admin_email",
+            "services",
+            "audit_classification_rules",
+        ]
     }
 }
 
@@ -1,8 +1,21 @@
-use crate::integrations::google_workspace::client::GoogleWorkspaceClient;
-use crate::integrations::provider::{CollectedEvidence, SyncContext, SyncResult};
+use crate::integrations::google_workspace::client::{GoogleActivity, GoogleWorkspaceClient};
+use crate::integrations::google_workspace::config::GoogleWorkspaceConfig;
+use crate::integrations::google_workspace::services::classifier::{ClassifiableEvent, EventClassifier};
+use crate::integrations::provider::{CollectedEvidence, EvidenceArtifact, SyncContext, SyncResult};
+use arrow::array::{ArrayRef, StringBuilder};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
 use chrono::Utc;
-use serde_json::json;
+use parquet::arrow::ArrowWriter;
+use serde_json::{json, Value};
+use crate::integrations::telemetry;
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Version of the fixed columnar schema emitted for audit-event artifacts.
+const AUDIT_ARTIFACT_SCHEMA_VERSION: i32 = 1;
 
 /// Audit Logs Collector for Google Workspace
 pub struct AuditCollector;
@@ -11,14 +24,29 @@ impl AuditCollector {
     /// Collect login audit data from Google Workspace
     pub async fn sync_login_audit(
         client: &GoogleWorkspaceClient,
-        _context: &SyncContext,
+        context: &SyncContext,
+        config: &GoogleWorkspaceConfig,
         days: u32,
     ) -> Result<SyncResult, String> {
+        let _span =
+            tracing::info_span!("collector.sync_login_audit", source = "google_workspace")
+                .entered();
+        let started = Instant::now();
         let mut result = SyncResult::default();
 
-        // Get login activities
-        let activities = client.list_login_activities(days).await?;
+        // Resume from the persisted high-water mark unless a full resync was
+        // requested; fall back to the fixed `days` window for the first run.
+        let high_water = resume_mark(context);
+        let mut activities = match &high_water {
+            Some(mark) => client.list_login_activities_since(mark).await?,
+            None => client.list_login_activities(days).await?,
+        };
         result.records_processed = activities.len() as i32;
+        // Drop anything at or before the mark so repeated syncs are idempotent.
+        if let Some(mark) = &high_water {
+            activities.retain(|a| newer_than(a, mark));
+        }
+        result.next_sync_token = max_activity_time(&activities).or(high_water);
 
         if activities.is_empty() {
             result.evidence_collected.push(CollectedEvidence {
@@ -40,60 +68,26 @@ impl AuditCollector {
                     "CC7.3".to_string(),
                 ],
             });
+            telemetry::record_collector("google_workspace", "login", started, &result, 0);
             return Ok(result);
         }
 
-        // Categorize login events
-        let mut login_success = Vec::new();
-        let mut login_failure = Vec::new();
-        let mut suspicious_logins = Vec::new();
-        let mut event_type_counts: HashMap<String, i32> = HashMap::new();
+        // Flatten and classify login events via the rule-driven classifier so
+        // adding a category (or tightening an existing one) doesn't require
+        // touching this collector.
+        let (classifiable, event_type_counts) = flatten_events(&activities);
+        let all_events: Vec<Value> = classifiable.iter().map(|e| e.detail.clone()).collect();
         let mut unique_users: HashMap<String, i32> = HashMap::new();
-
         for activity in &activities {
             let actor_email = activity.actor.as_ref().and_then(|a| a.email.clone()).unwrap_or_default();
-
-            // Count unique users
-            *unique_users.entry(actor_email.clone()).or_insert(0) += 1;
-
-            if let Some(ref events) = activity.events {
-                for event in events {
-                    let event_name = event.name.clone().unwrap_or_default();
-
-                    // Count event types
-                    *event_type_counts.entry(event_name.clone()).or_insert(0) += 1;
-
-                    let event_detail = json!({
-                        "time": activity.id.as_ref().and_then(|id| id.time.clone()),
-                        "actor_email": actor_email,
-                        "ip_address": activity.ip_address,
-                        "event_type": event.event_type,
-                        "event_name": event_name.clone(),
-                        "parameters": event.parameters,
-                    });
-
-                    // Categorize
-                    match event_name.as_str() {
-                        "login_success" => login_success.push(event_detail),
-                        "login_failure" | "login_challenge" | "login_verification" => {
-                            login_failure.push(event_detail.clone());
-
-                            // Check for suspicious patterns
-                            if let Some(ref params) = event.parameters {
-                                for param in params {
-                                    if param.name.as_deref() == Some("is_suspicious") && param.bool_value.unwrap_or(false) {
-                                        suspicious_logins.push(event_detail.clone());
-                                    }
-                                }
-                            }
-                        }
-                        "logout" => {} // Not tracking logouts
-                        _ => {}
-                    }
-                }
-            }
+            *unique_users.entry(actor_email).or_insert(0) += 1;
         }
 
+        let classifier = EventClassifier::for_login(config)?;
+        let outcome = classifier.classify(&classifiable);
+        let login_success_count = outcome.get("login_success").map(|b| b.events.len()).unwrap_or(0);
+        let login_failure_count = outcome.get("login_failure").map(|b| b.events.len()).unwrap_or(0);
+
         // Generate Login Audit Summary
         result.evidence_collected.push(CollectedEvidence {
             title: "Google Workspace Login Audit Summary".to_string(),
@@ -101,8 +95,8 @@ impl AuditCollector {
                 "{} login events in the last {} days ({} successful, {} failed)",
                 activities.len(),
                 days,
-                login_success.len(),
-                login_failure.len()
+                login_success_count,
+                login_failure_count,
             )),
             evidence_type: "automated".to_string(),
             source: "google_workspace".to_string(),
@@ -110,8 +104,8 @@ impl AuditCollector {
             data: json!({
                 "log_days": days,
                 "total_events": activities.len(),
-                "successful_logins": login_success.len(),
-                "failed_logins": login_failure.len(),
+                "successful_logins": login_success_count,
+                "failed_logins": login_failure_count,
                 "unique_users": unique_users.len(),
                 "event_type_distribution": event_type_counts,
                 "collected_at": Utc::now().to_rfc3339(),
@@ -122,79 +116,89 @@ impl AuditCollector {
             ],
         });
 
-        // Generate Failed Logins Report
-        if !login_failure.is_empty() {
-            // Group failures by user
-            let mut failures_by_user: HashMap<String, Vec<&serde_json::Value>> = HashMap::new();
-            for event in &login_failure {
+        let suspicious_login_count = outcome.get("suspicious_login").map(|b| b.events.len()).unwrap_or(0);
+
+        for (category, title, source_suffix, data_key, noun_phrase) in [
+            ("login_failure", "Google Workspace Failed Login Report", "failed-logins", "failed_logins", "failed login attempts"),
+            ("suspicious_login", "Google Workspace Suspicious Login Report", "suspicious-logins", "suspicious_logins", "suspicious login attempts"),
+            ("brute_force", "Google Workspace Brute Force Login Report", "brute-force-logins", "brute_force_logins", "brute-force login attempts"),
+        ] {
+            let Some(bucket) = outcome.get(category) else { continue };
+            if bucket.events.is_empty() {
+                continue;
+            }
+
+            let mut by_user: HashMap<String, i32> = HashMap::new();
+            for event in &bucket.events {
                 let user = event.get("actor_email").and_then(|v| v.as_str()).unwrap_or("unknown").to_string();
-                failures_by_user.entry(user).or_default().push(event);
+                *by_user.entry(user).or_insert(0) += 1;
             }
 
+            let mut data = serde_json::Map::new();
+            data.insert(format!("{}_count", data_key), json!(bucket.events.len()));
+            data.insert("unique_users_affected".to_string(), json!(by_user.len()));
+            data.insert(data_key.to_string(), json!(bucket.events));
+            data.insert("collected_at".to_string(), json!(Utc::now().to_rfc3339()));
+
             result.evidence_collected.push(CollectedEvidence {
-                title: "Google Workspace Failed Login Report".to_string(),
+                title: title.to_string(),
                 description: Some(format!(
-                    "{} failed login attempts across {} users in the last {} days",
-                    login_failure.len(),
-                    failures_by_user.len(),
+                    "{} {} across {} users in the last {} days",
+                    bucket.events.len(),
+                    noun_phrase,
+                    by_user.len(),
                     days
                 )),
                 evidence_type: "automated".to_string(),
                 source: "google_workspace".to_string(),
-                source_reference: Some("google_workspace:failed-logins".to_string()),
-                data: json!({
-                    "failed_login_count": login_failure.len(),
-                    "unique_users_affected": failures_by_user.len(),
-                    "failed_logins": login_failure,
-                    "collected_at": Utc::now().to_rfc3339(),
-                }),
-                control_codes: vec![
-                    "CC6.1".to_string(),
-                    "CC7.2".to_string(),
-                ],
+                source_reference: Some(format!("google_workspace:{}", source_suffix)),
+                data: Value::Object(data),
+                control_codes: bucket.control_codes.clone(),
             });
         }
 
-        // Generate Suspicious Login Report
-        if !suspicious_logins.is_empty() {
-            result.evidence_collected.push(CollectedEvidence {
-                title: "Google Workspace Suspicious Login Report".to_string(),
-                description: Some(format!(
-                    "{} suspicious login attempts detected in the last {} days",
-                    suspicious_logins.len(),
-                    days
-                )),
-                evidence_type: "automated".to_string(),
-                source: "google_workspace".to_string(),
-                source_reference: Some("google_workspace:suspicious-logins".to_string()),
-                data: json!({
-                    "suspicious_login_count": suspicious_logins.len(),
-                    "suspicious_logins": suspicious_logins,
-                    "collected_at": Utc::now().to_rfc3339(),
-                }),
-                control_codes: vec![
-                    "CC6.1".to_string(),
-                    "CC7.1".to_string(),
-                    "CC7.2".to_string(),
-                ],
-            });
+        // Emit a columnar artifact for the full flattened event stream so
+        // downstream analytics can scan it without parsing the JSON summaries.
+        if let Some(artifact) = build_audit_artifact(&all_events)? {
+            result.artifacts.push(artifact);
         }
 
         result.records_created = activities.len() as i32;
+        telemetry::record_collector(
+            "google_workspace",
+            "login",
+            started,
+            &result,
+            suspicious_login_count as u64,
+        );
         Ok(result)
     }
 
     /// Collect admin audit data from Google Workspace
     pub async fn sync_admin_audit(
         client: &GoogleWorkspaceClient,
-        _context: &SyncContext,
+        context: &SyncContext,
+        config: &GoogleWorkspaceConfig,
         days: u32,
     ) -> Result<SyncResult, String> {
+        let _span =
+            tracing::info_span!("collector.sync_admin_audit", source = "google_workspace")
+                .entered();
+        let started = Instant::now();
         let mut result = SyncResult::default();
 
-        // Get admin activities
-        let activities = client.list_admin_activities(days).await?;
+        // Resume from the persisted high-water mark unless a full resync was
+        // requested; fall back to the fixed `days` window for the first run.
+        let high_water = resume_mark(context);
+        let mut activities = match &high_water {
+            Some(mark) => client.list_admin_activities_since(mark).await?,
+            None => client.list_admin_activities(days).await?,
+        };
         result.records_processed = activities.len() as i32;
+        if let Some(mark) = &high_water {
+            activities.retain(|a| newer_than(a, mark));
+        }
+        result.next_sync_token = max_activity_time(&activities).or(high_water);
 
         if activities.is_empty() {
             result.evidence_collected.push(CollectedEvidence {
@@ -216,68 +220,19 @@ impl AuditCollector {
                     "CC7.3".to_string(),
                 ],
             });
+            telemetry::record_collector("google_workspace", "admin", started, &result, 0);
             return Ok(result);
         }
 
-        // Categorize admin events
-        let mut user_changes = Vec::new();
-        let mut group_changes = Vec::new();
-        let mut security_changes = Vec::new();
-        let mut application_changes = Vec::new();
-        let mut domain_changes = Vec::new();
-        let mut event_type_counts: HashMap<String, i32> = HashMap::new();
+        // Flatten and classify admin events via the rule-driven classifier
+        // (exact event-type rules first, substring fallbacks after) instead
+        // of the hardcoded `match` arms this replaces.
+        let (classifiable, event_type_counts) = flatten_events(&activities);
+        let all_events: Vec<Value> = classifiable.iter().map(|e| e.detail.clone()).collect();
 
-        for activity in &activities {
-            let actor_email = activity.actor.as_ref().and_then(|a| a.email.clone()).unwrap_or_default();
-
-            if let Some(ref events) = activity.events {
-                for event in events {
-                    let event_name = event.name.clone().unwrap_or_default();
-                    let event_type = event.event_type.clone().unwrap_or_default();
-
-                    // Count event types
-                    *event_type_counts.entry(event_name.clone()).or_insert(0) += 1;
-
-                    let event_detail = json!({
-                        "time": activity.id.as_ref().and_then(|id| id.time.clone()),
-                        "actor_email": actor_email,
-                        "ip_address": activity.ip_address,
-                        "event_type": event_type.clone(),
-                        "event_name": event_name.clone(),
-                        "parameters": event.parameters,
-                    });
-
-                    // Categorize by event type
-                    match event_type.as_str() {
-                        "USER_SETTINGS" | "CREATE_USER" | "DELETE_USER" | "SUSPEND_USER" | "UNSUSPEND_USER" => {
-                            user_changes.push(event_detail);
-                        }
-                        "GROUP_SETTINGS" | "CREATE_GROUP" | "DELETE_GROUP" | "ADD_GROUP_MEMBER" | "REMOVE_GROUP_MEMBER" => {
-                            group_changes.push(event_detail);
-                        }
-                        "SECURITY_SETTINGS" | "2SV_SETTING_CHANGE" | "SSO_SETTINGS_CHANGE" => {
-                            security_changes.push(event_detail);
-                        }
-                        "APPLICATION_SETTINGS" | "APP_INSTALL" | "APP_UNINSTALL" => {
-                            application_changes.push(event_detail);
-                        }
-                        "DOMAIN_SETTINGS" | "DNS_SETTINGS" => {
-                            domain_changes.push(event_detail);
-                        }
-                        _ => {
-                            // Check event name for categorization
-                            if event_name.contains("USER") || event_name.contains("user") {
-                                user_changes.push(event_detail);
-                            } else if event_name.contains("GROUP") || event_name.contains("group") {
-                                group_changes.push(event_detail);
-                            } else if event_name.contains("SECURITY") || event_name.contains("2SV") {
-                                security_changes.push(event_detail);
-                            }
-                        }
-                    }
-                }
-            }
-        }
+        let classifier = EventClassifier::for_admin(config)?;
+        let outcome = classifier.classify(&classifiable);
+        let category_count = |c: &str| outcome.get(c).map(|b| b.events.len()).unwrap_or(0);
 
         // Generate Admin Audit Summary
         result.evidence_collected.push(CollectedEvidence {
@@ -293,11 +248,11 @@ impl AuditCollector {
             data: json!({
                 "log_days": days,
                 "total_events": activities.len(),
-                "user_changes": user_changes.len(),
-                "group_changes": group_changes.len(),
-                "security_changes": security_changes.len(),
-                "application_changes": application_changes.len(),
-                "domain_changes": domain_changes.len(),
+                "user_changes": category_count("user_changes"),
+                "group_changes": category_count("group_changes"),
+                "security_changes": category_count("security_changes"),
+                "application_changes": category_count("application_changes"),
+                "domain_changes": category_count("domain_changes"),
                 "event_type_distribution": event_type_counts,
                 "collected_at": Utc::now().to_rfc3339(),
             }),
@@ -307,84 +262,208 @@ impl AuditCollector {
             ],
         });
 
-        // Generate User Changes Report
-        if !user_changes.is_empty() {
-            result.evidence_collected.push(CollectedEvidence {
-                title: "Google Workspace User Changes Report".to_string(),
-                description: Some(format!(
-                    "{} user-related administrative changes in the last {} days",
-                    user_changes.len(),
-                    days
-                )),
-                evidence_type: "automated".to_string(),
-                source: "google_workspace".to_string(),
-                source_reference: Some("google_workspace:user-changes".to_string()),
-                data: json!({
-                    "user_change_count": user_changes.len(),
-                    "user_changes": user_changes,
-                    "collected_at": Utc::now().to_rfc3339(),
-                }),
-                control_codes: vec![
-                    "CC6.1".to_string(),
-                    "CC6.2".to_string(),
-                    "CC7.2".to_string(),
-                ],
-            });
-        }
+        for (category, title, source_suffix, data_key, noun_phrase) in [
+            ("user_changes", "Google Workspace User Changes Report", "user-changes", "user_changes", "user-related administrative changes"),
+            ("security_changes", "Google Workspace Security Settings Changes Report", "security-changes", "security_changes", "security-related administrative changes"),
+            ("group_changes", "Google Workspace Group Changes Report", "group-changes", "group_changes", "group-related administrative changes"),
+        ] {
+            let Some(bucket) = outcome.get(category) else { continue };
+            if bucket.events.is_empty() {
+                continue;
+            }
+
+            let mut data = serde_json::Map::new();
+            data.insert(format!("{}_count", data_key), json!(bucket.events.len()));
+            data.insert(data_key.to_string(), json!(bucket.events));
+            data.insert("collected_at".to_string(), json!(Utc::now().to_rfc3339()));
 
-        // Generate Security Changes Report
-        if !security_changes.is_empty() {
             result.evidence_collected.push(CollectedEvidence {
-                title: "Google Workspace Security Settings Changes Report".to_string(),
+                title: title.to_string(),
                 description: Some(format!(
-                    "{} security-related administrative changes in the last {} days",
-                    security_changes.len(),
+                    "{} {} in the last {} days",
+                    bucket.events.len(),
+                    noun_phrase,
                     days
                 )),
                 evidence_type: "automated".to_string(),
                 source: "google_workspace".to_string(),
-                source_reference: Some("google_workspace:security-changes".to_string()),
-                data: json!({
-                    "security_change_count": security_changes.len(),
-                    "security_changes": security_changes,
-                    "collected_at": Utc::now().to_rfc3339(),
-                }),
-                control_codes: vec![
-                    "CC6.1".to_string(),
-                    "CC6.6".to_string(),
-                    "CC7.1".to_string(),
-                    "CC7.2".to_string(),
-                ],
+                source_reference: Some(format!("google_workspace:{}", source_suffix)),
+                data: Value::Object(data),
+                control_codes: bucket.control_codes.clone(),
             });
         }
 
-        // Generate Group Changes Report
-        if !group_changes.is_empty() {
-            result.evidence_collected.push(CollectedEvidence {
-                title: "Google Workspace Group Changes Report".to_string(),
-                description: Some(format!(
-                    "{} group-related administrative changes in the last {} days",
-                    group_changes.len(),
-                    days
-                )),
-                evidence_type: "automated".to_string(),
-                source: "google_workspace".to_string(),
-                source_reference: Some("google_workspace:group-changes".to_string()),
-                data: json!({
-                    "group_change_count": group_changes.len(),
-                    "group_changes": group_changes,
-                    "collected_at": Utc::now().to_rfc3339(),
-                }),
-                control_codes: vec![
-                    "CC6.1".to_string(),
-                    "CC6.2".to_string(),
-                    "CC6.3".to_string(),
-                    "CC7.2".to_string(),
-                ],
-            });
+        if let Some(artifact) = build_audit_artifact(&all_events)? {
+            result.artifacts.push(artifact);
         }
 
         result.records_created = activities.len() as i32;
+        telemetry::record_collector("google_workspace", "admin", started, &result, 0);
         Ok(result)
     }
 }
+
+/// Flatten raw Workspace activities into classifier-ready events, plus a
+/// count of events per `event_name` for the summary's distribution field.
+fn flatten_events(activities: &[GoogleActivity]) -> (Vec<ClassifiableEvent>, HashMap<String, i32>) {
+    let mut classifiable = Vec::new();
+    let mut event_type_counts: HashMap<String, i32> = HashMap::new();
+
+    for activity in activities {
+        let actor_email = activity.actor.as_ref().and_then(|a| a.email.clone()).unwrap_or_default();
+
+        let Some(events) = activity.events.as_ref() else {
+            continue;
+        };
+        for event in events {
+            let event_name = event.name.clone().unwrap_or_default();
+            let event_type = event.event_type.clone().unwrap_or_default();
+            *event_type_counts.entry(event_name.clone()).or_insert(0) += 1;
+
+            let time = activity.id.as_ref().and_then(|id| id.time.clone());
+            let detail = json!({
+                "time": time,
+                "actor_email": actor_email,
+                "ip_address": activity.ip_address,
+                "event_type": event_type,
+                "event_name": event_name,
+                "parameters": event.parameters,
+            });
+
+            classifiable.push(ClassifiableEvent {
+                event_type,
+                event_name,
+                actor_email: actor_email.clone(),
+                time,
+                parameters: event.parameters.clone().unwrap_or_default(),
+                detail,
+            });
+        }
+    }
+
+    (classifiable, event_type_counts)
+}
+
+/// Fixed Arrow schema for flattened Workspace audit events.
+fn audit_event_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("time", DataType::Utf8, true),
+        Field::new("actor_email", DataType::Utf8, true),
+        Field::new("ip_address", DataType::Utf8, true),
+        Field::new("event_type", DataType::Utf8, true),
+        Field::new("event_name", DataType::Utf8, true),
+        Field::new("parameters", DataType::Utf8, true),
+    ]))
+}
+
+/// Flatten the per-event JSON records into a columnar Parquet artifact,
+/// returning a reference-carrying [`EvidenceArtifact`] instead of inlining the
+/// raw events in `CollectedEvidence.data`. Returns `None` when there are no
+/// events to encode.
+fn build_audit_artifact(events: &[Value]) -> Result<Option<EvidenceArtifact>, String> {
+    if events.is_empty() {
+        return Ok(None);
+    }
+
+    let mut time = StringBuilder::new();
+    let mut actor = StringBuilder::new();
+    let mut ip = StringBuilder::new();
+    let mut etype = StringBuilder::new();
+    let mut ename = StringBuilder::new();
+    let mut params = StringBuilder::new();
+
+    for event in events {
+        append_str(&mut time, event.get("time"));
+        append_str(&mut actor, event.get("actor_email"));
+        append_str(&mut ip, event.get("ip_address"));
+        append_str(&mut etype, event.get("event_type"));
+        append_str(&mut ename, event.get("event_name"));
+        // Parameters are a nested structure; serialize them to a JSON string so
+        // the column stays flat and scannable.
+        match event.get("parameters") {
+            Some(Value::Null) | None => params.append_null(),
+            Some(v) => params.append_value(v.to_string()),
+        }
+    }
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(time.finish()),
+        Arc::new(actor.finish()),
+        Arc::new(ip.finish()),
+        Arc::new(etype.finish()),
+        Arc::new(ename.finish()),
+        Arc::new(params.finish()),
+    ];
+
+    let schema = audit_event_schema();
+    let batch = RecordBatch::try_new(schema.clone(), columns)
+        .map_err(|e| format!("failed to build audit record batch: {}", e))?;
+
+    let mut buffer: Vec<u8> = Vec::new();
+    {
+        let mut writer = ArrowWriter::try_new(&mut buffer, schema, None)
+            .map_err(|e| format!("failed to open parquet writer: {}", e))?;
+        writer
+            .write(&batch)
+            .map_err(|e| format!("failed to write audit parquet: {}", e))?;
+        writer
+            .close()
+            .map_err(|e| format!("failed to finalize audit parquet: {}", e))?;
+    }
+
+    let reference = format!("sha256:{:x}", Sha256::digest(&buffer));
+    Ok(Some(EvidenceArtifact {
+        format: "parquet".to_string(),
+        reference,
+        row_count: events.len() as i64,
+        schema_version: AUDIT_ARTIFACT_SCHEMA_VERSION,
+        payload: buffer,
+    }))
+}
+
+/// Append a JSON string field to a builder, mapping anything non-string
+/// (including absent/null) to a null cell.
+fn append_str(builder: &mut StringBuilder, value: Option<&Value>) {
+    match value.and_then(|v| v.as_str()) {
+        Some(s) => builder.append_value(s),
+        None => builder.append_null(),
+    }
+}
+
+/// The high-water mark to resume from, or `None` to pull the full `days` window
+/// (first run or an explicit full resync).
+fn resume_mark(context: &SyncContext) -> Option<String> {
+    if context.full_resync {
+        None
+    } else {
+        context.last_sync_token.clone()
+    }
+}
+
+/// The activity's RFC3339 collection timestamp, if present.
+fn activity_time(
+    activity: &crate::integrations::google_workspace::client::GoogleActivity,
+) -> Option<&str> {
+    activity.id.as_ref().and_then(|id| id.time.as_deref())
+}
+
+/// Whether an activity is strictly newer than the high-water `mark`. Records
+/// with no timestamp are kept so nothing is silently dropped.
+fn newer_than(
+    activity: &crate::integrations::google_workspace::client::GoogleActivity,
+    mark: &str,
+) -> bool {
+    activity_time(activity).map(|t| t > mark).unwrap_or(true)
+}
+
+/// The newest activity timestamp in the batch (RFC3339 sorts lexicographically
+/// in UTC), used as the next run's high-water mark.
+fn max_activity_time(
+    activities: &[crate::integrations::google_workspace::client::GoogleActivity],
+) -> Option<String> {
+    activities
+        .iter()
+        .filter_map(activity_time)
+        .max()
+        .map(|s| s.to_string())
+}
@@ -55,7 +55,7 @@ pub async fn run_sync(
             "Syncing Google Workspace login audit"
         );
 
-        match AuditCollector::sync_login_audit(client, context, config.services.log_days).await {
+        match AuditCollector::sync_login_audit(client, context, config, config.services.log_days).await {
             Ok(audit_result) => result.merge(audit_result),
             Err(e) => {
                 tracing::error!(error = %e, "Failed to sync Google Workspace login audit");
@@ -72,7 +72,7 @@ pub async fn run_sync(
             "Syncing Google Workspace admin audit"
         );
 
-        match AuditCollector::sync_admin_audit(client, context, config.services.log_days).await {
+        match AuditCollector::sync_admin_audit(client, context, config, config.services.log_days).await {
             Ok(audit_result) => result.merge(audit_result),
             Err(e) => {
                 tracing::error!(error = %e, "Failed to sync Google Workspace admin audit");
@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// Capabilities GRC identity controls need from a directory/IdP, independent
+/// of which concrete provider (Okta, Entra ID, Google Workspace, ...) backs
+/// it. A provider's low-level client implements this by mapping its own
+/// wire types onto the neutral `Idp*` types below; controls then evaluate
+/// against the trait object instead of a concrete client. The Okta client
+/// is the implementation in this crate.
+#[async_trait]
+pub trait IdentityProvider: Send + Sync {
+    /// Short identifier for the backing provider, e.g. `"okta"`.
+    fn provider_type(&self) -> &'static str;
+
+    /// List all directory users.
+    async fn list_users(&self) -> Result<Vec<IdpUser>, String>;
+
+    /// List all groups.
+    async fn list_groups(&self) -> Result<Vec<IdpGroup>, String>;
+
+    /// List the members of a group.
+    async fn list_group_members(&self, group_id: &str) -> Result<Vec<IdpUser>, String>;
+
+    /// List applications registered with the provider.
+    async fn list_applications(&self) -> Result<Vec<IdpApplication>, String>;
+
+    /// List the users assigned to an application.
+    async fn list_app_users(&self, app_id: &str) -> Result<Vec<IdpUser>, String>;
+
+    /// List a user's enrolled MFA factors.
+    async fn list_user_factors(&self, user_id: &str) -> Result<Vec<IdpFactor>, String>;
+
+    /// List security-relevant log events from the last `since_days` days.
+    async fn list_security_logs(&self, since_days: u32) -> Result<Vec<IdpLogEvent>, String>;
+}
+
+/// A directory user (or an application's assigned user, which carries the
+/// same shape), reduced to the fields identity controls actually evaluate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdpUser {
+    pub id: String,
+    pub login: String,
+    pub email: Option<String>,
+    pub display_name: Option<String>,
+    pub status: String,
+    pub last_login: Option<String>,
+    pub created: Option<String>,
+}
+
+/// A directory group.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdpGroup {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// An application registered with the provider.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdpApplication {
+    pub id: String,
+    pub name: String,
+    pub label: String,
+    pub status: String,
+    pub sign_on_mode: Option<String>,
+}
+
+/// An enrolled MFA factor, carrying enough to assert a minimum assurance bar
+/// without controls needing to know each provider's own factor-type strings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdpFactor {
+    pub id: String,
+    pub factor_type: String,
+    pub status: String,
+    pub assurance_level: IdpAssuranceLevel,
+}
+
+/// Ordered MFA strength ranking, lowest to highest, so controls can compare
+/// a user's factors against a minimum bar (e.g. `>= IdpAssuranceLevel::Possession`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum IdpAssuranceLevel {
+    Weak,
+    Possession,
+    PhishingResistant,
+}
+
+/// A security-relevant directory log event (sign-in, admin action, policy
+/// change, ...), reduced to the fields controls actually key off.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdpLogEvent {
+    pub id: String,
+    pub published: String,
+    pub event_type: String,
+    pub outcome: Option<String>,
+    pub actor_id: Option<String>,
+    pub actor_display_name: Option<String>,
+    pub ip_address: Option<String>,
+}
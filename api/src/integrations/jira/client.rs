@@ -91,6 +91,13 @@ pub struct IssueFields {
     pub labels: Option<Vec<String>>,
     #[serde(rename = "securitylevel")]
     pub security_level: Option<SecurityLevel>,
+    pub resolution: Option<IssueResolution>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IssueResolution {
+    pub id: String,
+    pub name: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -363,6 +370,174 @@ impl JiraClient {
         self.get(&url).await
     }
 
+    /// Create an issue, returning the new issue key.
+    pub async fn create_issue(
+        &self,
+        project_key: &str,
+        issue_type: &str,
+        summary: &str,
+        description: &str,
+        labels: &[String],
+    ) -> Result<String, String> {
+        let url = format!("{}/issue", self.config.api_url());
+        let body = serde_json::json!({
+            "fields": {
+                "project": { "key": project_key },
+                "issuetype": { "name": issue_type },
+                "summary": summary,
+                // Plain-text description rendered as an ADF doc node.
+                "description": {
+                    "type": "doc",
+                    "version": 1,
+                    "content": [{
+                        "type": "paragraph",
+                        "content": [{ "type": "text", "text": description }]
+                    }]
+                },
+                "labels": labels,
+            }
+        });
+
+        let created: CreatedIssue = self.post(&url, &body).await?;
+        Ok(created.key)
+    }
+
+    /// Add a comment to an issue.
+    pub async fn add_comment(&self, issue_key: &str, body: &str) -> Result<(), String> {
+        let url = format!("{}/issue/{}/comment", self.config.api_url(), issue_key);
+        let payload = serde_json::json!({
+            "body": {
+                "type": "doc",
+                "version": 1,
+                "content": [{
+                    "type": "paragraph",
+                    "content": [{ "type": "text", "text": body }]
+                }]
+            }
+        });
+        let _: serde_json::Value = self.post(&url, &payload).await?;
+        Ok(())
+    }
+
+    /// List the transitions available from an issue's current status.
+    pub async fn get_transitions(&self, issue_key: &str) -> Result<Vec<IssueTransition>, String> {
+        let url = format!("{}/issue/{}/transitions", self.config.api_url(), issue_key);
+        let resp: TransitionsResponse = self.get(&url).await?;
+        Ok(resp.transitions)
+    }
+
+    /// Move an issue to the named target status by resolving and executing the
+    /// matching transition. Returns `Ok(false)` when no transition matches.
+    pub async fn transition_issue(&self, issue_key: &str, to_status: &str) -> Result<bool, String> {
+        let transitions = self.get_transitions(issue_key).await?;
+        let target = to_status.to_lowercase();
+        let transition = transitions.into_iter().find(|t| {
+            t.name.to_lowercase() == target || t.to.name.to_lowercase() == target
+        });
+
+        match transition {
+            Some(t) => {
+                let url = format!("{}/issue/{}/transitions", self.config.api_url(), issue_key);
+                let payload = serde_json::json!({ "transition": { "id": t.id } });
+                let _: serde_json::Value = self.post(&url, &payload).await?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Fetch an issue's current status/resolution for pull-back syncs.
+    /// Returns `Ok(None)` when the issue no longer exists (e.g. deleted in
+    /// Jira) so callers can mark the link stale instead of failing the sync.
+    pub async fn get_issue_status(
+        &self,
+        issue_key: &str,
+    ) -> Result<Option<IssueStatusInfo>, String> {
+        let url = format!(
+            "{}/issue/{}?fields=status,resolution",
+            self.config.api_url(),
+            issue_key
+        );
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read response: {}", e))?;
+
+        if !status.is_success() {
+            return Err(format!("Jira API error ({}): {}", status, text));
+        }
+
+        // Only `status` and `resolution` are requested, so the body cannot be
+        // read as a full `JiraIssue`; pick the two names out directly.
+        let issue: serde_json::Value = serde_json::from_str(&text).map_err(|e| {
+            format!(
+                "Failed to parse response: {} - Body: {}",
+                e,
+                &text[..text.len().min(200)]
+            )
+        })?;
+        let fields = &issue["fields"];
+        let status = fields["status"]["name"]
+            .as_str()
+            .ok_or_else(|| "Jira issue response has no status name".to_string())?;
+
+        Ok(Some(IssueStatusInfo {
+            status: status.to_string(),
+            resolution: fields["resolution"]["name"].as_str().map(String::from),
+        }))
+    }
+
+    async fn post<T: for<'de> Deserialize<'de>>(
+        &self,
+        url: &str,
+        body: &serde_json::Value,
+    ) -> Result<T, String> {
+        let response = self
+            .client
+            .post(url)
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {}", e))?;
+
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read response: {}", e))?;
+
+        if !status.is_success() {
+            return Err(format!("Jira API error ({}): {}", status, text));
+        }
+
+        // Some mutating endpoints (transitions, some comments) return 204 with
+        // an empty body; treat that as a null JSON value.
+        if text.trim().is_empty() {
+            return serde_json::from_str("null")
+                .map_err(|e| format!("Failed to parse empty response: {}", e));
+        }
+
+        serde_json::from_str(&text).map_err(|e| {
+            format!(
+                "Failed to parse response: {} - Body: {}",
+                e,
+                &text[..text.len().min(200)]
+            )
+        })
+    }
+
     async fn get<T: for<'de> Deserialize<'de>>(&self, url: &str) -> Result<T, String> {
         let response = self
             .client
@@ -391,6 +566,34 @@ impl JiraClient {
     }
 }
 
+/// Current Jira-side state of a tracked issue, used to decide whether a
+/// linked OpenGRC record can be considered resolved.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueStatusInfo {
+    pub status: String,
+    pub resolution: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreatedIssue {
+    pub id: String,
+    pub key: String,
+    #[serde(rename = "self")]
+    pub self_link: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransitionsResponse {
+    pub transitions: Vec<IssueTransition>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IssueTransition {
+    pub id: String,
+    pub name: String,
+    pub to: IssueStatus,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ServerInfo {
     #[serde(rename = "baseUrl")]
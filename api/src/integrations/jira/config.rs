@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 
 /// Jira integration configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,6 +16,15 @@ pub struct JiraConfig {
     pub auth_method: JiraAuthMethod,
     /// Specific projects to sync (comma-separated, optional)
     pub projects: Option<String>,
+    /// Project to create new issues in when pushing findings/remediation
+    /// items (`services.issue_tracking`). Required only for that feature.
+    pub project_key: Option<String>,
+    /// Maps a Jira status or resolution name (case-insensitive) to an
+    /// OpenGRC finding status, e.g. `{"Done": "resolved", "Won't Fix": "resolved"}`.
+    /// Falls back to treating "done"/"resolved"/"closed" as resolved when no
+    /// entry matches.
+    #[serde(default)]
+    pub status_mapping: HashMap<String, String>,
     /// Services to enable
     #[serde(default)]
     pub services: JiraServicesConfig,
@@ -38,18 +48,97 @@ pub struct JiraServicesConfig {
     /// Sync issues
     #[serde(default = "default_true")]
     pub issues: bool,
+    /// Server-side scoping applied to the issue search (optional).
+    #[serde(default)]
+    pub issue_filter: IssueFilter,
     /// Sync users
     #[serde(default = "default_true")]
     pub users: bool,
     /// Sync project permissions
     #[serde(default = "default_true")]
     pub permissions: bool,
+    /// Push OpenGRC findings/remediation items into Jira as issues and pull
+    /// their status back. Off by default since it requires `project_key` and
+    /// writes to the connected Jira instance.
+    #[serde(default)]
+    pub issue_tracking: bool,
+}
+
+/// Structured filters ANDed into the `IssueCollector` JQL so large instances
+/// only collect the tickets relevant to an audit instead of every open issue.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct IssueFilter {
+    /// Raw JQL fragment ANDed in verbatim (e.g. `labels = compliance`).
+    pub jql: Option<String>,
+    /// Restrict to a status category: `new`, `indeterminate`, or `done`.
+    pub status_category: Option<String>,
+    /// Restrict to these priorities (e.g. `Highest`, `High`).
+    #[serde(default)]
+    pub priorities: Vec<String>,
+    /// Restrict to these components.
+    #[serde(default)]
+    pub components: Vec<String>,
+    /// Restrict to these issue types (e.g. `Bug`, `Vulnerability`).
+    #[serde(default)]
+    pub issue_types: Vec<String>,
+    /// Only issues updated within this many days (static window).
+    pub updated_within_days: Option<u32>,
+    /// When true, AND an `updated >= last_sync` clause driven by the sync
+    /// high-water mark so repeat syncs only pull changed issues.
+    #[serde(default)]
+    pub incremental: bool,
 }
 
 fn default_true() -> bool {
     true
 }
 
+impl IssueFilter {
+    /// JQL clauses contributed by the structured filters, ready to be ANDed
+    /// with the collector's project and ordering clauses.
+    pub fn jql_clauses(&self) -> Vec<String> {
+        let mut clauses = Vec::new();
+
+        if let Some(category) = &self.status_category {
+            clauses.push(format!("statusCategory = \"{}\"", escape(category)));
+        }
+        if !self.priorities.is_empty() {
+            clauses.push(format!("priority IN ({})", quote_list(&self.priorities)));
+        }
+        if !self.components.is_empty() {
+            clauses.push(format!("component IN ({})", quote_list(&self.components)));
+        }
+        if !self.issue_types.is_empty() {
+            clauses.push(format!("issuetype IN ({})", quote_list(&self.issue_types)));
+        }
+        if let Some(days) = self.updated_within_days {
+            clauses.push(format!("updated >= -{}d", days));
+        }
+        if let Some(fragment) = &self.jql {
+            let trimmed = fragment.trim();
+            if !trimmed.is_empty() {
+                clauses.push(format!("({})", trimmed));
+            }
+        }
+
+        clauses
+    }
+}
+
+/// Quote a value for inclusion in a JQL `IN (...)` list.
+fn quote_list(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|v| format!("\"{}\"", escape(v)))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Escape embedded quotes/backslashes so values can't break out of a JQL string.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
 impl JiraConfig {
     pub fn from_value(value: &Value) -> Result<Self, String> {
         serde_json::from_value(value.clone())
@@ -75,9 +164,39 @@ impl JiraConfig {
             return Err("Email is required for API token authentication".to_string());
         }
 
+        if self.services.issue_tracking && self.project_key.is_none() {
+            return Err("project_key is required when issue_tracking is enabled".to_string());
+        }
+
         Ok(())
     }
 
+    /// Map a Jira issue's status/resolution to an OpenGRC finding status
+    /// using `status_mapping`, preferring the resolution when present. Falls
+    /// back to a default done/resolved/closed heuristic when no mapping entry
+    /// matches.
+    pub fn map_status(&self, status: &str, resolution: Option<&str>) -> Option<String> {
+        if let Some(res) = resolution {
+            if let Some(mapped) = self.lookup_status_mapping(res) {
+                return Some(mapped);
+            }
+        }
+        if let Some(mapped) = self.lookup_status_mapping(status) {
+            return Some(mapped);
+        }
+
+        let fallback_source = resolution.unwrap_or(status).to_lowercase();
+        matches!(fallback_source.as_str(), "done" | "resolved" | "closed")
+            .then(|| "resolved".to_string())
+    }
+
+    fn lookup_status_mapping(&self, key: &str) -> Option<String> {
+        self.status_mapping
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.clone())
+    }
+
     /// Get list of projects to sync
     pub fn get_projects(&self) -> Vec<String> {
         self.projects
@@ -96,3 +215,79 @@ impl JiraConfig {
         format!("{}/rest/api/3", self.instance_url.trim_end_matches('/'))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_filter_yields_no_clauses() {
+        assert!(IssueFilter::default().jql_clauses().is_empty());
+    }
+
+    #[test]
+    fn structured_filters_compose_into_clauses() {
+        let filter = IssueFilter {
+            status_category: Some("done".to_string()),
+            priorities: vec!["High".to_string(), "Highest".to_string()],
+            issue_types: vec!["Bug".to_string()],
+            updated_within_days: Some(90),
+            jql: Some("labels = compliance".to_string()),
+            ..Default::default()
+        };
+        let clauses = filter.jql_clauses();
+        assert!(clauses.contains(&"statusCategory = \"done\"".to_string()));
+        assert!(clauses.contains(&"priority IN (\"High\",\"Highest\")".to_string()));
+        assert!(clauses.contains(&"issuetype IN (\"Bug\")".to_string()));
+        assert!(clauses.contains(&"updated >= -90d".to_string()));
+        assert!(clauses.contains(&"(labels = compliance)".to_string()));
+    }
+
+    #[test]
+    fn values_with_quotes_are_escaped() {
+        let filter = IssueFilter {
+            components: vec!["a\"b".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(filter.jql_clauses(), vec!["component IN (\"a\\\"b\")"]);
+    }
+
+    fn base_config() -> JiraConfig {
+        JiraConfig {
+            instance_url: "https://example.atlassian.net".to_string(),
+            access_token: "token".to_string(),
+            email: Some("user@example.com".to_string()),
+            auth_method: JiraAuthMethod::ApiToken,
+            projects: None,
+            project_key: None,
+            status_mapping: HashMap::new(),
+            services: JiraServicesConfig::default(),
+        }
+    }
+
+    #[test]
+    fn issue_tracking_requires_project_key() {
+        let mut config = base_config();
+        config.services.issue_tracking = true;
+        assert!(config.validate().is_err());
+        config.project_key = Some("OPS".to_string());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn map_status_uses_configured_mapping_case_insensitively() {
+        let mut config = base_config();
+        config.status_mapping.insert("Won't Fix".to_string(), "resolved".to_string());
+        assert_eq!(
+            config.map_status("Open", Some("won't fix")),
+            Some("resolved".to_string())
+        );
+    }
+
+    #[test]
+    fn map_status_falls_back_to_default_heuristic() {
+        let config = base_config();
+        assert_eq!(config.map_status("Done", None), Some("resolved".to_string()));
+        assert_eq!(config.map_status("In Progress", None), None);
+    }
+}
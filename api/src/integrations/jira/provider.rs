@@ -32,6 +32,7 @@ impl IntegrationProvider for JiraProvider {
             IntegrationCapability::AssetInventory,      // Projects
             IntegrationCapability::ConfigurationState, // Project settings, permissions
             IntegrationCapability::UserSync,           // Project users
+            IntegrationCapability::IssueTracking,      // Push findings, pull issue status
         ]
     }
 
@@ -133,7 +134,14 @@ impl IntegrationProvider for JiraProvider {
     }
 
     fn optional_fields(&self) -> Vec<&'static str> {
-        vec!["email", "auth_method", "projects", "services"]
+        vec![
+            "email",
+            "auth_method",
+            "projects",
+            "project_key",
+            "status_mapping",
+            "services",
+        ]
     }
 }
 
@@ -154,6 +162,7 @@ mod tests {
         assert!(caps.contains(&IntegrationCapability::AssetInventory));
         assert!(caps.contains(&IntegrationCapability::ConfigurationState));
         assert!(caps.contains(&IntegrationCapability::UserSync));
+        assert!(caps.contains(&IntegrationCapability::IssueTracking));
     }
 
     #[test]
@@ -1,4 +1,5 @@
 use crate::integrations::jira::client::{JiraClient, JiraProject};
+use crate::integrations::jira::config::IssueFilter;
 use crate::integrations::provider::{CollectedEvidence, SyncContext, SyncResult};
 use chrono::Utc;
 use serde_json::json;
@@ -8,11 +9,16 @@ use std::collections::HashMap;
 pub struct IssueCollector;
 
 impl IssueCollector {
-    /// Collect issue data from Jira
+    /// Collect issue data from Jira.
+    ///
+    /// `filter` contributes optional server-side scoping (raw JQL, status
+    /// category, priority, component, issue type, updated-since window) that is
+    /// ANDed into the search so large instances don't pull every open ticket.
     pub async fn sync(
         client: &JiraClient,
         projects: &[JiraProject],
-        _context: &SyncContext,
+        filter: &IssueFilter,
+        context: &SyncContext,
     ) -> Result<SyncResult, String> {
         let mut result = SyncResult::default();
 
@@ -27,11 +33,20 @@ impl IssueCollector {
             return Ok(result);
         }
 
-        // Search for open issues
-        let jql = format!(
-            "project IN ({}) AND resolution IS EMPTY ORDER BY created DESC",
-            project_keys.join(",")
-        );
+        // Start from the project + open-resolution scope, then AND in the
+        // configured filters and, when incremental, the high-water-mark clause.
+        let mut clauses = vec![
+            format!("project IN ({})", project_keys.join(",")),
+            "resolution IS EMPTY".to_string(),
+        ];
+        clauses.extend(filter.jql_clauses());
+        if filter.incremental {
+            if let Some(token) = &context.last_sync_token {
+                clauses.push(format!("updated >= \"{}\"", token));
+            }
+        }
+
+        let jql = format!("{} ORDER BY created DESC", clauses.join(" AND "));
 
         let issues = client.search_issues(&jql, 500).await?;
         result.records_processed = issues.len() as i32;
@@ -5,7 +5,9 @@ use super::services::{
     users::UserCollector,
 };
 use crate::integrations::provider::SyncError;
+use crate::integrations::telemetry;
 use crate::integrations::{SyncContext, SyncResult};
+use std::time::Instant;
 
 /// Run the full Jira sync across all enabled services
 pub async fn run_sync(
@@ -13,6 +15,12 @@ pub async fn run_sync(
     config: &JiraConfig,
     context: &SyncContext,
 ) -> Result<SyncResult, String> {
+    let span = tracing::info_span!(
+        "integration_sync",
+        integration = "jira",
+        integration_id = %context.integration_id,
+    );
+    let _guard = span.enter();
     let mut result = SyncResult::default();
 
     // First, collect projects (needed for other collectors)
@@ -50,8 +58,13 @@ pub async fn run_sync(
 
     // Sync projects
     if config.services.projects {
+        let _svc = tracing::info_span!("jira_service", service = "projects").entered();
+        let started = Instant::now();
         match ProjectCollector::sync(client, context).await {
-            Ok(project_result) => result.merge(project_result),
+            Ok(project_result) => {
+                telemetry::record_service("jira", "projects", started, &project_result);
+                result.merge(project_result)
+            }
             Err(e) => {
                 tracing::error!(error = %e, "Failed to sync projects");
                 result = result.with_error(SyncError::new("project_sync_failed", e));
@@ -67,8 +80,13 @@ pub async fn run_sync(
             "Syncing Jira issues"
         );
 
-        match IssueCollector::sync(client, &projects, context).await {
-            Ok(issue_result) => result.merge(issue_result),
+        let _svc = tracing::info_span!("jira_service", service = "issues").entered();
+        let started = Instant::now();
+        match IssueCollector::sync(client, &projects, &config.services.issue_filter, context).await {
+            Ok(issue_result) => {
+                telemetry::record_service("jira", "issues", started, &issue_result);
+                result.merge(issue_result)
+            }
             Err(e) => {
                 tracing::error!(error = %e, "Failed to sync issues");
                 result = result.with_error(SyncError::new("issue_sync_failed", e));
@@ -84,8 +102,13 @@ pub async fn run_sync(
             "Syncing Jira users"
         );
 
+        let _svc = tracing::info_span!("jira_service", service = "users").entered();
+        let started = Instant::now();
         match UserCollector::sync(client, &projects, context).await {
-            Ok(user_result) => result.merge(user_result),
+            Ok(user_result) => {
+                telemetry::record_service("jira", "users", started, &user_result);
+                result.merge(user_result)
+            }
             Err(e) => {
                 tracing::error!(error = %e, "Failed to sync users");
                 result = result.with_error(SyncError::new("user_sync_failed", e));
@@ -101,8 +124,13 @@ pub async fn run_sync(
             "Syncing Jira project permissions"
         );
 
+        let _svc = tracing::info_span!("jira_service", service = "permissions").entered();
+        let started = Instant::now();
         match PermissionsCollector::sync(client, &projects, context).await {
-            Ok(perm_result) => result.merge(perm_result),
+            Ok(perm_result) => {
+                telemetry::record_service("jira", "permissions", started, &perm_result);
+                result.merge(perm_result)
+            }
             Err(e) => {
                 tracing::error!(error = %e, "Failed to sync permissions");
                 result = result.with_error(SyncError::new("permissions_sync_failed", e));
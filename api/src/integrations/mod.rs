@@ -1,16 +1,25 @@
 pub mod aws;
 pub mod azure_ad;
+pub mod bridge;
 pub mod github;
 pub mod google_workspace;
+pub mod identity;
 pub mod jira;
 pub mod oauth;
 pub mod okta;
 pub mod provider;
+pub mod telemetry;
 
 pub use aws::AwsProvider;
 pub use azure_ad::AzureAdProvider;
+pub use bridge::{
+    FindingBridge, FindingBridgeConfig, FindingTicketStore, InMemoryFindingTicketStore,
+};
 pub use github::GitHubProvider;
 pub use google_workspace::GoogleWorkspaceProvider;
+pub use identity::{
+    IdentityProvider, IdpApplication, IdpAssuranceLevel, IdpFactor, IdpGroup, IdpLogEvent, IdpUser,
+};
 pub use jira::JiraProvider;
 pub use okta::OktaProvider;
 pub use oauth::{
@@ -6,11 +6,47 @@ use serde_json::Value;
 pub struct OktaConfig {
     /// Okta domain (e.g., "dev-123456.okta.com" or "your-company.okta.com")
     pub domain: String,
-    /// API token for authentication
-    pub api_token: String,
+    /// Either a static SSWS API token or an OAuth 2.0 service-app credential
+    #[serde(flatten)]
+    pub auth: OktaAuth,
     /// Services to enable
     #[serde(default)]
     pub services: OktaServicesConfig,
+    /// Maximum number of retry attempts for rate-limited (429) or
+    /// server-error (5xx) responses before giving up
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Optional global requests-per-second throttle applied to every
+    /// outbound Okta API call, so a nightly full sync doesn't need manual
+    /// tuning to stay under the tenant's rate limit
+    #[serde(default)]
+    pub requests_per_second: Option<f64>,
+}
+
+/// How this client authenticates to the Okta API. `ApiToken` is the legacy
+/// long-lived `SSWS` bearer secret; `OAuth2` mints short-lived access tokens
+/// via `private_key_jwt` client-credentials so collectors can run with
+/// scoped, rotatable credentials instead. Untagged so existing
+/// `{"domain": ..., "api_token": ...}` configs keep parsing unchanged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum OktaAuth {
+    ApiToken {
+        api_token: String,
+    },
+    OAuth2 {
+        /// Client ID of the Okta service app
+        client_id: String,
+        /// Scopes requested at the token endpoint (e.g. `okta.users.read`)
+        scopes: Vec<String>,
+        /// PEM-encoded RSA or EC private key used to sign the
+        /// `private_key_jwt` client assertion
+        private_key_pem: String,
+        /// Key id advertised in the service app's JWKS, embedded as the
+        /// assertion JWT's `kid` header so Okta can pick the matching key
+        #[serde(default)]
+        key_id: Option<String>,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,6 +92,10 @@ fn default_log_days() -> u32 {
     7
 }
 
+fn default_max_retries() -> u32 {
+    5
+}
+
 impl OktaConfig {
     pub fn from_value(value: &Value) -> Result<Self, String> {
         serde_json::from_value(value.clone())
@@ -66,8 +106,23 @@ impl OktaConfig {
         if self.domain.is_empty() {
             return Err("Domain is required".to_string());
         }
-        if self.api_token.is_empty() {
-            return Err("API token is required".to_string());
+        match &self.auth {
+            OktaAuth::ApiToken { api_token } => {
+                if api_token.is_empty() {
+                    return Err("API token is required".to_string());
+                }
+            }
+            OktaAuth::OAuth2 { client_id, scopes, private_key_pem, .. } => {
+                if client_id.is_empty() {
+                    return Err("OAuth2 client_id is required".to_string());
+                }
+                if scopes.is_empty() {
+                    return Err("OAuth2 requires at least one scope".to_string());
+                }
+                if private_key_pem.is_empty() {
+                    return Err("OAuth2 private key is required".to_string());
+                }
+            }
         }
         // Validate domain format
         if !self.domain.contains("okta.com") && !self.domain.contains("oktapreview.com") {
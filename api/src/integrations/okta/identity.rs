@@ -0,0 +1,195 @@
+use super::client::{
+    OktaApplication, OktaAppUser, OktaClient, OktaFactor, OktaGroup, OktaLogEvent, OktaUser,
+};
+use crate::integrations::identity::{
+    IdentityProvider, IdpApplication, IdpFactor, IdpGroup, IdpLogEvent, IdpUser,
+};
+use async_trait::async_trait;
+
+/// Adapts `OktaClient`'s raw API calls onto the provider-agnostic
+/// `IdentityProvider` trait, so identity controls can evaluate against Okta
+/// (or any other configured provider) through the same neutral types.
+#[async_trait]
+impl IdentityProvider for OktaClient {
+    fn provider_type(&self) -> &'static str {
+        "okta"
+    }
+
+    async fn list_users(&self) -> Result<Vec<IdpUser>, String> {
+        Ok(OktaClient::list_users(self)
+            .await?
+            .into_iter()
+            .map(IdpUser::from)
+            .collect())
+    }
+
+    async fn list_groups(&self) -> Result<Vec<IdpGroup>, String> {
+        Ok(OktaClient::list_groups(self)
+            .await?
+            .into_iter()
+            .map(IdpGroup::from)
+            .collect())
+    }
+
+    async fn list_group_members(&self, group_id: &str) -> Result<Vec<IdpUser>, String> {
+        Ok(OktaClient::list_group_members(self, group_id)
+            .await?
+            .into_iter()
+            .map(IdpUser::from)
+            .collect())
+    }
+
+    async fn list_applications(&self) -> Result<Vec<IdpApplication>, String> {
+        Ok(OktaClient::list_applications(self)
+            .await?
+            .into_iter()
+            .map(IdpApplication::from)
+            .collect())
+    }
+
+    async fn list_app_users(&self, app_id: &str) -> Result<Vec<IdpUser>, String> {
+        Ok(OktaClient::list_app_users(self, app_id)
+            .await?
+            .into_iter()
+            .map(IdpUser::from)
+            .collect())
+    }
+
+    async fn list_user_factors(&self, user_id: &str) -> Result<Vec<IdpFactor>, String> {
+        Ok(OktaClient::list_user_factors(self, user_id)
+            .await?
+            .into_iter()
+            .map(IdpFactor::from)
+            .collect())
+    }
+
+    async fn list_security_logs(&self, since_days: u32) -> Result<Vec<IdpLogEvent>, String> {
+        Ok(OktaClient::list_security_logs(self, since_days)
+            .await?
+            .into_iter()
+            .map(IdpLogEvent::from)
+            .collect())
+    }
+}
+
+impl From<OktaUser> for IdpUser {
+    fn from(user: OktaUser) -> Self {
+        Self {
+            id: user.id,
+            login: user.profile.login,
+            email: Some(user.profile.email),
+            display_name: user.profile.display_name,
+            status: user.status,
+            last_login: user.last_login,
+            created: Some(user.created),
+        }
+    }
+}
+
+impl From<OktaGroup> for IdpGroup {
+    fn from(group: OktaGroup) -> Self {
+        Self {
+            id: group.id,
+            name: group.profile.name,
+            description: group.profile.description,
+        }
+    }
+}
+
+impl From<OktaApplication> for IdpApplication {
+    fn from(app: OktaApplication) -> Self {
+        Self {
+            id: app.id,
+            name: app.name,
+            label: app.label,
+            status: app.status,
+            sign_on_mode: app.sign_on_mode,
+        }
+    }
+}
+
+/// An app-user assignment carries its own username/status rather than a full
+/// profile, so it maps onto `IdpUser` with the directory-specific fields left
+/// empty instead of guessed at.
+impl From<OktaAppUser> for IdpUser {
+    fn from(app_user: OktaAppUser) -> Self {
+        Self {
+            id: app_user.id,
+            login: app_user
+                .credentials
+                .and_then(|c| c.user_name)
+                .unwrap_or_default(),
+            email: None,
+            display_name: None,
+            status: app_user.status,
+            last_login: None,
+            created: app_user.created,
+        }
+    }
+}
+
+impl From<OktaFactor> for IdpFactor {
+    fn from(factor: OktaFactor) -> Self {
+        Self {
+            id: factor.id,
+            factor_type: factor.kind.factor_type_str(),
+            assurance_level: factor.kind.assurance_level(),
+            status: factor.status,
+        }
+    }
+}
+
+impl From<OktaLogEvent> for IdpLogEvent {
+    fn from(event: OktaLogEvent) -> Self {
+        Self {
+            id: event.uuid,
+            published: event.published,
+            event_type: event.event_type,
+            outcome: event.outcome.and_then(|o| o.result),
+            actor_id: event.actor.as_ref().and_then(|a| a.id.clone()),
+            actor_display_name: event.actor.and_then(|a| a.display_name),
+            ip_address: event.client.and_then(|c| c.ip_address),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::okta::client::{OktaUserProfile, OktaUserType};
+
+    #[test]
+    fn maps_okta_user_to_neutral_user() {
+        let okta_user = OktaUser {
+            id: "00u1".to_string(),
+            status: "ACTIVE".to_string(),
+            created: "2024-01-01T00:00:00.000Z".to_string(),
+            last_login: Some("2024-06-01T00:00:00.000Z".to_string()),
+            last_updated: None,
+            password_changed: None,
+            status_changed: None,
+            user_type: Some(OktaUserType { id: "otu1".to_string() }),
+            profile: OktaUserProfile {
+                login: "jane@example.com".to_string(),
+                email: "jane@example.com".to_string(),
+                first_name: Some("Jane".to_string()),
+                last_name: Some("Doe".to_string()),
+                display_name: Some("Jane Doe".to_string()),
+                nick_name: None,
+                mobile_phone: None,
+                second_email: None,
+                department: None,
+                title: None,
+                manager: None,
+                employee_number: None,
+                organization: None,
+            },
+        };
+
+        let idp_user = IdpUser::from(okta_user);
+        assert_eq!(idp_user.id, "00u1");
+        assert_eq!(idp_user.login, "jane@example.com");
+        assert_eq!(idp_user.status, "ACTIVE");
+        assert_eq!(idp_user.last_login.as_deref(), Some("2024-06-01T00:00:00.000Z"));
+    }
+}
@@ -1,9 +1,12 @@
 pub mod client;
 pub mod config;
+pub mod identity;
+pub mod poller;
 pub mod provider;
 pub mod services;
 pub mod sync;
 
 pub use client::OktaClient;
 pub use config::OktaConfig;
+pub use poller::SystemLogPoller;
 pub use provider::OktaProvider;
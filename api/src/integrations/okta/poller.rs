@@ -0,0 +1,81 @@
+use super::client::{OktaClient, OktaLogEvent};
+use futures::stream::{self, BoxStream, StreamExt};
+
+/// Drives Okta's System Log polling contract: resume from an opaque `next`
+/// Link-header cursor rather than a recomputed timestamp, keep following
+/// `next` links across empty pages, and hand the latest cursor back to the
+/// caller to persist for the next invocation.
+pub struct SystemLogPoller<'a> {
+    client: &'a OktaClient,
+}
+
+impl<'a> SystemLogPoller<'a> {
+    pub fn new(client: &'a OktaClient) -> Self {
+        Self { client }
+    }
+
+    /// Pull up to `max_pages` pages starting from `cursor` (if resuming) or
+    /// `since`/`filter` (for a fresh poll), returning the collected events
+    /// plus the cursor the caller should persist and pass back in as
+    /// `cursor` next time. The cursor advances even across pages with zero
+    /// events, since Okta's polling contract guarantees `next` points past
+    /// them rather than ending the stream.
+    pub async fn poll_batch(
+        &self,
+        since: Option<&str>,
+        filter: Option<&str>,
+        cursor: Option<&str>,
+        max_pages: usize,
+    ) -> Result<(Vec<OktaLogEvent>, Option<String>), String> {
+        let mut url = Some(self.client.system_log_poll_url(since, filter, cursor));
+        let mut events = Vec::new();
+        let mut last_cursor = cursor.map(|c| c.to_string());
+        let mut pages = 0;
+
+        while let Some(current_url) = url.take() {
+            if pages >= max_pages {
+                break;
+            }
+
+            let (page_events, next) = self.client.fetch_log_page(&current_url).await?;
+            pages += 1;
+
+            if next.is_some() {
+                last_cursor = next.clone();
+            }
+            events.extend(page_events);
+            url = next;
+        }
+
+        Ok((events, last_cursor))
+    }
+
+    /// Stream System Log events one at a time, transparently following
+    /// `next` links as they're consumed. Unlike `poll_batch`, this never
+    /// buffers more than a single page in memory, so it's the preferred
+    /// entry point for continuous/long-running polling. The stream ends only
+    /// when a page fails to fetch or Okta stops returning a `next` link.
+    pub fn stream(
+        &'a self,
+        since: Option<String>,
+        filter: Option<String>,
+        cursor: Option<String>,
+    ) -> BoxStream<'a, OktaLogEvent> {
+        let start_url = self
+            .client
+            .system_log_poll_url(since.as_deref(), filter.as_deref(), cursor.as_deref());
+
+        stream::unfold(Some(start_url), move |url| async move {
+            let current_url = url?;
+            match self.client.fetch_log_page(&current_url).await {
+                Ok((events, next)) => Some((stream::iter(events), next)),
+                Err(e) => {
+                    tracing::warn!("Okta system log poll failed: {}", e);
+                    None
+                }
+            }
+        })
+        .flatten()
+        .boxed()
+    }
+}
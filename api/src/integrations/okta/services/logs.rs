@@ -1,9 +1,14 @@
-use crate::integrations::okta::client::OktaClient;
+use crate::integrations::okta::client::{OktaClient, SECURITY_LOG_FILTER};
+use crate::integrations::okta::poller::SystemLogPoller;
 use crate::integrations::provider::{CollectedEvidence, SyncContext, SyncResult};
 use chrono::Utc;
 use serde_json::json;
 use std::collections::HashMap;
 
+/// Safety limit on pages pulled per sync run, mirroring `OktaClient`'s own
+/// pagination cap so a stalled poll can't run away indefinitely.
+const MAX_POLL_PAGES: usize = 100;
+
 /// System Logs Collector for Okta
 pub struct LogsCollector;
 
@@ -11,13 +16,32 @@ impl LogsCollector {
     /// Collect system log data from Okta
     pub async fn sync(
         client: &OktaClient,
-        _context: &SyncContext,
+        context: &SyncContext,
         days: u32,
     ) -> Result<SyncResult, String> {
         let mut result = SyncResult::default();
 
-        // Get security-related logs
-        let logs = client.list_security_logs(days).await?;
+        // Resume from the persisted Link-header cursor unless a full resync
+        // was requested; fall back to the fixed `days` window for the first
+        // run, since there's no cursor yet to resume from.
+        let cursor = resume_cursor(context);
+        let since = if cursor.is_none() {
+            let since = Utc::now() - chrono::Duration::days(days as i64);
+            Some(since.format("%Y-%m-%dT%H:%M:%S.000Z").to_string())
+        } else {
+            None
+        };
+
+        let poller = SystemLogPoller::new(client);
+        let (logs, next_cursor) = poller
+            .poll_batch(
+                since.as_deref(),
+                Some(SECURITY_LOG_FILTER),
+                cursor.as_deref(),
+                MAX_POLL_PAGES,
+            )
+            .await?;
+        result.next_sync_token = next_cursor.or(cursor);
         result.records_processed = logs.len() as i32;
 
         if logs.is_empty() {
@@ -310,6 +334,16 @@ impl LogsCollector {
     }
 }
 
+/// The Link-header cursor to resume from, or `None` to start a fresh poll
+/// from the `days` window (first run or an explicit full resync).
+fn resume_cursor(context: &SyncContext) -> Option<String> {
+    if context.full_resync {
+        None
+    } else {
+        context.page_cursor.clone()
+    }
+}
+
 /// Detect potentially suspicious activity in logs
 fn detect_suspicious_activity(logs: &[crate::integrations::okta::client::OktaLogEvent]) -> Vec<serde_json::Value> {
     let mut suspicious = Vec::new();
@@ -38,18 +38,19 @@ impl MfaCollector {
 
                     // Count factor types
                     for factor in &active_factors {
-                        *factor_type_counts.entry(factor.factor_type.clone()).or_insert(0) += 1;
+                        *factor_type_counts.entry(factor.kind.factor_type_str()).or_insert(0) += 1;
                     }
 
                     let factor_details: Vec<_> = active_factors
                         .iter()
                         .map(|f| {
                             json!({
-                                "type": f.factor_type,
+                                "type": f.kind.factor_type_str(),
                                 "provider": f.provider,
                                 "vendor_name": f.vendor_name,
                                 "status": f.status,
                                 "created": f.created,
+                                "assurance_level": format!("{:?}", f.assurance_level()),
                             })
                         })
                         .collect();
@@ -82,7 +83,7 @@ impl MfaCollector {
                             "email": user.profile.email,
                             "display_name": user.profile.display_name,
                             "factor_count": active_factors.len(),
-                            "factor_types": active_factors.iter().map(|f| &f.factor_type).collect::<Vec<_>>(),
+                            "factor_types": active_factors.iter().map(|f| f.kind.factor_type_str()).collect::<Vec<_>>(),
                         }));
                     }
                 }
@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use uuid::Uuid;
 
+pub mod provenance;
+
 /// Capabilities that an integration can provide
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -23,6 +25,8 @@ pub enum IntegrationCapability {
     ConfigurationState,
     /// Can receive webhooks for real-time updates
     Webhooks,
+    /// Can push findings/remediation items as tickets and pull their status back
+    IssueTracking,
 }
 
 /// Context passed to sync operations
@@ -33,6 +37,20 @@ pub struct SyncContext {
     pub sync_log_id: Uuid,
     pub full_sync: bool,
     pub sync_type: Option<String>,
+    /// High-water mark from the previous sync (RFC3339), used to narrow
+    /// provider queries to records changed since the last run. `None` forces a
+    /// full scan.
+    pub last_sync_token: Option<String>,
+    /// Opaque provider cursor (e.g. a Google `nextPageToken`) persisted from the
+    /// previous run so paginated pulls resume where they left off.
+    pub page_cursor: Option<String>,
+    /// Force a full backfill, ignoring `last_sync_token`/`page_cursor`. Used for
+    /// the initial sync or operator-triggered re-pulls.
+    pub full_resync: bool,
+    /// The previous sync's open-alert set, keyed by `provider:repo#number`, used
+    /// to compute newly-opened and resolved deltas. `None` is treated as an
+    /// empty prior set.
+    pub prior_alert_state: Option<serde_json::Value>,
 }
 
 /// Result of a sync operation
@@ -45,8 +63,36 @@ pub struct SyncResult {
     pub records_deleted: i32,
     pub errors: Vec<SyncError>,
     pub evidence_collected: Vec<CollectedEvidence>,
+    /// Columnar artifacts (Parquet/Arrow-IPC) produced for high-volume event
+    /// streams, referenced in place of inlining thousands of JSON records.
+    #[serde(default)]
+    pub artifacts: Vec<EvidenceArtifact>,
     /// Security alert data for CloudTrail events
     pub security_alerts: Option<SecurityAlertInfo>,
+    /// Resume point to persist for the next incremental run: an RFC3339
+    /// high-water mark, or for the Okta System Log collector the `next`
+    /// Link-header cursor URL.
+    #[serde(default)]
+    pub next_sync_token: Option<String>,
+}
+
+/// A columnar artifact emitted for a high-volume event stream.
+///
+/// The raw `payload` carries the serialized Arrow/Parquet bytes so the sync
+/// pipeline can persist it to evidence storage; `reference` is a stable
+/// content handle for downstream analytics.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceArtifact {
+    /// Serialization format, e.g. `"parquet"` or `"arrow_ipc"`.
+    pub format: String,
+    /// Content-addressed handle (`sha256:<hex>`) for the artifact bytes.
+    pub reference: String,
+    /// Number of rows encoded in the artifact.
+    pub row_count: i64,
+    /// Version of the fixed column schema used to encode the artifact.
+    pub schema_version: i32,
+    /// The serialized columnar bytes.
+    pub payload: Vec<u8>,
 }
 
 /// Security alert information collected during sync
@@ -70,7 +116,9 @@ impl Default for SyncResult {
             records_deleted: 0,
             errors: Vec::new(),
             evidence_collected: Vec::new(),
+            artifacts: Vec::new(),
             security_alerts: None,
+            next_sync_token: None,
         }
     }
 }
@@ -89,9 +137,14 @@ impl SyncResult {
         self.records_deleted += other.records_deleted;
         self.errors.extend(other.errors);
         self.evidence_collected.extend(other.evidence_collected);
+        self.artifacts.extend(other.artifacts);
         if !other.success {
             self.success = false;
         }
+        // Carry the freshest high-water mark forward.
+        if other.next_sync_token.is_some() {
+            self.next_sync_token = other.next_sync_token;
+        }
         // Merge security alerts
         if let Some(other_alerts) = other.security_alerts {
             if let Some(ref mut alerts) = self.security_alerts {
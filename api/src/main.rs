@@ -1,8 +1,12 @@
 use anyhow::Result;
 use opengrc_api::{
-    cache::CacheClient, config::Config, middleware::AuthState, routes,
+    cache::{CacheClient, CacheConfig}, config::Config, middleware::AuthState, routes,
     search::SearchClient, services::AppServices, storage::StorageClient,
-    utils::EncryptionService, workers::ControlTestingWorker,
+    utils::EncryptionService,
+    workers::{
+        ControlTestingWorker, DigestWorker, FrameworkMaintenanceWorker, NotificationQueueWorker,
+        RetentionWorker, SiemStreamWorker, TaskRecurrenceWorker,
+    },
 };
 use sqlx::postgres::PgPoolOptions;
 use std::sync::Arc;
@@ -12,13 +16,37 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    tracing_subscriber::registry()
+    let registry = tracing_subscriber::registry()
         .with(
             tracing_subscriber::EnvFilter::try_from_default_env()
                 .unwrap_or_else(|_| "info,opengrc_api=debug".into()),
         )
-        .with(tracing_subscriber::fmt::layer().json())
-        .init();
+        .with(tracing_subscriber::fmt::layer().json());
+
+    // Opt-in OpenTelemetry: when enabled, export sync spans over OTLP alongside
+    // the JSON logs. Disabled builds/configs keep only the fmt layer.
+    #[cfg(feature = "otel")]
+    {
+        use opengrc_api::integrations::telemetry::{init_tracer, TelemetryConfig};
+        let otel_config = TelemetryConfig::from_env();
+        if otel_config.enabled {
+            match init_tracer(&otel_config) {
+                Ok(tracer) => {
+                    registry
+                        .with(tracing_opentelemetry::layer().with_tracer(tracer))
+                        .init();
+                }
+                Err(e) => {
+                    registry.init();
+                    tracing::warn!("OpenTelemetry disabled: {}", e);
+                }
+            }
+        } else {
+            registry.init();
+        }
+    }
+    #[cfg(not(feature = "otel"))]
+    registry.init();
 
     tracing::info!("Starting OpenGRC API");
 
@@ -40,7 +68,8 @@ async fn main() -> Result<()> {
 
     tracing::info!("Database migrations completed");
 
-    let cache = CacheClient::new(config.redis_url()).await?;
+    let cache_config = CacheConfig::from_env()?;
+    let cache = CacheClient::with_config(config.redis_url(), &cache_config).await?;
     tracing::info!("Redis connection established");
 
     let storage = StorageClient::new(&config.s3).await?;
@@ -81,6 +110,7 @@ async fn main() -> Result<()> {
         config.titanium_vault.client_id.clone(),
         config.titanium_vault.client_secret.clone(),
         config.titanium_vault.redirect_uri.clone(),
+        services.db.clone(),
     ));
 
     let app = routes::create_router(services.clone(), auth_state, config.cors.origins.clone());
@@ -90,6 +120,48 @@ async fn main() -> Result<()> {
     tokio::spawn(worker.run());
     tracing::info!("Control testing worker started");
 
+    // Start the outbound notification queue worker
+    let notification_worker = Arc::new(NotificationQueueWorker::new(services.db.clone()));
+    tokio::spawn(notification_worker.run());
+    tracing::info!("Notification queue worker started");
+
+    // Keep Teams OAuth tokens fresh ahead of their expiry
+    tokio::spawn(opengrc_api::services::chat_delivery::run_teams_token_refresh(
+        services.db.clone(),
+    ));
+    tracing::info!("Teams token refresh task started");
+
+    // Start the scheduled digest worker
+    let digest_worker = Arc::new(DigestWorker::new(services.collaboration.clone()));
+    tokio::spawn(digest_worker.run());
+    tracing::info!("Digest scheduler started");
+
+    // Start the framework maintenance / cache-scrub worker
+    let maintenance_worker = Arc::new(FrameworkMaintenanceWorker::new(
+        services.db.clone(),
+        services.cache.clone(),
+    ));
+    tokio::spawn(maintenance_worker.run());
+    tracing::info!("Framework maintenance worker started");
+
+    // Start the recurring task occurrence scheduler
+    let task_recurrence_worker = Arc::new(TaskRecurrenceWorker::new(services.task.clone()));
+    tokio::spawn(task_recurrence_worker.run());
+    tracing::info!("Task recurrence worker started");
+
+    // Start the scheduled data-retention purge worker
+    let retention_worker = Arc::new(RetentionWorker::new(
+        services.db.clone(),
+        services.retention.clone(),
+    ));
+    tokio::spawn(retention_worker.run());
+    tracing::info!("Data retention worker started");
+
+    // Start the SIEM stream delivery worker
+    let siem_stream_worker = Arc::new(SiemStreamWorker::new(services.db.clone()));
+    tokio::spawn(siem_stream_worker.run());
+    tracing::info!("SIEM stream worker started");
+
     let addr = format!("{}:{}", config.server.host, config.server.port);
     let listener = tokio::net::TcpListener::bind(&addr).await?;
 
@@ -1,3 +1,6 @@
+use crate::middleware::capabilities::CapabilityResolver;
+use crate::middleware::jwks::{JwksCache, SsoClaims, TokenVerification};
+use crate::middleware::sso_session::SsoSessionStore;
 use crate::utils::{AppError, AppResult};
 use axum::{
     extract::{Request, State},
@@ -6,6 +9,7 @@ use axum::{
     response::Response,
 };
 use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
 use std::sync::Arc;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +18,17 @@ pub struct AuthUser {
     pub email: String,
     pub organization_id: Option<String>,
     pub roles: Vec<String>,
+    /// OpenGRC capabilities (e.g. `control:test`, `admin:org`) resolved from
+    /// the IdP's roles/scope via `CapabilityResolver`. Empty for tokens
+    /// validated before capability resolution ran (e.g. very old sessions).
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+}
+
+impl AuthUser {
+    pub fn has_capability(&self, capability: &str) -> bool {
+        self.capabilities.iter().any(|c| c == capability)
+    }
 }
 
 #[derive(Clone)]
@@ -23,16 +38,44 @@ pub struct AuthState {
     pub client_secret: String,
     pub redirect_uri: String,
     pub client: reqwest::Client,
+    pub jwks: Arc<JwksCache>,
+    pub sessions: SsoSessionStore,
+    pub capabilities: CapabilityResolver,
 }
 
 impl AuthState {
-    pub fn new(tv_api_url: String, client_id: String, client_secret: String, redirect_uri: String) -> Self {
+    pub fn new(
+        tv_api_url: String,
+        client_id: String,
+        client_secret: String,
+        redirect_uri: String,
+        db: PgPool,
+    ) -> Self {
+        let client = reqwest::Client::new();
         Self {
+            jwks: Arc::new(JwksCache::new(client.clone())),
+            sessions: SsoSessionStore::new(db.clone()),
+            capabilities: CapabilityResolver::new(db),
             tv_api_url,
             client_id,
             client_secret,
             redirect_uri,
-            client: reqwest::Client::new(),
+            client,
+        }
+    }
+}
+
+impl From<SsoClaims> for AuthUser {
+    fn from(claims: SsoClaims) -> Self {
+        let roles = claims.role_list();
+        let roles = if roles.is_empty() { vec!["user".to_string()] } else { roles };
+
+        Self {
+            id: claims.sub,
+            email: claims.email.unwrap_or_else(|| "unknown".to_string()),
+            organization_id: claims.organization_id,
+            roles,
+            capabilities: Vec::new(),
         }
     }
 }
@@ -68,6 +111,34 @@ fn extract_token(headers: &HeaderMap) -> AppResult<String> {
 }
 
 async fn validate_token(auth_state: &AuthState, token: &str) -> AppResult<AuthUser> {
+    if auth_state.sessions.is_revoked(token).await? {
+        return Err(AppError::Unauthorized("Session has been revoked".to_string()));
+    }
+
+    match auth_state
+        .jwks
+        .verify(token, &auth_state.tv_api_url, &auth_state.tv_api_url, &auth_state.client_id)
+        .await?
+    {
+        TokenVerification::Valid(claims) => {
+            let capabilities = auth_state
+                .capabilities
+                .resolve(claims.organization_id.as_deref(), &claims.role_list(), claims.scope.as_deref())
+                .await;
+            let mut user: AuthUser = claims.into();
+            user.capabilities = capabilities;
+            return Ok(user);
+        }
+        // No key id, or one missing from the current key set: the IdP may have rotated
+        // ahead of us, or this isn't a JWT TitaniumVault issued. Fall back to
+        // the remote userinfo round-trip rather than reject outright.
+        TokenVerification::UnknownKey => {}
+    }
+
+    validate_token_remote(auth_state, token).await
+}
+
+async fn validate_token_remote(auth_state: &AuthState, token: &str) -> AppResult<AuthUser> {
     let url = format!("{}/userinfo", auth_state.tv_api_url.trim_end_matches('/'));
 
     let response = auth_state
@@ -90,6 +161,18 @@ async fn validate_token(auth_state: &AuthState, token: &str) -> AppResult<AuthUs
         AppError::Unauthorized("Invalid token response".to_string())
     })?;
 
+    let organization_id = userinfo.get("organization_id").and_then(|v| v.as_str()).map(|s| s.to_string());
+    let roles: Vec<String> = userinfo.get("roles")
+        .and_then(|v| v.as_array())
+        .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
+        .unwrap_or_else(|| vec!["user".to_string()]);
+    let scope = userinfo.get("scope").or_else(|| userinfo.get("scp")).and_then(|v| v.as_str());
+
+    let capabilities = auth_state
+        .capabilities
+        .resolve(organization_id.as_deref(), &roles, scope)
+        .await;
+
     // Extract user info from TV userinfo response
     let user = AuthUser {
         id: userinfo.get("sub")
@@ -100,13 +183,9 @@ async fn validate_token(auth_state: &AuthState, token: &str) -> AppResult<AuthUs
             .and_then(|v| v.as_str())
             .unwrap_or("unknown")
             .to_string(),
-        organization_id: userinfo.get("organization_id")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string()),
-        roles: userinfo.get("roles")
-            .and_then(|v| v.as_array())
-            .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
-            .unwrap_or_else(|| vec!["user".to_string()]),
+        organization_id,
+        roles,
+        capabilities,
     };
 
     Ok(user)
@@ -0,0 +1,90 @@
+use sqlx::PgPool;
+
+/// Built-in IdP role -> OpenGRC capability mapping, used whenever an
+/// organization hasn't configured an override for a given role. Capability
+/// codes follow the same `resource:action` shape as the in-app `Permission`
+/// table so the two stay comparable even though SSO resolution happens
+/// before a row-level org/user lookup is possible.
+const DEFAULT_ROLE_CAPABILITIES: &[(&str, &[&str])] = &[
+    ("admin", &["admin:org", "control:test", "evidence:write", "task:assign"]),
+    ("manager", &["control:test", "evidence:write", "task:assign"]),
+    ("contributor", &["evidence:write", "task:assign"]),
+    ("editor", &["evidence:write", "task:assign"]),
+    ("auditor", &["control:test"]),
+    ("reviewer", &["control:test"]),
+    ("viewer", &[]),
+    ("user", &[]),
+];
+
+/// Resolves an SSO token's `roles` array and `scope` claim into the set of
+/// OpenGRC capabilities the caller is allowed to exercise, applying any
+/// org-level override before falling back to `DEFAULT_ROLE_CAPABILITIES`.
+#[derive(Clone)]
+pub struct CapabilityResolver {
+    db: PgPool,
+}
+
+impl CapabilityResolver {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+
+    pub async fn resolve(
+        &self,
+        organization_id: Option<&str>,
+        roles: &[String],
+        scope: Option<&str>,
+    ) -> Vec<String> {
+        let mut capabilities = Vec::new();
+
+        for role in roles {
+            let role = role.to_lowercase();
+            let resolved = match organization_id {
+                Some(org_id) => match self.org_override(org_id, &role).await {
+                    Some(overridden) => overridden,
+                    None => default_capabilities(&role),
+                },
+                None => default_capabilities(&role),
+            };
+            capabilities.extend(resolved);
+        }
+
+        // A scope token already shaped like a capability (`resource:action`)
+        // is passed through directly rather than requiring a role mapping
+        // for it, since the IdP may grant capabilities via scope alone.
+        if let Some(scope) = scope {
+            capabilities.extend(
+                scope
+                    .split_whitespace()
+                    .filter(|s| s.contains(':'))
+                    .map(|s| s.to_string()),
+            );
+        }
+
+        capabilities.sort();
+        capabilities.dedup();
+        capabilities
+    }
+
+    async fn org_override(&self, organization_id: &str, idp_role: &str) -> Option<Vec<String>> {
+        sqlx::query_scalar::<_, Vec<String>>(
+            "SELECT capabilities FROM sso_capability_overrides WHERE organization_id = $1 AND idp_role = $2",
+        )
+        .bind(organization_id)
+        .bind(idp_role)
+        .fetch_optional(&self.db)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!("Failed to look up SSO capability override: {:?}", e);
+            None
+        })
+    }
+}
+
+fn default_capabilities(role: &str) -> Vec<String> {
+    DEFAULT_ROLE_CAPABILITIES
+        .iter()
+        .find(|(name, _)| *name == role)
+        .map(|(_, caps)| caps.iter().map(|c| c.to_string()).collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,227 @@
+use crate::utils::{AppError, AppResult};
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+/// How long a fetched JWKS is trusted before the next lookup triggers a
+/// refresh, even if the key id being looked up is already cached.
+const JWKS_CACHE_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// Claims extracted from a verified SSO bearer token. Mirrors the subset of
+/// the TitaniumVault `/userinfo` response that `AuthUser`/`SSOUser` need.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SsoClaims {
+    pub sub: String,
+    pub email: Option<String>,
+    pub role: Option<String>,
+    pub roles: Option<Vec<String>>,
+    /// Space-delimited OAuth scope claim (`scope` or `scp`), in addition to
+    /// whatever `role`/`roles` the IdP sends.
+    #[serde(alias = "scp")]
+    pub scope: Option<String>,
+    pub organization_id: Option<String>,
+    pub exp: i64,
+    pub iat: Option<i64>,
+}
+
+impl SsoClaims {
+    /// All IdP roles assigned to this token, regardless of whether the IdP
+    /// sent a single `role` or a `roles` array.
+    pub fn role_list(&self) -> Vec<String> {
+        self.roles
+            .clone()
+            .filter(|r| !r.is_empty())
+            .or_else(|| self.role.clone().map(|r| vec![r]))
+            .unwrap_or_default()
+    }
+}
+
+/// Outcome of a local verification attempt. `UnknownKey` means the token has
+/// no `kid`, or its `kid` isn't in the key set (which is refetched only when
+/// empty or stale) — the caller should fall back to the remote `/userinfo`
+/// proxy rather than treat this as an invalid token.
+pub enum TokenVerification {
+    Valid(SsoClaims),
+    UnknownKey,
+}
+
+#[derive(Clone)]
+struct CachedKey {
+    key: DecodingKey,
+    algorithm: Algorithm,
+}
+
+#[derive(Default)]
+struct JwksState {
+    keys: HashMap<String, CachedKey>,
+    fetched_at: Option<Instant>,
+}
+
+#[derive(Deserialize)]
+struct Jwk {
+    kty: String,
+    kid: String,
+    alg: Option<String>,
+    n: Option<String>,
+    e: Option<String>,
+    crv: Option<String>,
+    x: Option<String>,
+    y: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JwksResponse {
+    keys: Vec<Jwk>,
+}
+
+/// Caches the IdP's JSON Web Key Set so SSO bearer tokens can be verified
+/// locally (RS256/ES256 signature + `exp`/`iss`/`aud`) instead of
+/// round-tripping to TitaniumVault's `/userinfo` on every authenticated
+/// request.
+pub struct JwksCache {
+    state: RwLock<JwksState>,
+    client: reqwest::Client,
+}
+
+impl JwksCache {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self {
+            state: RwLock::new(JwksState::default()),
+            client,
+        }
+    }
+
+    /// Verify `token` against the cached JWKS for `tv_api_url`, checking the
+    /// signature plus `exp`/`iss`/`aud`. Returns `UnknownKey` (not an error)
+    /// when the token has no `kid` or its `kid` can't be resolved from the
+    /// key set (see [`Self::get_key`] for when that set is refetched), so
+    /// the caller can fall back to the remote userinfo proxy.
+    pub async fn verify(
+        &self,
+        token: &str,
+        tv_api_url: &str,
+        issuer: &str,
+        audience: &str,
+    ) -> AppResult<TokenVerification> {
+        let header = decode_header(token)
+            .map_err(|e| AppError::Unauthorized(format!("Invalid token header: {e}")))?;
+
+        let Some(kid) = header.kid else {
+            return Ok(TokenVerification::UnknownKey);
+        };
+
+        let Some(cached) = self.get_key(tv_api_url, &kid).await else {
+            return Ok(TokenVerification::UnknownKey);
+        };
+
+        let mut validation = Validation::new(cached.algorithm);
+        validation.set_issuer(&[issuer]);
+        validation.set_audience(&[audience]);
+
+        let data = decode::<SsoClaims>(token, &cached.key, &validation)
+            .map_err(|e| AppError::Unauthorized(format!("Invalid token: {e}")))?;
+
+        Ok(TokenVerification::Valid(data.claims))
+    }
+
+    /// Look up the decoding key for `kid`, refreshing the cache from
+    /// `{tv_api_url}/.well-known/jwks.json` first if it's empty or stale.
+    /// A `kid` that's simply absent from an otherwise-fresh cache is treated
+    /// as confirmed-absent-for-now rather than triggering its own refresh -
+    /// otherwise a token with an unrecognized `kid` would force a live
+    /// round-trip to the IdP on every single request carrying it, completely
+    /// bypassing the TTL.
+    async fn get_key(&self, tv_api_url: &str, kid: &str) -> Option<CachedKey> {
+        if let Some(cached) = self.cached_key(kid).await {
+            return Some(cached);
+        }
+
+        if self.is_stale().await {
+            self.refresh(tv_api_url).await;
+            return self.cached_key(kid).await;
+        }
+
+        None
+    }
+
+    /// Returns the cached key for `kid` only if the cache is still within
+    /// its TTL. Does *not* distinguish "stale" from "fresh but absent" -
+    /// callers needing that distinction should also check `is_stale`.
+    async fn cached_key(&self, kid: &str) -> Option<CachedKey> {
+        let state = self.state.read().await;
+        let fresh = state
+            .fetched_at
+            .map(|t| t.elapsed() < JWKS_CACHE_TTL)
+            .unwrap_or(false);
+        if !fresh {
+            return None;
+        }
+        state.keys.get(kid).cloned()
+    }
+
+    /// Whether the cache is empty or past its TTL and due for a refresh.
+    async fn is_stale(&self) -> bool {
+        let state = self.state.read().await;
+        state
+            .fetched_at
+            .map(|t| t.elapsed() >= JWKS_CACHE_TTL)
+            .unwrap_or(true)
+    }
+
+    async fn refresh(&self, tv_api_url: &str) {
+        let url = format!("{}/.well-known/jwks.json", tv_api_url.trim_end_matches('/'));
+
+        let jwks = match self.client.get(&url).send().await {
+            Ok(resp) if resp.status().is_success() => match resp.json::<JwksResponse>().await {
+                Ok(jwks) => jwks,
+                Err(e) => {
+                    tracing::warn!("Failed to parse JWKS response: {:?}", e);
+                    return;
+                }
+            },
+            Ok(resp) => {
+                tracing::warn!("JWKS fetch returned status {}", resp.status());
+                return;
+            }
+            Err(e) => {
+                tracing::warn!("Failed to fetch JWKS from {}: {:?}", url, e);
+                return;
+            }
+        };
+
+        let keys = jwks
+            .keys
+            .iter()
+            .filter_map(|jwk| decode_jwk(jwk).map(|k| (jwk.kid.clone(), k)))
+            .collect();
+
+        let mut state = self.state.write().await;
+        state.keys = keys;
+        state.fetched_at = Some(Instant::now());
+    }
+}
+
+fn decode_jwk(jwk: &Jwk) -> Option<CachedKey> {
+    match jwk.kty.as_str() {
+        "RSA" => {
+            let key = DecodingKey::from_rsa_components(jwk.n.as_deref()?, jwk.e.as_deref()?).ok()?;
+            let algorithm = match jwk.alg.as_deref() {
+                Some("RS384") => Algorithm::RS384,
+                Some("RS512") => Algorithm::RS512,
+                _ => Algorithm::RS256,
+            };
+            Some(CachedKey { key, algorithm })
+        }
+        "EC" => {
+            let key = DecodingKey::from_ec_components(jwk.x.as_deref()?, jwk.y.as_deref()?).ok()?;
+            let algorithm = match jwk.crv.as_deref() {
+                Some("P-384") => Algorithm::ES384,
+                _ => Algorithm::ES256,
+            };
+            Some(CachedKey { key, algorithm })
+        }
+        _ => None,
+    }
+}
@@ -1,7 +1,13 @@
 pub mod auth;
+pub mod capabilities;
+pub mod jwks;
 pub mod logging;
 pub mod rate_limit;
+pub mod sso_session;
 
 pub use auth::{auth_middleware, get_auth_user, AuthState, AuthUser};
+pub use capabilities::CapabilityResolver;
+pub use jwks::{JwksCache, SsoClaims, TokenVerification};
 pub use logging::logging_middleware;
 pub use rate_limit::{rate_limit_middleware, RateLimiter, RateLimitConfig, get_rate_limit_config_for_tier};
+pub use sso_session::SsoSessionStore;
@@ -0,0 +1,119 @@
+use chrono::{DateTime, Duration, Utc};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::utils::AppResult;
+
+/// Server-side tracking for SSO sessions issued via `/api/sso/exchange`, so
+/// `/api/sso/refresh` can rotate the stored refresh token and `/api/sso/logout`
+/// can revoke a session rather than only telling the client to drop its cookie.
+/// Tokens are stored hashed, never in cleartext.
+#[derive(Clone)]
+pub struct SsoSessionStore {
+    db: PgPool,
+}
+
+impl SsoSessionStore {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+
+    /// Record a freshly issued access/refresh token pair for `subject`.
+    pub async fn record(
+        &self,
+        subject: &str,
+        access_token: &str,
+        refresh_token: Option<&str>,
+        expires_in: i64,
+    ) -> AppResult<()> {
+        let expires_at = Utc::now() + Duration::seconds(expires_in.max(0));
+
+        sqlx::query(
+            r#"
+            INSERT INTO sso_refresh_sessions
+                (id, subject, access_token_hash, refresh_token_hash, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(subject)
+        .bind(hash_token(access_token))
+        .bind(refresh_token.map(hash_token))
+        .bind(expires_at)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Rotate the session keyed by `old_refresh_token`: it stops being valid
+    /// for another refresh and the new access/refresh token pair takes over.
+    /// Returns the session's `subject` if an active session was found.
+    pub async fn rotate(
+        &self,
+        old_refresh_token: &str,
+        new_access_token: &str,
+        new_refresh_token: Option<&str>,
+        expires_in: i64,
+    ) -> AppResult<Option<String>> {
+        let expires_at = Utc::now() + Duration::seconds(expires_in.max(0));
+
+        let subject: Option<String> = sqlx::query_scalar(
+            r#"
+            UPDATE sso_refresh_sessions SET
+                access_token_hash = $1,
+                refresh_token_hash = $2,
+                expires_at = $3,
+                last_seen_at = NOW()
+            WHERE refresh_token_hash = $4 AND revoked_at IS NULL
+            RETURNING subject
+            "#,
+        )
+        .bind(hash_token(new_access_token))
+        .bind(new_refresh_token.map(hash_token))
+        .bind(expires_at)
+        .bind(hash_token(old_refresh_token))
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(subject)
+    }
+
+    /// Revoke the session identified by its current access token. A no-op
+    /// (not an error) if no matching session is tracked, since sessions
+    /// established before this table existed won't have one.
+    pub async fn revoke_by_access_token(&self, access_token: &str) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE sso_refresh_sessions SET revoked_at = NOW()
+            WHERE access_token_hash = $1 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(hash_token(access_token))
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Whether `access_token` maps to a session that has been revoked.
+    /// Tokens with no tracked session (issued before this store existed, or
+    /// never recorded) are treated as active.
+    pub async fn is_revoked(&self, access_token: &str) -> AppResult<bool> {
+        let revoked_at: Option<Option<DateTime<Utc>>> = sqlx::query_scalar(
+            "SELECT revoked_at FROM sso_refresh_sessions WHERE access_token_hash = $1",
+        )
+        .bind(hash_token(access_token))
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(matches!(revoked_at, Some(Some(_))))
+    }
+}
+
+fn hash_token(token: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(token.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
@@ -108,6 +108,7 @@ pub struct NotificationPreferences {
     pub email_enabled: bool,
     pub slack_enabled: bool,
     pub teams_enabled: bool,
+    pub mattermost_enabled: bool,
     pub enabled_types: serde_json::Value,
     pub email_digest_enabled: bool,
     pub email_digest_frequency: Option<String>,
@@ -121,12 +122,33 @@ pub struct NotificationPreferences {
     pub updated_at: DateTime<Utc>,
 }
 
+impl NotificationPreferences {
+    /// Whether a given notification type is enabled for this user.
+    ///
+    /// `enabled_types` is stored as a JSON object mapping type name to a boolean;
+    /// a type is considered enabled unless it is explicitly set to `false`. An
+    /// array of enabled type names is also accepted for forward compatibility.
+    pub fn is_type_enabled(&self, notification_type: &str) -> bool {
+        match &self.enabled_types {
+            serde_json::Value::Object(map) => map
+                .get(notification_type)
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true),
+            serde_json::Value::Array(types) => {
+                types.iter().any(|v| v.as_str() == Some(notification_type))
+            }
+            _ => true,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateNotificationPreferences {
     pub in_app_enabled: Option<bool>,
     pub email_enabled: Option<bool>,
     pub slack_enabled: Option<bool>,
     pub teams_enabled: Option<bool>,
+    pub mattermost_enabled: Option<bool>,
     pub enabled_types: Option<serde_json::Value>,
     pub email_digest_enabled: Option<bool>,
     pub email_digest_frequency: Option<String>,
@@ -179,6 +201,23 @@ pub struct EmailDigest {
     pub created_at: DateTime<Utc>,
 }
 
+/// A scheduling candidate for the digest worker: who might receive a digest and
+/// the inputs needed to decide whether the current tick crosses their local send
+/// hour.
+#[derive(Debug, Clone, FromRow)]
+pub struct DigestCandidate {
+    pub organization_id: Uuid,
+    pub user_id: Uuid,
+    /// Hour of the day (0-23, local) at which the digest should be delivered.
+    pub send_hour: i32,
+    /// For weekly digests, the day of week (0 = Sunday) to deliver on.
+    pub send_day_of_week: Option<i32>,
+    /// Recipient's UTC offset in seconds (east positive).
+    pub tz_offset_seconds: i32,
+    /// When the user last received a digest of this type, if ever.
+    pub last_digest_at: Option<DateTime<Utc>>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DigestContent {
     pub tasks_due: Vec<DigestTask>,
@@ -382,6 +421,86 @@ pub struct TeamsUserConnection {
     pub connected_at: DateTime<Utc>,
 }
 
+// =====================================================
+// MATTERMOST INTEGRATION
+// =====================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MattermostServer {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub base_url: String,
+    pub server_name: Option<String>,
+    pub access_token: String,
+    pub bot_user_id: Option<String>,
+    pub incoming_webhook_url: Option<String>,
+    pub default_channel_id: Option<String>,
+    pub default_channel_name: Option<String>,
+    pub status: String,
+    pub last_activity_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MattermostServerResponse {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub base_url: String,
+    pub server_name: Option<String>,
+    pub bot_user_id: Option<String>,
+    pub default_channel_id: Option<String>,
+    pub default_channel_name: Option<String>,
+    pub status: String,
+    pub last_activity_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectMattermostServer {
+    pub base_url: String,
+    pub access_token: String,
+    pub server_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MattermostChannelMapping {
+    pub id: Uuid,
+    pub server_id: Uuid,
+    pub notification_type: String,
+    pub channel_id: String,
+    pub channel_name: Option<String>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateMattermostChannelMapping {
+    pub notification_type: String,
+    pub channel_id: String,
+    pub channel_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateMattermostChannelMapping {
+    pub channel_id: Option<String>,
+    pub channel_name: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MattermostUserConnection {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub user_id: Uuid,
+    pub server_id: Uuid,
+    pub mattermost_user_id: String,
+    pub mattermost_username: Option<String>,
+    pub dm_channel_id: Option<String>,
+    pub connected_at: DateTime<Utc>,
+}
+
 // =====================================================
 // REAL-TIME COLLABORATION
 // =====================================================
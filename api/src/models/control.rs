@@ -168,6 +168,12 @@ pub struct ControlTest {
     pub automation_config: Option<serde_json::Value>,
     pub frequency: Option<String>,
     pub next_due_at: Option<DateTime<Utc>>,
+    /// Structured expectation about cloud configuration (a
+    /// `control_assertion::ControlAssertion`), evaluated automatically
+    /// against the evidence collected when an integration's collect-evidence
+    /// sync runs, instead of requiring a human to run the test. The
+    /// `trigger_sync` path does not evaluate assertions.
+    pub assertion: Option<serde_json::Value>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -180,6 +186,7 @@ pub struct CreateControlTest {
     pub automation_config: Option<serde_json::Value>,
     pub frequency: Option<String>,
     pub next_due_at: Option<DateTime<Utc>>,
+    pub assertion: Option<serde_json::Value>,
 }
 
 /// Update control test request
@@ -191,6 +198,7 @@ pub struct UpdateControlTest {
     pub automation_config: Option<serde_json::Value>,
     pub frequency: Option<String>,
     pub next_due_at: Option<DateTime<Utc>>,
+    pub assertion: Option<serde_json::Value>,
 }
 
 /// Control test result
@@ -203,9 +211,26 @@ pub struct ControlTestResult {
     pub status: String,
     pub notes: Option<String>,
     pub evidence_ids: Option<Vec<Uuid>>,
+    /// True when this result was written by the assertion evaluator rather
+    /// than a human running the test.
+    pub automated: bool,
+    /// Offending resources reported by the assertion evaluator on failure
+    /// (e.g. the IAM principals or S3 buckets that violated it).
+    pub offending_resources: Option<serde_json::Value>,
     pub created_at: DateTime<Utc>,
 }
 
+/// One control test's automated pass/fail history, used to derive
+/// time-to-remediation trends for `ControlStats`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AssertionRemediationTrend {
+    pub control_test_id: Uuid,
+    pub control_id: Uuid,
+    pub test_name: String,
+    pub remediation_count: i64,
+    pub avg_remediation_seconds: Option<f64>,
+}
+
 /// Create test result request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateTestResult {
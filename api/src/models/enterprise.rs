@@ -488,6 +488,14 @@ pub struct AuditExportConfiguration {
     pub total_events_exported: Option<i64>,
     pub total_failures: Option<i64>,
     pub last_error: Option<String>,
+    // Continuous streaming mode: push each new ActivityLog entry as it's
+    // written, instead of relying on the batch export routes below.
+    pub streaming_enabled: bool,
+    pub circuit_breaker_state: String,
+    pub circuit_breaker_failure_count: i32,
+    pub circuit_breaker_opened_at: Option<DateTime<Utc>>,
+    pub circuit_breaker_threshold: i32,
+    pub circuit_breaker_reset_ms: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -516,6 +524,12 @@ pub struct AuditExportConfigurationResponse {
     pub total_events_exported: Option<i64>,
     pub total_failures: Option<i64>,
     pub last_error: Option<String>,
+    pub streaming_enabled: bool,
+    pub circuit_breaker_state: String,
+    pub circuit_breaker_failure_count: i32,
+    pub circuit_breaker_opened_at: Option<DateTime<Utc>>,
+    pub circuit_breaker_threshold: i32,
+    pub circuit_breaker_reset_ms: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -538,6 +552,7 @@ pub struct CreateAuditExportConfiguration {
     pub min_severity: Option<String>,
     pub batch_size: Option<i32>,
     pub flush_interval_seconds: Option<i32>,
+    pub streaming_enabled: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -553,6 +568,7 @@ pub struct UpdateAuditExportConfiguration {
     pub s3_access_key: Option<String>,
     pub s3_secret_key: Option<String>,
     pub format: Option<String>,
+    pub streaming_enabled: Option<bool>,
     pub include_pii: Option<bool>,
     pub event_types: Option<Vec<String>>,
     pub min_severity: Option<String>,
@@ -581,6 +597,11 @@ pub struct ActivityLog {
     pub session_id: Option<String>,
     pub resource_name: Option<String>,
     pub created_at: DateTime<Utc>,
+    // Hash chain: entry_hash = SHA256(canonical_json(entry) || prev_hash), so
+    // deleting, reordering, or mutating any entry breaks verification from
+    // that point forward. See EnterpriseService::verify_chain.
+    pub prev_hash: Option<String>,
+    pub entry_hash: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -604,6 +625,8 @@ pub struct ActivityLogWithUser {
     pub session_id: Option<String>,
     pub resource_name: Option<String>,
     pub created_at: DateTime<Utc>,
+    pub prev_hash: Option<String>,
+    pub entry_hash: Option<String>,
     // Joined user fields
     pub user_name: Option<String>,
     pub user_email: Option<String>,
@@ -671,6 +694,39 @@ pub struct LeefEvent {
     pub attributes: std::collections::HashMap<String, String>,
 }
 
+// ============================================================================
+// ACTIVITY LOG HASH CHAIN
+// ============================================================================
+
+/// Result of walking an organization's ActivityLog chain in insertion order
+/// and recomputing each entry's hash from its stored fields.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainVerificationResult {
+    pub valid: bool,
+    pub verified_count: i64,
+    /// 0-based position (in insertion order) of the first entry whose
+    /// recomputed hash diverges from what's stored, if any.
+    pub first_divergence_index: Option<i64>,
+    pub first_divergence_id: Option<Uuid>,
+    pub expected_hash: Option<String>,
+    pub actual_hash: Option<String>,
+}
+
+/// A daily checkpoint of the chain tip for an organization, so a verifier
+/// doesn't have to replay the full history to detect tampering with entries
+/// that predate the anchor.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ActivityLogChainAnchor {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub anchor_date: chrono::NaiveDate,
+    pub tip_activity_log_id: Option<Uuid>,
+    pub tip_hash: String,
+    pub entry_count: i64,
+    pub signature: String,
+    pub created_at: DateTime<Utc>,
+}
+
 // ============================================================================
 // BRANDING / WHITE-LABELING
 // ============================================================================
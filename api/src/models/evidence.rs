@@ -59,6 +59,8 @@ pub struct Evidence {
     pub valid_until: Option<DateTime<Utc>>,
     pub uploaded_by: Option<Uuid>,
     pub created_at: DateTime<Utc>,
+    /// Monotonic head version; incremented on every in-place update.
+    pub version: i32,
 }
 
 /// Evidence with linked controls
@@ -87,6 +89,31 @@ pub struct EvidenceControlLink {
     pub control_test_result_id: Option<Uuid>,
     pub linked_by: Option<Uuid>,
     pub linked_at: DateTime<Utc>,
+    /// Evidence head version pinned at link time, so a tested control resolves
+    /// the exact historical snapshot rather than the current head.
+    pub evidence_version: Option<i32>,
+}
+
+/// An immutable prior state of an evidence record.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct EvidenceVersion {
+    pub id: Uuid,
+    pub evidence_id: Uuid,
+    pub organization_id: Uuid,
+    pub version: i32,
+    pub title: String,
+    pub description: Option<String>,
+    pub evidence_type: String,
+    pub source: String,
+    pub source_reference: Option<String>,
+    pub file_path: Option<String>,
+    pub file_size: Option<i64>,
+    pub mime_type: Option<String>,
+    pub collected_at: DateTime<Utc>,
+    pub valid_from: Option<DateTime<Utc>>,
+    pub valid_until: Option<DateTime<Utc>>,
+    pub updated_by: Option<Uuid>,
+    pub updated_at: DateTime<Utc>,
 }
 
 /// Create evidence request
@@ -126,6 +153,136 @@ pub struct ListEvidenceQuery {
     pub expired: Option<bool>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
+    /// Opaque keyset cursor (base64 of `collected_at` + `id`). When set, takes
+    /// precedence over `offset` for O(page) deep pagination.
+    pub after: Option<String>,
+}
+
+/// A page of evidence with an opaque cursor for the next page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PagedEvidence {
+    pub items: Vec<EvidenceWithLinks>,
+    /// Cursor to pass as `after` for the next page; `None` at the end of the set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+/// Full-text evidence search query params
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct EvidenceSearchQuery {
+    /// Free-text query (supports `websearch_to_tsquery` operators).
+    pub query: String,
+    pub evidence_type: Option<String>,
+    pub source: Option<String>,
+    pub expired: Option<bool>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+    /// Minimum trigram similarity for fuzzy title matches (0.0–1.0).
+    pub min_similarity: Option<f64>,
+    /// Treat the final term as a prefix for as-you-type search.
+    #[serde(default)]
+    pub prefix: bool,
+}
+
+/// A single ranked search hit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceSearchHit {
+    #[serde(flatten)]
+    pub evidence: Evidence,
+    pub linked_control_count: i64,
+    /// Blended relevance score (ts_rank_cd weighted over trigram similarity).
+    pub score: f64,
+}
+
+/// Facet bucket with a count over the full matched set.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SearchFacet {
+    pub value: String,
+    pub count: i64,
+}
+
+/// Ranked hits plus facet counts computed over all matches.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceSearchResults {
+    pub hits: Vec<EvidenceSearchHit>,
+    pub total_matched: i64,
+    pub facets_by_type: Vec<SearchFacet>,
+    pub facets_by_source: Vec<SearchFacet>,
+}
+
+/// A single operation in a batch apply request.
+///
+/// Tagged on `op` so clients send `{"op": "create", ...fields}`; the payload
+/// for create/update is flattened in place to match the standalone endpoints.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum EvidenceBatchOp {
+    Create {
+        #[serde(flatten)]
+        input: CreateEvidence,
+    },
+    Update {
+        id: Uuid,
+        #[serde(flatten)]
+        input: UpdateEvidence,
+    },
+    Delete {
+        id: Uuid,
+    },
+    Link {
+        evidence_id: Uuid,
+        control_ids: Vec<Uuid>,
+    },
+    Unlink {
+        evidence_id: Uuid,
+        control_ids: Vec<Uuid>,
+    },
+}
+
+/// Batch apply request body.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceBatchRequest {
+    pub ops: Vec<EvidenceBatchOp>,
+    /// When true, any failing op rolls the whole batch back.
+    #[serde(default)]
+    pub atomic: bool,
+}
+
+/// Positional result for one batch op.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchOpResult {
+    pub index: usize,
+    pub success: bool,
+    /// The created/updated evidence, when the op produced one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evidence: Option<Evidence>,
+    /// Rows affected for delete/link/unlink ops.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub affected: Option<i64>,
+    /// Per-op error message when `success` is false.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BatchOpResult {
+    pub(crate) fn ok(index: usize, evidence: Option<Evidence>, affected: Option<i64>) -> Self {
+        Self { index, success: true, evidence, affected, error: None }
+    }
+
+    pub(crate) fn failed(index: usize, error: String) -> Self {
+        Self { index, success: false, evidence: None, affected: None, error: Some(error) }
+    }
+
+    /// Placeholder for ops skipped after an atomic rollback.
+    pub(crate) fn rolled_back(index: usize) -> Self {
+        Self {
+            index,
+            success: false,
+            evidence: None,
+            affected: None,
+            error: Some("rolled back: an earlier op in the atomic batch failed".to_string()),
+        }
+    }
 }
 
 /// Evidence statistics
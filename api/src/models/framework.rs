@@ -88,6 +88,225 @@ pub struct RequirementGapAnalysis {
     pub category: Option<String>,
     pub control_count: i64,
     pub is_covered: bool,
+    /// Controls mapped directly to this requirement.
+    pub direct_control_count: i64,
+    /// Controls counted via an `exact`/`superset` mapping from another
+    /// framework. Zero unless gap analysis runs with `include_inherited`.
+    pub inherited_control_count: i64,
+    /// Source requirements whose coverage was inherited into this one.
+    pub inherited_from: Vec<InheritedRequirement>,
+}
+
+/// A node in the hierarchical coverage rollup. Leaves carry their own
+/// mapping-based coverage; internal nodes aggregate their descendant leaves.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequirementGapNode {
+    pub id: Uuid,
+    pub code: String,
+    pub name: String,
+    /// Controls mapped directly to this node.
+    pub direct_control_count: i64,
+    /// Total leaf requirements at or below this node.
+    pub descendant_leaf_count: i64,
+    /// Descendant leaves that are covered.
+    pub covered_leaf_count: i64,
+    /// `covered_leaf_count / descendant_leaf_count` as a percentage.
+    pub rollup_coverage_percentage: f64,
+    /// A leaf is covered when mapped; an internal node only when every
+    /// descendant leaf is covered.
+    pub is_covered: bool,
+    pub children: Vec<RequirementGapNode>,
+}
+
+/// A source requirement that contributed inherited coverage.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InheritedRequirement {
+    pub id: Uuid,
+    pub code: String,
+    pub relationship: String,
+    pub control_count: i64,
+}
+
+/// A directed crosswalk between two requirements in different frameworks.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RequirementMapping {
+    pub id: Uuid,
+    pub source_req_id: Uuid,
+    pub target_req_id: Uuid,
+    pub relationship: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request to crosswalk one requirement onto another.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MapRequirements {
+    pub source_req_id: Uuid,
+    pub target_req_id: Uuid,
+    /// One of `exact`, `subset`, `superset`, `related`.
+    pub relationship: String,
+}
+
+impl MapRequirements {
+    /// Relationships that propagate coverage during inherited gap analysis.
+    pub const INHERITING: [&'static str; 2] = ["exact", "superset"];
+
+    pub fn validate(&self) -> Result<(), String> {
+        const VALID: [&str; 4] = ["exact", "subset", "superset", "related"];
+        if !VALID.contains(&self.relationship.as_str()) {
+            return Err(format!(
+                "relationship must be one of {:?}, got {:?}",
+                VALID, self.relationship
+            ));
+        }
+        if self.source_req_id == self.target_req_id {
+            return Err("a requirement cannot be mapped to itself".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// An editgroup groups a batch of framework/requirement mutations so they are
+/// committed atomically and reviewable as one reversible unit.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Editgroup {
+    pub id: Uuid,
+    pub description: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An immutable revision of a framework's identity.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FrameworkRevision {
+    pub id: Uuid,
+    pub ident: Uuid,
+    pub editgroup_id: Uuid,
+    /// One of `created`, `updated`, `deleted`.
+    pub op: String,
+    pub is_current: bool,
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub is_system: Option<bool>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An immutable revision of a requirement's identity.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RequirementRevision {
+    pub id: Uuid,
+    pub ident: Uuid,
+    pub framework_ident: Uuid,
+    pub editgroup_id: Uuid,
+    /// One of `created`, `updated`, `deleted`.
+    pub op: String,
+    pub is_current: bool,
+    pub code: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub parent_ident: Option<Uuid>,
+    pub sort_order: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Requirement-level diff between two framework revisions.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RevisionDiff {
+    pub added: Vec<RequirementRevision>,
+    pub removed: Vec<RequirementRevision>,
+    pub changed: Vec<RequirementChange>,
+}
+
+/// A requirement present in both revisions whose fields differ.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequirementChange {
+    pub ident: Uuid,
+    pub before: RequirementRevision,
+    pub after: RequirementRevision,
+}
+
+/// The kind of mutation recorded in the change feed.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+#[serde(rename_all = "PascalCase")]
+pub enum ChangeOp {
+    Created,
+    Updated,
+    Deleted,
+}
+
+/// A single ordered entry in the framework/requirement change feed.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ChangeEvent {
+    pub seq: i64,
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub op: ChangeOp,
+    /// The entity state captured when the change was recorded; `null` snapshots
+    /// are retained for deletes so the event survives the row.
+    pub current_value: Option<serde_json::Value>,
+}
+
+/// A resumable page of the change feed plus its new high-water mark.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeBatch {
+    pub changes: Vec<ChangeEvent>,
+    pub last_seq: i64,
+}
+
+/// A symmetric equivalence between two requirements in different frameworks.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RequirementCrosswalk {
+    pub id: Uuid,
+    pub requirement_a_id: Uuid,
+    pub requirement_b_id: Uuid,
+    /// One of `Equivalent`, `Subset`, `Related`.
+    pub relationship: String,
+    pub confidence: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Request to crosswalk two requirements as equivalent (or related).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateCrosswalk {
+    pub requirement_a_id: Uuid,
+    pub requirement_b_id: Uuid,
+    pub relationship: String,
+    pub confidence: Option<f64>,
+}
+
+impl CreateCrosswalk {
+    pub fn validate(&self) -> Result<(), String> {
+        const VALID: [&str; 3] = ["Equivalent", "Subset", "Related"];
+        if !VALID.contains(&self.relationship.as_str()) {
+            return Err(format!(
+                "relationship must be one of {:?}, got {:?}",
+                VALID, self.relationship
+            ));
+        }
+        if self.requirement_a_id == self.requirement_b_id {
+            return Err("a requirement cannot be crosswalked to itself".to_string());
+        }
+        if let Some(c) = self.confidence {
+            if !(0.0..=1.0).contains(&c) {
+                return Err("confidence must be between 0 and 1".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A candidate equivalence proposed by `suggest_crosswalks`, ranked by
+/// normalized token-overlap (Jaccard) similarity.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrosswalkSuggestion {
+    pub requirement_a_id: Uuid,
+    pub requirement_a_code: String,
+    pub requirement_b_id: Uuid,
+    pub requirement_b_code: String,
+    pub similarity: f64,
 }
 
 /// Gap analysis summary for a framework
@@ -101,6 +320,9 @@ pub struct FrameworkGapAnalysis {
     pub coverage_percentage: f64,
     pub by_category: Vec<CategoryGapAnalysis>,
     pub requirements: Vec<RequirementGapAnalysis>,
+    /// Hierarchical coverage rollup over the `parent_id` tree: one root node
+    /// per top-level requirement (including any with an orphaned `parent_id`).
+    pub tree: Vec<RequirementGapNode>,
 }
 
 /// Gap analysis breakdown by category
@@ -142,6 +364,102 @@ impl FrameworkRequirement {
     }
 }
 
+/// Build the hierarchical coverage rollup from the flat requirement list.
+///
+/// `coverage` maps each requirement id to its `(direct_control_count,
+/// is_covered)` as computed by the flat gap analysis. Coverage is rolled up
+/// bottom-up: a leaf is covered when it has ≥1 mapping, an internal node only
+/// when all of its descendant leaves are. Requirements whose `parent_id` points
+/// at a missing row are re-parented to the top level so nothing is dropped.
+/// Requirements on a `parent_id` cycle have no path to the top level and are
+/// not included in the rollup.
+pub fn build_gap_rollup(
+    requirements: &[FrameworkRequirement],
+    coverage: &std::collections::HashMap<Uuid, (i64, bool)>,
+) -> Vec<RequirementGapNode> {
+    use std::collections::{HashMap, HashSet};
+
+    let present: HashSet<Uuid> = requirements.iter().map(|r| r.id).collect();
+    let by_id: HashMap<Uuid, &FrameworkRequirement> =
+        requirements.iter().map(|r| (r.id, r)).collect();
+
+    // Adjacency of parent -> children. Orphaned parents attach to the root.
+    let mut children_of: HashMap<Option<Uuid>, Vec<Uuid>> = HashMap::new();
+    for req in requirements {
+        let parent = match req.parent_id {
+            Some(pid) if present.contains(&pid) => Some(pid),
+            _ => None,
+        };
+        children_of.entry(parent).or_default().push(req.id);
+    }
+
+    fn build(
+        id: Uuid,
+        by_id: &HashMap<Uuid, &FrameworkRequirement>,
+        children_of: &HashMap<Option<Uuid>, Vec<Uuid>>,
+        coverage: &HashMap<Uuid, (i64, bool)>,
+        ancestors: &mut HashSet<Uuid>,
+    ) -> RequirementGapNode {
+        let req = by_id[&id];
+        let (direct_control_count, covered) = coverage.get(&id).copied().unwrap_or((0, false));
+
+        // Break cycles: a child already on the current path is skipped.
+        ancestors.insert(id);
+        let child_nodes: Vec<RequirementGapNode> = children_of
+            .get(&Some(id))
+            .map(|kids| {
+                kids.iter()
+                    .filter(|k| !ancestors.contains(k))
+                    .map(|k| build(*k, by_id, children_of, coverage, ancestors))
+                    .collect()
+            })
+            .unwrap_or_default();
+        ancestors.remove(&id);
+
+        if child_nodes.is_empty() {
+            // Leaf: it is its own single descendant leaf.
+            let covered_leaf_count = if covered { 1 } else { 0 };
+            return RequirementGapNode {
+                id,
+                code: req.code.clone(),
+                name: req.name.clone(),
+                direct_control_count,
+                descendant_leaf_count: 1,
+                covered_leaf_count,
+                rollup_coverage_percentage: if covered { 100.0 } else { 0.0 },
+                is_covered: covered,
+                children: child_nodes,
+            };
+        }
+
+        let descendant_leaf_count: i64 = child_nodes.iter().map(|c| c.descendant_leaf_count).sum();
+        let covered_leaf_count: i64 = child_nodes.iter().map(|c| c.covered_leaf_count).sum();
+        RequirementGapNode {
+            id,
+            code: req.code.clone(),
+            name: req.name.clone(),
+            direct_control_count,
+            descendant_leaf_count,
+            covered_leaf_count,
+            rollup_coverage_percentage: if descendant_leaf_count > 0 {
+                (covered_leaf_count as f64 / descendant_leaf_count as f64) * 100.0
+            } else {
+                0.0
+            },
+            is_covered: descendant_leaf_count > 0 && covered_leaf_count == descendant_leaf_count,
+            children: child_nodes,
+        }
+    }
+
+    let mut roots: Vec<Uuid> = children_of.get(&None).cloned().unwrap_or_default();
+    roots.sort_by_key(|id| by_id[id].sort_order);
+    let mut ancestors: HashSet<Uuid> = HashSet::new();
+    roots
+        .into_iter()
+        .map(|id| build(id, &by_id, &children_of, coverage, &mut ancestors))
+        .collect()
+}
+
 /// Build a tree structure from flat list of requirements
 pub fn build_requirement_tree(requirements: Vec<FrameworkRequirement>) -> Vec<RequirementTree> {
     use std::collections::HashMap;
@@ -828,7 +828,11 @@ pub fn get_available_integrations() -> Vec<AvailableIntegration> {
                 "required": ["domain"],
                 "properties": {
                     "domain": { "type": "string", "title": "Okta Domain", "placeholder": "your-org.okta.com" },
-                    "api_token": { "type": "string", "title": "API Token", "secret": true }
+                    "api_token": { "type": "string", "title": "API Token", "secret": true },
+                    "client_id": { "type": "string", "title": "OAuth2 Client ID (service app, instead of API Token)" },
+                    "scopes": { "type": "string", "title": "OAuth2 Scopes (comma-separated)" },
+                    "private_key_pem": { "type": "string", "title": "OAuth2 Private Key (PEM)", "secret": true },
+                    "key_id": { "type": "string", "title": "OAuth2 Key ID" }
                 }
             }),
             logo_url: Some("/integrations/okta.svg".to_string()),
@@ -1129,3 +1133,36 @@ pub fn get_available_integrations() -> Vec<AvailableIntegration> {
         },
     ]
 }
+
+// ============================================================================
+// ISSUE TRACKING LINKS
+// ============================================================================
+
+/// Links an OpenGRC record (a failed control test, an overdue questionnaire
+/// assignment, a risk item) to the external issue-tracker ticket opened for
+/// it, so repeat syncs can pull the ticket's resolution back instead of
+/// re-creating it.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct IntegrationIssueLink {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub integration_id: Uuid,
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub external_key: String,
+    pub external_status: Option<String>,
+    pub external_resolution: Option<String>,
+    pub mapped_status: Option<String>,
+    pub stale: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Outcome of one push-and-pull issue tracking sync pass.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IssueTrackingSyncReport {
+    pub issues_created: i32,
+    pub issues_updated: i32,
+    pub issues_marked_stale: i32,
+    pub errors: Vec<String>,
+}
@@ -8,6 +8,7 @@ pub mod framework;
 pub mod integration;
 pub mod policy;
 pub mod questionnaire;
+pub mod retention;
 pub mod risk;
 pub mod task;
 pub mod vendor;
@@ -44,18 +45,26 @@ pub use framework::{
     Framework, FrameworkRequirement, CreateFramework, UpdateFramework,
     CreateFrameworkRequirement, UpdateFrameworkRequirement, FrameworkWithRequirements,
     FrameworkGapAnalysis, CategoryGapAnalysis, RequirementGapAnalysis,
+    InheritedRequirement, RequirementMapping, MapRequirements,
+    Editgroup, FrameworkRevision, RequirementRevision, RevisionDiff, RequirementChange,
+    ChangeOp, ChangeEvent, ChangeBatch, RequirementGapNode,
+    RequirementCrosswalk, CreateCrosswalk, CrosswalkSuggestion,
 };
 
 pub use control::{
-    Control, ControlWithMappings, MappedRequirement, ControlRequirementMapping,
+    AssertionRemediationTrend, Control, ControlWithMappings, MappedRequirement, ControlRequirementMapping,
     CreateControl, UpdateControl, ControlTest, CreateControlTest, UpdateControlTest,
     ControlTestResult, CreateTestResult, ListControlsQuery, ControlStats,
 };
 
 pub use evidence::{
-    Evidence, EvidenceWithLinks, LinkedControl, EvidenceControlLink,
-    CreateEvidence, UpdateEvidence, ListEvidenceQuery, EvidenceStats,
+    Evidence, EvidenceWithLinks, LinkedControl, EvidenceControlLink, EvidenceVersion,
+    CreateEvidence, UpdateEvidence, ListEvidenceQuery, PagedEvidence, EvidenceStats,
     TypeCount, SourceCount,
+    // Full-text search types
+    EvidenceSearchQuery, EvidenceSearchHit, SearchFacet, EvidenceSearchResults,
+    // Batch mutation types
+    EvidenceBatchOp, EvidenceBatchRequest, BatchOpResult,
     // Evidence automation types
     EvidenceWithFreshness, EvidenceCollectionTask, CreateEvidenceCollectionTask,
     UpdateEvidenceCollectionTask, EvidenceCollectionRun, EvidenceChange,
@@ -70,6 +79,13 @@ pub use policy::{
     CreatePolicy, UpdatePolicy, ListPoliciesQuery, PolicyStats, CategoryCount,
 };
 
+pub use retention::{
+    DataRetentionPolicy, UpdateDataRetentionPolicy,
+    DataRetentionPolicyForEntity, CreateRetentionPolicyForEntity, UpdateRetentionPolicyForEntity,
+    LegalHold, CreateLegalHold, RetentionPurgeResult, RetentionPurgeSummary,
+    RETENTION_ENTITY_TYPES,
+};
+
 pub use risk::{
     Risk, RiskWithControls, LinkedControlSummary, RiskControlMapping,
     CreateRisk, UpdateRisk, ListRisksQuery, RiskStats, StatusCount, RiskCategoryCount,
@@ -108,6 +124,8 @@ pub use integration::{
     OAuthCallbackParams, OAuthTokenResponse, OAuthRefreshRequest, OAuthProviderConfig,
     // Error handling and retry types
     SyncErrorCategory, CircuitBreakerState,
+    // Issue tracking links
+    IntegrationIssueLink, IssueTrackingSyncReport,
 };
 
 pub use questionnaire::{
@@ -116,6 +134,7 @@ pub use questionnaire::{
     CreateQuestionnaireSection, UpdateQuestionnaireSection, QuestionnaireQuestion,
     CreateQuestionnaireQuestion, UpdateQuestionnaireQuestion, QuestionnaireAssignment,
     QuestionnaireAssignmentWithDetails, CreateQuestionnaireAssignment,
+    QuestionnaireAssignmentWithToken,
     ReviewQuestionnaireAssignment, ListQuestionnaireAssignmentsQuery, QuestionnaireResponse,
     QuestionnaireResponseWithQuestion, SaveQuestionnaireResponse, BulkSaveQuestionnaireResponses,
     QuestionnaireResponseComment, CreateResponseComment, VendorPortalAccess,
@@ -126,7 +145,8 @@ pub use task::{
     Task, TaskWithAssignee, TaskComment, TaskCommentWithUser,
     CreateTask, UpdateTask, CreateTaskComment, ListTasksQuery, TaskStats,
     TaskTypeCount, TaskPriorityCount, TaskAssigneeCount, RecurrencePattern,
-    TaskRecurrenceHistory,
+    TaskRecurrenceHistory, TaskAnalyticsQuery, TaskAnalyticsGroupBy,
+    TaskAnalyticsMetric, TaskAnalyticsBucket, TaskAnalytics,
 };
 
 pub use enterprise::{
@@ -149,6 +169,7 @@ pub use enterprise::{
     CreateAuditExportConfiguration, UpdateAuditExportConfiguration,
     ActivityLog, ActivityLogWithUser, CreateActivityLog, ListActivityLogsQuery,
     ExportActivityLogsRequest, CefEvent, LeefEvent,
+    ChainVerificationResult, ActivityLogChainAnchor,
     // Branding
     BrandingConfiguration, UpdateBrandingConfiguration, SetCustomDomainRequest,
     DomainVerification, DomainVerificationInstructions,
@@ -171,7 +192,8 @@ pub use collaboration::{
     // Notification Preferences
     NotificationPreferences, UpdateNotificationPreferences, NOTIFICATION_TYPES,
     // Email Digests
-    EmailDigest, DigestContent, DigestTask, DigestMention, DigestComment, DigestNotification,
+    EmailDigest, DigestCandidate, DigestContent, DigestTask, DigestMention, DigestComment,
+    DigestNotification,
     // Slack Integration
     SlackWorkspace, SlackWorkspaceResponse, ConnectSlackWorkspace,
     SlackChannelMapping, CreateSlackChannelMapping, UpdateSlackChannelMapping,
@@ -179,6 +201,10 @@ pub use collaboration::{
     // Teams Integration
     TeamsTenant, TeamsTenantResponse, ConnectTeamsTenant,
     TeamsChannelMapping, CreateTeamsChannelMapping, TeamsUserConnection,
+    // Mattermost Integration
+    MattermostServer, MattermostServerResponse, ConnectMattermostServer,
+    MattermostChannelMapping, CreateMattermostChannelMapping, UpdateMattermostChannelMapping,
+    MattermostUserConnection,
     // Real-time Collaboration
     WebSocketSession, CreateWebSocketSession, CollaborationPresence,
     PresenceInfo, UpdatePresence, CollaborationEvent, COLLABORATION_EVENT_TYPES,
@@ -2,12 +2,13 @@ use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 // ==================== TEMPLATES ====================
 
 /// Questionnaire template
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct QuestionnaireTemplate {
     pub id: Uuid,
     pub organization_id: Uuid,
@@ -23,7 +24,7 @@ pub struct QuestionnaireTemplate {
 }
 
 /// Template with sections and questions
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct QuestionnaireTemplateWithDetails {
     #[serde(flatten)]
     pub template: QuestionnaireTemplate,
@@ -32,7 +33,7 @@ pub struct QuestionnaireTemplateWithDetails {
 }
 
 /// Create template request
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateQuestionnaireTemplate {
     pub name: String,
     pub description: Option<String>,
@@ -41,7 +42,7 @@ pub struct CreateQuestionnaireTemplate {
 }
 
 /// Update template request
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UpdateQuestionnaireTemplate {
     pub name: Option<String>,
     pub description: Option<String>,
@@ -53,7 +54,7 @@ pub struct UpdateQuestionnaireTemplate {
 // ==================== SECTIONS ====================
 
 /// Questionnaire section
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct QuestionnaireSection {
     pub id: Uuid,
     pub template_id: Uuid,
@@ -64,7 +65,7 @@ pub struct QuestionnaireSection {
 }
 
 /// Section with questions
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct QuestionnaireSectionWithQuestions {
     #[serde(flatten)]
     pub section: QuestionnaireSection,
@@ -72,7 +73,7 @@ pub struct QuestionnaireSectionWithQuestions {
 }
 
 /// Create section request
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateQuestionnaireSection {
     pub name: String,
     pub description: Option<String>,
@@ -80,7 +81,7 @@ pub struct CreateQuestionnaireSection {
 }
 
 /// Update section request
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UpdateQuestionnaireSection {
     pub name: Option<String>,
     pub description: Option<String>,
@@ -90,7 +91,7 @@ pub struct UpdateQuestionnaireSection {
 // ==================== QUESTIONS ====================
 
 /// Questionnaire question
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct QuestionnaireQuestion {
     pub id: Uuid,
     pub template_id: Uuid,
@@ -109,7 +110,7 @@ pub struct QuestionnaireQuestion {
 }
 
 /// Create question request
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateQuestionnaireQuestion {
     pub section_id: Option<Uuid>,
     pub question_text: String,
@@ -124,7 +125,7 @@ pub struct CreateQuestionnaireQuestion {
 }
 
 /// Update question request
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UpdateQuestionnaireQuestion {
     pub section_id: Option<Uuid>,
     pub question_text: Option<String>,
@@ -141,7 +142,7 @@ pub struct UpdateQuestionnaireQuestion {
 // ==================== ASSIGNMENTS ====================
 
 /// Questionnaire assignment (sent to vendor)
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct QuestionnaireAssignment {
     pub id: Uuid,
     pub organization_id: Uuid,
@@ -156,6 +157,7 @@ pub struct QuestionnaireAssignment {
     pub reviewed_by: Option<Uuid>,
     pub reviewed_at: Option<DateTime<Utc>>,
     pub review_notes: Option<String>,
+    #[schema(value_type = Option<String>)]
     pub score: Option<Decimal>,
     pub risk_rating: Option<String>,
     pub reminder_sent_at: Option<DateTime<Utc>>,
@@ -165,7 +167,7 @@ pub struct QuestionnaireAssignment {
 }
 
 /// Assignment with vendor and template info
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct QuestionnaireAssignmentWithDetails {
     #[serde(flatten)]
     pub assignment: QuestionnaireAssignment,
@@ -176,16 +178,32 @@ pub struct QuestionnaireAssignmentWithDetails {
 }
 
 /// Create assignment request
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateQuestionnaireAssignment {
     pub template_id: Uuid,
     pub vendor_id: Uuid,
     pub due_date: Option<NaiveDate>,
     pub expires_at: Option<DateTime<Utc>>,
+    /// Scopes granted to the vendor-portal access token minted for this
+    /// assignment, e.g. `["response:write", "questionnaire:submit"]`.
+    /// Defaults to the full read/write/submit set so existing callers keep
+    /// today's behavior; pass a narrower set to issue a read-only reviewer
+    /// link instead.
+    pub scope: Option<Vec<String>>,
+}
+
+/// An assignment together with the freshly-minted portal access token.
+/// Only returned from `create_assignment` — the token itself is never
+/// persisted or returned again afterwards.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct QuestionnaireAssignmentWithToken {
+    #[serde(flatten)]
+    pub assignment: QuestionnaireAssignment,
+    pub portal_token: String,
 }
 
 /// Review assignment request
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ReviewQuestionnaireAssignment {
     pub status: String, // approved, rejected
     pub review_notes: Option<String>,
@@ -203,7 +221,7 @@ pub struct ListQuestionnaireAssignmentsQuery {
 // ==================== RESPONSES ====================
 
 /// Questionnaire response
-#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct QuestionnaireResponse {
     pub id: Uuid,
     pub assignment_id: Uuid,
@@ -228,7 +246,7 @@ pub struct QuestionnaireResponseWithQuestion {
 }
 
 /// Save response request (from vendor portal)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SaveQuestionnaireResponse {
     pub question_id: Uuid,
     pub response_text: Option<String>,
@@ -264,7 +282,7 @@ pub struct CreateResponseComment {
 // ==================== VENDOR PORTAL ====================
 
 /// Vendor portal access (for external vendors to fill questionnaires)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct VendorPortalAccess {
     pub assignment_id: Uuid,
     pub vendor_name: String,
@@ -285,7 +303,7 @@ pub struct VendorPortalSubmission {
 // ==================== STATISTICS ====================
 
 /// Questionnaire statistics
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct QuestionnaireStats {
     pub total_templates: i64,
     pub published_templates: i64,
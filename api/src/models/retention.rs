@@ -0,0 +1,86 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Entity types eligible for configurable data-retention purge.
+pub const RETENTION_ENTITY_TYPES: &[&str] = &["activity_log", "evidence", "entity_comment"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DataRetentionPolicy {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub default_retention_days: i32,
+    pub purge_action: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateDataRetentionPolicy {
+    pub default_retention_days: Option<i32>,
+    pub purge_action: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DataRetentionPolicyForEntity {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub entity_type: String,
+    pub retention_days: i32,
+    pub purge_action: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateRetentionPolicyForEntity {
+    pub entity_type: String,
+    pub retention_days: i32,
+    pub purge_action: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateRetentionPolicyForEntity {
+    pub retention_days: Option<i32>,
+    pub purge_action: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LegalHold {
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub reason: String,
+    pub placed_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub released_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateLegalHold {
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub reason: String,
+}
+
+/// Outcome of purging a single entity type, whether a dry run or a live one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionPurgeResult {
+    pub entity_type: String,
+    pub retention_days: i32,
+    pub purge_action: String,
+    pub affected_count: i64,
+    pub held_count: i64,
+}
+
+/// Summary of a full purge run across every configured entity type, suitable
+/// for both the dry-run response and the tamper-evident `ActivityLog` entry
+/// written on a live run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionPurgeSummary {
+    pub dry_run: bool,
+    pub results: Vec<RetentionPurgeResult>,
+    pub run_at: DateTime<Utc>,
+}
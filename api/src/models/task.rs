@@ -1,4 +1,5 @@
-use chrono::{DateTime, Utc};
+use crate::utils::RRule;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Utc, Weekday};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
@@ -123,6 +124,95 @@ impl RecurrencePattern {
             _ => None,
         }
     }
+
+    /// Pure computation of the next occurrence after `from`, with no database
+    /// access. `day_of_week` is 0=Sunday..6=Saturday; `day_of_month` and
+    /// `month_of_year` are clamped to the target month's length (e.g. day 31
+    /// in February becomes 28 or 29).
+    pub fn next_after(
+        &self,
+        from: DateTime<Utc>,
+        interval: i32,
+        day_of_week: Option<i32>,
+        day_of_month: Option<i32>,
+        month_of_year: Option<i32>,
+    ) -> DateTime<Utc> {
+        let interval = interval.max(1);
+        match self {
+            Self::Daily => from + Duration::days(interval as i64),
+            Self::Weekly => Self::advance_weekly(from, interval, day_of_week),
+            // Biweekly always steps two weeks at a time, regardless of the
+            // stored interval.
+            Self::Biweekly => Self::advance_weekly(from, 2, day_of_week),
+            Self::Monthly => Self::advance_months(from, interval, day_of_month),
+            Self::Quarterly => Self::advance_months(from, interval * 3, day_of_month),
+            Self::Yearly => Self::advance_years(from, interval, day_of_month, month_of_year),
+        }
+    }
+
+    /// Step forward `interval` weeks, then roll forward to the next matching
+    /// `day_of_week` if one is set.
+    fn advance_weekly(from: DateTime<Utc>, interval: i32, day_of_week: Option<i32>) -> DateTime<Utc> {
+        let base = from + Duration::weeks(interval as i64);
+        let Some(dow) = day_of_week else {
+            return base;
+        };
+        let target = Self::weekday_from_index(dow);
+        let days_forward = (target.num_days_from_sunday() as i64
+            - base.weekday().num_days_from_sunday() as i64
+            + 7)
+            % 7;
+        base + Duration::days(days_forward)
+    }
+
+    fn weekday_from_index(dow: i32) -> Weekday {
+        match dow {
+            0 => Weekday::Sun,
+            1 => Weekday::Mon,
+            2 => Weekday::Tue,
+            3 => Weekday::Wed,
+            4 => Weekday::Thu,
+            5 => Weekday::Fri,
+            6 => Weekday::Sat,
+            _ => Weekday::Mon,
+        }
+    }
+
+    /// Step forward `months` months, clamping `day_of_month` (or today's day,
+    /// if unset) to the target month's length.
+    fn advance_months(from: DateTime<Utc>, months: i32, day_of_month: Option<i32>) -> DateTime<Utc> {
+        let dom = day_of_month.unwrap_or(from.day() as i32).max(1) as u32;
+        let total_months = from.year() as i64 * 12 + (from.month() as i64 - 1) + months as i64;
+        let year = total_months.div_euclid(12) as i32;
+        let month = total_months.rem_euclid(12) as u32 + 1;
+        Self::date_at(year, month, dom).unwrap_or(from)
+    }
+
+    /// Step forward `years` years, applying `month_of_year`/`day_of_month`
+    /// (or today's month/day, if unset) and clamping the day to that month's
+    /// length.
+    fn advance_years(
+        from: DateTime<Utc>,
+        years: i32,
+        day_of_month: Option<i32>,
+        month_of_year: Option<i32>,
+    ) -> DateTime<Utc> {
+        let moy = month_of_year.unwrap_or(from.month() as i32).clamp(1, 12) as u32;
+        let dom = day_of_month.unwrap_or(from.day() as i32).max(1) as u32;
+        Self::date_at(from.year() + years, moy, dom).unwrap_or(from)
+    }
+
+    /// Midnight UTC on `(year, month, day)`, clamping `day` to the month's
+    /// actual length.
+    fn date_at(year: i32, month: u32, day: u32) -> Option<DateTime<Utc>> {
+        let days_in_month = NaiveDate::from_ymd_opt(year, month, 1)
+            .and_then(|d| d.with_month(month + 1).or_else(|| NaiveDate::from_ymd_opt(year + 1, 1, 1)))
+            .map(|d| d.pred_opt().unwrap().day())
+            .unwrap_or(28);
+        let actual_day = day.min(days_in_month);
+        NaiveDate::from_ymd_opt(year, month, actual_day)
+            .and_then(|d| d.and_time(NaiveTime::from_hms_opt(0, 0, 0)?).and_local_timezone(Utc).single())
+    }
 }
 
 /// Task entity
@@ -153,6 +243,7 @@ pub struct Task {
     pub recurrence_end_at: Option<DateTime<Utc>>,
     pub recurrence_count: Option<i32>,
     pub recurrence_occurrences: Option<i32>,
+    pub recurrence_rrule: Option<String>,
     pub parent_task_id: Option<Uuid>,
     pub next_occurrence_at: Option<DateTime<Utc>>,
     pub last_occurrence_at: Option<DateTime<Utc>>,
@@ -207,6 +298,7 @@ pub struct CreateTask {
     pub recurrence_month_of_year: Option<i32>,
     pub recurrence_end_at: Option<DateTime<Utc>>,
     pub recurrence_count: Option<i32>,
+    pub recurrence_rrule: Option<String>,
 }
 
 /// Update task request
@@ -230,6 +322,7 @@ pub struct UpdateTask {
     pub recurrence_month_of_year: Option<i32>,
     pub recurrence_end_at: Option<DateTime<Utc>>,
     pub recurrence_count: Option<i32>,
+    pub recurrence_rrule: Option<String>,
 }
 
 /// Create task comment request
@@ -290,6 +383,65 @@ pub struct TaskAssigneeCount {
     pub count: i64,
 }
 
+/// Group-by dimension for the task analytics endpoint
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskAnalyticsGroupBy {
+    #[default]
+    Status,
+    Type,
+    Priority,
+    Assignee,
+    RelatedEntityType,
+    Day,
+    Week,
+    Month,
+}
+
+/// Metric computed per bucket by the task analytics endpoint
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskAnalyticsMetric {
+    #[default]
+    Count,
+    Completed,
+    Overdue,
+    AvgCompletionHours,
+}
+
+/// Query for the time-bucketed, filterable task analytics endpoint. Mirrors
+/// `ListTasksQuery`'s filters plus a `from`/`to` window and the dimension
+/// along which to bucket and the metric to compute per bucket.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TaskAnalyticsQuery {
+    pub status: Option<String>,
+    pub task_type: Option<String>,
+    pub priority: Option<String>,
+    pub assignee_id: Option<Uuid>,
+    pub related_entity_type: Option<String>,
+    pub related_entity_id: Option<Uuid>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub group_by: TaskAnalyticsGroupBy,
+    pub metric: TaskAnalyticsMetric,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TaskAnalyticsBucket {
+    pub bucket: String,
+    pub value: f64,
+}
+
+/// Time-bucketed, filterable task analytics: burndown/overdue-accumulation
+/// series for a chosen window and dimension, as opposed to the fixed
+/// snapshot `TaskStats` returns.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskAnalytics {
+    pub group_by: TaskAnalyticsGroupBy,
+    pub metric: TaskAnalyticsMetric,
+    pub buckets: Vec<TaskAnalyticsBucket>,
+}
+
 /// Task recurrence history entry
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct TaskRecurrenceHistory {
@@ -325,34 +477,50 @@ impl Task {
         }
         // Validate recurrence settings
         if input.is_recurring == Some(true) {
-            if input.recurrence_pattern.is_none() {
-                return Err("Recurrence pattern is required for recurring tasks".to_string());
-            }
-            if let Some(ref pattern) = input.recurrence_pattern {
-                if !["daily", "weekly", "biweekly", "monthly", "quarterly", "yearly"]
-                    .contains(&pattern.as_str())
-                {
-                    return Err("Invalid recurrence pattern".to_string());
+            let legacy_fields_used = input.recurrence_pattern.is_some()
+                || input.recurrence_interval.is_some()
+                || input.recurrence_day_of_week.is_some()
+                || input.recurrence_day_of_month.is_some()
+                || input.recurrence_month_of_year.is_some();
+
+            if let Some(ref rrule) = input.recurrence_rrule {
+                if legacy_fields_used {
+                    return Err(
+                        "recurrence_rrule cannot be combined with recurrence_pattern or the legacy recurrence_day_of_*/month_of_year fields"
+                            .to_string(),
+                    );
                 }
-            }
-            if let Some(interval) = input.recurrence_interval {
-                if interval < 1 {
-                    return Err("Recurrence interval must be at least 1".to_string());
+                RRule::parse(rrule).map_err(|e| format!("Invalid recurrence_rrule: {e}"))?;
+            } else {
+                if input.recurrence_pattern.is_none() {
+                    return Err("Recurrence pattern is required for recurring tasks".to_string());
                 }
-            }
-            if let Some(dow) = input.recurrence_day_of_week {
-                if !(0..=6).contains(&dow) {
-                    return Err("Day of week must be 0-6 (Sunday-Saturday)".to_string());
+                if let Some(ref pattern) = input.recurrence_pattern {
+                    if !["daily", "weekly", "biweekly", "monthly", "quarterly", "yearly"]
+                        .contains(&pattern.as_str())
+                    {
+                        return Err("Invalid recurrence pattern".to_string());
+                    }
                 }
-            }
-            if let Some(dom) = input.recurrence_day_of_month {
-                if !(1..=31).contains(&dom) {
-                    return Err("Day of month must be 1-31".to_string());
+                if let Some(interval) = input.recurrence_interval {
+                    if interval < 1 {
+                        return Err("Recurrence interval must be at least 1".to_string());
+                    }
                 }
-            }
-            if let Some(moy) = input.recurrence_month_of_year {
-                if !(1..=12).contains(&moy) {
-                    return Err("Month of year must be 1-12".to_string());
+                if let Some(dow) = input.recurrence_day_of_week {
+                    if !(0..=6).contains(&dow) {
+                        return Err("Day of week must be 0-6 (Sunday-Saturday)".to_string());
+                    }
+                }
+                if let Some(dom) = input.recurrence_day_of_month {
+                    if !(1..=31).contains(&dom) {
+                        return Err("Day of month must be 1-31".to_string());
+                    }
+                }
+                if let Some(moy) = input.recurrence_month_of_year {
+                    if !(1..=12).contains(&moy) {
+                        return Err("Month of year must be 1-12".to_string());
+                    }
                 }
             }
         }
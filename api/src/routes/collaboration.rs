@@ -1,6 +1,7 @@
 use axum::{
+    body::Bytes,
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     Extension, Json,
 };
 use serde::{Deserialize, Serialize};
@@ -14,7 +15,10 @@ use crate::models::collaboration::{
     UpdatePresence, UserSearchResult, NOTIFICATION_TYPES,
 };
 use crate::services::AppServices;
-use crate::utils::{AppError, AppResult};
+use crate::utils::{hmac_sha256, AppError, AppResult};
+
+/// Maximum age of a Slack-signed request before it is rejected as a replay.
+const SLACK_MAX_SKEW_SECS: i64 = 5 * 60;
 
 fn get_org_id(user: &AuthUser) -> AppResult<Uuid> {
     user.organization_id
@@ -85,9 +89,6 @@ pub async fn create_comment(
         .create_comment(org_id, user_id, &path.entity_type, path.entity_id, input)
         .await?;
 
-    // TODO: Send mention notifications via NotificationService
-    // This would require getting user info and entity title
-
     Ok((StatusCode::CREATED, Json(comment)))
 }
 
@@ -463,6 +464,22 @@ pub async fn disconnect_slack_workspace(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Rebuild the Slack identity mapping for a workspace (admin action).
+pub async fn resync_slack_users(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Path(workspace_id): Path<Uuid>,
+) -> AppResult<Json<serde_json::Value>> {
+    let org_id = get_org_id(&user)?;
+
+    let mapped = services
+        .collaboration
+        .resync_slack_users(org_id, workspace_id)
+        .await?;
+
+    Ok(Json(serde_json::json!({ "mapped_users": mapped })))
+}
+
 // =====================================================
 // MICROSOFT TEAMS INTEGRATION
 // =====================================================
@@ -724,3 +741,283 @@ pub async fn process_digests(
         "daily_digests_created": daily_count
     })))
 }
+
+// =====================================================
+// INBOUND SLACK (events + slash commands)
+// =====================================================
+
+/// POST /api/v1/collaboration/slack/events
+///
+/// Slack Events API webhook. Verifies the request signature over the raw body,
+/// answers the `url_verification` handshake, and routes `app_mention` payloads
+/// into entity comments.
+pub async fn slack_events(
+    State(services): State<Arc<AppServices>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> AppResult<Json<serde_json::Value>> {
+    verify_slack_signature(&headers, &body)?;
+
+    let payload: serde_json::Value = serde_json::from_slice(&body)
+        .map_err(|e| AppError::BadRequest(format!("Invalid Slack payload: {}", e)))?;
+
+    match payload.get("type").and_then(|v| v.as_str()) {
+        // Initial endpoint verification handshake.
+        Some("url_verification") => {
+            let challenge = payload
+                .get("challenge")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| AppError::BadRequest("Missing challenge".to_string()))?;
+            Ok(Json(serde_json::json!({ "challenge": challenge })))
+        }
+        Some("event_callback") => {
+            let team_id = payload
+                .get("team_id")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| AppError::BadRequest("Missing team_id".to_string()))?;
+            let event = payload
+                .get("event")
+                .ok_or_else(|| AppError::BadRequest("Missing event".to_string()))?;
+
+            if event.get("type").and_then(|v| v.as_str()) == Some("app_mention") {
+                let slack_user = event.get("user").and_then(|v| v.as_str()).unwrap_or("");
+                let text = event.get("text").and_then(|v| v.as_str()).unwrap_or("");
+                create_comment_from_slack(&services, team_id, slack_user, text).await?;
+            }
+            Ok(Json(serde_json::json!({ "ok": true })))
+        }
+        _ => Ok(Json(serde_json::json!({ "ok": true }))),
+    }
+}
+
+/// POST /api/v1/collaboration/slack/commands
+///
+/// Slash-command webhook. Slack sends an `application/x-www-form-urlencoded`
+/// body; the signature still covers the raw bytes.
+pub async fn slack_command(
+    State(services): State<Arc<AppServices>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> AppResult<Json<serde_json::Value>> {
+    verify_slack_signature(&headers, &body)?;
+
+    let form = parse_form_urlencoded(&body);
+
+    let team_id = form.get("team_id").map(String::as_str).unwrap_or("");
+    let slack_user = form.get("user_id").map(String::as_str).unwrap_or("");
+    let text = form.get("text").map(String::as_str).unwrap_or("");
+
+    create_comment_from_slack(&services, team_id, slack_user, text).await?;
+
+    Ok(Json(serde_json::json!({
+        "response_type": "ephemeral",
+        "text": "Comment posted to OpenGRC."
+    })))
+}
+
+/// Resolve the Slack `team_id` to an org and turn an `app_mention`/command body
+/// into an entity comment. The body is expected to reference an entity as
+/// `<entity_type> <entity_id> <comment text>` after the bot mention/command.
+async fn create_comment_from_slack(
+    services: &Arc<AppServices>,
+    team_id: &str,
+    slack_user_id: &str,
+    text: &str,
+) -> AppResult<()> {
+    let (org_id, bot_token) = resolve_slack_workspace(&services.db, team_id).await?;
+    let user_id = resolve_slack_author(&services.db, org_id, &bot_token, slack_user_id).await?;
+
+    // Strip a leading bot mention (`<@U...>`) before parsing the entity ref.
+    let rest = text
+        .trim_start()
+        .strip_prefix("<@")
+        .and_then(|r| r.split_once('>'))
+        .map(|(_, r)| r.trim())
+        .unwrap_or(text)
+        .trim();
+    let mut parts = rest.splitn(3, char::is_whitespace);
+    let entity_type = parts.next().unwrap_or("").trim();
+    let entity_id = parts
+        .next()
+        .and_then(|s| Uuid::parse_str(s.trim()).ok())
+        .ok_or_else(|| AppError::BadRequest("Expected '<entity_type> <entity_id> <text>'".to_string()))?;
+    let content = parts.next().unwrap_or("").trim().to_string();
+
+    services
+        .collaboration
+        .create_comment(
+            org_id,
+            user_id,
+            entity_type,
+            entity_id,
+            CreateEntityComment { content, parent_comment_id: None },
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Look up the active workspace for a Slack `team_id`, returning its org and
+/// bot access token.
+async fn resolve_slack_workspace(db: &sqlx::PgPool, team_id: &str) -> AppResult<(Uuid, String)> {
+    sqlx::query_as::<_, (Uuid, String)>(
+        r#"
+        SELECT organization_id, access_token
+        FROM slack_workspaces
+        WHERE team_id = $1 AND status = 'active'
+        "#,
+    )
+    .bind(team_id)
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("No active workspace for team {}", team_id)))
+}
+
+/// Resolve a Slack user to an OpenGRC user by matching their Slack profile
+/// email against the org's users.
+async fn resolve_slack_author(
+    db: &sqlx::PgPool,
+    org_id: Uuid,
+    bot_token: &str,
+    slack_user_id: &str,
+) -> AppResult<Uuid> {
+    let client = reqwest::Client::new();
+    let info: serde_json::Value = client
+        .get("https://slack.com/api/users.info")
+        .bearer_auth(bot_token)
+        .query(&[("user", slack_user_id)])
+        .send()
+        .await
+        .map_err(|e| AppError::ExternalServiceError(format!("Slack users.info: {}", e)))?
+        .json()
+        .await
+        .map_err(|e| AppError::ExternalServiceError(format!("Slack users.info parse: {}", e)))?;
+
+    let email = info
+        .get("user")
+        .and_then(|u| u.get("profile"))
+        .and_then(|p| p.get("email"))
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| AppError::BadRequest("Slack user has no visible email".to_string()))?;
+
+    let user_id = sqlx::query_scalar::<_, Uuid>(
+        "SELECT id FROM users WHERE organization_id = $1 AND LOWER(email) = LOWER($2)",
+    )
+    .bind(org_id)
+    .bind(email)
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("No OpenGRC user for Slack email {}", email)))?;
+
+    // Backfill the user's timezone from their Slack profile so scheduled digests
+    // can land in their local morning.
+    if let Some(tz_offset) = info.get("user").and_then(|u| u.get("tz_offset")).and_then(|v| v.as_i64()) {
+        let tz_label = info
+            .get("user")
+            .and_then(|u| u.get("tz_label").or_else(|| u.get("tz")))
+            .and_then(|v| v.as_str());
+        let _ = sqlx::query(
+            r#"
+            INSERT INTO notification_preferences (organization_id, user_id, tz_offset_seconds, tz_label)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (organization_id, user_id) DO UPDATE SET
+                tz_offset_seconds = EXCLUDED.tz_offset_seconds,
+                tz_label = EXCLUDED.tz_label,
+                updated_at = NOW()
+            "#,
+        )
+        .bind(org_id)
+        .bind(user_id)
+        .bind(tz_offset as i32)
+        .bind(tz_label)
+        .execute(db)
+        .await;
+    }
+
+    Ok(user_id)
+}
+
+/// Verify a Slack request signature over the raw body per Slack's
+/// `v0:{timestamp}:{body}` HMAC-SHA256 scheme, rejecting stale timestamps.
+fn verify_slack_signature(headers: &HeaderMap, body: &[u8]) -> AppResult<()> {
+    let signing_secret = std::env::var("SLACK_SIGNING_SECRET").map_err(|_| {
+        AppError::InternalServerError("Slack signing secret not configured".to_string())
+    })?;
+
+    let signature = header_str(headers, "X-Slack-Signature")?;
+    let timestamp = header_str(headers, "X-Slack-Request-Timestamp")?;
+
+    let ts: i64 = timestamp
+        .parse()
+        .map_err(|_| AppError::BadRequest("Invalid Slack timestamp".to_string()))?;
+    let now = chrono::Utc::now().timestamp();
+    if (now - ts).abs() > SLACK_MAX_SKEW_SECS {
+        return Err(AppError::Unauthorized("Stale Slack request".to_string()));
+    }
+
+    let basestring = format!("v0:{}:{}", timestamp, String::from_utf8_lossy(body));
+    let expected = format!(
+        "v0={}",
+        hex_encode(&hmac_sha256(signing_secret.as_bytes(), basestring.as_bytes()))
+    );
+
+    if constant_time_eq(expected.as_bytes(), signature.as_bytes()) {
+        Ok(())
+    } else {
+        Err(AppError::Unauthorized("Invalid Slack signature".to_string()))
+    }
+}
+
+/// Minimal `application/x-www-form-urlencoded` parser for Slack command bodies,
+/// avoiding a dedicated decode dependency.
+fn parse_form_urlencoded(body: &[u8]) -> std::collections::HashMap<String, String> {
+    let raw = String::from_utf8_lossy(body);
+    raw.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(k, v)| (url_decode(k), url_decode(v)))
+        .collect()
+}
+
+fn url_decode(input: &str) -> String {
+    let bytes = input.replace('+', " ").into_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 3 <= bytes.len() {
+            if let Ok(byte) = u8::from_str_radix(&String::from_utf8_lossy(&bytes[i + 1..i + 3]), 16) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> AppResult<&'a str> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| AppError::BadRequest(format!("Missing {} header", name)))
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        out.push_str(&format!("{:02x}", b));
+    }
+    out
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
@@ -8,8 +8,8 @@ use uuid::Uuid;
 
 use crate::middleware::AuthUser;
 use crate::models::{
-    Control, ControlStats, ControlTest, ControlTestResult, ControlWithMappings,
-    CreateControl, CreateControlTest, CreateTestResult, ListControlsQuery,
+    AssertionRemediationTrend, Control, ControlStats, ControlTest, ControlTestResult,
+    ControlWithMappings, CreateControl, CreateControlTest, CreateTestResult, ListControlsQuery,
     UpdateControl,
 };
 use crate::services::AppServices;
@@ -194,6 +194,16 @@ pub async fn record_test_result(
     Ok(Json(result))
 }
 
+/// GET /api/v1/controls/assertion-trends
+pub async fn get_assertion_remediation_trends(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+) -> AppResult<Json<Vec<AssertionRemediationTrend>>> {
+    let org_id = get_org_id(&user)?;
+    let trends = services.control.get_assertion_remediation_trends(org_id).await?;
+    Ok(Json(trends))
+}
+
 // ==================== Helpers ====================
 
 fn get_org_id(user: &AuthUser) -> AppResult<Uuid> {
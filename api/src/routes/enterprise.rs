@@ -16,6 +16,7 @@ use crate::models::{
     GenerateScimTokenResponse,
     AuditExportConfigurationResponse, CreateAuditExportConfiguration,
     ActivityLogWithUser, ListActivityLogsQuery,
+    ChainVerificationResult, ActivityLogChainAnchor,
     BrandingConfiguration, UpdateBrandingConfiguration, SetCustomDomainRequest,
     DomainVerificationInstructions,
     ApiKeyResponse, CreateApiKey, CreateApiKeyResponse, RevokeApiKeyRequest,
@@ -311,6 +312,24 @@ pub async fn list_activity_logs(
     Ok(Json(PaginatedActivityLogs { data: logs, total, page, page_size }))
 }
 
+pub async fn verify_activity_log_chain(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+) -> AppResult<Json<ChainVerificationResult>> {
+    let org_id = get_org_id(&user)?;
+    let result = services.enterprise.verify_chain(org_id).await?;
+    Ok(Json(result))
+}
+
+pub async fn anchor_activity_log_chain(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+) -> AppResult<Json<ActivityLogChainAnchor>> {
+    let org_id = get_org_id(&user)?;
+    let anchor = services.enterprise.anchor_chain_tip(org_id).await?;
+    Ok(Json(anchor))
+}
+
 // ============================================================================
 // AUDIT EXPORT CONFIGURATIONS
 // ============================================================================
@@ -344,6 +363,25 @@ pub async fn delete_audit_export_configuration(
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[derive(serde::Deserialize)]
+pub struct SetStreamingEnabledRequest {
+    pub enabled: bool,
+}
+
+pub async fn set_audit_export_streaming_enabled(
+    State(services): State<Arc<AppServices>>,
+    Path(config_id): Path<Uuid>,
+    Extension(user): Extension<AuthUser>,
+    Json(input): Json<SetStreamingEnabledRequest>,
+) -> AppResult<Json<AuditExportConfigurationResponse>> {
+    let org_id = get_org_id(&user)?;
+    let config = services
+        .enterprise
+        .set_streaming_enabled(org_id, config_id, input.enabled)
+        .await?;
+    Ok(Json(config))
+}
+
 // ============================================================================
 // BRANDING
 // ============================================================================
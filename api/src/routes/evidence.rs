@@ -8,8 +8,9 @@ use uuid::Uuid;
 
 use crate::middleware::AuthUser;
 use crate::models::{
-    CreateEvidence, Evidence, EvidenceStats, EvidenceWithLinks, ListEvidenceQuery,
-    UpdateEvidence,
+    BatchOpResult, CreateEvidence, Evidence, EvidenceBatchRequest, EvidenceSearchQuery,
+    EvidenceSearchResults, EvidenceStats, EvidenceVersion, EvidenceWithLinks, ListEvidenceQuery,
+    PagedEvidence, UpdateEvidence,
 };
 use crate::services::evidence::{PresignedDownloadResponse, PresignedUploadResponse};
 use crate::services::AppServices;
@@ -26,6 +27,7 @@ pub struct ListEvidenceParams {
     pub expired: Option<bool>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
+    pub after: Option<String>,
 }
 
 impl From<ListEvidenceParams> for ListEvidenceQuery {
@@ -38,6 +40,7 @@ impl From<ListEvidenceParams> for ListEvidenceQuery {
             expired: params.expired,
             limit: params.limit,
             offset: params.offset,
+            after: params.after,
         }
     }
 }
@@ -73,6 +76,31 @@ pub async fn list_evidence(
     Ok(Json(evidence))
 }
 
+/// GET /api/v1/evidence/page
+///
+/// Keyset-paginated evidence list. Pass the `next_cursor` from the previous
+/// response as `after` to stream the whole set in O(page) time.
+pub async fn list_evidence_page(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Query(params): Query<ListEvidenceParams>,
+) -> AppResult<Json<PagedEvidence>> {
+    let org_id = get_org_id(&user)?;
+    let page = services.evidence.list_evidence_page(org_id, params.into()).await?;
+    Ok(Json(page))
+}
+
+/// GET /api/v1/evidence/search
+pub async fn search_evidence(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Query(query): Query<EvidenceSearchQuery>,
+) -> AppResult<Json<EvidenceSearchResults>> {
+    let org_id = get_org_id(&user)?;
+    let results = services.evidence.search_evidence(org_id, query).await?;
+    Ok(Json(results))
+}
+
 /// GET /api/v1/evidence/stats
 pub async fn get_evidence_stats(
     State(services): State<Arc<AppServices>>,
@@ -94,6 +122,21 @@ pub async fn get_evidence(
     Ok(Json(evidence))
 }
 
+/// POST /api/v1/evidence/batch
+pub async fn batch_apply(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Json(input): Json<EvidenceBatchRequest>,
+) -> AppResult<Json<Vec<BatchOpResult>>> {
+    let org_id = get_org_id(&user)?;
+    let user_id = Uuid::parse_str(&user.id).ok();
+    let results = services
+        .evidence
+        .batch_apply(org_id, user_id, input.ops, input.atomic)
+        .await?;
+    Ok(Json(results))
+}
+
 /// POST /api/v1/evidence
 pub async fn create_evidence(
     State(services): State<Arc<AppServices>>,
@@ -129,6 +172,45 @@ pub async fn delete_evidence(
     Ok(Json(serde_json::json!({ "success": true })))
 }
 
+// ==================== Version History ====================
+
+/// GET /api/v1/evidence/:id/versions
+pub async fn list_evidence_versions(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<Vec<EvidenceVersion>>> {
+    let org_id = get_org_id(&user)?;
+    let versions = services.evidence.list_evidence_versions(org_id, id).await?;
+    Ok(Json(versions))
+}
+
+/// GET /api/v1/evidence/:id/versions/:version
+pub async fn get_evidence_version(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Path((id, version)): Path<(Uuid, i32)>,
+) -> AppResult<Json<EvidenceVersion>> {
+    let org_id = get_org_id(&user)?;
+    let snapshot = services.evidence.get_evidence_version(org_id, id, version).await?;
+    Ok(Json(snapshot))
+}
+
+/// POST /api/v1/evidence/:id/versions/:version/restore
+pub async fn restore_evidence_version(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Path((id, version)): Path<(Uuid, i32)>,
+) -> AppResult<Json<Evidence>> {
+    let org_id = get_org_id(&user)?;
+    let user_id = Uuid::parse_str(&user.id).ok();
+    let evidence = services
+        .evidence
+        .restore_version(org_id, id, version, user_id)
+        .await?;
+    Ok(Json(evidence))
+}
+
 // ==================== Control Links ====================
 
 /// POST /api/v1/evidence/:id/controls
@@ -0,0 +1,87 @@
+use axum::{
+    extract::{Path, Query, State},
+    Extension,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::middleware::AuthUser;
+use crate::services::AppServices;
+use crate::utils::{AppError, AppResult};
+
+const PARQUET_CONTENT_TYPE: &str = "application/vnd.apache.parquet";
+
+fn get_org_id(user: &AuthUser) -> AppResult<Uuid> {
+    user.organization_id
+        .as_ref()
+        .and_then(|id| Uuid::parse_str(id).ok())
+        .ok_or_else(|| AppError::BadRequest("User not associated with an organization".to_string()))
+}
+
+/// Which gap-analysis table to materialize in a single Parquet download.
+#[derive(Debug, Deserialize, Default)]
+pub struct GapExportQuery {
+    /// `requirements` (default) or `categories` for the `by_category` rollups.
+    #[serde(default)]
+    pub table: GapTable,
+}
+
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum GapTable {
+    #[default]
+    Requirements,
+    Categories,
+}
+
+fn parquet_response(filename: String, bytes: Vec<u8>) -> axum::response::Response {
+    axum::response::Response::builder()
+        .status(axum::http::StatusCode::OK)
+        .header(axum::http::header::CONTENT_TYPE, PARQUET_CONTENT_TYPE)
+        .header(
+            axum::http::header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", filename),
+        )
+        .body(axum::body::Body::from(bytes))
+        .unwrap()
+}
+
+/// GET /api/v1/export/evidence.parquet
+pub async fn export_evidence_parquet(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+) -> AppResult<axum::response::Response> {
+    let org_id = get_org_id(&user)?;
+    let bytes = services
+        .export
+        .export_evidence(org_id)
+        .write_parquet(Vec::new())
+        .await?;
+    Ok(parquet_response(format!("evidence-{}.parquet", org_id), bytes))
+}
+
+/// GET /api/v1/export/frameworks/:id/gap-analysis.parquet
+pub async fn export_gap_analysis_parquet(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Path(framework_id): Path<Uuid>,
+    Query(query): Query<GapExportQuery>,
+) -> AppResult<axum::response::Response> {
+    let org_id = get_org_id(&user)?;
+    let analysis = services
+        .framework
+        .get_gap_analysis(org_id, framework_id, true)
+        .await?;
+    let (requirements, categories) = services.export.export_gap_analysis(&analysis);
+
+    let (stream, suffix) = match query.table {
+        GapTable::Requirements => (requirements, "requirements"),
+        GapTable::Categories => (categories, "categories"),
+    };
+    let bytes = stream.write_parquet(Vec::new()).await?;
+    Ok(parquet_response(
+        format!("gap-analysis-{}-{}.parquet", framework_id, suffix),
+        bytes,
+    ))
+}
@@ -10,12 +10,21 @@ use crate::middleware::AuthUser;
 use crate::models::{
     CreateFramework, CreateFrameworkRequirement, Framework, FrameworkRequirement,
     FrameworkWithRequirements, UpdateFramework, UpdateFrameworkRequirement,
-    FrameworkGapAnalysis,
+    FrameworkGapAnalysis, MapRequirements, RequirementMapping,
+    FrameworkRevision, RevisionDiff, ChangeBatch,
+    RequirementCrosswalk, CreateCrosswalk, CrosswalkSuggestion,
 };
 use crate::models::framework::build_requirement_tree;
 use crate::services::AppServices;
 use crate::utils::{AppError, AppResult};
 
+fn get_org_id(user: &AuthUser) -> AppResult<Uuid> {
+    user.organization_id
+        .as_ref()
+        .and_then(|id| Uuid::parse_str(id).ok())
+        .ok_or_else(|| AppError::BadRequest("User not associated with an organization".to_string()))
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ListFrameworksQuery {
     pub category: Option<String>,
@@ -27,6 +36,12 @@ pub struct ListRequirementsQuery {
     pub tree: Option<bool>,
 }
 
+/// Optional editgroup to group a mutation into a larger reviewable batch.
+#[derive(Debug, Deserialize, Default)]
+pub struct EditgroupQuery {
+    pub editgroup: Option<Uuid>,
+}
+
 // ==================== Framework Routes ====================
 
 /// GET /api/v1/frameworks
@@ -64,9 +79,10 @@ pub async fn create_framework(
 pub async fn update_framework(
     State(services): State<Arc<AppServices>>,
     Path(id): Path<Uuid>,
+    Query(eg): Query<EditgroupQuery>,
     Json(input): Json<UpdateFramework>,
 ) -> AppResult<Json<Framework>> {
-    let framework = services.framework.update_framework(id, input).await?;
+    let framework = services.framework.update_framework(id, input, eg.editgroup).await?;
     Ok(Json(framework))
 }
 
@@ -74,8 +90,9 @@ pub async fn update_framework(
 pub async fn delete_framework(
     State(services): State<Arc<AppServices>>,
     Path(id): Path<Uuid>,
+    Query(eg): Query<EditgroupQuery>,
 ) -> AppResult<Json<serde_json::Value>> {
-    services.framework.delete_framework(id).await?;
+    services.framework.delete_framework(id, eg.editgroup).await?;
     Ok(Json(serde_json::json!({ "success": true })))
 }
 
@@ -329,9 +346,10 @@ fn parse_csv_line(line: &str) -> Vec<String> {
 pub async fn update_requirement(
     State(services): State<Arc<AppServices>>,
     Path((_framework_id, id)): Path<(Uuid, Uuid)>,
+    Query(eg): Query<EditgroupQuery>,
     Json(input): Json<UpdateFrameworkRequirement>,
 ) -> AppResult<Json<FrameworkRequirement>> {
-    let requirement = services.framework.update_requirement(id, input).await?;
+    let requirement = services.framework.update_requirement(id, input, eg.editgroup).await?;
     Ok(Json(requirement))
 }
 
@@ -339,18 +357,75 @@ pub async fn update_requirement(
 pub async fn delete_requirement(
     State(services): State<Arc<AppServices>>,
     Path((_framework_id, id)): Path<(Uuid, Uuid)>,
+    Query(eg): Query<EditgroupQuery>,
 ) -> AppResult<Json<serde_json::Value>> {
-    services.framework.delete_requirement(id).await?;
+    services.framework.delete_requirement(id, eg.editgroup).await?;
     Ok(Json(serde_json::json!({ "success": true })))
 }
 
+// ==================== Revision history ====================
+
+/// GET /api/v1/frameworks/:id/history
+pub async fn get_framework_history(
+    State(services): State<Arc<AppServices>>,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<Vec<FrameworkRevision>>> {
+    let history = services.framework.get_framework_history(id).await?;
+    Ok(Json(history))
+}
+
+/// Query identifying the two revisions to diff.
+#[derive(Debug, Deserialize)]
+pub struct DiffRevisionsQuery {
+    pub from: Uuid,
+    pub to: Uuid,
+}
+
+/// GET /api/v1/frameworks/revisions/diff
+pub async fn diff_revisions(
+    State(services): State<Arc<AppServices>>,
+    Query(query): Query<DiffRevisionsQuery>,
+) -> AppResult<Json<RevisionDiff>> {
+    let diff = services.framework.diff_revisions(query.from, query.to).await?;
+    Ok(Json(diff))
+}
+
+/// Cursor and page size for the change feed.
+#[derive(Debug, Deserialize)]
+pub struct ChangesQuery {
+    #[serde(default)]
+    pub since: i64,
+    pub limit: Option<usize>,
+}
+
+/// GET /api/v1/frameworks/changes
+pub async fn list_changes(
+    State(services): State<Arc<AppServices>>,
+    Query(query): Query<ChangesQuery>,
+) -> AppResult<Json<ChangeBatch>> {
+    let batch = services
+        .framework
+        .list_changes(query.since, query.limit.unwrap_or(100))
+        .await?;
+    Ok(Json(batch))
+}
+
 // ==================== Gap Analysis ====================
 
+/// Gap-analysis query options.
+#[derive(Debug, Deserialize, Default)]
+pub struct GapAnalysisQuery {
+    /// Count controls inherited via exact/superset cross-framework mappings.
+    #[serde(default)]
+    pub include_inherited: bool,
+}
+
 /// GET /api/v1/frameworks/:framework_id/gap-analysis
 pub async fn get_gap_analysis(
     State(services): State<Arc<AppServices>>,
     Extension(user): Extension<AuthUser>,
     Path(framework_id): Path<Uuid>,
+    Query(query): Query<GapAnalysisQuery>,
 ) -> AppResult<Json<FrameworkGapAnalysis>> {
     let org_id = user
         .organization_id
@@ -358,6 +433,92 @@ pub async fn get_gap_analysis(
         .and_then(|id| Uuid::parse_str(id).ok())
         .ok_or_else(|| AppError::BadRequest("User not associated with an organization".to_string()))?;
 
-    let analysis = services.framework.get_gap_analysis(org_id, framework_id).await?;
+    let analysis = services
+        .framework
+        .get_gap_analysis(org_id, framework_id, query.include_inherited)
+        .await?;
+    Ok(Json(analysis))
+}
+
+/// POST /api/v1/requirements/mappings
+pub async fn map_requirements(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Json(input): Json<MapRequirements>,
+) -> AppResult<Json<RequirementMapping>> {
+    // Org scoping is enforced at the control-count layer; mappings themselves
+    // are framework metadata shared across the tenant.
+    let _ = get_org_id(&user)?;
+    let mapping = services.framework.map_requirements(input).await?;
+    Ok(Json(mapping))
+}
+
+/// GET /api/v1/requirements/:id/mappings
+pub async fn list_requirement_mappings(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Path(requirement_id): Path<Uuid>,
+) -> AppResult<Json<Vec<RequirementMapping>>> {
+    let _ = get_org_id(&user)?;
+    let mappings = services.framework.list_requirement_mappings(requirement_id).await?;
+    Ok(Json(mappings))
+}
+
+// ==================== Cross-framework crosswalk ====================
+
+/// POST /api/v1/requirements/crosswalks
+pub async fn create_crosswalk(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Json(input): Json<CreateCrosswalk>,
+) -> AppResult<Json<RequirementCrosswalk>> {
+    let _ = get_org_id(&user)?;
+    let crosswalk = services.framework.create_crosswalk(input).await?;
+    Ok(Json(crosswalk))
+}
+
+/// GET /api/v1/requirements/:id/crosswalks
+pub async fn list_crosswalks(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Path(requirement_id): Path<Uuid>,
+) -> AppResult<Json<Vec<RequirementCrosswalk>>> {
+    let _ = get_org_id(&user)?;
+    let crosswalks = services.framework.list_crosswalks(requirement_id).await?;
+    Ok(Json(crosswalks))
+}
+
+/// The two frameworks to compare for crosswalk suggestions.
+#[derive(Debug, Deserialize)]
+pub struct SuggestCrosswalksQuery {
+    pub framework_a: Uuid,
+    pub framework_b: Uuid,
+}
+
+/// GET /api/v1/requirements/crosswalks/suggest
+pub async fn suggest_crosswalks(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Query(query): Query<SuggestCrosswalksQuery>,
+) -> AppResult<Json<Vec<CrosswalkSuggestion>>> {
+    let _ = get_org_id(&user)?;
+    let suggestions = services
+        .framework
+        .suggest_crosswalks(query.framework_a, query.framework_b)
+        .await?;
+    Ok(Json(suggestions))
+}
+
+/// GET /api/v1/frameworks/:framework_id/gap-analysis/crosswalk
+pub async fn get_gap_analysis_with_crosswalk(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Path(framework_id): Path<Uuid>,
+) -> AppResult<Json<FrameworkGapAnalysis>> {
+    let org_id = get_org_id(&user)?;
+    let analysis = services
+        .framework
+        .get_gap_analysis_with_crosswalk(org_id, framework_id)
+        .await?;
     Ok(Json(analysis))
 }
@@ -237,6 +237,10 @@ pub async fn collect_evidence(
         sync_log_id: Uuid::new_v4(), // Temporary ID for evidence collection
         full_sync: true,
         sync_type: Some("evidence_collection".to_string()),
+        last_sync_token: None,
+        page_cursor: None,
+        full_resync: true,
+        prior_alert_state: None,
     };
 
     // Run sync with the appropriate provider
@@ -248,6 +252,17 @@ pub async fn collect_evidence(
     // Store security alerts before consuming evidence_collected
     let security_alerts = sync_result.security_alerts.clone();
 
+    // Continuously evaluate any control-test assertions bound to this
+    // integration type against the freshest collected evidence, closing the
+    // loop between the evidence pipeline and control attestation.
+    if let Err(e) = services
+        .control
+        .evaluate_assertions(org_id, &integration_type, &sync_result.evidence_collected)
+        .await
+    {
+        tracing::warn!("Failed to evaluate control assertions for integration {}: {}", id, e);
+    }
+
     // Persist collected evidence
     let evidence_count = services
         .evidence
@@ -316,6 +331,108 @@ pub async fn collect_evidence(
     })))
 }
 
+/// Push unresolved findings (failed control tests, overdue questionnaire
+/// assignments, open risks) into Jira as issues and pull the status of
+/// previously-linked issues back.
+#[utoipa::path(
+    post,
+    path = "/api/v1/integrations/{id}/issue-tracking/sync",
+    params(("id" = Uuid, Path, description = "Integration id")),
+    responses((status = 200, description = "Sync report", body = Value)),
+    security(("bearer_auth" = [])),
+    tag = "integrations"
+)]
+pub async fn sync_jira_issue_tracking(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+) -> AppResult<Json<Value>> {
+    use crate::integrations::jira::{JiraClient, JiraConfig};
+
+    let org_id = get_org_id(&user)?;
+
+    let integration_with_stats = services.integration.get_integration(org_id, id).await?;
+    let integration = &integration_with_stats.integration;
+    if integration.integration_type != "jira" {
+        return Err(AppError::BadRequest(
+            "Issue tracking sync is only supported for Jira integrations".to_string(),
+        ));
+    }
+
+    let encrypted_config = integration
+        .config
+        .as_ref()
+        .ok_or_else(|| AppError::BadRequest("Integration has no configuration".to_string()))?;
+    let config_value = services.integration.decrypt_config(encrypted_config)?;
+    let jira_config = JiraConfig::from_value(&config_value).map_err(AppError::BadRequest)?;
+
+    if !jira_config.services.issue_tracking {
+        return Err(AppError::BadRequest(
+            "issue_tracking is not enabled for this integration".to_string(),
+        ));
+    }
+
+    let client = JiraClient::new(jira_config.clone())
+        .await
+        .map_err(AppError::BadRequest)?;
+
+    let report = services
+        .jira_issue_tracking
+        .sync(org_id, id, &client, &jira_config)
+        .await?;
+
+    Ok(Json(json!({ "data": report })))
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct TransitionIssueLinkInput {
+    pub to_status: String,
+}
+
+/// Move a linked Jira issue to the named status, e.g. "In Progress" once
+/// remediation starts on the OpenGRC side.
+#[utoipa::path(
+    post,
+    path = "/api/v1/integrations/{integration_id}/issue-tracking/{link_id}/transition",
+    params(
+        ("integration_id" = Uuid, Path, description = "Integration id"),
+        ("link_id" = Uuid, Path, description = "Issue link id"),
+    ),
+    request_body = TransitionIssueLinkInput,
+    responses((status = 200, description = "Transition result", body = Value)),
+    security(("bearer_auth" = [])),
+    tag = "integrations"
+)]
+pub async fn transition_issue_link(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Path((integration_id, link_id)): Path<(Uuid, Uuid)>,
+    Json(input): Json<TransitionIssueLinkInput>,
+) -> AppResult<Json<Value>> {
+    use crate::integrations::jira::{JiraClient, JiraConfig};
+
+    let org_id = get_org_id(&user)?;
+
+    let integration_with_stats = services.integration.get_integration(org_id, integration_id).await?;
+    let integration = &integration_with_stats.integration;
+    let encrypted_config = integration
+        .config
+        .as_ref()
+        .ok_or_else(|| AppError::BadRequest("Integration has no configuration".to_string()))?;
+    let config_value = services.integration.decrypt_config(encrypted_config)?;
+    let jira_config = JiraConfig::from_value(&config_value).map_err(AppError::BadRequest)?;
+    let client = JiraClient::new(jira_config)
+        .await
+        .map_err(AppError::BadRequest)?;
+
+    let moved = services
+        .jira_issue_tracking
+        .transition(org_id, link_id, &input.to_status, &client)
+        .await?;
+
+    Ok(Json(json!({ "data": { "transitioned": moved } })))
+}
+
 /// Get sync logs for an integration
 #[derive(Debug, Deserialize, Default)]
 pub struct SyncLogsQuery {
@@ -10,18 +10,23 @@ pub mod control_test_automation;
 pub mod enterprise;
 pub mod evidence;
 pub mod evidence_automation;
+pub mod export;
 pub mod frameworks;
 pub mod health;
 pub mod integrations;
 pub mod notifications;
+pub mod openapi;
 pub mod policies;
 pub mod policy_templates;
 pub mod questionnaires;
 pub mod reports;
+pub mod retention;
 pub mod risks;
 pub mod search;
 pub mod soc2;
 pub mod sso;
+pub mod sso_device;
+pub mod storage;
 pub mod tasks;
 pub mod vendors;
 
@@ -40,6 +45,8 @@ use tower_http::{
     cors::CorsLayer,
     trace::TraceLayer,
 };
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
 
 use crate::middleware::{auth_middleware, logging_middleware, AuthState};
 use crate::services::AppServices;
@@ -76,11 +83,17 @@ pub fn create_router(services: Arc<AppServices>, auth_state: Arc<AuthState>, cor
         .route("/api/v1/vendor-portal", get(questionnaires::get_portal_access))
         .route("/api/v1/vendor-portal/response", post(questionnaires::save_portal_response))
         .route("/api/v1/vendor-portal/submit", post(questionnaires::submit_portal_questionnaire))
+        // Local-storage download tokens carry their own HMAC-signed expiry, so
+        // this is authenticated by the signed query string, not a session.
+        .route("/api/v1/storage/download/*key", get(storage::download_file))
         .with_state(services.clone());
 
     // SSO routes - no auth middleware (used to establish authentication)
     let sso_routes = Router::new()
         .route("/api/sso/exchange", post(sso::exchange_code))
+        .route("/api/sso/refresh", post(sso::refresh_token))
+        .route("/api/sso/device/code", post(sso_device::request_device_code))
+        .route("/api/sso/device/token", post(sso_device::poll_device_token))
         .route("/api/sso/userinfo", post(sso::get_userinfo))
         .route("/api/sso/validate", get(sso::validate_sso))
         .route("/api/sso/logout", post(sso::logout_sso))
@@ -90,6 +103,7 @@ pub fn create_router(services: Arc<AppServices>, auth_state: Arc<AuthState>, cor
         .route("/api/v1/auth/me", get(auth::me))
         .route("/api/v1/controls", get(controls::list_controls))
         .route("/api/v1/controls/stats", get(controls::get_control_stats))
+        .route("/api/v1/controls/assertion-trends", get(controls::get_assertion_remediation_trends))
         .route("/api/v1/controls/:id", get(controls::get_control))
         .route("/api/v1/controls", post(controls::create_control))
         .route("/api/v1/controls/:id", put(controls::update_control))
@@ -100,13 +114,27 @@ pub fn create_router(services: Arc<AppServices>, auth_state: Arc<AuthState>, cor
         .route("/api/v1/controls/:id/tests", post(controls::create_control_test))
         .route("/api/v1/controls/:control_id/tests/:test_id/results", post(controls::record_test_result))
         .route("/api/v1/evidence", get(evidence::list_evidence))
+        .route("/api/v1/evidence/page", get(evidence::list_evidence_page))
+        .route("/api/v1/evidence/search", get(evidence::search_evidence))
         .route("/api/v1/evidence/stats", get(evidence::get_evidence_stats))
         .route("/api/v1/evidence/:id", get(evidence::get_evidence))
         .route("/api/v1/evidence", post(evidence::create_evidence))
+        .route("/api/v1/evidence/batch", post(evidence::batch_apply))
         .route("/api/v1/evidence/:id", put(evidence::update_evidence))
         .route("/api/v1/evidence/:id", delete(evidence::delete_evidence))
         .route("/api/v1/evidence/:id/controls", post(evidence::link_controls))
         .route("/api/v1/evidence/:id/controls", delete(evidence::unlink_controls))
+        .route("/api/v1/evidence/:id/versions", get(evidence::list_evidence_versions))
+        .route("/api/v1/evidence/:id/versions/:version", get(evidence::get_evidence_version))
+        .route(
+            "/api/v1/evidence/:id/versions/:version/restore",
+            post(evidence::restore_evidence_version),
+        )
+        .route("/api/v1/export/evidence.parquet", get(export::export_evidence_parquet))
+        .route(
+            "/api/v1/export/frameworks/:id/gap-analysis.parquet",
+            get(export::export_gap_analysis_parquet),
+        )
         .route("/api/v1/evidence/:id/upload-url", post(evidence::get_upload_url))
         .route("/api/v1/evidence/:id/confirm-upload", post(evidence::confirm_upload))
         .route("/api/v1/evidence/:id/download-url", get(evidence::get_download_url))
@@ -197,6 +225,8 @@ pub fn create_router(services: Arc<AppServices>, auth_state: Arc<AuthState>, cor
         .route("/api/v1/integrations/:id/test", post(integrations::test_connection))
         .route("/api/v1/integrations/:id/sync", post(integrations::trigger_sync))
         .route("/api/v1/integrations/:id/collect-evidence", post(integrations::collect_evidence))
+        .route("/api/v1/integrations/:id/issue-tracking/sync", post(integrations::sync_jira_issue_tracking))
+        .route("/api/v1/integrations/:id/issue-tracking/:link_id/transition", post(integrations::transition_issue_link))
         .route("/api/v1/integrations/:id/logs", get(integrations::get_sync_logs))
         // OAuth routes
         .route("/api/v1/integrations/oauth/:type/authorize", post(integrations::oauth_authorize))
@@ -220,7 +250,16 @@ pub fn create_router(services: Arc<AppServices>, auth_state: Arc<AuthState>, cor
         .route("/api/v1/frameworks/:framework_id/requirements/import", post(frameworks::import_requirements))
         .route("/api/v1/frameworks/:framework_id/requirements/:id", put(frameworks::update_requirement))
         .route("/api/v1/frameworks/:framework_id/requirements/:id", delete(frameworks::delete_requirement))
+        .route("/api/v1/frameworks/:id/history", get(frameworks::get_framework_history))
+        .route("/api/v1/frameworks/revisions/diff", get(frameworks::diff_revisions))
+        .route("/api/v1/frameworks/changes", get(frameworks::list_changes))
         .route("/api/v1/frameworks/:framework_id/gap-analysis", get(frameworks::get_gap_analysis))
+        .route("/api/v1/requirements/mappings", post(frameworks::map_requirements))
+        .route("/api/v1/requirements/:id/mappings", get(frameworks::list_requirement_mappings))
+        .route("/api/v1/requirements/crosswalks", post(frameworks::create_crosswalk))
+        .route("/api/v1/requirements/crosswalks/suggest", get(frameworks::suggest_crosswalks))
+        .route("/api/v1/requirements/:id/crosswalks", get(frameworks::list_crosswalks))
+        .route("/api/v1/frameworks/:framework_id/gap-analysis/crosswalk", get(frameworks::get_gap_analysis_with_crosswalk))
         .route("/api/v1/reports/types", get(reports::list_report_types))
         .route("/api/v1/reports/:report_type/csv", get(reports::generate_csv_report))
         .route("/api/v1/reports/:report_type/pdf", get(reports::generate_pdf_report))
@@ -312,6 +351,7 @@ pub fn create_router(services: Arc<AppServices>, auth_state: Arc<AuthState>, cor
         // Task routes
         .route("/api/v1/tasks", get(tasks::list_tasks))
         .route("/api/v1/tasks/stats", get(tasks::get_task_stats))
+        .route("/api/v1/tasks/analytics", get(tasks::get_task_analytics))
         .route("/api/v1/tasks/my", get(tasks::get_my_tasks))
         .route("/api/v1/tasks/overdue", get(tasks::get_overdue_tasks))
         .route("/api/v1/tasks/recurring", get(tasks::list_recurring_tasks))
@@ -409,10 +449,13 @@ pub fn create_router(services: Arc<AppServices>, auth_state: Arc<AuthState>, cor
         .route("/api/v1/scim/token", delete(enterprise::revoke_scim_token))
         // Enterprise Features - Audit Logs
         .route("/api/v1/audit-logs", get(enterprise::list_activity_logs))
+        .route("/api/v1/audit-logs/verify-chain", get(enterprise::verify_activity_log_chain))
+        .route("/api/v1/audit-logs/chain/anchor", post(enterprise::anchor_activity_log_chain))
         // Enterprise Features - Audit Exports (SIEM)
         .route("/api/v1/audit-exports", get(enterprise::list_audit_export_configurations))
         .route("/api/v1/audit-exports", post(enterprise::create_audit_export_configuration))
         .route("/api/v1/audit-exports/:id", delete(enterprise::delete_audit_export_configuration))
+        .route("/api/v1/audit-exports/:id/streaming", put(enterprise::set_audit_export_streaming_enabled))
         // Enterprise Features - Branding (White-labeling)
         .route("/api/v1/branding", get(enterprise::get_branding))
         .route("/api/v1/branding", put(enterprise::update_branding))
@@ -426,6 +469,20 @@ pub fn create_router(services: Arc<AppServices>, auth_state: Arc<AuthState>, cor
         .route("/api/v1/usage/stats", get(enterprise::get_usage_stats))
         // Enterprise Features - Stats
         .route("/api/v1/enterprise/stats", get(enterprise::get_enterprise_stats))
+        // Data Retention - Policy Configuration
+        .route("/api/v1/retention/policy", get(retention::get_policy))
+        .route("/api/v1/retention/policy", put(retention::update_policy))
+        .route("/api/v1/retention/policy/entities", get(retention::list_entity_policies))
+        .route("/api/v1/retention/policy/entities", post(retention::upsert_entity_policy))
+        .route("/api/v1/retention/policy/entities/:entity_type", put(retention::update_entity_policy))
+        .route("/api/v1/retention/policy/entities/:entity_type", delete(retention::delete_entity_policy))
+        // Data Retention - Legal Holds
+        .route("/api/v1/retention/legal-holds", get(retention::list_legal_holds))
+        .route("/api/v1/retention/legal-holds", post(retention::place_legal_hold))
+        .route("/api/v1/retention/legal-holds/:hold_id/release", post(retention::release_legal_hold))
+        // Data Retention - Purge
+        .route("/api/v1/retention/purge/preview", get(retention::preview_purge))
+        .route("/api/v1/retention/purge/run", post(retention::run_purge))
         .layer(middleware::from_fn_with_state(
             auth_state.clone(),
             auth_middleware,
@@ -436,6 +493,7 @@ pub fn create_router(services: Arc<AppServices>, auth_state: Arc<AuthState>, cor
         .merge(public_routes)
         .merge(sso_routes)
         .merge(protected_routes)
+        .merge(SwaggerUi::new("/api-docs").url("/api-docs/openapi.json", openapi::ApiDoc::openapi()))
         .layer(CompressionLayer::new())
         .layer(TraceLayer::new_for_http())
         .layer(middleware::from_fn(logging_middleware))
@@ -0,0 +1,94 @@
+use utoipa::{
+    openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi,
+};
+
+use crate::routes::{integrations, questionnaires};
+use crate::utils::{ErrorResponse, FieldError};
+
+/// Aggregated OpenAPI 3.0 spec for the questionnaire and issue-tracking
+/// surfaces. Other route modules aren't annotated yet - extend `paths`
+/// and `components(schemas(...))` as they pick up `#[utoipa::path]`.
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        questionnaires::list_templates,
+        questionnaires::get_template,
+        questionnaires::create_template,
+        questionnaires::update_template,
+        questionnaires::delete_template,
+        questionnaires::publish_template,
+        questionnaires::create_section,
+        questionnaires::update_section,
+        questionnaires::delete_section,
+        questionnaires::create_question,
+        questionnaires::update_question,
+        questionnaires::delete_question,
+        questionnaires::list_assignments,
+        questionnaires::get_assignment,
+        questionnaires::create_assignment,
+        questionnaires::review_assignment,
+        questionnaires::delete_assignment,
+        questionnaires::get_stats,
+        questionnaires::get_portal_access,
+        questionnaires::save_portal_response,
+        questionnaires::submit_portal_questionnaire,
+        integrations::sync_jira_issue_tracking,
+        integrations::transition_issue_link,
+    ),
+    components(schemas(
+        crate::models::QuestionnaireTemplate,
+        crate::models::QuestionnaireTemplateWithDetails,
+        crate::models::CreateQuestionnaireTemplate,
+        crate::models::UpdateQuestionnaireTemplate,
+        crate::models::QuestionnaireSection,
+        crate::models::QuestionnaireSectionWithQuestions,
+        crate::models::CreateQuestionnaireSection,
+        crate::models::UpdateQuestionnaireSection,
+        crate::models::QuestionnaireQuestion,
+        crate::models::CreateQuestionnaireQuestion,
+        crate::models::UpdateQuestionnaireQuestion,
+        crate::models::QuestionnaireAssignment,
+        crate::models::QuestionnaireAssignmentWithDetails,
+        crate::models::CreateQuestionnaireAssignment,
+        crate::models::QuestionnaireAssignmentWithToken,
+        crate::models::ReviewQuestionnaireAssignment,
+        crate::models::QuestionnaireResponse,
+        crate::models::SaveQuestionnaireResponse,
+        crate::models::VendorPortalAccess,
+        crate::models::QuestionnaireStats,
+        integrations::TransitionIssueLinkInput,
+        ErrorResponse,
+        FieldError,
+    )),
+    modifiers(&SecurityAddon),
+    tags(
+        (name = "questionnaires", description = "Questionnaire templates, assignments, and the vendor portal"),
+        (name = "integrations", description = "Third-party integrations and issue tracking"),
+    )
+)]
+pub struct ApiDoc;
+
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi
+            .components
+            .as_mut()
+            .expect("ApiDoc always registers at least one schema");
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+        components.add_security_scheme(
+            "portal_token",
+            SecurityScheme::ApiKey(ApiKey::Query(ApiKeyValue::new("token"))),
+        );
+    }
+}
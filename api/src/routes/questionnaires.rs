@@ -4,21 +4,26 @@ use axum::{
 };
 use serde::Deserialize;
 use std::sync::Arc;
+use utoipa::IntoParams;
 use uuid::Uuid;
 
 use crate::middleware::AuthUser;
 use crate::models::{
     CreateQuestionnaireAssignment, CreateQuestionnaireQuestion, CreateQuestionnaireSection,
     CreateQuestionnaireTemplate, ListQuestionnaireAssignmentsQuery, QuestionnaireAssignment,
-    QuestionnaireAssignmentWithDetails, QuestionnaireQuestion, QuestionnaireSection,
-    QuestionnaireStats, QuestionnaireTemplate, QuestionnaireTemplateWithDetails,
-    ReviewQuestionnaireAssignment, SaveQuestionnaireResponse, UpdateQuestionnaireQuestion,
-    UpdateQuestionnaireSection, UpdateQuestionnaireTemplate, VendorPortalAccess,
-    QuestionnaireResponse,
+    QuestionnaireAssignmentWithDetails, QuestionnaireAssignmentWithToken, QuestionnaireQuestion,
+    QuestionnaireSection, QuestionnaireStats, QuestionnaireTemplate,
+    QuestionnaireTemplateWithDetails, ReviewQuestionnaireAssignment, SaveQuestionnaireResponse,
+    UpdateQuestionnaireQuestion, UpdateQuestionnaireSection, UpdateQuestionnaireTemplate,
+    VendorPortalAccess, QuestionnaireResponse,
 };
 use crate::services::AppServices;
 use crate::utils::{AppError, AppResult};
 
+/// Default scope granted to a vendor-portal link when the caller doesn't
+/// restrict it: full read/respond/submit access to the assignment.
+const DEFAULT_PORTAL_SCOPE: &[&str] = &["response:read", "response:write", "questionnaire:submit"];
+
 fn get_org_id(user: &AuthUser) -> AppResult<Uuid> {
     user.organization_id
         .as_ref()
@@ -32,11 +37,19 @@ fn get_user_id(user: &AuthUser) -> AppResult<Uuid> {
 
 // ==================== Templates ====================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
 pub struct ListTemplatesParams {
     pub status: Option<String>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/questionnaires/templates",
+    params(ListTemplatesParams),
+    responses((status = 200, description = "Templates for the caller's organization", body = [QuestionnaireTemplate])),
+    security(("bearer_auth" = [])),
+    tag = "questionnaires"
+)]
 pub async fn list_templates(
     State(services): State<Arc<AppServices>>,
     Extension(user): Extension<AuthUser>,
@@ -50,6 +63,14 @@ pub async fn list_templates(
     Ok(Json(templates))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/questionnaires/templates/{id}",
+    params(("id" = Uuid, Path, description = "Template id")),
+    responses((status = 200, body = QuestionnaireTemplateWithDetails)),
+    security(("bearer_auth" = [])),
+    tag = "questionnaires"
+)]
 pub async fn get_template(
     State(services): State<Arc<AppServices>>,
     Extension(user): Extension<AuthUser>,
@@ -60,6 +81,14 @@ pub async fn get_template(
     Ok(Json(template))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/questionnaires/templates",
+    request_body = CreateQuestionnaireTemplate,
+    responses((status = 200, body = QuestionnaireTemplate)),
+    security(("bearer_auth" = [])),
+    tag = "questionnaires"
+)]
 pub async fn create_template(
     State(services): State<Arc<AppServices>>,
     Extension(user): Extension<AuthUser>,
@@ -74,6 +103,15 @@ pub async fn create_template(
     Ok(Json(template))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/v1/questionnaires/templates/{id}",
+    params(("id" = Uuid, Path, description = "Template id")),
+    request_body = UpdateQuestionnaireTemplate,
+    responses((status = 200, body = QuestionnaireTemplate)),
+    security(("bearer_auth" = [])),
+    tag = "questionnaires"
+)]
 pub async fn update_template(
     State(services): State<Arc<AppServices>>,
     Extension(user): Extension<AuthUser>,
@@ -88,6 +126,14 @@ pub async fn update_template(
     Ok(Json(template))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/v1/questionnaires/templates/{id}",
+    params(("id" = Uuid, Path, description = "Template id")),
+    responses((status = 200, description = "Template deleted")),
+    security(("bearer_auth" = [])),
+    tag = "questionnaires"
+)]
 pub async fn delete_template(
     State(services): State<Arc<AppServices>>,
     Extension(user): Extension<AuthUser>,
@@ -98,6 +144,14 @@ pub async fn delete_template(
     Ok(Json(()))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/questionnaires/templates/{id}/publish",
+    params(("id" = Uuid, Path, description = "Template id")),
+    responses((status = 200, body = QuestionnaireTemplate)),
+    security(("bearer_auth" = [])),
+    tag = "questionnaires"
+)]
 pub async fn publish_template(
     State(services): State<Arc<AppServices>>,
     Extension(user): Extension<AuthUser>,
@@ -116,6 +170,15 @@ pub struct SectionPath {
     pub section_id: Uuid,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/questionnaires/templates/{template_id}/sections",
+    params(("template_id" = Uuid, Path, description = "Template id")),
+    request_body = CreateQuestionnaireSection,
+    responses((status = 200, body = QuestionnaireSection)),
+    security(("bearer_auth" = [])),
+    tag = "questionnaires"
+)]
 pub async fn create_section(
     State(services): State<Arc<AppServices>>,
     Extension(user): Extension<AuthUser>,
@@ -130,6 +193,18 @@ pub async fn create_section(
     Ok(Json(section))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/v1/questionnaires/templates/{template_id}/sections/{section_id}",
+    params(
+        ("template_id" = Uuid, Path, description = "Template id"),
+        ("section_id" = Uuid, Path, description = "Section id"),
+    ),
+    request_body = UpdateQuestionnaireSection,
+    responses((status = 200, body = QuestionnaireSection)),
+    security(("bearer_auth" = [])),
+    tag = "questionnaires"
+)]
 pub async fn update_section(
     State(services): State<Arc<AppServices>>,
     Extension(user): Extension<AuthUser>,
@@ -144,6 +219,17 @@ pub async fn update_section(
     Ok(Json(section))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/v1/questionnaires/templates/{template_id}/sections/{section_id}",
+    params(
+        ("template_id" = Uuid, Path, description = "Template id"),
+        ("section_id" = Uuid, Path, description = "Section id"),
+    ),
+    responses((status = 200, description = "Section deleted")),
+    security(("bearer_auth" = [])),
+    tag = "questionnaires"
+)]
 pub async fn delete_section(
     State(services): State<Arc<AppServices>>,
     Extension(user): Extension<AuthUser>,
@@ -165,6 +251,15 @@ pub struct QuestionPath {
     pub question_id: Uuid,
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/questionnaires/templates/{template_id}/questions",
+    params(("template_id" = Uuid, Path, description = "Template id")),
+    request_body = CreateQuestionnaireQuestion,
+    responses((status = 200, body = QuestionnaireQuestion)),
+    security(("bearer_auth" = [])),
+    tag = "questionnaires"
+)]
 pub async fn create_question(
     State(services): State<Arc<AppServices>>,
     Extension(user): Extension<AuthUser>,
@@ -179,6 +274,18 @@ pub async fn create_question(
     Ok(Json(question))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/v1/questionnaires/templates/{template_id}/questions/{question_id}",
+    params(
+        ("template_id" = Uuid, Path, description = "Template id"),
+        ("question_id" = Uuid, Path, description = "Question id"),
+    ),
+    request_body = UpdateQuestionnaireQuestion,
+    responses((status = 200, body = QuestionnaireQuestion)),
+    security(("bearer_auth" = [])),
+    tag = "questionnaires"
+)]
 pub async fn update_question(
     State(services): State<Arc<AppServices>>,
     Extension(user): Extension<AuthUser>,
@@ -193,6 +300,17 @@ pub async fn update_question(
     Ok(Json(question))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/v1/questionnaires/templates/{template_id}/questions/{question_id}",
+    params(
+        ("template_id" = Uuid, Path, description = "Template id"),
+        ("question_id" = Uuid, Path, description = "Question id"),
+    ),
+    responses((status = 200, description = "Question deleted")),
+    security(("bearer_auth" = [])),
+    tag = "questionnaires"
+)]
 pub async fn delete_question(
     State(services): State<Arc<AppServices>>,
     Extension(user): Extension<AuthUser>,
@@ -208,7 +326,7 @@ pub async fn delete_question(
 
 // ==================== Assignments ====================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
 pub struct ListAssignmentsParams {
     pub vendor_id: Option<Uuid>,
     pub status: Option<String>,
@@ -227,6 +345,14 @@ impl From<ListAssignmentsParams> for ListQuestionnaireAssignmentsQuery {
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/questionnaires/assignments",
+    params(ListAssignmentsParams),
+    responses((status = 200, body = [QuestionnaireAssignmentWithDetails])),
+    security(("bearer_auth" = [])),
+    tag = "questionnaires"
+)]
 pub async fn list_assignments(
     State(services): State<Arc<AppServices>>,
     Extension(user): Extension<AuthUser>,
@@ -240,6 +366,14 @@ pub async fn list_assignments(
     Ok(Json(assignments))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/questionnaires/assignments/{id}",
+    params(("id" = Uuid, Path, description = "Assignment id")),
+    responses((status = 200, body = QuestionnaireAssignmentWithDetails)),
+    security(("bearer_auth" = [])),
+    tag = "questionnaires"
+)]
 pub async fn get_assignment(
     State(services): State<Arc<AppServices>>,
     Extension(user): Extension<AuthUser>,
@@ -250,20 +384,50 @@ pub async fn get_assignment(
     Ok(Json(assignment))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/questionnaires/assignments",
+    request_body = CreateQuestionnaireAssignment,
+    responses((status = 200, description = "Assignment created, with its one-time vendor-portal token", body = QuestionnaireAssignmentWithToken)),
+    security(("bearer_auth" = [])),
+    tag = "questionnaires"
+)]
 pub async fn create_assignment(
     State(services): State<Arc<AppServices>>,
     Extension(user): Extension<AuthUser>,
     Json(input): Json<CreateQuestionnaireAssignment>,
-) -> AppResult<Json<QuestionnaireAssignment>> {
+) -> AppResult<Json<QuestionnaireAssignmentWithToken>> {
     let org_id = get_org_id(&user)?;
     let user_id = get_user_id(&user)?;
+    let scope = input
+        .scope
+        .clone()
+        .unwrap_or_else(|| DEFAULT_PORTAL_SCOPE.iter().map(|s| s.to_string()).collect());
     let assignment = services
         .questionnaire
         .create_assignment(org_id, Some(user_id), input)
         .await?;
-    Ok(Json(assignment))
+    let expires_at = assignment
+        .expires_at
+        .unwrap_or_else(|| chrono::Utc::now() + chrono::Duration::days(30));
+    let portal_token = services
+        .portal_tokens
+        .issue(assignment.id, org_id, scope, expires_at)?;
+    Ok(Json(QuestionnaireAssignmentWithToken {
+        assignment,
+        portal_token,
+    }))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/questionnaires/assignments/{id}/review",
+    params(("id" = Uuid, Path, description = "Assignment id")),
+    request_body = ReviewQuestionnaireAssignment,
+    responses((status = 200, body = QuestionnaireAssignment)),
+    security(("bearer_auth" = [])),
+    tag = "questionnaires"
+)]
 pub async fn review_assignment(
     State(services): State<Arc<AppServices>>,
     Extension(user): Extension<AuthUser>,
@@ -279,6 +443,14 @@ pub async fn review_assignment(
     Ok(Json(assignment))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/v1/questionnaires/assignments/{id}",
+    params(("id" = Uuid, Path, description = "Assignment id")),
+    responses((status = 200, description = "Assignment deleted")),
+    security(("bearer_auth" = [])),
+    tag = "questionnaires"
+)]
 pub async fn delete_assignment(
     State(services): State<Arc<AppServices>>,
     Extension(user): Extension<AuthUser>,
@@ -291,6 +463,13 @@ pub async fn delete_assignment(
 
 // ==================== Statistics ====================
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/questionnaires/stats",
+    responses((status = 200, body = QuestionnaireStats)),
+    security(("bearer_auth" = [])),
+    tag = "questionnaires"
+)]
 pub async fn get_stats(
     State(services): State<Arc<AppServices>>,
     Extension(user): Extension<AuthUser>,
@@ -302,38 +481,72 @@ pub async fn get_stats(
 
 // ==================== Vendor Portal (Public) ====================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
 pub struct PortalAccessQuery {
     pub token: String,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/vendor-portal",
+    params(PortalAccessQuery),
+    responses((status = 200, body = VendorPortalAccess)),
+    security(("portal_token" = [])),
+    tag = "questionnaires"
+)]
 pub async fn get_portal_access(
     State(services): State<Arc<AppServices>>,
     Query(query): Query<PortalAccessQuery>,
 ) -> AppResult<Json<VendorPortalAccess>> {
-    let access = services.questionnaire.get_portal_access(&query.token).await?;
+    let claims = services.portal_tokens.verify(&query.token).await?;
+    claims.require_scope("response:read")?;
+    let access = services
+        .questionnaire
+        .get_portal_access_by_assignment(claims.organization_id, claims.assignment_id)
+        .await?;
     Ok(Json(access))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/vendor-portal/response",
+    params(PortalAccessQuery),
+    request_body = SaveQuestionnaireResponse,
+    responses((status = 200, body = QuestionnaireResponse)),
+    security(("portal_token" = [])),
+    tag = "questionnaires"
+)]
 pub async fn save_portal_response(
     State(services): State<Arc<AppServices>>,
     Query(query): Query<PortalAccessQuery>,
     Json(input): Json<SaveQuestionnaireResponse>,
 ) -> AppResult<Json<QuestionnaireResponse>> {
+    let claims = services.portal_tokens.verify(&query.token).await?;
+    claims.require_scope("response:write")?;
     let response = services
         .questionnaire
-        .save_response(&query.token, input)
+        .save_response_by_assignment(claims.organization_id, claims.assignment_id, input)
         .await?;
     Ok(Json(response))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/vendor-portal/submit",
+    params(PortalAccessQuery),
+    responses((status = 200, description = "Questionnaire submitted")),
+    security(("portal_token" = [])),
+    tag = "questionnaires"
+)]
 pub async fn submit_portal_questionnaire(
     State(services): State<Arc<AppServices>>,
     Query(query): Query<PortalAccessQuery>,
 ) -> AppResult<Json<()>> {
+    let claims = services.portal_tokens.verify(&query.token).await?;
+    claims.require_scope("questionnaire:submit")?;
     services
         .questionnaire
-        .submit_questionnaire(&query.token)
+        .submit_questionnaire_by_assignment(claims.organization_id, claims.assignment_id)
         .await?;
     Ok(Json(()))
 }
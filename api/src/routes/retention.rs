@@ -0,0 +1,142 @@
+use axum::{
+    extract::{Path, State},
+    Extension, Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::middleware::AuthUser;
+use crate::models::{
+    CreateLegalHold, CreateRetentionPolicyForEntity, DataRetentionPolicy,
+    DataRetentionPolicyForEntity, LegalHold, RetentionPurgeSummary, UpdateDataRetentionPolicy,
+    UpdateRetentionPolicyForEntity,
+};
+use crate::services::AppServices;
+use crate::utils::{AppError, AppResult};
+
+fn get_org_id(user: &AuthUser) -> AppResult<Uuid> {
+    user.organization_id
+        .as_ref()
+        .and_then(|id| Uuid::parse_str(id).ok())
+        .ok_or_else(|| AppError::BadRequest("User not associated with an organization".to_string()))
+}
+
+fn get_user_id(user: &AuthUser) -> AppResult<Uuid> {
+    Uuid::parse_str(&user.id).map_err(|_| AppError::BadRequest("Invalid user ID".to_string()))
+}
+
+// ==================== Policy ====================
+
+pub async fn get_policy(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+) -> AppResult<Json<DataRetentionPolicy>> {
+    let org_id = get_org_id(&user)?;
+    let policy = services.retention.get_policy(org_id).await?;
+    Ok(Json(policy))
+}
+
+pub async fn update_policy(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Json(input): Json<UpdateDataRetentionPolicy>,
+) -> AppResult<Json<DataRetentionPolicy>> {
+    let org_id = get_org_id(&user)?;
+    let policy = services.retention.update_policy(org_id, input).await?;
+    Ok(Json(policy))
+}
+
+pub async fn list_entity_policies(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+) -> AppResult<Json<Vec<DataRetentionPolicyForEntity>>> {
+    let org_id = get_org_id(&user)?;
+    let policies = services.retention.list_entity_policies(org_id).await?;
+    Ok(Json(policies))
+}
+
+pub async fn upsert_entity_policy(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Json(input): Json<CreateRetentionPolicyForEntity>,
+) -> AppResult<Json<DataRetentionPolicyForEntity>> {
+    let org_id = get_org_id(&user)?;
+    let policy = services.retention.upsert_entity_policy(org_id, input).await?;
+    Ok(Json(policy))
+}
+
+pub async fn update_entity_policy(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Path(entity_type): Path<String>,
+    Json(input): Json<UpdateRetentionPolicyForEntity>,
+) -> AppResult<Json<DataRetentionPolicyForEntity>> {
+    let org_id = get_org_id(&user)?;
+    let policy = services
+        .retention
+        .update_entity_policy(org_id, &entity_type, input)
+        .await?;
+    Ok(Json(policy))
+}
+
+pub async fn delete_entity_policy(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Path(entity_type): Path<String>,
+) -> AppResult<Json<serde_json::Value>> {
+    let org_id = get_org_id(&user)?;
+    services.retention.delete_entity_policy(org_id, &entity_type).await?;
+    Ok(Json(serde_json::json!({ "success": true })))
+}
+
+// ==================== Legal Holds ====================
+
+pub async fn list_legal_holds(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+) -> AppResult<Json<Vec<LegalHold>>> {
+    let org_id = get_org_id(&user)?;
+    let holds = services.retention.list_legal_holds(org_id).await?;
+    Ok(Json(holds))
+}
+
+pub async fn place_legal_hold(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Json(input): Json<CreateLegalHold>,
+) -> AppResult<Json<LegalHold>> {
+    let org_id = get_org_id(&user)?;
+    let user_id = get_user_id(&user)?;
+    let hold = services.retention.place_legal_hold(org_id, user_id, input).await?;
+    Ok(Json(hold))
+}
+
+pub async fn release_legal_hold(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Path(hold_id): Path<Uuid>,
+) -> AppResult<Json<LegalHold>> {
+    let org_id = get_org_id(&user)?;
+    let hold = services.retention.release_legal_hold(org_id, hold_id).await?;
+    Ok(Json(hold))
+}
+
+// ==================== Purge ====================
+
+pub async fn preview_purge(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+) -> AppResult<Json<RetentionPurgeSummary>> {
+    let org_id = get_org_id(&user)?;
+    let summary = services.retention.run_purge(org_id, true).await?;
+    Ok(Json(summary))
+}
+
+pub async fn run_purge(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+) -> AppResult<Json<RetentionPurgeSummary>> {
+    let org_id = get_org_id(&user)?;
+    let summary = services.retention.run_purge(org_id, false).await?;
+    Ok(Json(summary))
+}
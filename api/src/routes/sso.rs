@@ -3,10 +3,12 @@ use axum::{
     http::HeaderMap,
     Json,
 };
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::middleware::jwks::TokenVerification;
 use crate::middleware::AuthState;
 use crate::utils::{AppError, AppResult};
 
@@ -15,6 +17,11 @@ pub struct ExchangeCodeRequest {
     pub code: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TokenResponse {
     pub access_token: String,
@@ -37,6 +44,10 @@ pub struct SSOUser {
     pub role: String,
     pub exp: Option<i64>,
     pub iat: Option<i64>,
+    /// OpenGRC capabilities resolved from the IdP's full `roles`/`scope`
+    /// claims, not just the single `role` string above.
+    #[serde(default)]
+    pub capabilities: Vec<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -94,11 +105,119 @@ pub async fn exchange_code(
         .await
         .map_err(|e| AppError::BadRequest(format!("Failed to parse token response: {}", e)))?;
 
+    if let Some(subject) = unverified_subject(&token_response.access_token) {
+        if let Err(e) = auth_state
+            .sessions
+            .record(
+                &subject,
+                &token_response.access_token,
+                token_response.refresh_token.as_deref(),
+                token_response.expires_in,
+            )
+            .await
+        {
+            tracing::warn!("Failed to record SSO session: {:?}", e);
+        }
+    }
+
     tracing::info!("Successfully exchanged authorization code for access token");
 
     Ok(Json(token_response))
 }
 
+/// POST /api/sso/refresh
+/// Exchanges a refresh token (from the request body, falling back to the
+/// `tv_refresh_token` cookie) for a fresh access/refresh token pair, and
+/// rotates the server-side session so the old refresh token can't be reused.
+pub async fn refresh_token(
+    State(auth_state): State<Arc<AuthState>>,
+    headers: HeaderMap,
+    Json(payload): Json<RefreshTokenRequest>,
+) -> AppResult<Json<TokenResponse>> {
+    let refresh_token = payload
+        .refresh_token
+        .or_else(|| cookie_value(&headers, "tv_refresh_token"))
+        .ok_or_else(|| AppError::BadRequest("Missing refresh token".to_string()))?;
+
+    tracing::info!("Refreshing SSO access token");
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| AppError::BadRequest(format!("Failed to create HTTP client: {}", e)))?;
+
+    let token_endpoint = format!("{}/oauth/token", auth_state.tv_api_url.trim_end_matches('/'));
+
+    let form_params = [
+        ("grant_type", "refresh_token"),
+        ("refresh_token", refresh_token.as_str()),
+        ("client_id", &auth_state.client_id),
+        ("client_secret", &auth_state.client_secret),
+    ];
+
+    let response = client
+        .post(&token_endpoint)
+        .form(&form_params)
+        .send()
+        .await
+        .map_err(|e| {
+            tracing::error!("Network error during token refresh: {}", e);
+            AppError::BadRequest(format!("Failed to refresh token: {}", e))
+        })?;
+
+    let status = response.status();
+
+    if !status.is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        tracing::error!("Token refresh failed with status {}: {}", status, error_text);
+        return Err(AppError::Unauthorized(format!("Token refresh failed: {}", error_text)));
+    }
+
+    let token_response: TokenResponse = response
+        .json()
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Failed to parse token response: {}", e)))?;
+
+    match auth_state
+        .sessions
+        .rotate(
+            &refresh_token,
+            &token_response.access_token,
+            token_response.refresh_token.as_deref(),
+            token_response.expires_in,
+        )
+        .await
+    {
+        Ok(Some(subject)) => {
+            tracing::debug!("Rotated SSO session for subject: {}", subject);
+        }
+        Ok(None) => {
+            // No tracked session for this refresh token (issued before
+            // session tracking existed, or already rotated) - record a new
+            // one so future refreshes/logouts can still be tracked.
+            if let Some(subject) = unverified_subject(&token_response.access_token) {
+                if let Err(e) = auth_state
+                    .sessions
+                    .record(
+                        &subject,
+                        &token_response.access_token,
+                        token_response.refresh_token.as_deref(),
+                        token_response.expires_in,
+                    )
+                    .await
+                {
+                    tracing::warn!("Failed to record rotated SSO session: {:?}", e);
+                }
+            }
+        }
+        Err(e) => tracing::warn!("Failed to rotate SSO session: {:?}", e),
+    }
+
+    tracing::info!("Successfully refreshed SSO access token");
+
+    Ok(Json(token_response))
+}
+
 /// POST /api/sso/userinfo
 /// Proxies the /userinfo request to TitaniumVault to avoid CORS issues
 pub async fn get_userinfo(
@@ -178,7 +297,7 @@ pub async fn validate_sso(
 
     tracing::debug!("Validating SSO token: {}...", &sso_token[..std::cmp::min(10, sso_token.len())]);
 
-    match validate_token_with_tv(&sso_token, &auth_state.tv_api_url).await {
+    match validate_token_with_tv(&sso_token, &auth_state).await {
         Ok(user) => {
             tracing::info!("SSO validation successful for user: {}", user.email);
             Ok(Json(SSOValidationResponse {
@@ -199,23 +318,86 @@ pub async fn validate_sso(
 }
 
 /// POST /api/sso/logout
-/// Returns success (cookie clearing handled by client)
-pub async fn logout_sso() -> AppResult<Json<serde_json::Value>> {
+/// Revokes the server-side session for the bearer token, if one is tracked,
+/// then returns success (cookie clearing is still the client's job).
+pub async fn logout_sso(
+    State(auth_state): State<Arc<AuthState>>,
+    headers: HeaderMap,
+) -> AppResult<Json<serde_json::Value>> {
     tracing::debug!("Processing SSO logout request");
 
+    if let Some(auth_header) = headers.get("Authorization").and_then(|h| h.to_str().ok()) {
+        if let Some(token) = auth_header.strip_prefix("Bearer ") {
+            if let Err(e) = auth_state.sessions.revoke_by_access_token(token).await {
+                tracing::warn!("Failed to revoke SSO session: {:?}", e);
+            }
+        }
+    }
+
     Ok(Json(serde_json::json!({
         "status": "success",
         "message": "SSO logout successful"
     })))
 }
 
-async fn validate_token_with_tv(token: &str, tv_api_url: &str) -> Result<SSOUser, AppError> {
+/// Best-effort extraction of the `sub` claim from a JWT without verifying
+/// its signature. Only used for session bookkeeping immediately after a
+/// token was issued or refreshed directly by TitaniumVault in this same
+/// request - never for authentication decisions.
+pub(crate) fn unverified_subject(token: &str) -> Option<String> {
+    let payload = token.split('.').nth(1)?;
+    let decoded = URL_SAFE_NO_PAD.decode(payload).ok()?;
+    let claims: serde_json::Value = serde_json::from_slice(&decoded).ok()?;
+    claims.get("sub")?.as_str().map(|s| s.to_string())
+}
+
+fn cookie_value(headers: &HeaderMap, name: &str) -> Option<String> {
+    let cookie_header = headers.get("Cookie")?.to_str().ok()?;
+    cookie_header.split(';').find_map(|pair| {
+        let (key, value) = pair.trim().split_once('=')?;
+        (key == name).then(|| value.to_string())
+    })
+}
+
+async fn validate_token_with_tv(token: &str, auth_state: &AuthState) -> Result<SSOUser, AppError> {
+    if auth_state.sessions.is_revoked(token).await? {
+        return Err(AppError::Unauthorized("Session has been revoked".to_string()));
+    }
+
+    match auth_state
+        .jwks
+        .verify(token, &auth_state.tv_api_url, &auth_state.tv_api_url, &auth_state.client_id)
+        .await?
+    {
+        TokenVerification::Valid(claims) => {
+            tracing::debug!("Validated SSO token locally via cached JWKS");
+            let capabilities = auth_state
+                .capabilities
+                .resolve(claims.organization_id.as_deref(), &claims.role_list(), claims.scope.as_deref())
+                .await;
+            return Ok(SSOUser {
+                sub: claims.sub,
+                email: claims.email.unwrap_or_else(|| "unknown".to_string()),
+                role: claims
+                    .role
+                    .or_else(|| claims.roles.and_then(|r| r.into_iter().next()))
+                    .unwrap_or_else(|| "user".to_string()),
+                exp: Some(claims.exp),
+                iat: claims.iat,
+                capabilities,
+            });
+        }
+        TokenVerification::UnknownKey => {
+            tracing::debug!("No cached JWKS key for SSO token, falling back to TitaniumVault userinfo");
+        }
+    }
+
     let client = reqwest::Client::builder()
         .timeout(Duration::from_secs(10))
         .build()
         .map_err(|e| AppError::BadRequest(format!("Failed to create HTTP client: {}", e)))?;
 
-    let userinfo_endpoint = format!("{}/userinfo", tv_api_url.trim_end_matches('/'));
+    let userinfo_endpoint = format!("{}/userinfo", auth_state.tv_api_url.trim_end_matches('/'));
     tracing::debug!("Validating SSO token with endpoint: {}", userinfo_endpoint);
 
     let response = client
@@ -244,6 +426,18 @@ async fn validate_token_with_tv(token: &str, tv_api_url: &str) -> Result<SSOUser
 
     tracing::debug!("Received userinfo for SSO: {:?}", userinfo);
 
+    let organization_id = userinfo.get("organization_id").and_then(|v| v.as_str()).map(|s| s.to_string());
+    let roles: Vec<String> = userinfo.get("roles")
+        .and_then(|v| v.as_array())
+        .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
+        .unwrap_or_default();
+    let scope = userinfo.get("scope").or_else(|| userinfo.get("scp")).and_then(|v| v.as_str());
+
+    let capabilities = auth_state
+        .capabilities
+        .resolve(organization_id.as_deref(), &roles, scope)
+        .await;
+
     let sso_user = SSOUser {
         sub: userinfo.get("sub")
             .and_then(|v| v.as_str())
@@ -255,16 +449,12 @@ async fn validate_token_with_tv(token: &str, tv_api_url: &str) -> Result<SSOUser
             .to_string(),
         role: userinfo.get("role")
             .and_then(|v| v.as_str())
-            .or_else(|| {
-                userinfo.get("roles")
-                    .and_then(|v| v.as_array())
-                    .and_then(|arr| arr.first())
-                    .and_then(|v| v.as_str())
-            })
+            .or_else(|| roles.first().map(|s| s.as_str()))
             .unwrap_or("user")
             .to_string(),
         exp: userinfo.get("exp").and_then(|v| v.as_i64()),
         iat: userinfo.get("iat").and_then(|v| v.as_i64()),
+        capabilities,
     };
 
     Ok(sso_user)
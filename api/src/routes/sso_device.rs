@@ -0,0 +1,159 @@
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::middleware::AuthState;
+use crate::routes::sso::{unverified_subject, TokenResponse};
+use crate::utils::{AppError, AppResult};
+
+#[derive(Debug, Deserialize)]
+pub struct DeviceCodeRequest {
+    pub scope: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeviceCodeResponse {
+    pub device_code: String,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub verification_uri_complete: Option<String>,
+    pub expires_in: i64,
+    pub interval: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeviceTokenRequest {
+    pub device_code: String,
+}
+
+/// POST /api/sso/device/code
+/// Starts the OAuth 2.0 Device Authorization Grant (RFC 8628) so CLI tools
+/// and headless evidence-collection agents can authenticate without a
+/// browser or an embedded long-lived secret.
+pub async fn request_device_code(
+    State(auth_state): State<Arc<AuthState>>,
+    Json(payload): Json<DeviceCodeRequest>,
+) -> AppResult<Json<DeviceCodeResponse>> {
+    tracing::info!("Requesting device authorization code");
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| AppError::BadRequest(format!("Failed to create HTTP client: {}", e)))?;
+
+    let device_endpoint = format!("{}/oauth/device/code", auth_state.tv_api_url.trim_end_matches('/'));
+    let scope = payload.scope.unwrap_or_else(|| "openid".to_string());
+
+    let form_params = [
+        ("client_id", auth_state.client_id.as_str()),
+        ("scope", scope.as_str()),
+    ];
+
+    let response = client
+        .post(&device_endpoint)
+        .form(&form_params)
+        .send()
+        .await
+        .map_err(|e| {
+            tracing::error!("Network error during device code request: {}", e);
+            AppError::BadRequest(format!("Failed to request device code: {}", e))
+        })?;
+
+    let status = response.status();
+
+    if !status.is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        tracing::error!("Device code request failed with status {}: {}", status, error_text);
+        return Err(AppError::BadRequest(format!("Device code request failed: {}", error_text)));
+    }
+
+    let device_response: DeviceCodeResponse = response
+        .json()
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Failed to parse device code response: {}", e)))?;
+
+    tracing::info!("Issued device authorization code");
+
+    Ok(Json(device_response))
+}
+
+/// POST /api/sso/device/token
+/// Polls the token endpoint for a pending device code grant. The IdP's
+/// `error` field is translated into typed `AppError` variants so a poller
+/// can distinguish "keep waiting" (`authorization_pending`, `slow_down`)
+/// from a terminal failure (`access_denied`, `expired_token`).
+pub async fn poll_device_token(
+    State(auth_state): State<Arc<AuthState>>,
+    Json(payload): Json<DeviceTokenRequest>,
+) -> AppResult<Json<TokenResponse>> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| AppError::BadRequest(format!("Failed to create HTTP client: {}", e)))?;
+
+    let token_endpoint = format!("{}/oauth/token", auth_state.tv_api_url.trim_end_matches('/'));
+
+    let form_params = [
+        ("grant_type", "urn:ietf:params:oauth:device_code"),
+        ("device_code", payload.device_code.as_str()),
+        ("client_id", auth_state.client_id.as_str()),
+        ("client_secret", auth_state.client_secret.as_str()),
+    ];
+
+    let response = client
+        .post(&token_endpoint)
+        .form(&form_params)
+        .send()
+        .await
+        .map_err(|e| {
+            tracing::error!("Network error during device token poll: {}", e);
+            AppError::BadRequest(format!("Failed to poll device token: {}", e))
+        })?;
+
+    let status = response.status();
+
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Failed to parse device token response: {}", e)))?;
+
+    if !status.is_success() {
+        let error_code = body.get("error").and_then(|v| v.as_str()).unwrap_or("");
+        let description = body
+            .get("error_description")
+            .and_then(|v| v.as_str())
+            .unwrap_or(error_code)
+            .to_string();
+
+        return Err(match error_code {
+            "authorization_pending" => AppError::DeviceAuthorizationPending(description),
+            "slow_down" => AppError::DeviceSlowDown(description),
+            "access_denied" => AppError::DeviceAccessDenied(description),
+            "expired_token" => AppError::DeviceTokenExpired(description),
+            _ => AppError::BadRequest(format!("Device token poll failed: {}", description)),
+        });
+    }
+
+    let token_response: TokenResponse = serde_json::from_value(body)
+        .map_err(|e| AppError::BadRequest(format!("Failed to parse device token response: {}", e)))?;
+
+    if let Some(subject) = unverified_subject(&token_response.access_token) {
+        if let Err(e) = auth_state
+            .sessions
+            .record(
+                &subject,
+                &token_response.access_token,
+                token_response.refresh_token.as_deref(),
+                token_response.expires_in,
+            )
+            .await
+        {
+            tracing::warn!("Failed to record SSO session from device flow: {:?}", e);
+        }
+    }
+
+    tracing::info!("Successfully completed device authorization grant");
+
+    Ok(Json(token_response))
+}
@@ -0,0 +1,45 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::Response,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::services::AppServices;
+use crate::utils::{AppError, AppResult};
+
+#[derive(Debug, Deserialize, Default)]
+pub struct DownloadTokenQuery {
+    /// Absent when no signing secret is configured - unsigned legacy URLs.
+    #[serde(default)]
+    pub expires: u64,
+    #[serde(default)]
+    pub sig: String,
+}
+
+/// GET /api/v1/storage/download/*key
+///
+/// Serves a local-storage object directly (decrypting it first when storage
+/// encryption is enabled). This is the endpoint `StorageClient::sign_local_url`
+/// points callers at, so it verifies the signed `expires`/`sig` query pair
+/// before returning anything: with a signing secret configured, an unsigned
+/// or expired/mismatched request is rejected. With no secret configured the
+/// check is a no-op and unsigned URLs are served.
+pub async fn download_file(
+    State(services): State<Arc<AppServices>>,
+    Path(key): Path<String>,
+    Query(query): Query<DownloadTokenQuery>,
+) -> AppResult<Response> {
+    services
+        .storage
+        .verify_download_token(&key, query.expires, &query.sig)?;
+
+    let (data, content_type) = services.storage.download_evidence(&key).await?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, content_type)
+        .body(axum::body::Body::from(data))
+        .map_err(|e| AppError::InternalServerError(format!("Failed to build download response: {}", e)))
+}
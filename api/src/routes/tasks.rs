@@ -8,7 +8,8 @@ use uuid::Uuid;
 
 use crate::middleware::AuthUser;
 use crate::models::{
-    CreateTask, CreateTaskComment, ListTasksQuery, Task, TaskComment, TaskCommentWithUser,
+    CreateTask, CreateTaskComment, ListTasksQuery, Task, TaskAnalytics, TaskAnalyticsGroupBy,
+    TaskAnalyticsMetric, TaskAnalyticsQuery, TaskComment, TaskCommentWithUser,
     TaskRecurrenceHistory, TaskStats, TaskWithAssignee, UpdateTask,
 };
 use chrono::{DateTime, Utc};
@@ -65,6 +66,39 @@ impl From<ListTasksParams> for ListTasksQuery {
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct TaskAnalyticsParams {
+    pub status: Option<String>,
+    pub task_type: Option<String>,
+    pub priority: Option<String>,
+    pub assignee_id: Option<Uuid>,
+    pub related_entity_type: Option<String>,
+    pub related_entity_id: Option<Uuid>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub group_by: TaskAnalyticsGroupBy,
+    #[serde(default)]
+    pub metric: TaskAnalyticsMetric,
+}
+
+impl From<TaskAnalyticsParams> for TaskAnalyticsQuery {
+    fn from(params: TaskAnalyticsParams) -> Self {
+        TaskAnalyticsQuery {
+            status: params.status,
+            task_type: params.task_type,
+            priority: params.priority,
+            assignee_id: params.assignee_id,
+            related_entity_type: params.related_entity_type,
+            related_entity_id: params.related_entity_id,
+            from: params.from,
+            to: params.to,
+            group_by: params.group_by,
+            metric: params.metric,
+        }
+    }
+}
+
 // ==================== Task CRUD ====================
 
 pub async fn list_tasks(
@@ -140,6 +174,16 @@ pub async fn get_task_stats(
     Ok(Json(stats))
 }
 
+pub async fn get_task_analytics(
+    State(services): State<Arc<AppServices>>,
+    Extension(user): Extension<AuthUser>,
+    Query(params): Query<TaskAnalyticsParams>,
+) -> AppResult<Json<TaskAnalytics>> {
+    let org_id = get_org_id(&user)?;
+    let analytics = services.task.get_analytics(org_id, params.into()).await?;
+    Ok(Json(analytics))
+}
+
 // ==================== My Tasks ====================
 
 pub async fn get_my_tasks(
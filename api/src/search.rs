@@ -376,9 +376,21 @@ impl SearchClient {
         }
 
         if let Some(types) = doc_types {
-            if !types.is_empty() {
+            // `types` is caller-controlled (it comes straight off the `?types=`
+            // query string) and gets interpolated into a quoted filter literal,
+            // so it must be checked against the fixed set of known doc types
+            // before being trusted - an unvalidated value could break out of
+            // the quoted literal and rewrite the filter's logical grouping,
+            // bypassing the organization_id scoping filter above.
+            let known_types: Vec<String> = types
+                .iter()
+                .filter(|t| matches!(t.as_str(), "control" | "risk" | "policy" | "evidence" | "vendor" | "framework" | "asset"))
+                .cloned()
+                .collect();
+
+            if !known_types.is_empty() {
                 let type_filter: Vec<String> =
-                    types.iter().map(|t| format!("doc_type = \"{}\"", t)).collect();
+                    known_types.iter().map(|t| format!("doc_type = \"{}\"", t)).collect();
                 filters.push(format!("({})", type_filter.join(" OR ")));
             }
         }
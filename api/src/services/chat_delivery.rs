@@ -0,0 +1,386 @@
+//! Outbound delivery of mention notifications to connected Slack workspaces,
+//! Microsoft Teams tenants, and self-hosted Mattermost servers.
+//!
+//! The stored bot tokens become an actual notification channel: a comment
+//! that @mentions a user is sent as a Slack or Mattermost direct message
+//! when the recipient has a mapped chat identity, and otherwise posted to the
+//! org's default Slack channel via `chat.postMessage` or default Mattermost
+//! channel via its `POST /api/v4/posts` endpoint; Teams mentions go to the
+//! default Teams channel via the Graph
+//! `POST /teams/{id}/channels/{id}/messages` endpoint. Delivery respects each
+//! recipient's [`NotificationPreferences`].
+
+use chrono::{DateTime, Duration, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::utils::{AppError, AppResult};
+
+/// Notification type toggled by users who do not want mention pings.
+pub const MENTION_NOTIFICATION_TYPE: &str = "comment_mention";
+
+/// Refresh a Teams access token this many seconds before it actually expires so
+/// an in-flight Graph call never races the expiry.
+const TEAMS_REFRESH_SKEW_SECS: i64 = 5 * 60;
+
+/// A rendered mention message ready to post to a chat provider.
+pub struct MentionMessage {
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub excerpt: String,
+    pub commenter_name: String,
+}
+
+impl MentionMessage {
+    /// Format the message body: a link to the entity plus a short excerpt.
+    pub fn render(&self) -> String {
+        let base = std::env::var("API_BASE_URL")
+            .unwrap_or_else(|_| "https://app.opengrc.io".to_string());
+        let link = format!("{}/{}/{}", base, self.entity_type, self.entity_id);
+        format!(
+            "*{}* mentioned you on {} {}\n{}\n> {}",
+            self.commenter_name, self.entity_type, self.entity_id, link, self.excerpt
+        )
+    }
+}
+
+/// Post a message to a Slack channel with a workspace bot token.
+pub async fn post_slack_message(token: &str, channel: &str, text: &str) -> AppResult<()> {
+    let client = reqwest::Client::new();
+    let response: serde_json::Value = client
+        .post("https://slack.com/api/chat.postMessage")
+        .bearer_auth(token)
+        .json(&serde_json::json!({ "channel": channel, "text": text }))
+        .send()
+        .await
+        .map_err(|e| AppError::ExternalServiceError(format!("Slack postMessage: {}", e)))?
+        .json()
+        .await
+        .map_err(|e| AppError::ExternalServiceError(format!("Slack postMessage parse: {}", e)))?;
+
+    if response.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+        Ok(())
+    } else {
+        let err = response.get("error").and_then(|v| v.as_str()).unwrap_or("unknown");
+        Err(AppError::ExternalServiceError(format!("Slack postMessage failed: {}", err)))
+    }
+}
+
+/// Open (or fetch) a direct-message conversation with a Slack user and return
+/// its channel id, suitable for `chat.postMessage`.
+pub async fn open_slack_dm(token: &str, slack_user_id: &str) -> AppResult<String> {
+    let client = reqwest::Client::new();
+    let response: serde_json::Value = client
+        .post("https://slack.com/api/conversations.open")
+        .bearer_auth(token)
+        .json(&serde_json::json!({ "users": slack_user_id }))
+        .send()
+        .await
+        .map_err(|e| AppError::ExternalServiceError(format!("Slack conversations.open: {}", e)))?
+        .json()
+        .await
+        .map_err(|e| {
+            AppError::ExternalServiceError(format!("Slack conversations.open parse: {}", e))
+        })?;
+
+    if response.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+        response
+            .get("channel")
+            .and_then(|c| c.get("id"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| {
+                AppError::ExternalServiceError("Slack conversations.open returned no channel".to_string())
+            })
+    } else {
+        let err = response.get("error").and_then(|v| v.as_str()).unwrap_or("unknown");
+        Err(AppError::ExternalServiceError(format!("Slack conversations.open failed: {}", err)))
+    }
+}
+
+/// Post a message to a Teams channel via the Microsoft Graph API.
+pub async fn post_teams_message(
+    token: &str,
+    team_id: &str,
+    channel_id: &str,
+    text: &str,
+) -> AppResult<()> {
+    let client = reqwest::Client::new();
+    let url = format!(
+        "https://graph.microsoft.com/v1.0/teams/{}/channels/{}/messages",
+        team_id, channel_id
+    );
+    let response = client
+        .post(&url)
+        .bearer_auth(token)
+        .json(&serde_json::json!({ "body": { "contentType": "text", "content": text } }))
+        .send()
+        .await
+        .map_err(|e| AppError::ExternalServiceError(format!("Teams sendMessage: {}", e)))?;
+
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        let status = response.status();
+        Err(AppError::ExternalServiceError(format!("Teams sendMessage failed: {}", status)))
+    }
+}
+
+/// Post a message to a Mattermost channel via the posts API using a
+/// server's personal-access-token or bot token.
+pub async fn post_mattermost_message(
+    base_url: &str,
+    token: &str,
+    channel_id: &str,
+    text: &str,
+) -> AppResult<()> {
+    let client = reqwest::Client::new();
+    let response = client
+        .post(format!("{}/api/v4/posts", base_url.trim_end_matches('/')))
+        .bearer_auth(token)
+        .json(&serde_json::json!({ "channel_id": channel_id, "message": text }))
+        .send()
+        .await
+        .map_err(|e| AppError::ExternalServiceError(format!("Mattermost post: {}", e)))?;
+
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        let status = response.status();
+        Err(AppError::ExternalServiceError(format!("Mattermost post failed: {}", status)))
+    }
+}
+
+/// Open (or fetch) a direct-message channel between the server's bot user and
+/// a Mattermost user, returning its channel id, suitable for `post_mattermost_message`.
+pub async fn open_mattermost_dm(
+    base_url: &str,
+    token: &str,
+    bot_user_id: &str,
+    mattermost_user_id: &str,
+) -> AppResult<String> {
+    let client = reqwest::Client::new();
+    let response = client
+        .post(format!("{}/api/v4/channels/direct", base_url.trim_end_matches('/')))
+        .bearer_auth(token)
+        .json(&serde_json::json!([bot_user_id, mattermost_user_id]))
+        .send()
+        .await
+        .map_err(|e| AppError::ExternalServiceError(format!("Mattermost channels.direct: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        return Err(AppError::ExternalServiceError(format!(
+            "Mattermost channels.direct failed: {}",
+            status
+        )));
+    }
+
+    let channel: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| AppError::ExternalServiceError(format!("Mattermost channels.direct parse: {}", e)))?;
+
+    channel
+        .get("id")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .ok_or_else(|| AppError::ExternalServiceError("Mattermost channels.direct returned no channel id".to_string()))
+}
+
+/// Look up a Mattermost user by email, returning their user id and username.
+pub async fn lookup_mattermost_user_by_email(
+    base_url: &str,
+    token: &str,
+    email: &str,
+) -> AppResult<Option<(String, String)>> {
+    let client = reqwest::Client::new();
+    let response = client
+        .get(format!(
+            "{}/api/v4/users/email/{}",
+            base_url.trim_end_matches('/'),
+            email
+        ))
+        .bearer_auth(token)
+        .send()
+        .await
+        .map_err(|e| AppError::ExternalServiceError(format!("Mattermost users.email: {}", e)))?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        let status = response.status();
+        return Err(AppError::ExternalServiceError(format!(
+            "Mattermost users.email failed: {}",
+            status
+        )));
+    }
+
+    let user: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| AppError::ExternalServiceError(format!("Mattermost users.email parse: {}", e)))?;
+
+    let id = match user.get("id").and_then(|v| v.as_str()) {
+        Some(id) => id.to_string(),
+        None => return Ok(None),
+    };
+    let username = user
+        .get("username")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+
+    Ok(Some((id, username)))
+}
+
+/// An active Teams tenant row as needed for token management.
+#[derive(sqlx::FromRow)]
+struct TeamsTenantToken {
+    id: Uuid,
+    access_token: String,
+    refresh_token: Option<String>,
+    token_expires_at: Option<DateTime<Utc>>,
+}
+
+/// Return a usable Teams access token for an org, transparently refreshing it
+/// via the OAuth refresh-token grant when it is at or near expiry.
+///
+/// All Teams API callers should obtain their token through this function so the
+/// refresh is invisible to them.
+pub async fn get_teams_token(db: &PgPool, org_id: Uuid) -> AppResult<String> {
+    let tenant = sqlx::query_as::<_, TeamsTenantToken>(
+        r#"
+        SELECT id, access_token, refresh_token, token_expires_at
+        FROM teams_tenants
+        WHERE organization_id = $1 AND status = 'active'
+        ORDER BY updated_at DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(org_id)
+    .fetch_optional(db)
+    .await?
+    .ok_or_else(|| AppError::NotFound("No active Teams tenant for organization".to_string()))?;
+
+    let expiring = tenant
+        .token_expires_at
+        .map(|exp| exp <= Utc::now() + Duration::seconds(TEAMS_REFRESH_SKEW_SECS))
+        .unwrap_or(true);
+
+    if expiring {
+        if let Some(refresh_token) = tenant.refresh_token.as_deref() {
+            return refresh_teams_token(db, tenant.id, refresh_token).await;
+        }
+    }
+
+    Ok(tenant.access_token)
+}
+
+/// Exchange a stored refresh token for a fresh access token and persist the
+/// rotated credentials on the tenant row, returning the new access token.
+async fn refresh_teams_token(db: &PgPool, tenant_row_id: Uuid, refresh_token: &str) -> AppResult<String> {
+    let client_id = std::env::var("TEAMS_CLIENT_ID")
+        .map_err(|_| AppError::InternalServerError("Teams client ID not configured".to_string()))?;
+    let client_secret = std::env::var("TEAMS_CLIENT_SECRET").map_err(|_| {
+        AppError::InternalServerError("Teams client secret not configured".to_string())
+    })?;
+
+    let client = reqwest::Client::new();
+    let token_response: serde_json::Value = client
+        .post("https://login.microsoftonline.com/common/oauth2/v2.0/token")
+        .form(&[
+            ("client_id", client_id.as_str()),
+            ("client_secret", client_secret.as_str()),
+            ("refresh_token", refresh_token),
+            ("grant_type", "refresh_token"),
+        ])
+        .send()
+        .await
+        .map_err(|e| AppError::ExternalServiceError(format!("Teams token refresh: {}", e)))?
+        .json()
+        .await
+        .map_err(|e| {
+            AppError::ExternalServiceError(format!("Teams token refresh parse: {}", e))
+        })?;
+
+    if let Some(error) = token_response.get("error_description").and_then(|v| v.as_str()) {
+        return Err(AppError::ExternalServiceError(format!(
+            "Teams token refresh failed: {}",
+            error
+        )));
+    }
+
+    let access_token = token_response
+        .get("access_token")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| AppError::ExternalServiceError("Missing refreshed access token".to_string()))?;
+
+    // Microsoft may or may not rotate the refresh token; keep the old one if not.
+    let new_refresh_token = token_response
+        .get("refresh_token")
+        .and_then(|v| v.as_str())
+        .unwrap_or(refresh_token);
+
+    let expires_in = token_response
+        .get("expires_in")
+        .and_then(|v| v.as_i64())
+        .unwrap_or(3600);
+    let token_expires_at = Utc::now() + Duration::seconds(expires_in);
+
+    sqlx::query(
+        r#"
+        UPDATE teams_tenants
+        SET access_token = $2, refresh_token = $3, token_expires_at = $4, updated_at = NOW()
+        WHERE id = $1
+        "#,
+    )
+    .bind(tenant_row_id)
+    .bind(access_token)
+    .bind(new_refresh_token)
+    .bind(token_expires_at)
+    .execute(db)
+    .await?;
+
+    Ok(access_token.to_string())
+}
+
+/// Periodically refresh Teams tokens that are within the skew window so tenant
+/// connections never silently lapse between user-driven Graph calls.
+pub async fn run_teams_token_refresh(db: PgPool) {
+    tracing::info!("Starting Teams token refresh task");
+
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(300));
+
+    loop {
+        interval.tick().await;
+
+        let due: Vec<Uuid> = match sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT DISTINCT organization_id
+            FROM teams_tenants
+            WHERE status = 'active'
+              AND refresh_token IS NOT NULL
+              AND (token_expires_at IS NULL
+                   OR token_expires_at <= NOW() + make_interval(secs => $1))
+            "#,
+        )
+        .bind(TEAMS_REFRESH_SKEW_SECS as f64)
+        .fetch_all(&db)
+        .await
+        {
+            Ok(rows) => rows,
+            Err(e) => {
+                tracing::error!("Failed to scan Teams tokens for refresh: {}", e);
+                continue;
+            }
+        };
+
+        for org_id in due {
+            if let Err(e) = get_teams_token(&db, org_id).await {
+                tracing::warn!(%org_id, error = %e, "Teams token refresh failed");
+            }
+        }
+    }
+}
@@ -1,12 +1,15 @@
 use crate::cache::{org_cache_key, CacheClient};
 use crate::models::collaboration::{
     CollaborationEvent, CollaborationPresence, CommentEntityTypeCount,
-    CommentStats, CreateEntityComment, DigestComment, DigestContent, DigestMention,
+    CommentStats, CreateEntityComment, DigestCandidate, DigestComment, DigestContent, DigestMention,
     DigestNotification, DigestTask, EmailDigest, EntityComment, EntityCommentWithUser,
     ListCommentsQuery, MentionInfo, NotificationPreferences, PresenceInfo, UpdateEntityComment,
     UpdateNotificationPreferences, UpdatePresence, UserSearchResult, WebSocketSession,
     COMMENTABLE_ENTITY_TYPES,
 };
+use crate::services::chat_delivery::{
+    get_teams_token, lookup_mattermost_user_by_email, MentionMessage, MENTION_NOTIFICATION_TYPE,
+};
 use crate::services::notification::{CreateNotification, NotificationService};
 use crate::utils::{AppError, AppResult};
 use chrono::{DateTime, Duration, Utc};
@@ -272,6 +275,25 @@ impl CollaborationService {
         self.invalidate_comment_caches(org_id, entity_type, entity_id)
             .await;
 
+        // Enqueue the mention for durable delivery to the org's connected chat
+        // workspaces. Enqueue failures must not fail the comment write, so errors
+        // are logged and swallowed; the queue worker handles retries from there.
+        if !mentioned_user_ids.is_empty() {
+            if let Err(e) = self
+                .enqueue_chat_mentions(
+                    org_id,
+                    user_id,
+                    entity_type,
+                    entity_id,
+                    &input.content,
+                    &mentioned_user_ids,
+                )
+                .await
+            {
+                tracing::warn!(error = %e, "Failed to enqueue chat mention notifications");
+            }
+        }
+
         // Return enriched comment
         let enriched = self.enrich_comments(vec![comment]).await?;
         enriched
@@ -280,6 +302,387 @@ impl CollaborationService {
             .ok_or_else(|| AppError::InternalServerError("Failed to enrich comment".to_string()))
     }
 
+    /// Enqueue mention notifications for durable delivery to the org's connected
+    /// Slack workspace, Teams tenant, and Mattermost server.
+    ///
+    /// Mentioned users who have a mapped Slack or Mattermost identity are DM'd
+    /// individually; any remaining recipients without a mapping fall back to a
+    /// single post in the provider's default channel. A recipient who disabled
+    /// the `comment_mention` type, or the corresponding provider, suppresses
+    /// that provider's delivery. The commenter never notifies themselves about
+    /// their own mention.
+    async fn enqueue_chat_mentions(
+        &self,
+        org_id: Uuid,
+        commenter_user_id: Uuid,
+        entity_type: &str,
+        entity_id: Uuid,
+        content: &str,
+        mentioned_user_ids: &[Uuid],
+    ) -> AppResult<()> {
+        // Partition the eligible recipients (excluding the commenter) into those
+        // reachable by DM on each provider, those needing the shared-channel
+        // fallback, and whether any want Teams.
+        let mut slack_dm_user_ids: Vec<String> = Vec::new();
+        let mut slack_needs_channel = false;
+        let mut mattermost_dm_user_ids: Vec<String> = Vec::new();
+        let mut mattermost_needs_channel = false;
+        let mut want_teams = false;
+        for mentioned_user_id in mentioned_user_ids {
+            if *mentioned_user_id == commenter_user_id {
+                continue;
+            }
+            let prefs = self
+                .get_notification_preferences(org_id, *mentioned_user_id)
+                .await?;
+            if !prefs.is_type_enabled(MENTION_NOTIFICATION_TYPE) {
+                continue;
+            }
+            want_teams |= prefs.teams_enabled;
+
+            if prefs.slack_enabled {
+                match self.lookup_slack_user(org_id, *mentioned_user_id).await? {
+                    Some(slack_user_id) => slack_dm_user_ids.push(slack_user_id),
+                    None => slack_needs_channel = true,
+                }
+            }
+
+            if prefs.mattermost_enabled {
+                match self
+                    .lookup_mattermost_user(org_id, *mentioned_user_id)
+                    .await?
+                {
+                    Some(mattermost_user_id) => mattermost_dm_user_ids.push(mattermost_user_id),
+                    None => mattermost_needs_channel = true,
+                }
+            }
+        }
+
+        let want_slack = !slack_dm_user_ids.is_empty() || slack_needs_channel;
+        let want_mattermost = !mattermost_dm_user_ids.is_empty() || mattermost_needs_channel;
+        if !want_slack && !want_teams && !want_mattermost {
+            return Ok(());
+        }
+
+        let commenter_name = sqlx::query_scalar::<_, String>(
+            "SELECT name FROM users WHERE id = $1 AND organization_id = $2",
+        )
+        .bind(commenter_user_id)
+        .bind(org_id)
+        .fetch_optional(&self.db)
+        .await?
+        .unwrap_or_else(|| "Someone".to_string());
+
+        let message = MentionMessage {
+            entity_type: entity_type.to_string(),
+            entity_id,
+            excerpt: content.chars().take(200).collect(),
+            commenter_name,
+        };
+        let payload = serde_json::json!({ "text": message.render() });
+
+        // Direct messages to mapped recipients.
+        for slack_user_id in &slack_dm_user_ids {
+            self.enqueue_message(org_id, "slack_dm", slack_user_id, None, &payload)
+                .await?;
+        }
+
+        // Shared-channel fallback for Slack-enabled recipients without a mapping.
+        if slack_needs_channel {
+            if let Some(channel) = sqlx::query_scalar::<_, Option<String>>(
+                r#"
+                SELECT default_channel_name
+                FROM slack_workspaces
+                WHERE organization_id = $1 AND status = 'active'
+                ORDER BY last_activity_at DESC NULLS LAST, created_at DESC
+                LIMIT 1
+                "#,
+            )
+            .bind(org_id)
+            .fetch_optional(&self.db)
+            .await?
+            .flatten()
+            .filter(|c| !c.is_empty())
+            {
+                self.enqueue_message(org_id, "slack", &channel, None, &payload)
+                    .await?;
+            }
+        }
+
+        if want_teams {
+            if let Some((team_id, channel_id)) =
+                sqlx::query_as::<_, (Option<String>, Option<String>)>(
+                    r#"
+                SELECT default_team_id, default_channel_id
+                FROM teams_tenants
+                WHERE organization_id = $1 AND status = 'active'
+                ORDER BY last_activity_at DESC NULLS LAST, created_at DESC
+                LIMIT 1
+                "#,
+                )
+                .bind(org_id)
+                .fetch_optional(&self.db)
+                .await?
+            {
+                if let (Some(team_id), Some(channel_id)) = (team_id, channel_id) {
+                    self.enqueue_message(org_id, "teams", &channel_id, Some(&team_id), &payload)
+                        .await?;
+                }
+            }
+        }
+
+        // Direct messages to mapped Mattermost recipients.
+        for mattermost_user_id in &mattermost_dm_user_ids {
+            self.enqueue_message(org_id, "mattermost_dm", mattermost_user_id, None, &payload)
+                .await?;
+        }
+
+        // Shared-channel fallback for Mattermost-enabled recipients without a mapping.
+        if mattermost_needs_channel {
+            if let Some(channel) = sqlx::query_scalar::<_, Option<String>>(
+                r#"
+                SELECT default_channel_id
+                FROM mattermost_servers
+                WHERE organization_id = $1 AND status = 'active'
+                ORDER BY last_activity_at DESC NULLS LAST, created_at DESC
+                LIMIT 1
+                "#,
+            )
+            .bind(org_id)
+            .fetch_optional(&self.db)
+            .await?
+            .flatten()
+            .filter(|c| !c.is_empty())
+            {
+                self.enqueue_message(org_id, "mattermost", &channel, None, &payload)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Return a usable Teams access token for the org, refreshing it if it is at
+    /// or near expiry. All Teams API callers should use this so the refresh is
+    /// transparent.
+    pub async fn get_teams_token(&self, org_id: Uuid) -> AppResult<String> {
+        get_teams_token(&self.db, org_id).await
+    }
+
+    /// Look up a user's mapped Slack identity, if any.
+    async fn lookup_slack_user(&self, org_id: Uuid, user_id: Uuid) -> AppResult<Option<String>> {
+        let slack_user_id = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT slack_user_id
+            FROM slack_user_connections
+            WHERE organization_id = $1 AND user_id = $2
+            ORDER BY connected_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(org_id)
+        .bind(user_id)
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(slack_user_id)
+    }
+
+    /// Rebuild the Slack identity mapping for a workspace by looking each org
+    /// user up by email via `users.lookupByEmail`. Returns the number of users
+    /// successfully mapped.
+    pub async fn resync_slack_users(&self, org_id: Uuid, workspace_id: Uuid) -> AppResult<usize> {
+        let token = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT access_token
+            FROM slack_workspaces
+            WHERE id = $1 AND organization_id = $2 AND status = 'active'
+            "#,
+        )
+        .bind(workspace_id)
+        .bind(org_id)
+        .fetch_optional(&self.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("No active workspace".to_string()))?;
+
+        let users = sqlx::query_as::<_, (Uuid, String)>(
+            "SELECT id, email FROM users WHERE organization_id = $1 AND email IS NOT NULL",
+        )
+        .bind(org_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        let client = reqwest::Client::new();
+        let mut mapped = 0;
+
+        for (user_id, email) in users {
+            let info: serde_json::Value = match client
+                .get("https://slack.com/api/users.lookupByEmail")
+                .bearer_auth(&token)
+                .query(&[("email", email.as_str())])
+                .send()
+                .await
+            {
+                Ok(resp) => match resp.json().await {
+                    Ok(json) => json,
+                    Err(_) => continue,
+                },
+                Err(_) => continue,
+            };
+
+            if !info.get("ok").and_then(|v| v.as_bool()).unwrap_or(false) {
+                continue;
+            }
+
+            let slack_user = match info.get("user") {
+                Some(u) => u,
+                None => continue,
+            };
+            let slack_user_id = match slack_user.get("id").and_then(|v| v.as_str()) {
+                Some(id) => id,
+                None => continue,
+            };
+            let slack_username = slack_user
+                .get("name")
+                .and_then(|v| v.as_str())
+                .or_else(|| {
+                    slack_user
+                        .get("profile")
+                        .and_then(|p| p.get("display_name"))
+                        .and_then(|v| v.as_str())
+                });
+
+            sqlx::query(
+                r#"
+                INSERT INTO slack_user_connections
+                    (organization_id, user_id, workspace_id, slack_user_id, slack_username)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (organization_id, user_id, workspace_id) DO UPDATE SET
+                    slack_user_id = EXCLUDED.slack_user_id,
+                    slack_username = EXCLUDED.slack_username,
+                    connected_at = NOW()
+                "#,
+            )
+            .bind(org_id)
+            .bind(user_id)
+            .bind(workspace_id)
+            .bind(slack_user_id)
+            .bind(slack_username)
+            .execute(&self.db)
+            .await?;
+
+            mapped += 1;
+        }
+
+        Ok(mapped)
+    }
+
+    /// Look up a user's mapped Mattermost identity, if any.
+    async fn lookup_mattermost_user(&self, org_id: Uuid, user_id: Uuid) -> AppResult<Option<String>> {
+        let mattermost_user_id = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT mattermost_user_id
+            FROM mattermost_user_connections
+            WHERE organization_id = $1 AND user_id = $2
+            ORDER BY connected_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(org_id)
+        .bind(user_id)
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(mattermost_user_id)
+    }
+
+    /// Rebuild the Mattermost identity mapping for a server by looking each org
+    /// user up by email via `GET /api/v4/users/email/{email}`. Returns the
+    /// number of users successfully mapped.
+    pub async fn resync_mattermost_users(&self, org_id: Uuid, server_id: Uuid) -> AppResult<usize> {
+        let (base_url, token) = sqlx::query_as::<_, (String, String)>(
+            r#"
+            SELECT base_url, access_token
+            FROM mattermost_servers
+            WHERE id = $1 AND organization_id = $2 AND status = 'active'
+            "#,
+        )
+        .bind(server_id)
+        .bind(org_id)
+        .fetch_optional(&self.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("No active Mattermost server".to_string()))?;
+
+        let users = sqlx::query_as::<_, (Uuid, String)>(
+            "SELECT id, email FROM users WHERE organization_id = $1 AND email IS NOT NULL",
+        )
+        .bind(org_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        let mut mapped = 0;
+
+        for (user_id, email) in users {
+            let found = match lookup_mattermost_user_by_email(&base_url, &token, &email).await {
+                Ok(found) => found,
+                Err(_) => continue,
+            };
+            let (mattermost_user_id, mattermost_username) = match found {
+                Some((id, username)) => (id, username),
+                None => continue,
+            };
+
+            sqlx::query(
+                r#"
+                INSERT INTO mattermost_user_connections
+                    (organization_id, user_id, server_id, mattermost_user_id, mattermost_username)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (organization_id, user_id, server_id) DO UPDATE SET
+                    mattermost_user_id = EXCLUDED.mattermost_user_id,
+                    mattermost_username = EXCLUDED.mattermost_username,
+                    connected_at = NOW()
+                "#,
+            )
+            .bind(org_id)
+            .bind(user_id)
+            .bind(server_id)
+            .bind(&mattermost_user_id)
+            .bind(&mattermost_username)
+            .execute(&self.db)
+            .await?;
+
+            mapped += 1;
+        }
+
+        Ok(mapped)
+    }
+
+    /// Insert a single row into the durable outbound `notification_queue`.
+    async fn enqueue_message(
+        &self,
+        org_id: Uuid,
+        target_kind: &str,
+        channel: &str,
+        thread_ref: Option<&str>,
+        payload: &serde_json::Value,
+    ) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO notification_queue (organization_id, target_kind, channel, thread_ref, payload)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(org_id)
+        .bind(target_kind)
+        .bind(channel)
+        .bind(thread_ref)
+        .bind(payload)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
     /// Update a comment
     pub async fn update_comment(
         &self,
@@ -655,7 +1058,7 @@ impl CollaborationService {
         let prefs = sqlx::query_as::<_, NotificationPreferences>(
             r#"
             SELECT id, organization_id, user_id, in_app_enabled, email_enabled,
-                   slack_enabled, teams_enabled, enabled_types, email_digest_enabled,
+                   slack_enabled, teams_enabled, mattermost_enabled, enabled_types, email_digest_enabled,
                    email_digest_frequency, email_digest_day_of_week, email_digest_hour,
                    quiet_hours_enabled, quiet_hours_start, quiet_hours_end, quiet_hours_timezone,
                    created_at, updated_at
@@ -678,7 +1081,7 @@ impl CollaborationService {
             INSERT INTO notification_preferences (organization_id, user_id)
             VALUES ($1, $2)
             RETURNING id, organization_id, user_id, in_app_enabled, email_enabled,
-                      slack_enabled, teams_enabled, enabled_types, email_digest_enabled,
+                      slack_enabled, teams_enabled, mattermost_enabled, enabled_types, email_digest_enabled,
                       email_digest_frequency, email_digest_day_of_week, email_digest_hour,
                       quiet_hours_enabled, quiet_hours_start, quiet_hours_end, quiet_hours_timezone,
                       created_at, updated_at
@@ -710,19 +1113,20 @@ impl CollaborationService {
                 email_enabled = COALESCE($4, email_enabled),
                 slack_enabled = COALESCE($5, slack_enabled),
                 teams_enabled = COALESCE($6, teams_enabled),
-                enabled_types = COALESCE($7, enabled_types),
-                email_digest_enabled = COALESCE($8, email_digest_enabled),
-                email_digest_frequency = COALESCE($9, email_digest_frequency),
-                email_digest_day_of_week = COALESCE($10, email_digest_day_of_week),
-                email_digest_hour = COALESCE($11, email_digest_hour),
-                quiet_hours_enabled = COALESCE($12, quiet_hours_enabled),
-                quiet_hours_start = COALESCE($13, quiet_hours_start),
-                quiet_hours_end = COALESCE($14, quiet_hours_end),
-                quiet_hours_timezone = COALESCE($15, quiet_hours_timezone),
+                mattermost_enabled = COALESCE($7, mattermost_enabled),
+                enabled_types = COALESCE($8, enabled_types),
+                email_digest_enabled = COALESCE($9, email_digest_enabled),
+                email_digest_frequency = COALESCE($10, email_digest_frequency),
+                email_digest_day_of_week = COALESCE($11, email_digest_day_of_week),
+                email_digest_hour = COALESCE($12, email_digest_hour),
+                quiet_hours_enabled = COALESCE($13, quiet_hours_enabled),
+                quiet_hours_start = COALESCE($14, quiet_hours_start),
+                quiet_hours_end = COALESCE($15, quiet_hours_end),
+                quiet_hours_timezone = COALESCE($16, quiet_hours_timezone),
                 updated_at = NOW()
             WHERE organization_id = $1 AND user_id = $2
             RETURNING id, organization_id, user_id, in_app_enabled, email_enabled,
-                      slack_enabled, teams_enabled, enabled_types, email_digest_enabled,
+                      slack_enabled, teams_enabled, mattermost_enabled, enabled_types, email_digest_enabled,
                       email_digest_frequency, email_digest_day_of_week, email_digest_hour,
                       quiet_hours_enabled, quiet_hours_start, quiet_hours_end, quiet_hours_timezone,
                       created_at, updated_at
@@ -734,6 +1138,7 @@ impl CollaborationService {
         .bind(input.email_enabled)
         .bind(input.slack_enabled)
         .bind(input.teams_enabled)
+        .bind(input.mattermost_enabled)
         .bind(&input.enabled_types)
         .bind(input.email_digest_enabled)
         .bind(&input.email_digest_frequency)
@@ -1056,6 +1461,68 @@ impl CollaborationService {
         Ok(users)
     }
 
+    /// Candidates for scheduled digest delivery, with the scheduling inputs the
+    /// worker needs to decide whether a given tick crosses the user's local send
+    /// hour.
+    pub async fn get_digest_candidates(
+        &self,
+        digest_type: &str,
+    ) -> AppResult<Vec<DigestCandidate>> {
+        let candidates = sqlx::query_as::<_, DigestCandidate>(
+            r#"
+            SELECT
+                np.organization_id,
+                np.user_id,
+                COALESCE(np.email_digest_hour, 9) AS send_hour,
+                np.email_digest_day_of_week AS send_day_of_week,
+                COALESCE(np.tz_offset_seconds, 0) AS tz_offset_seconds,
+                (
+                    SELECT MAX(ed.created_at)
+                    FROM email_digests ed
+                    WHERE ed.user_id = np.user_id AND ed.digest_type = $1
+                ) AS last_digest_at
+            FROM notification_preferences np
+            JOIN users u ON np.user_id = u.id
+            WHERE np.email_digest_enabled = TRUE
+              AND np.email_digest_frequency = $1
+            "#,
+        )
+        .bind(digest_type)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(candidates)
+    }
+
+    /// Record a user's timezone (seconds east of UTC plus a human label),
+    /// typically backfilled from a connected Slack profile.
+    pub async fn update_user_timezone(
+        &self,
+        org_id: Uuid,
+        user_id: Uuid,
+        tz_offset_seconds: i32,
+        tz_label: Option<&str>,
+    ) -> AppResult<()> {
+        // Ensure a preferences row exists before updating it.
+        let _ = self.get_notification_preferences(org_id, user_id).await?;
+
+        sqlx::query(
+            r#"
+            UPDATE notification_preferences
+            SET tz_offset_seconds = $3, tz_label = $4, updated_at = NOW()
+            WHERE organization_id = $1 AND user_id = $2
+            "#,
+        )
+        .bind(org_id)
+        .bind(user_id)
+        .bind(tz_offset_seconds)
+        .bind(tz_label)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
     /// Create email digest content for a user
     pub async fn create_digest_content(
         &self,
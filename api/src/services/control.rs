@@ -1,9 +1,11 @@
 use crate::cache::{org_cache_key, CacheClient};
+use crate::integrations::provider::CollectedEvidence;
 use crate::models::{
-    Control, ControlRequirementMapping, ControlStats, ControlTest, ControlTestResult,
-    ControlWithMappings, CreateControl, CreateControlTest, CreateTestResult,
+    AssertionRemediationTrend, Control, ControlRequirementMapping, ControlStats, ControlTest,
+    ControlTestResult, ControlWithMappings, CreateControl, CreateControlTest, CreateTestResult,
     ListControlsQuery, MappedRequirement, UpdateControl,
 };
+use crate::services::control_assertion::{self, ControlAssertion};
 use crate::utils::{AppError, AppResult};
 use sqlx::PgPool;
 use std::time::Duration;
@@ -399,7 +401,7 @@ impl ControlService {
         let tests = sqlx::query_as::<_, ControlTest>(
             r#"
             SELECT id, control_id, name, description, test_type, automation_config,
-                   frequency, next_due_at, created_at
+                   frequency, next_due_at, assertion, created_at
             FROM control_tests
             WHERE control_id = $1
             ORDER BY name ASC
@@ -427,10 +429,10 @@ impl ControlService {
         let test = sqlx::query_as::<_, ControlTest>(
             r#"
             INSERT INTO control_tests (control_id, name, description, test_type,
-                                       automation_config, frequency, next_due_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+                                       automation_config, frequency, next_due_at, assertion)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
             RETURNING id, control_id, name, description, test_type, automation_config,
-                      frequency, next_due_at, created_at
+                      frequency, next_due_at, assertion, created_at
             "#,
         )
         .bind(control_id)
@@ -440,6 +442,7 @@ impl ControlService {
         .bind(&input.automation_config)
         .bind(&input.frequency)
         .bind(input.next_due_at)
+        .bind(&input.assertion)
         .fetch_one(&self.db)
         .await?;
 
@@ -464,7 +467,8 @@ impl ControlService {
             r#"
             INSERT INTO control_test_results (control_test_id, performed_by, status, notes, evidence_ids)
             VALUES ($1, $2, $3, $4, $5)
-            RETURNING id, control_test_id, performed_by, performed_at, status, notes, evidence_ids, created_at
+            RETURNING id, control_test_id, performed_by, performed_at, status, notes, evidence_ids,
+                      automated, offending_resources, created_at
             "#,
         )
         .bind(test_id)
@@ -480,6 +484,127 @@ impl ControlService {
         Ok(result)
     }
 
+    // ==================== Continuous Control Monitoring ====================
+
+    /// Evaluate every control test whose `assertion` targets evidence from
+    /// `integration_type` (bound via `automation_config.integration_type`)
+    /// against the freshest collected evidence from a completed sync,
+    /// writing a `ControlTestResult` for each one evaluated. Tests with no
+    /// assertion, or whose assertion targets evidence this sync didn't
+    /// collect, are left untouched.
+    pub async fn evaluate_assertions(
+        &self,
+        org_id: Uuid,
+        integration_type: &str,
+        evidence: &[CollectedEvidence],
+    ) -> AppResult<Vec<ControlTestResult>> {
+        let tests = sqlx::query_as::<_, ControlTest>(
+            r#"
+            SELECT ct.id, ct.control_id, ct.name, ct.description, ct.test_type,
+                   ct.automation_config, ct.frequency, ct.next_due_at, ct.assertion, ct.created_at
+            FROM control_tests ct
+            JOIN controls c ON c.id = ct.control_id
+            WHERE c.organization_id = $1
+              AND ct.assertion IS NOT NULL
+              AND ct.automation_config ->> 'integration_type' = $2
+            "#,
+        )
+        .bind(org_id)
+        .bind(integration_type)
+        .fetch_all(&self.db)
+        .await?;
+
+        let mut results = Vec::new();
+        for test in tests {
+            let Some(assertion_value) = test.assertion.clone() else {
+                continue;
+            };
+            let assertion: ControlAssertion = match serde_json::from_value(assertion_value) {
+                Ok(a) => a,
+                Err(e) => {
+                    tracing::warn!("Control test {} has an unparsable assertion: {}", test.id, e);
+                    continue;
+                }
+            };
+
+            let Some(evaluation) = control_assertion::evaluate_first_match(&assertion, evidence) else {
+                continue;
+            };
+
+            let status = if evaluation.passed { "pass" } else { "fail" };
+            let result = sqlx::query_as::<_, ControlTestResult>(
+                r#"
+                INSERT INTO control_test_results
+                    (control_test_id, performed_by, status, notes, offending_resources, automated)
+                VALUES ($1, NULL, $2, $3, $4, TRUE)
+                RETURNING id, control_test_id, performed_by, performed_at, status, notes,
+                          evidence_ids, automated, offending_resources, created_at
+                "#,
+            )
+            .bind(test.id)
+            .bind(status)
+            .bind(format!(
+                "Automatically evaluated against {} evidence ({})",
+                assertion.source, assertion.source_reference
+            ))
+            .bind(&evaluation.offending_resources)
+            .fetch_one(&self.db)
+            .await?;
+
+            tracing::info!(
+                "Automated assertion for control test {} evaluated to {}",
+                test.id,
+                status
+            );
+
+            self.invalidate_control_cache(org_id, test.control_id).await?;
+            results.push(result);
+        }
+
+        Ok(results)
+    }
+
+    /// Time-to-remediation trend per control test with an assertion: how
+    /// many times an automated failure was followed by an automated pass,
+    /// and the average time that took.
+    pub async fn get_assertion_remediation_trends(
+        &self,
+        org_id: Uuid,
+    ) -> AppResult<Vec<AssertionRemediationTrend>> {
+        let trends = sqlx::query_as::<_, AssertionRemediationTrend>(
+            r#"
+            WITH ordered AS (
+                SELECT
+                    r.control_test_id,
+                    r.status,
+                    r.performed_at,
+                    LAG(r.status) OVER (PARTITION BY r.control_test_id ORDER BY r.performed_at) AS prev_status,
+                    LAG(r.performed_at) OVER (PARTITION BY r.control_test_id ORDER BY r.performed_at) AS prev_performed_at
+                FROM control_test_results r
+                JOIN control_tests ct ON ct.id = r.control_test_id
+                JOIN controls c ON c.id = ct.control_id
+                WHERE r.automated = TRUE AND c.organization_id = $1
+            )
+            SELECT
+                ct.id AS control_test_id,
+                ct.control_id,
+                ct.name AS test_name,
+                COUNT(o.*) AS remediation_count,
+                AVG(EXTRACT(EPOCH FROM (o.performed_at - o.prev_performed_at))) AS avg_remediation_seconds
+            FROM ordered o
+            JOIN control_tests ct ON ct.id = o.control_test_id
+            WHERE o.status = 'pass' AND o.prev_status = 'fail'
+            GROUP BY ct.id, ct.control_id, ct.name
+            ORDER BY remediation_count DESC
+            "#,
+        )
+        .bind(org_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(trends)
+    }
+
     // ==================== Statistics ====================
 
     /// Get control statistics for dashboard
@@ -0,0 +1,157 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::integrations::provider::CollectedEvidence;
+
+/// A structured expectation about cloud configuration, attached to a
+/// `ControlTest.assertion` and evaluated automatically against the freshest
+/// [`CollectedEvidence`] a linked integration sync produces, instead of
+/// requiring a human to run the test.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControlAssertion {
+    /// Evidence source this assertion is evaluated against (e.g. "aws").
+    pub source: String,
+    /// Evidence `source_reference` identifying which collected evidence
+    /// record carries the data to check (e.g. "iam:privilege-escalation").
+    pub source_reference: String,
+    /// Dot-separated path into the evidence's `data` payload, e.g.
+    /// "escalation_capable_principals".
+    pub path: String,
+    /// The rule to apply to the value found at `path`.
+    pub rule: AssertionRule,
+}
+
+/// Rules a [`ControlAssertion`] can apply to the value at its `path`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum AssertionRule {
+    /// Pass only if the array at `path` is empty (e.g. "no IAM principal may
+    /// hold iam:*").
+    ArrayEmpty,
+    /// Pass only if the array at `path` has at most `max` entries.
+    ArrayMaxLen { max: usize },
+    /// Pass only if the boolean at `path` equals `expected`.
+    BoolEquals { expected: bool },
+}
+
+/// Result of evaluating a [`ControlAssertion`] against one evidence record.
+pub struct AssertionEvaluation {
+    pub passed: bool,
+    /// The offending resources (e.g. the principals/buckets that violated
+    /// the assertion), recorded alongside the pass/fail so a reviewer can
+    /// see what needs remediation without re-running the sync.
+    pub offending_resources: Value,
+}
+
+fn value_at_path<'a>(data: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(data, |value, segment| value.get(segment))
+}
+
+/// Evaluate `assertion` against one collected-evidence record, returning
+/// `None` if the record isn't the one the assertion targets.
+pub fn evaluate(assertion: &ControlAssertion, evidence: &CollectedEvidence) -> Option<AssertionEvaluation> {
+    if evidence.source != assertion.source
+        || evidence.source_reference.as_deref() != Some(assertion.source_reference.as_str())
+    {
+        return None;
+    }
+
+    let value = value_at_path(&evidence.data, &assertion.path)?;
+
+    Some(match &assertion.rule {
+        AssertionRule::ArrayEmpty => {
+            let offenders = value.as_array().cloned().unwrap_or_default();
+            AssertionEvaluation {
+                passed: offenders.is_empty(),
+                offending_resources: Value::Array(offenders),
+            }
+        }
+        AssertionRule::ArrayMaxLen { max } => {
+            let offenders = value.as_array().cloned().unwrap_or_default();
+            AssertionEvaluation {
+                passed: offenders.len() <= *max,
+                offending_resources: Value::Array(offenders),
+            }
+        }
+        AssertionRule::BoolEquals { expected } => {
+            let actual = value.as_bool().unwrap_or(false);
+            AssertionEvaluation {
+                passed: actual == *expected,
+                offending_resources: serde_json::json!({ "actual": actual }),
+            }
+        }
+    })
+}
+
+/// Evaluate `assertion` against the first matching record in `evidence`,
+/// ignoring records the assertion doesn't target.
+pub fn evaluate_first_match(
+    assertion: &ControlAssertion,
+    evidence: &[CollectedEvidence],
+) -> Option<AssertionEvaluation> {
+    evidence.iter().find_map(|e| evaluate(assertion, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evidence(source: &str, source_reference: &str, data: Value) -> CollectedEvidence {
+        CollectedEvidence {
+            title: "test".to_string(),
+            description: None,
+            evidence_type: "automated".to_string(),
+            source: source.to_string(),
+            source_reference: Some(source_reference.to_string()),
+            data,
+            control_codes: vec![],
+        }
+    }
+
+    #[test]
+    fn array_empty_passes_when_empty() {
+        let assertion = ControlAssertion {
+            source: "aws".to_string(),
+            source_reference: "iam:privilege-escalation".to_string(),
+            path: "escalation_capable_principals".to_string(),
+            rule: AssertionRule::ArrayEmpty,
+        };
+        let ev = evidence(
+            "aws",
+            "iam:privilege-escalation",
+            serde_json::json!({ "escalation_capable_principals": [] }),
+        );
+        let result = evaluate(&assertion, &ev).unwrap();
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn array_empty_fails_and_lists_offenders() {
+        let assertion = ControlAssertion {
+            source: "aws".to_string(),
+            source_reference: "iam:privilege-escalation".to_string(),
+            path: "escalation_capable_principals".to_string(),
+            rule: AssertionRule::ArrayEmpty,
+        };
+        let ev = evidence(
+            "aws",
+            "iam:privilege-escalation",
+            serde_json::json!({ "escalation_capable_principals": [{"arn": "a"}] }),
+        );
+        let result = evaluate(&assertion, &ev).unwrap();
+        assert!(!result.passed);
+        assert_eq!(result.offending_resources.as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn evaluate_ignores_non_matching_evidence() {
+        let assertion = ControlAssertion {
+            source: "aws".to_string(),
+            source_reference: "iam:privilege-escalation".to_string(),
+            path: "escalation_capable_principals".to_string(),
+            rule: AssertionRule::ArrayEmpty,
+        };
+        let ev = evidence("aws", "s3:public-buckets", serde_json::json!({}));
+        assert!(evaluate(&assertion, &ev).is_none());
+    }
+}
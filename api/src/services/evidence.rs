@@ -1,13 +1,28 @@
 use crate::cache::{org_cache_key, CacheClient};
+use crate::services::evidence_metrics;
 use crate::models::{
-    CreateEvidence, Evidence, EvidenceControlLink, EvidenceStats, EvidenceWithLinks,
-    LinkedControl, ListEvidenceQuery, SourceCount, TypeCount, UpdateEvidence,
+    BatchOpResult, CreateEvidence, Evidence, EvidenceBatchOp, EvidenceControlLink,
+    EvidenceSearchHit, EvidenceSearchQuery, EvidenceSearchResults, EvidenceStats, EvidenceVersion,
+    EvidenceWithLinks, LinkedControl, ListEvidenceQuery, PagedEvidence, SearchFacet, SourceCount,
+    TypeCount, UpdateEvidence,
 };
 use crate::utils::{AppError, AppResult};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use sqlx::PgPool;
 use std::time::Duration;
 use uuid::Uuid;
 
+/// Default trigram similarity cutoff for fuzzy title matches.
+const DEFAULT_MIN_SIMILARITY: f64 = 0.3;
+
+/// Row returned by the ranked evidence search.
+#[derive(sqlx::FromRow)]
+struct SearchRow {
+    #[sqlx(flatten)]
+    evidence: Evidence,
+    score: f64,
+}
+
 const CACHE_TTL: Duration = Duration::from_secs(1800); // 30 minutes
 const CACHE_PREFIX_EVIDENCE: &str = "evidence";
 const CACHE_PREFIX_EVIDENCE_STATS: &str = "evidence:stats";
@@ -31,6 +46,7 @@ impl EvidenceService {
         org_id: Uuid,
         query: ListEvidenceQuery,
     ) -> AppResult<Vec<EvidenceWithLinks>> {
+        let started = std::time::Instant::now();
         let limit = query.limit.unwrap_or(100).min(500);
         let offset = query.offset.unwrap_or(0);
 
@@ -40,7 +56,8 @@ impl EvidenceService {
                 r#"
                 SELECT DISTINCT e.id, e.organization_id, e.title, e.description, e.evidence_type,
                        e.source, e.source_reference, e.file_path, e.file_size, e.mime_type,
-                       e.collected_at, e.valid_from, e.valid_until, e.uploaded_by, e.created_at
+                       e.collected_at, e.valid_from, e.valid_until, e.uploaded_by, e.created_at,
+                       e.version
                 FROM evidence e
                 JOIN evidence_control_links ecl ON e.id = ecl.evidence_id
                 WHERE e.organization_id = $1
@@ -67,7 +84,7 @@ impl EvidenceService {
                 r#"
                 SELECT id, organization_id, title, description, evidence_type, source,
                        source_reference, file_path, file_size, mime_type, collected_at,
-                       valid_from, valid_until, uploaded_by, created_at
+                       valid_from, valid_until, uploaded_by, created_at, version
                 FROM evidence
                 WHERE organization_id = $1
                   AND (LOWER(title) LIKE $2 OR LOWER(description) LIKE $2)
@@ -92,7 +109,7 @@ impl EvidenceService {
                 r#"
                 SELECT id, organization_id, title, description, evidence_type, source,
                        source_reference, file_path, file_size, mime_type, collected_at,
-                       valid_from, valid_until, uploaded_by, created_at
+                       valid_from, valid_until, uploaded_by, created_at, version
                 FROM evidence
                 WHERE organization_id = $1
                   AND ($2::text IS NULL OR evidence_type = $2)
@@ -114,26 +131,86 @@ impl EvidenceService {
 
         // Get linked control counts in one query
         let evidence_ids: Vec<Uuid> = evidence.iter().map(|e| e.id).collect();
+        let count_map = self.linked_control_counts(&evidence_ids).await?;
 
-        let counts: Vec<(Uuid, i64)> = if !evidence_ids.is_empty() {
-            sqlx::query_as(
-                r#"
-                SELECT evidence_id, COUNT(*) as count
-                FROM evidence_control_links
-                WHERE evidence_id = ANY($1)
-                GROUP BY evidence_id
-                "#,
-            )
-            .bind(&evidence_ids)
-            .fetch_all(&self.db)
-            .await?
+        let result: Vec<EvidenceWithLinks> = evidence
+            .into_iter()
+            .map(|ev| {
+                let count = count_map.get(&ev.id).copied().unwrap_or(0);
+                EvidenceWithLinks {
+                    evidence: ev,
+                    linked_control_count: count,
+                    linked_controls: None,
+                }
+            })
+            .collect();
+
+        evidence_metrics::record_method("list_evidence", started);
+        Ok(result)
+    }
+
+    /// List evidence using keyset (cursor) pagination.
+    ///
+    /// Ordered by `(collected_at, id)` descending, the same order as
+    /// [`list_evidence`], but paged with a `(collected_at, id) < cursor`
+    /// predicate instead of `OFFSET`, so deep pages cost O(page) rather than
+    /// O(offset). The `control_id`/`search` filters from the offset path are not
+    /// supported here; use [`list_evidence`] for those.
+    pub async fn list_evidence_page(
+        &self,
+        org_id: Uuid,
+        query: ListEvidenceQuery,
+    ) -> AppResult<PagedEvidence> {
+        let limit = query.limit.unwrap_or(100).min(500);
+        let cursor = query.after.as_deref().map(Self::decode_cursor).transpose()?;
+        let (cursor_ts, cursor_id) = match cursor {
+            Some((ts, id)) => (Some(ts), Some(id)),
+            None => (None, None),
+        };
+
+        // Fetch one extra row to decide whether a further page exists.
+        let evidence: Vec<Evidence> = sqlx::query_as::<_, Evidence>(
+            r#"
+            SELECT id, organization_id, title, description, evidence_type, source,
+                   source_reference, file_path, file_size, mime_type, collected_at,
+                   valid_from, valid_until, uploaded_by, created_at, version
+            FROM evidence
+            WHERE organization_id = $1
+              AND ($2::text IS NULL OR evidence_type = $2)
+              AND ($3::text IS NULL OR source = $3)
+              AND ($4::bool IS NULL OR ($4 = true AND valid_until < NOW()) OR ($4 = false AND (valid_until IS NULL OR valid_until >= NOW())))
+              AND ($5::timestamptz IS NULL OR (collected_at, id) < ($5, $6))
+            ORDER BY collected_at DESC, id DESC
+            LIMIT $7
+            "#,
+        )
+        .bind(org_id)
+        .bind(&query.evidence_type)
+        .bind(&query.source)
+        .bind(query.expired)
+        .bind(cursor_ts)
+        .bind(cursor_id)
+        .bind(limit + 1)
+        .fetch_all(&self.db)
+        .await?;
+
+        let has_more = evidence.len() as i64 > limit;
+        let mut evidence = evidence;
+        if has_more {
+            evidence.truncate(limit as usize);
+        }
+        let next_cursor = if has_more {
+            evidence
+                .last()
+                .map(|e| Self::encode_cursor(e.collected_at, e.id))
         } else {
-            vec![]
+            None
         };
 
-        let count_map: std::collections::HashMap<Uuid, i64> = counts.into_iter().collect();
+        let evidence_ids: Vec<Uuid> = evidence.iter().map(|e| e.id).collect();
+        let count_map = self.linked_control_counts(&evidence_ids).await?;
 
-        let result: Vec<EvidenceWithLinks> = evidence
+        let items = evidence
             .into_iter()
             .map(|ev| {
                 let count = count_map.get(&ev.id).copied().unwrap_or(0);
@@ -145,24 +222,223 @@ impl EvidenceService {
             })
             .collect();
 
-        Ok(result)
+        Ok(PagedEvidence { items, next_cursor })
+    }
+
+    /// Encode a keyset cursor from a row's ordering key.
+    fn encode_cursor(collected_at: chrono::DateTime<chrono::Utc>, id: Uuid) -> String {
+        let raw = format!("{}|{}", collected_at.to_rfc3339(), id);
+        URL_SAFE_NO_PAD.encode(raw.as_bytes())
+    }
+
+    /// Decode a keyset cursor back into its `(collected_at, id)` ordering key.
+    fn decode_cursor(cursor: &str) -> AppResult<(chrono::DateTime<chrono::Utc>, Uuid)> {
+        let bad = || AppError::BadRequest("Invalid pagination cursor".to_string());
+        let bytes = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| bad())?;
+        let decoded = String::from_utf8(bytes).map_err(|_| bad())?;
+        let (ts, id) = decoded.split_once('|').ok_or_else(bad)?;
+        let collected_at = chrono::DateTime::parse_from_rfc3339(ts)
+            .map_err(|_| bad())?
+            .with_timezone(&chrono::Utc);
+        let id = Uuid::parse_str(id).map_err(|_| bad())?;
+        Ok((collected_at, id))
+    }
+
+    /// Full-text + fuzzy evidence search.
+    ///
+    /// Blends `ts_rank_cd` over the weighted `search_vector` with `pg_trgm`
+    /// title similarity (ranking ranked higher than similarity), so a
+    /// misspelled query like "pentst report" still surfaces "pentest report".
+    /// Facet counts for `evidence_type` and `source` are computed over the full
+    /// matched set, not just the returned page.
+    pub async fn search_evidence(
+        &self,
+        org_id: Uuid,
+        query: EvidenceSearchQuery,
+    ) -> AppResult<EvidenceSearchResults> {
+        let limit = query.limit.unwrap_or(50).min(500);
+        let offset = query.offset.unwrap_or(0);
+        let min_similarity = query.min_similarity.unwrap_or(DEFAULT_MIN_SIMILARITY);
+
+        // Prefix search rewrites the final term into a `to_tsquery` prefix; the
+        // default path uses `websearch_to_tsquery` so operators (quotes, OR)
+        // keep working.
+        let (tsquery_fn, tsquery_input) = if query.prefix {
+            ("to_tsquery('english', $2)", Self::to_prefix_tsquery(&query.query))
+        } else {
+            ("websearch_to_tsquery('english', $2)", query.query.clone())
+        };
+
+        // Shared WHERE predicate for both the page query and the facet queries.
+        let predicate = format!(
+            r#"
+            organization_id = $1
+              AND (
+                    search_vector @@ {tsq}
+                    OR similarity(title, $3) >= $4
+                    OR title ILIKE ('%' || $3 || '%')
+                  )
+              AND ($5::text IS NULL OR evidence_type = $5)
+              AND ($6::text IS NULL OR source = $6)
+              AND ($7::bool IS NULL OR ($7 = true AND valid_until < NOW()) OR ($7 = false AND (valid_until IS NULL OR valid_until >= NOW())))
+            "#,
+            tsq = tsquery_fn
+        );
+
+        let hits_sql = format!(
+            r#"
+            SELECT id, organization_id, title, description, evidence_type, source,
+                   source_reference, file_path, file_size, mime_type, collected_at,
+                   valid_from, valid_until, uploaded_by, created_at, version,
+                   (ts_rank_cd(search_vector, {tsq}) + similarity(title, $3) * 0.3)::double precision AS score
+            FROM evidence
+            WHERE {predicate}
+            ORDER BY score DESC, collected_at DESC, id DESC
+            LIMIT $8 OFFSET $9
+            "#,
+            tsq = tsquery_fn,
+            predicate = predicate
+        );
+
+        let rows: Vec<SearchRow> = sqlx::query_as(&hits_sql)
+            .bind(org_id)
+            .bind(&tsquery_input)
+            .bind(&query.query)
+            .bind(min_similarity)
+            .bind(&query.evidence_type)
+            .bind(&query.source)
+            .bind(query.expired)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.db)
+            .await?;
+
+        // Linked-control counts for the page.
+        let evidence_ids: Vec<Uuid> = rows.iter().map(|r| r.evidence.id).collect();
+        let count_map = self.linked_control_counts(&evidence_ids).await?;
+
+        let hits = rows
+            .into_iter()
+            .map(|r| {
+                let linked_control_count = count_map.get(&r.evidence.id).copied().unwrap_or(0);
+                EvidenceSearchHit {
+                    evidence: r.evidence,
+                    linked_control_count,
+                    score: r.score,
+                }
+            })
+            .collect();
+
+        // Facets over the whole matched set.
+        let facets_by_type = self
+            .search_facets(org_id, &predicate, "evidence_type", &tsquery_input, &query, min_similarity)
+            .await?;
+        let facets_by_source = self
+            .search_facets(org_id, &predicate, "source", &tsquery_input, &query, min_similarity)
+            .await?;
+        let total_matched = facets_by_type.iter().map(|f| f.count).sum();
+
+        Ok(EvidenceSearchResults {
+            hits,
+            total_matched,
+            facets_by_type,
+            facets_by_source,
+        })
+    }
+
+    /// Run a faceted `GROUP BY` over the matched set for one column. The column
+    /// name is a fixed internal string (`evidence_type`/`source`), never user
+    /// input, so interpolating it is safe; all values are bound.
+    async fn search_facets(
+        &self,
+        org_id: Uuid,
+        predicate: &str,
+        column: &str,
+        tsquery_input: &str,
+        query: &EvidenceSearchQuery,
+        min_similarity: f64,
+    ) -> AppResult<Vec<SearchFacet>> {
+        let sql = format!(
+            r#"
+            SELECT {column} AS value, COUNT(*) AS count
+            FROM evidence
+            WHERE {predicate}
+            GROUP BY {column}
+            ORDER BY count DESC
+            "#,
+            column = column,
+            predicate = predicate
+        );
+
+        let facets = sqlx::query_as::<_, SearchFacet>(&sql)
+            .bind(org_id)
+            .bind(tsquery_input)
+            .bind(&query.query)
+            .bind(min_similarity)
+            .bind(&query.evidence_type)
+            .bind(&query.source)
+            .bind(query.expired)
+            .fetch_all(&self.db)
+            .await?;
+
+        Ok(facets)
+    }
+
+    /// Rewrite a free-text query into a `to_tsquery` prefix expression, ANDing
+    /// the sanitized terms and marking the last one as a prefix (`term:*`).
+    fn to_prefix_tsquery(query: &str) -> String {
+        let mut tokens: Vec<String> = query
+            .split_whitespace()
+            .map(|t| t.chars().filter(|c| c.is_alphanumeric()).collect::<String>())
+            .filter(|t| !t.is_empty())
+            .collect();
+        if let Some(last) = tokens.last_mut() {
+            last.push_str(":*");
+        }
+        tokens.join(" & ")
+    }
+
+    /// Linked-control counts for a set of evidence ids, keyed by id.
+    async fn linked_control_counts(
+        &self,
+        evidence_ids: &[Uuid],
+    ) -> AppResult<std::collections::HashMap<Uuid, i64>> {
+        if evidence_ids.is_empty() {
+            return Ok(std::collections::HashMap::new());
+        }
+        let counts: Vec<(Uuid, i64)> = sqlx::query_as(
+            r#"
+            SELECT evidence_id, COUNT(*) as count
+            FROM evidence_control_links
+            WHERE evidence_id = ANY($1)
+            GROUP BY evidence_id
+            "#,
+        )
+        .bind(evidence_ids)
+        .fetch_all(&self.db)
+        .await?;
+        Ok(counts.into_iter().collect())
     }
 
     /// Get a single evidence by ID with linked controls
     pub async fn get_evidence(&self, org_id: Uuid, id: Uuid) -> AppResult<EvidenceWithLinks> {
+        let started = std::time::Instant::now();
         let cache_key = org_cache_key(&org_id.to_string(), CACHE_PREFIX_EVIDENCE, &id.to_string());
 
         // Try cache first
         if let Some(cached) = self.cache.get::<EvidenceWithLinks>(&cache_key).await? {
             tracing::debug!("Cache hit for evidence {}", id);
+            evidence_metrics::record_cache(CACHE_PREFIX_EVIDENCE, true);
+            evidence_metrics::record_method("get_evidence", started);
             return Ok(cached);
         }
+        evidence_metrics::record_cache(CACHE_PREFIX_EVIDENCE, false);
 
         let evidence = sqlx::query_as::<_, Evidence>(
             r#"
             SELECT id, organization_id, title, description, evidence_type, source,
                    source_reference, file_path, file_size, mime_type, collected_at,
-                   valid_from, valid_until, uploaded_by, created_at
+                   valid_from, valid_until, uploaded_by, created_at, version
             FROM evidence
             WHERE id = $1 AND organization_id = $2
             "#,
@@ -198,6 +474,7 @@ impl EvidenceService {
         // Cache the result
         self.cache.set(&cache_key, &result, Some(CACHE_TTL)).await?;
 
+        evidence_metrics::record_method("get_evidence", started);
         Ok(result)
     }
 
@@ -218,7 +495,7 @@ impl EvidenceService {
             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
             RETURNING id, organization_id, title, description, evidence_type, source,
                       source_reference, file_path, file_size, mime_type, collected_at,
-                      valid_from, valid_until, uploaded_by, created_at
+                      valid_from, valid_until, uploaded_by, created_at, version
             "#,
         )
         .bind(org_id)
@@ -244,15 +521,24 @@ impl EvidenceService {
         Ok(evidence)
     }
 
-    /// Update evidence
+    /// Update evidence, archiving the prior state into the version history.
+    ///
+    /// The current head is snapshotted into `evidence_versions` at its existing
+    /// `version` before the head is mutated and its `version` bumped, so history
+    /// is append-only and every past state stays resolvable.
     pub async fn update_evidence(
         &self,
         org_id: Uuid,
         id: Uuid,
         input: UpdateEvidence,
     ) -> AppResult<Evidence> {
-        // Verify evidence exists
-        let _ = self.get_evidence(org_id, id).await?;
+        let mut tx = self.db.begin().await?;
+
+        // Archive the current head (if any) before mutating it.
+        let archived = self.archive_head(&mut tx, org_id, id, None).await?;
+        if !archived {
+            return Err(AppError::NotFound(format!("Evidence not found: {}", id)));
+        }
 
         let evidence = sqlx::query_as::<_, Evidence>(
             r#"
@@ -264,11 +550,12 @@ impl EvidenceService {
                 source = COALESCE($6, source),
                 source_reference = COALESCE($7, source_reference),
                 valid_from = COALESCE($8, valid_from),
-                valid_until = COALESCE($9, valid_until)
+                valid_until = COALESCE($9, valid_until),
+                version = version + 1
             WHERE id = $1 AND organization_id = $2
             RETURNING id, organization_id, title, description, evidence_type, source,
                       source_reference, file_path, file_size, mime_type, collected_at,
-                      valid_from, valid_until, uploaded_by, created_at
+                      valid_from, valid_until, uploaded_by, created_at, version
             "#,
         )
         .bind(id)
@@ -280,9 +567,11 @@ impl EvidenceService {
         .bind(&input.source_reference)
         .bind(input.valid_from)
         .bind(input.valid_until)
-        .fetch_one(&self.db)
+        .fetch_one(&mut *tx)
         .await?;
 
+        tx.commit().await?;
+
         // Invalidate caches
         self.invalidate_evidence_cache(org_id, id).await?;
 
@@ -320,8 +609,10 @@ impl EvidenceService {
         control_ids: Vec<Uuid>,
         user_id: Option<Uuid>,
     ) -> AppResult<Vec<EvidenceControlLink>> {
-        // Verify evidence exists
-        self.get_evidence(org_id, evidence_id).await?;
+        let started = std::time::Instant::now();
+        // Verify evidence exists and capture the head version to pin the links to.
+        let head = self.get_evidence(org_id, evidence_id).await?;
+        let version = head.evidence.version;
 
         let mut tx = self.db.begin().await?;
         let mut links = Vec::new();
@@ -339,14 +630,15 @@ impl EvidenceService {
             if existing.is_none() {
                 let link = sqlx::query_as::<_, EvidenceControlLink>(
                     r#"
-                    INSERT INTO evidence_control_links (evidence_id, control_id, linked_by)
-                    VALUES ($1, $2, $3)
-                    RETURNING id, evidence_id, control_id, control_test_result_id, linked_by, linked_at
+                    INSERT INTO evidence_control_links (evidence_id, control_id, linked_by, evidence_version)
+                    VALUES ($1, $2, $3, $4)
+                    RETURNING id, evidence_id, control_id, control_test_result_id, linked_by, linked_at, evidence_version
                     "#,
                 )
                 .bind(evidence_id)
                 .bind(control_id)
                 .bind(user_id)
+                .bind(version)
                 .fetch_one(&mut *tx)
                 .await?;
 
@@ -365,6 +657,7 @@ impl EvidenceService {
             evidence_id
         );
 
+        evidence_metrics::record_method("link_to_controls", started);
         Ok(links)
     }
 
@@ -375,6 +668,7 @@ impl EvidenceService {
         evidence_id: Uuid,
         control_ids: Vec<Uuid>,
     ) -> AppResult<i64> {
+        let started = std::time::Instant::now();
         // Verify evidence exists
         self.get_evidence(org_id, evidence_id).await?;
 
@@ -397,20 +691,374 @@ impl EvidenceService {
             evidence_id
         );
 
+        evidence_metrics::record_method("unlink_from_controls", started);
         Ok(deleted)
     }
 
+    // ==================== Version History ====================
+
+    /// List every archived prior version of an evidence record, newest first.
+    pub async fn list_evidence_versions(
+        &self,
+        org_id: Uuid,
+        id: Uuid,
+    ) -> AppResult<Vec<EvidenceVersion>> {
+        let versions = sqlx::query_as::<_, EvidenceVersion>(
+            r#"
+            SELECT id, evidence_id, organization_id, version, title, description, evidence_type,
+                   source, source_reference, file_path, file_size, mime_type, collected_at,
+                   valid_from, valid_until, updated_by, updated_at
+            FROM evidence_versions
+            WHERE evidence_id = $1 AND organization_id = $2
+            ORDER BY version DESC
+            "#,
+        )
+        .bind(id)
+        .bind(org_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(versions)
+    }
+
+    /// Fetch one archived version of an evidence record.
+    pub async fn get_evidence_version(
+        &self,
+        org_id: Uuid,
+        id: Uuid,
+        version: i32,
+    ) -> AppResult<EvidenceVersion> {
+        let snapshot = sqlx::query_as::<_, EvidenceVersion>(
+            r#"
+            SELECT id, evidence_id, organization_id, version, title, description, evidence_type,
+                   source, source_reference, file_path, file_size, mime_type, collected_at,
+                   valid_from, valid_until, updated_by, updated_at
+            FROM evidence_versions
+            WHERE evidence_id = $1 AND organization_id = $2 AND version = $3
+            "#,
+        )
+        .bind(id)
+        .bind(org_id)
+        .bind(version)
+        .fetch_optional(&self.db)
+        .await?
+        .ok_or_else(|| {
+            AppError::NotFound(format!("Evidence {} has no version {}", id, version))
+        })?;
+
+        Ok(snapshot)
+    }
+
+    /// Restore an old version as a new head, archiving the current head first.
+    ///
+    /// History is never rewritten: the restore produces a fresh, higher version
+    /// whose content matches the requested snapshot.
+    pub async fn restore_version(
+        &self,
+        org_id: Uuid,
+        id: Uuid,
+        version: i32,
+        user_id: Option<Uuid>,
+    ) -> AppResult<Evidence> {
+        let snapshot = self.get_evidence_version(org_id, id, version).await?;
+
+        let mut tx = self.db.begin().await?;
+
+        let archived = self.archive_head(&mut tx, org_id, id, user_id).await?;
+        if !archived {
+            return Err(AppError::NotFound(format!("Evidence not found: {}", id)));
+        }
+
+        let evidence = sqlx::query_as::<_, Evidence>(
+            r#"
+            UPDATE evidence
+            SET
+                title = $3,
+                description = $4,
+                evidence_type = $5,
+                source = $6,
+                source_reference = $7,
+                valid_from = $8,
+                valid_until = $9,
+                version = version + 1
+            WHERE id = $1 AND organization_id = $2
+            RETURNING id, organization_id, title, description, evidence_type, source,
+                      source_reference, file_path, file_size, mime_type, collected_at,
+                      valid_from, valid_until, uploaded_by, created_at, version
+            "#,
+        )
+        .bind(id)
+        .bind(org_id)
+        .bind(&snapshot.title)
+        .bind(&snapshot.description)
+        .bind(&snapshot.evidence_type)
+        .bind(&snapshot.source)
+        .bind(&snapshot.source_reference)
+        .bind(snapshot.valid_from)
+        .bind(snapshot.valid_until)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        self.invalidate_evidence_cache(org_id, id).await?;
+
+        tracing::info!(
+            "Restored evidence {} from version {} as version {}",
+            id,
+            version,
+            evidence.version
+        );
+
+        Ok(evidence)
+    }
+
+    /// Copy the current head into `evidence_versions` at its existing version.
+    ///
+    /// Returns `false` when the head does not exist. `updated_by` records who
+    /// caused the archival (the restoring/updating user).
+    async fn archive_head(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        org_id: Uuid,
+        id: Uuid,
+        updated_by: Option<Uuid>,
+    ) -> AppResult<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO evidence_versions
+                (evidence_id, organization_id, version, title, description, evidence_type,
+                 source, source_reference, file_path, file_size, mime_type, collected_at,
+                 valid_from, valid_until, updated_by)
+            SELECT id, organization_id, version, title, description, evidence_type,
+                   source, source_reference, file_path, file_size, mime_type, collected_at,
+                   valid_from, valid_until, $3
+            FROM evidence
+            WHERE id = $1 AND organization_id = $2
+            ON CONFLICT (evidence_id, version) DO NOTHING
+            "#,
+        )
+        .bind(id)
+        .bind(org_id)
+        .bind(updated_by)
+        .execute(&mut **tx)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // ==================== Batch Mutations ====================
+
+    /// Apply a batch of evidence/control-link mutations in a single transaction.
+    ///
+    /// Each op runs inside its own savepoint so that, when `atomic` is false, a
+    /// failing op rolls back only its own work and the rest still commit; when
+    /// `atomic` is true the first failure aborts the whole transaction. Results
+    /// positionally match the input ops. Caches are invalidated once at the end.
+    pub async fn batch_apply(
+        &self,
+        org_id: Uuid,
+        user_id: Option<Uuid>,
+        ops: Vec<EvidenceBatchOp>,
+        atomic: bool,
+    ) -> AppResult<Vec<BatchOpResult>> {
+        let total = ops.len();
+        let mut tx = self.db.begin().await?;
+        let mut results: Vec<BatchOpResult> = Vec::with_capacity(total);
+        let mut aborted = false;
+
+        for (index, op) in ops.into_iter().enumerate() {
+            let mut sp = tx.begin().await?;
+            match Self::apply_batch_op(&mut sp, org_id, user_id, op).await {
+                Ok((evidence, affected)) => {
+                    sp.commit().await?;
+                    results.push(BatchOpResult::ok(index, evidence, affected));
+                }
+                Err(e) => {
+                    sp.rollback().await?;
+                    results.push(BatchOpResult::failed(index, e.to_string()));
+                    if atomic {
+                        aborted = true;
+                        // Mark the remaining ops as rolled back to preserve positions.
+                        for skipped in (index + 1)..total {
+                            results.push(BatchOpResult::rolled_back(skipped));
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+
+        if aborted {
+            tx.rollback().await?;
+        } else {
+            tx.commit().await?;
+            self.invalidate_org_evidence_caches(org_id).await?;
+        }
+
+        Ok(results)
+    }
+
+    /// Execute one batch op against the supplied transaction/savepoint.
+    async fn apply_batch_op(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        org_id: Uuid,
+        user_id: Option<Uuid>,
+        op: EvidenceBatchOp,
+    ) -> AppResult<(Option<Evidence>, Option<i64>)> {
+        match op {
+            EvidenceBatchOp::Create { input } => {
+                Evidence::validate_create(&input).map_err(AppError::ValidationError)?;
+                let evidence = sqlx::query_as::<_, Evidence>(
+                    r#"
+                    INSERT INTO evidence (organization_id, title, description, evidence_type, source,
+                                          source_reference, file_path, file_size, mime_type,
+                                          valid_from, valid_until, uploaded_by)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                    RETURNING id, organization_id, title, description, evidence_type, source,
+                              source_reference, file_path, file_size, mime_type, collected_at,
+                              valid_from, valid_until, uploaded_by, created_at, version
+                    "#,
+                )
+                .bind(org_id)
+                .bind(&input.title)
+                .bind(&input.description)
+                .bind(input.evidence_type.as_deref().unwrap_or("document"))
+                .bind(input.source.as_deref().unwrap_or("manual"))
+                .bind(&input.source_reference)
+                .bind(&input.file_path)
+                .bind(input.file_size)
+                .bind(&input.mime_type)
+                .bind(input.valid_from)
+                .bind(input.valid_until)
+                .bind(user_id)
+                .fetch_one(&mut **tx)
+                .await?;
+                Ok((Some(evidence), None))
+            }
+            EvidenceBatchOp::Update { id, input } => {
+                let evidence = sqlx::query_as::<_, Evidence>(
+                    r#"
+                    UPDATE evidence
+                    SET
+                        title = COALESCE($3, title),
+                        description = COALESCE($4, description),
+                        evidence_type = COALESCE($5, evidence_type),
+                        source = COALESCE($6, source),
+                        source_reference = COALESCE($7, source_reference),
+                        valid_from = COALESCE($8, valid_from),
+                        valid_until = COALESCE($9, valid_until)
+                    WHERE id = $1 AND organization_id = $2
+                    RETURNING id, organization_id, title, description, evidence_type, source,
+                              source_reference, file_path, file_size, mime_type, collected_at,
+                              valid_from, valid_until, uploaded_by, created_at, version
+                    "#,
+                )
+                .bind(id)
+                .bind(org_id)
+                .bind(&input.title)
+                .bind(&input.description)
+                .bind(&input.evidence_type)
+                .bind(&input.source)
+                .bind(&input.source_reference)
+                .bind(input.valid_from)
+                .bind(input.valid_until)
+                .fetch_optional(&mut **tx)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("Evidence not found: {}", id)))?;
+                Ok((Some(evidence), None))
+            }
+            EvidenceBatchOp::Delete { id } => {
+                let result = sqlx::query("DELETE FROM evidence WHERE id = $1 AND organization_id = $2")
+                    .bind(id)
+                    .bind(org_id)
+                    .execute(&mut **tx)
+                    .await?;
+                if result.rows_affected() == 0 {
+                    return Err(AppError::NotFound(format!("Evidence not found: {}", id)));
+                }
+                Ok((None, Some(result.rows_affected() as i64)))
+            }
+            EvidenceBatchOp::Link { evidence_id, control_ids } => {
+                let version = Self::ensure_evidence_exists(tx, org_id, evidence_id).await?;
+                let mut linked = 0i64;
+                for control_id in control_ids {
+                    let existing: Option<(Uuid,)> = sqlx::query_as(
+                        "SELECT id FROM evidence_control_links WHERE evidence_id = $1 AND control_id = $2",
+                    )
+                    .bind(evidence_id)
+                    .bind(control_id)
+                    .fetch_optional(&mut **tx)
+                    .await?;
+                    if existing.is_none() {
+                        sqlx::query(
+                            r#"
+                            INSERT INTO evidence_control_links (evidence_id, control_id, linked_by, evidence_version)
+                            VALUES ($1, $2, $3, $4)
+                            "#,
+                        )
+                        .bind(evidence_id)
+                        .bind(control_id)
+                        .bind(user_id)
+                        .bind(version)
+                        .execute(&mut **tx)
+                        .await?;
+                        linked += 1;
+                    }
+                }
+                Ok((None, Some(linked)))
+            }
+            EvidenceBatchOp::Unlink { evidence_id, control_ids } => {
+                Self::ensure_evidence_exists(tx, org_id, evidence_id).await?;
+                let result = sqlx::query(
+                    "DELETE FROM evidence_control_links WHERE evidence_id = $1 AND control_id = ANY($2)",
+                )
+                .bind(evidence_id)
+                .bind(&control_ids)
+                .execute(&mut **tx)
+                .await?;
+                Ok((None, Some(result.rows_affected() as i64)))
+            }
+        }
+    }
+
+    /// Confirm an evidence row exists for the org within a transaction, returning
+    /// its current head version.
+    async fn ensure_evidence_exists(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        org_id: Uuid,
+        evidence_id: Uuid,
+    ) -> AppResult<i32> {
+        let row: Option<(i32,)> =
+            sqlx::query_as("SELECT version FROM evidence WHERE id = $1 AND organization_id = $2")
+                .bind(evidence_id)
+                .bind(org_id)
+                .fetch_optional(&mut **tx)
+                .await?;
+        row.map(|(v,)| v)
+            .ok_or_else(|| AppError::NotFound(format!("Evidence not found: {}", evidence_id)))
+    }
+
     // ==================== Statistics ====================
 
     /// Get evidence statistics
     pub async fn get_stats(&self, org_id: Uuid) -> AppResult<EvidenceStats> {
+        let started = std::time::Instant::now();
         let cache_key = org_cache_key(&org_id.to_string(), CACHE_PREFIX_EVIDENCE_STATS, "summary");
 
         // Try cache first
         if let Some(cached) = self.cache.get::<EvidenceStats>(&cache_key).await? {
             tracing::debug!("Cache hit for evidence stats");
+            evidence_metrics::record_cache(CACHE_PREFIX_EVIDENCE_STATS, true);
+            evidence_metrics::set_expiry(
+                &org_id.to_string(),
+                cached.expired,
+                cached.expiring_soon,
+            );
+            evidence_metrics::record_method("get_stats", started);
             return Ok(cached);
         }
+        evidence_metrics::record_cache(CACHE_PREFIX_EVIDENCE_STATS, false);
 
         // Get total and expiry stats
         let (total, expiring_soon, expired): (i64, i64, i64) = sqlx::query_as(
@@ -468,6 +1116,8 @@ impl EvidenceService {
             .set(&cache_key, &stats, Some(Duration::from_secs(300)))
             .await?;
 
+        evidence_metrics::set_expiry(&org_id.to_string(), stats.expired, stats.expiring_soon);
+        evidence_metrics::record_method("get_stats", started);
         Ok(stats)
     }
 
@@ -0,0 +1,149 @@
+//! OpenTelemetry instrumentation for [`EvidenceService`](super::evidence::EvidenceService).
+//!
+//! Opt-in in exactly the same way as the integration telemetry
+//! ([`crate::integrations::telemetry`]): unless `OPENGRC_OTEL_ENABLED` is set,
+//! [`metrics`] returns `None` and every call site is a cheap no-op. When
+//! enabled, counters track cache hits/misses per prefix, histograms time each
+//! DB method, and a gauge (observable callback) reports expired /
+//! expiring-soon evidence per org so operators can dashboard cache hit ratio
+//! and tune the 30-minute TTL.
+
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use opentelemetry::metrics::{Counter, Histogram, Meter};
+use opentelemetry::{global, KeyValue};
+
+use crate::integrations::telemetry::TelemetryConfig;
+
+/// Counters, histograms and the expiry gauge shared across the evidence service.
+pub struct EvidenceMetrics {
+    cache_hits: Counter<u64>,
+    cache_misses: Counter<u64>,
+    method_duration: Histogram<f64>,
+    /// Latest per-org expiry counts, published through an observable gauge.
+    expiry: &'static Mutex<Vec<ExpiryGauge>>,
+}
+
+/// A single org's expiry snapshot, surfaced through the observable gauge.
+#[derive(Clone)]
+struct ExpiryGauge {
+    org_id: String,
+    expired: u64,
+    expiring_soon: u64,
+}
+
+static EXPIRY: Mutex<Vec<ExpiryGauge>> = Mutex::new(Vec::new());
+
+impl EvidenceMetrics {
+    fn new(meter: &Meter) -> Self {
+        let expiry = &EXPIRY;
+        meter
+            .u64_observable_gauge("opengrc.evidence.expired")
+            .with_description("Expired evidence items per organization")
+            .with_callback(move |observer| {
+                if let Ok(snapshots) = expiry.lock() {
+                    for s in snapshots.iter() {
+                        observer.observe(s.expired, &[KeyValue::new("org", s.org_id.clone())]);
+                    }
+                }
+            })
+            .init();
+        meter
+            .u64_observable_gauge("opengrc.evidence.expiring_soon")
+            .with_description("Evidence expiring within 30 days per organization")
+            .with_callback(move |observer| {
+                if let Ok(snapshots) = expiry.lock() {
+                    for s in snapshots.iter() {
+                        observer
+                            .observe(s.expiring_soon, &[KeyValue::new("org", s.org_id.clone())]);
+                    }
+                }
+            })
+            .init();
+
+        Self {
+            cache_hits: meter
+                .u64_counter("opengrc.evidence.cache_hits")
+                .with_description("Evidence cache hits, tagged by cache prefix")
+                .init(),
+            cache_misses: meter
+                .u64_counter("opengrc.evidence.cache_misses")
+                .with_description("Evidence cache misses, tagged by cache prefix")
+                .init(),
+            method_duration: meter
+                .f64_histogram("opengrc.evidence.method_duration_seconds")
+                .with_description("Per-method evidence service latency in seconds")
+                .init(),
+            expiry,
+        }
+    }
+
+    /// Record a cache lookup outcome for the given prefix (`evidence`, `evidence:stats`).
+    pub fn record_cache(&self, prefix: &str, hit: bool) {
+        let attrs = [KeyValue::new("prefix", prefix.to_string())];
+        if hit {
+            self.cache_hits.add(1, &attrs);
+        } else {
+            self.cache_misses.add(1, &attrs);
+        }
+    }
+
+    /// Record the latency of a service method (`list_evidence`, `get_evidence`, …).
+    pub fn record_method(&self, method: &str, duration: Duration) {
+        self.method_duration
+            .record(duration.as_secs_f64(), &[KeyValue::new("method", method.to_string())]);
+    }
+
+    /// Publish the latest expiry counts for an org, derived from `get_stats`.
+    pub fn set_expiry(&self, org_id: &str, expired: i64, expiring_soon: i64) {
+        if let Ok(mut snapshots) = self.expiry.lock() {
+            let snapshot = ExpiryGauge {
+                org_id: org_id.to_string(),
+                expired: expired.max(0) as u64,
+                expiring_soon: expiring_soon.max(0) as u64,
+            };
+            match snapshots.iter_mut().find(|s| s.org_id == org_id) {
+                Some(existing) => *existing = snapshot,
+                None => snapshots.push(snapshot),
+            }
+        }
+    }
+}
+
+static METRICS: OnceLock<Option<EvidenceMetrics>> = OnceLock::new();
+
+/// Global [`EvidenceMetrics`], initialized lazily from [`TelemetryConfig::from_env`].
+/// Returns `None` when telemetry is disabled so callers can skip the work.
+pub fn metrics() -> Option<&'static EvidenceMetrics> {
+    METRICS
+        .get_or_init(|| {
+            if TelemetryConfig::from_env().enabled {
+                Some(EvidenceMetrics::new(&global::meter("opengrc.evidence")))
+            } else {
+                None
+            }
+        })
+        .as_ref()
+}
+
+/// Record a cache lookup against the global metrics if telemetry is enabled.
+pub fn record_cache(prefix: &str, hit: bool) {
+    if let Some(m) = metrics() {
+        m.record_cache(prefix, hit);
+    }
+}
+
+/// Record a method's elapsed time against the global metrics if telemetry is enabled.
+pub fn record_method(method: &str, start: std::time::Instant) {
+    if let Some(m) = metrics() {
+        m.record_method(method, start.elapsed());
+    }
+}
+
+/// Publish an org's expiry counts against the global metrics if telemetry is enabled.
+pub fn set_expiry(org_id: &str, expired: i64, expiring_soon: i64) {
+    if let Some(m) = metrics() {
+        m.set_expiry(org_id, expired, expiring_soon);
+    }
+}
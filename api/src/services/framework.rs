@@ -3,25 +3,44 @@ use crate::models::{
     CreateFramework, CreateFrameworkRequirement, Framework, FrameworkRequirement,
     FrameworkWithRequirements, UpdateFramework, UpdateFrameworkRequirement,
     FrameworkGapAnalysis, CategoryGapAnalysis, RequirementGapAnalysis,
+    InheritedRequirement, MapRequirements, RequirementMapping,
+    FrameworkRevision, RequirementRevision, RevisionDiff, RequirementChange,
+    ChangeBatch, ChangeEvent,
+    RequirementCrosswalk, CreateCrosswalk, CrosswalkSuggestion,
 };
 use crate::utils::{AppError, AppResult};
 use sqlx::PgPool;
 use std::collections::HashMap;
-use std::time::Duration;
 use uuid::Uuid;
 
-const CACHE_TTL: Duration = Duration::from_secs(3600); // 1 hour
 const CACHE_PREFIX_FRAMEWORK: &str = "framework";
 const CACHE_PREFIX_FRAMEWORKS_LIST: &str = "frameworks:list";
 const CACHE_PREFIX_REQUIREMENT: &str = "framework_req";
 const CACHE_PREFIX_REQUIREMENTS_LIST: &str = "framework_reqs";
 
+/// How many raw change-log rows `list_changes` is willing to scan per call
+/// for each requested page entry. Coalescing by (entity_type, entity_id) can
+/// only shrink a batch of rows, never grow it, so this multiplier gives
+/// enough headroom to usually fill a page while still bounding memory for a
+/// cold-start or far-behind consumer.
+const CHANGE_LOG_CANDIDATE_MULTIPLIER: i64 = 8;
+
 #[derive(Clone)]
 pub struct FrameworkService {
     db: PgPool,
     cache: CacheClient,
 }
 
+/// Aggregated inherited-coverage row for one (target, source) mapping.
+#[derive(sqlx::FromRow)]
+struct InheritedRow {
+    target_req_id: Uuid,
+    source_req_id: Uuid,
+    source_code: String,
+    relationship: String,
+    control_count: i64,
+}
+
 impl FrameworkService {
     pub fn new(db: PgPool, cache: CacheClient) -> Self {
         Self { db, cache }
@@ -54,7 +73,7 @@ impl FrameworkService {
                     r#"
                     SELECT id, name, version, description, category, is_system, created_at
                     FROM frameworks
-                    WHERE category = $1 AND is_system = $2
+                    WHERE category = $1 AND is_system = $2 AND NOT deleted
                     ORDER BY is_system DESC, name ASC
                     "#,
                 )
@@ -68,7 +87,7 @@ impl FrameworkService {
                     r#"
                     SELECT id, name, version, description, category, is_system, created_at
                     FROM frameworks
-                    WHERE category = $1
+                    WHERE category = $1 AND NOT deleted
                     ORDER BY is_system DESC, name ASC
                     "#,
                 )
@@ -81,7 +100,7 @@ impl FrameworkService {
                     r#"
                     SELECT id, name, version, description, category, is_system, created_at
                     FROM frameworks
-                    WHERE is_system = $1
+                    WHERE is_system = $1 AND NOT deleted
                     ORDER BY is_system DESC, name ASC
                     "#,
                 )
@@ -94,6 +113,7 @@ impl FrameworkService {
                     r#"
                     SELECT id, name, version, description, category, is_system, created_at
                     FROM frameworks
+                    WHERE NOT deleted
                     ORDER BY is_system DESC, name ASC
                     "#,
                 )
@@ -103,7 +123,9 @@ impl FrameworkService {
         };
 
         // Cache the result
-        self.cache.set(&cache_key, &frameworks, Some(CACHE_TTL)).await?;
+        self.cache
+            .set_tagged(&cache_key, &frameworks, Some(self.cache.list_ttl()), &[Self::TAG_FRAMEWORK_LIST.to_string()])
+            .await?;
 
         Ok(frameworks)
     }
@@ -122,7 +144,7 @@ impl FrameworkService {
             r#"
             SELECT id, name, version, description, category, is_system, created_at
             FROM frameworks
-            WHERE id = $1
+            WHERE id = $1 AND NOT deleted
             "#,
         )
         .bind(id)
@@ -131,7 +153,9 @@ impl FrameworkService {
         .ok_or_else(|| AppError::NotFound(format!("Framework {} not found", id)))?;
 
         // Cache the result
-        self.cache.set(&cache_key, &framework, Some(CACHE_TTL)).await?;
+        self.cache
+            .set_tagged(&cache_key, &framework, Some(self.cache.framework_ttl()), &[Self::tag_framework(id)])
+            .await?;
 
         Ok(framework)
     }
@@ -161,7 +185,14 @@ impl FrameworkService {
         };
 
         // Cache the result
-        self.cache.set(&cache_key, &result, Some(CACHE_TTL)).await?;
+        self.cache
+            .set_tagged(
+                &cache_key,
+                &result,
+                Some(self.cache.framework_ttl()),
+                &[Self::tag_framework(id), Self::tag_framework_reqs(id)],
+            )
+            .await?;
 
         Ok(result)
     }
@@ -186,20 +217,38 @@ impl FrameworkService {
         .fetch_one(&self.db)
         .await?;
 
+        // Record the genesis revision.
+        let mut tx = self.db.begin().await?;
+        let editgroup = Self::resolve_editgroup(&mut tx, None).await?;
+        Self::commit_framework_revision(&mut tx, framework.id, editgroup, "created", false).await?;
+        Self::append_change(&mut tx, "framework", framework.id, "created", &framework).await?;
+        tx.commit().await?;
+
         // Invalidate list cache
         self.invalidate_framework_list_cache().await?;
 
         // Cache the new framework
         let cache_key = cache_key(CACHE_PREFIX_FRAMEWORK, &framework.id.to_string());
-        self.cache.set(&cache_key, &framework, Some(CACHE_TTL)).await?;
+        self.cache
+            .set_tagged(&cache_key, &framework, Some(self.cache.framework_ttl()), &[Self::tag_framework(framework.id)])
+            .await?;
 
         tracing::info!("Created framework: {} ({})", framework.name, framework.id);
 
         Ok(framework)
     }
 
-    /// Update an existing framework
-    pub async fn update_framework(&self, id: Uuid, input: UpdateFramework) -> AppResult<Framework> {
+    /// Update an existing framework, recording an immutable revision.
+    ///
+    /// When `editgroup` is `None` a single-mutation editgroup is opened and
+    /// committed so standalone edits still land in the audit trail; pass
+    /// `Some(id)` to group several edits under one reviewable unit.
+    pub async fn update_framework(
+        &self,
+        id: Uuid,
+        input: UpdateFramework,
+        editgroup: Option<Uuid>,
+    ) -> AppResult<Framework> {
         // Check if framework exists and is not system
         let existing = self.get_framework(id).await?;
         if existing.is_system {
@@ -208,6 +257,9 @@ impl FrameworkService {
             ));
         }
 
+        let mut tx = self.db.begin().await?;
+        let editgroup = Self::resolve_editgroup(&mut tx, editgroup).await?;
+
         let framework = sqlx::query_as::<_, Framework>(
             r#"
             UPDATE frameworks
@@ -225,23 +277,33 @@ impl FrameworkService {
         .bind(&input.version)
         .bind(&input.description)
         .bind(&input.category)
-        .fetch_one(&self.db)
+        .fetch_one(&mut *tx)
         .await?;
 
+        Self::commit_framework_revision(&mut tx, id, editgroup, "updated", false).await?;
+        Self::append_change(&mut tx, "framework", id, "updated", &framework).await?;
+        tx.commit().await?;
+
         // Invalidate caches
         self.invalidate_framework_cache(id).await?;
 
         // Cache updated framework
         let cache_key = cache_key(CACHE_PREFIX_FRAMEWORK, &framework.id.to_string());
-        self.cache.set(&cache_key, &framework, Some(CACHE_TTL)).await?;
+        self.cache
+            .set_tagged(&cache_key, &framework, Some(self.cache.framework_ttl()), &[Self::tag_framework(framework.id)])
+            .await?;
 
         tracing::info!("Updated framework: {} ({})", framework.name, framework.id);
 
         Ok(framework)
     }
 
-    /// Delete a framework
-    pub async fn delete_framework(&self, id: Uuid) -> AppResult<()> {
+    /// Soft-delete a framework by writing a tombstone revision.
+    ///
+    /// The live row is retained (flagged `deleted`) so the full revision
+    /// history — including requirements that still carry control mappings —
+    /// stays resolvable.
+    pub async fn delete_framework(&self, id: Uuid, editgroup: Option<Uuid>) -> AppResult<()> {
         // Check if framework exists and is not system
         let existing = self.get_framework(id).await?;
         if existing.is_system {
@@ -250,34 +312,22 @@ impl FrameworkService {
             ));
         }
 
-        // Check if framework has any control mappings
-        let mapping_count: (i64,) = sqlx::query_as(
-            r#"
-            SELECT COUNT(*) FROM control_requirement_mappings crm
-            JOIN framework_requirements fr ON crm.framework_requirement_id = fr.id
-            WHERE fr.framework_id = $1
-            "#,
-        )
-        .bind(id)
-        .fetch_one(&self.db)
-        .await?;
-
-        if mapping_count.0 > 0 {
-            return Err(AppError::Conflict(format!(
-                "Cannot delete framework with {} existing control mappings",
-                mapping_count.0
-            )));
-        }
+        let mut tx = self.db.begin().await?;
+        let editgroup = Self::resolve_editgroup(&mut tx, editgroup).await?;
 
-        sqlx::query("DELETE FROM frameworks WHERE id = $1")
+        sqlx::query("UPDATE frameworks SET deleted = true WHERE id = $1")
             .bind(id)
-            .execute(&self.db)
+            .execute(&mut *tx)
             .await?;
 
+        Self::commit_framework_revision(&mut tx, id, editgroup, "deleted", true).await?;
+        Self::append_change(&mut tx, "framework", id, "deleted", &existing).await?;
+        tx.commit().await?;
+
         // Invalidate caches
         self.invalidate_framework_cache(id).await?;
 
-        tracing::info!("Deleted framework: {}", id);
+        tracing::info!("Soft-deleted framework: {}", id);
 
         Ok(())
     }
@@ -298,7 +348,7 @@ impl FrameworkService {
             r#"
             SELECT id, framework_id, code, name, description, category, parent_id, sort_order
             FROM framework_requirements
-            WHERE framework_id = $1
+            WHERE framework_id = $1 AND NOT deleted
             ORDER BY sort_order ASC, code ASC
             "#,
         )
@@ -307,7 +357,9 @@ impl FrameworkService {
         .await?;
 
         // Cache the result
-        self.cache.set(&cache_key, &requirements, Some(CACHE_TTL)).await?;
+        self.cache
+            .set_tagged(&cache_key, &requirements, Some(self.cache.list_ttl()), &[Self::tag_framework_reqs(framework_id)])
+            .await?;
 
         Ok(requirements)
     }
@@ -326,7 +378,7 @@ impl FrameworkService {
             r#"
             SELECT id, framework_id, code, name, description, category, parent_id, sort_order
             FROM framework_requirements
-            WHERE id = $1
+            WHERE id = $1 AND NOT deleted
             "#,
         )
         .bind(id)
@@ -335,7 +387,17 @@ impl FrameworkService {
         .ok_or_else(|| AppError::NotFound(format!("Requirement {} not found", id)))?;
 
         // Cache the result
-        self.cache.set(&cache_key, &requirement, Some(CACHE_TTL)).await?;
+        self.cache
+            .set_tagged(
+                &cache_key,
+                &requirement,
+                Some(self.cache.requirement_ttl()),
+                &[
+                    Self::tag_requirement(requirement.id),
+                    Self::tag_framework_reqs(requirement.framework_id),
+                ],
+            )
+            .await?;
 
         Ok(requirement)
     }
@@ -390,12 +452,30 @@ impl FrameworkService {
             AppError::DatabaseError(e)
         })?;
 
+        // Record the genesis revision.
+        let mut tx = self.db.begin().await?;
+        let editgroup = Self::resolve_editgroup(&mut tx, None).await?;
+        Self::commit_requirement_revision(&mut tx, requirement.id, editgroup, "created", false)
+            .await?;
+        Self::append_change(&mut tx, "requirement", requirement.id, "created", &requirement).await?;
+        tx.commit().await?;
+
         // Invalidate requirement list cache
         self.invalidate_requirement_list_cache(framework_id).await?;
 
         // Cache the new requirement
         let cache_key = cache_key(CACHE_PREFIX_REQUIREMENT, &requirement.id.to_string());
-        self.cache.set(&cache_key, &requirement, Some(CACHE_TTL)).await?;
+        self.cache
+            .set_tagged(
+                &cache_key,
+                &requirement,
+                Some(self.cache.requirement_ttl()),
+                &[
+                    Self::tag_requirement(requirement.id),
+                    Self::tag_framework_reqs(requirement.framework_id),
+                ],
+            )
+            .await?;
 
         tracing::info!(
             "Created requirement: {} ({}) for framework {}",
@@ -407,11 +487,16 @@ impl FrameworkService {
         Ok(requirement)
     }
 
-    /// Update a requirement
+    /// Update a requirement, recording an immutable revision.
+    ///
+    /// See [`update_framework`](Self::update_framework) for `editgroup`
+    /// semantics. Because versioning preserves the prior state, requirements
+    /// that still carry control mappings may be revised freely.
     pub async fn update_requirement(
         &self,
         id: Uuid,
         input: UpdateFrameworkRequirement,
+        editgroup: Option<Uuid>,
     ) -> AppResult<FrameworkRequirement> {
         let existing = self.get_requirement(id).await?;
 
@@ -438,6 +523,9 @@ impl FrameworkService {
             }
         }
 
+        let mut tx = self.db.begin().await?;
+        let editgroup = Self::resolve_editgroup(&mut tx, editgroup).await?;
+
         let requirement = sqlx::query_as::<_, FrameworkRequirement>(
             r#"
             UPDATE framework_requirements
@@ -459,23 +547,42 @@ impl FrameworkService {
         .bind(&input.category)
         .bind(input.parent_id)
         .bind(input.sort_order)
-        .fetch_one(&self.db)
+        .fetch_one(&mut *tx)
         .await?;
 
+        Self::commit_requirement_revision(&mut tx, id, editgroup, "updated", false).await?;
+        Self::append_change(&mut tx, "requirement", id, "updated", &requirement).await?;
+        tx.commit().await?;
+
         // Invalidate caches
         self.invalidate_requirement_cache(id, existing.framework_id).await?;
 
         // Cache updated requirement
         let cache_key = cache_key(CACHE_PREFIX_REQUIREMENT, &requirement.id.to_string());
-        self.cache.set(&cache_key, &requirement, Some(CACHE_TTL)).await?;
+        self.cache
+            .set_tagged(
+                &cache_key,
+                &requirement,
+                Some(self.cache.requirement_ttl()),
+                &[
+                    Self::tag_requirement(requirement.id),
+                    Self::tag_framework_reqs(requirement.framework_id),
+                ],
+            )
+            .await?;
 
         tracing::info!("Updated requirement: {} ({})", requirement.code, requirement.id);
 
         Ok(requirement)
     }
 
-    /// Delete a requirement
-    pub async fn delete_requirement(&self, id: Uuid) -> AppResult<()> {
+    /// Soft-delete a requirement by writing a tombstone revision.
+    ///
+    /// Versioning keeps the prior state resolvable, so the historical
+    /// control-mapping guard is relaxed: a mapped requirement may be retired
+    /// without losing its audit trail. Child requirements still block the
+    /// delete to avoid orphaning the live hierarchy.
+    pub async fn delete_requirement(&self, id: Uuid, editgroup: Option<Uuid>) -> AppResult<()> {
         let existing = self.get_requirement(id).await?;
 
         // Check framework is not system
@@ -486,24 +593,9 @@ impl FrameworkService {
             ));
         }
 
-        // Check for control mappings
-        let mapping_count: (i64,) = sqlx::query_as(
-            "SELECT COUNT(*) FROM control_requirement_mappings WHERE framework_requirement_id = $1",
-        )
-        .bind(id)
-        .fetch_one(&self.db)
-        .await?;
-
-        if mapping_count.0 > 0 {
-            return Err(AppError::Conflict(format!(
-                "Cannot delete requirement with {} existing control mappings",
-                mapping_count.0
-            )));
-        }
-
-        // Check for child requirements
+        // Check for live child requirements
         let child_count: (i64,) = sqlx::query_as(
-            "SELECT COUNT(*) FROM framework_requirements WHERE parent_id = $1",
+            "SELECT COUNT(*) FROM framework_requirements WHERE parent_id = $1 AND NOT deleted",
         )
         .bind(id)
         .fetch_one(&self.db)
@@ -516,15 +608,22 @@ impl FrameworkService {
             )));
         }
 
-        sqlx::query("DELETE FROM framework_requirements WHERE id = $1")
+        let mut tx = self.db.begin().await?;
+        let editgroup = Self::resolve_editgroup(&mut tx, editgroup).await?;
+
+        sqlx::query("UPDATE framework_requirements SET deleted = true WHERE id = $1")
             .bind(id)
-            .execute(&self.db)
+            .execute(&mut *tx)
             .await?;
 
+        Self::commit_requirement_revision(&mut tx, id, editgroup, "deleted", true).await?;
+        Self::append_change(&mut tx, "requirement", id, "deleted", &existing).await?;
+        tx.commit().await?;
+
         // Invalidate caches
         self.invalidate_requirement_cache(id, existing.framework_id).await?;
 
-        tracing::info!("Deleted requirement: {}", id);
+        tracing::info!("Soft-deleted requirement: {}", id);
 
         Ok(())
     }
@@ -548,8 +647,9 @@ impl FrameworkService {
                 .map_err(|e| AppError::ValidationError(e))?;
         }
 
-        // Use transaction for batch insert
+        // Use transaction for batch insert; the whole batch shares one editgroup.
         let mut tx = self.db.begin().await?;
+        let editgroup = Self::resolve_editgroup(&mut tx, None).await?;
 
         let mut created = Vec::with_capacity(requirements.len());
 
@@ -571,6 +671,10 @@ impl FrameworkService {
             .fetch_one(&mut *tx)
             .await?;
 
+            Self::commit_requirement_revision(&mut tx, requirement.id, editgroup, "created", false)
+                .await?;
+            Self::append_change(&mut tx, "requirement", requirement.id, "created", &requirement)
+                .await?;
             created.push(requirement);
         }
 
@@ -595,6 +699,7 @@ impl FrameworkService {
         &self,
         org_id: Uuid,
         framework_id: Uuid,
+        include_inherited: bool,
     ) -> AppResult<FrameworkGapAnalysis> {
         // Get framework info
         let framework = self.get_framework(framework_id).await?;
@@ -620,11 +725,52 @@ impl FrameworkService {
 
         let count_map: HashMap<Uuid, i64> = mapping_counts.into_iter().collect();
 
+        // Controls inherited from exact/superset-mapped requirements in other
+        // frameworks, keyed by the target requirement in this framework.
+        let mut inherited_map: HashMap<Uuid, Vec<InheritedRequirement>> = HashMap::new();
+        if include_inherited {
+            let req_ids: Vec<Uuid> = requirements.iter().map(|r| r.id).collect();
+            let inherited: Vec<InheritedRow> = sqlx::query_as(
+                r#"
+                SELECT rm.target_req_id, rm.source_req_id, sr.code AS source_code,
+                       rm.relationship, COUNT(crm.id) AS control_count
+                FROM requirement_mappings rm
+                JOIN framework_requirements sr ON sr.id = rm.source_req_id
+                LEFT JOIN control_requirement_mappings crm
+                       ON crm.framework_requirement_id = rm.source_req_id
+                LEFT JOIN controls c ON crm.control_id = c.id AND c.organization_id = $1
+                WHERE rm.target_req_id = ANY($2)
+                  AND rm.relationship IN ('exact', 'superset')
+                GROUP BY rm.target_req_id, rm.source_req_id, sr.code, rm.relationship
+                HAVING COUNT(crm.id) > 0
+                "#,
+            )
+            .bind(org_id)
+            .bind(&req_ids)
+            .fetch_all(&self.db)
+            .await?;
+
+            for row in inherited {
+                inherited_map.entry(row.target_req_id).or_default().push(
+                    InheritedRequirement {
+                        id: row.source_req_id,
+                        code: row.source_code,
+                        relationship: row.relationship,
+                        control_count: row.control_count,
+                    },
+                );
+            }
+        }
+
         // Build requirement analysis
         let req_analysis: Vec<RequirementGapAnalysis> = requirements
             .iter()
             .map(|req| {
-                let control_count = count_map.get(&req.id).copied().unwrap_or(0);
+                let direct_control_count = count_map.get(&req.id).copied().unwrap_or(0);
+                let inherited_from = inherited_map.remove(&req.id).unwrap_or_default();
+                let inherited_control_count =
+                    inherited_from.iter().map(|s| s.control_count).sum();
+                let control_count = direct_control_count + inherited_control_count;
                 RequirementGapAnalysis {
                     id: req.id,
                     code: req.code.clone(),
@@ -632,6 +778,9 @@ impl FrameworkService {
                     category: req.category.clone(),
                     control_count,
                     is_covered: control_count > 0,
+                    direct_control_count,
+                    inherited_control_count,
+                    inherited_from,
                 }
             })
             .collect();
@@ -670,6 +819,13 @@ impl FrameworkService {
             })
             .collect();
 
+        // Hierarchical rollup over the parent_id tree.
+        let coverage: HashMap<Uuid, (i64, bool)> = req_analysis
+            .iter()
+            .map(|r| (r.id, (r.direct_control_count, r.is_covered)))
+            .collect();
+        let tree = crate::models::framework::build_gap_rollup(&requirements, &coverage);
+
         Ok(FrameworkGapAnalysis {
             framework_id,
             framework_name: framework.name,
@@ -679,47 +835,591 @@ impl FrameworkService {
             coverage_percentage,
             by_category,
             requirements: req_analysis,
+            tree,
         })
     }
 
-    // ==================== Cache Invalidation ====================
+    // ==================== Requirement Crosswalk ====================
 
-    async fn invalidate_framework_cache(&self, id: Uuid) -> AppResult<()> {
-        // Delete specific framework cache
-        let cache_key = cache_key(CACHE_PREFIX_FRAMEWORK, &id.to_string());
-        self.cache.delete(&cache_key).await?;
+    /// Crosswalk one requirement onto another in a different framework.
+    ///
+    /// The mapping is directed `source -> target`; `exact`/`superset`
+    /// relationships propagate the source's coverage into the target during
+    /// inherited gap analysis. Re-mapping the same pair updates the
+    /// relationship rather than erroring.
+    pub async fn map_requirements(&self, input: MapRequirements) -> AppResult<RequirementMapping> {
+        input.validate().map_err(AppError::BadRequest)?;
 
-        // Delete framework with requirements cache
-        let with_reqs_key = format!("{}:with_reqs:{}", CACHE_PREFIX_FRAMEWORK, id);
-        self.cache.delete(&with_reqs_key).await?;
+        let mapping = sqlx::query_as::<_, RequirementMapping>(
+            r#"
+            INSERT INTO requirement_mappings (source_req_id, target_req_id, relationship)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (source_req_id, target_req_id)
+            DO UPDATE SET relationship = EXCLUDED.relationship
+            RETURNING id, source_req_id, target_req_id, relationship, created_at
+            "#,
+        )
+        .bind(input.source_req_id)
+        .bind(input.target_req_id)
+        .bind(&input.relationship)
+        .fetch_one(&self.db)
+        .await?;
 
-        // Invalidate list caches
-        self.invalidate_framework_list_cache().await?;
+        Ok(mapping)
+    }
+
+    /// List crosswalk mappings targeting a requirement.
+    pub async fn list_requirement_mappings(
+        &self,
+        target_req_id: Uuid,
+    ) -> AppResult<Vec<RequirementMapping>> {
+        let mappings = sqlx::query_as::<_, RequirementMapping>(
+            r#"
+            SELECT id, source_req_id, target_req_id, relationship, created_at
+            FROM requirement_mappings
+            WHERE target_req_id = $1
+            ORDER BY created_at
+            "#,
+        )
+        .bind(target_req_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(mappings)
+    }
+
+    // ==================== Cross-framework crosswalk ====================
+
+    /// Record a symmetric equivalence between two requirements.
+    ///
+    /// The pair is normalised to `(min, max)` id order so the same equivalence
+    /// is stored once regardless of argument order; re-submitting updates the
+    /// relationship and confidence rather than erroring.
+    pub async fn create_crosswalk(
+        &self,
+        input: CreateCrosswalk,
+    ) -> AppResult<RequirementCrosswalk> {
+        input.validate().map_err(AppError::BadRequest)?;
+
+        // Normalise so (a, b) and (b, a) collapse onto one row.
+        let (a, b) = if input.requirement_a_id <= input.requirement_b_id {
+            (input.requirement_a_id, input.requirement_b_id)
+        } else {
+            (input.requirement_b_id, input.requirement_a_id)
+        };
+
+        let crosswalk = sqlx::query_as::<_, RequirementCrosswalk>(
+            r#"
+            INSERT INTO framework_requirement_crosswalks
+                (requirement_a_id, requirement_b_id, relationship, confidence)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (requirement_a_id, requirement_b_id)
+            DO UPDATE SET relationship = EXCLUDED.relationship, confidence = EXCLUDED.confidence
+            RETURNING id, requirement_a_id, requirement_b_id, relationship, confidence, created_at
+            "#,
+        )
+        .bind(a)
+        .bind(b)
+        .bind(&input.relationship)
+        .bind(input.confidence.unwrap_or(1.0))
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(crosswalk)
+    }
+
+    /// List every crosswalk touching a requirement, in either direction.
+    pub async fn list_crosswalks(
+        &self,
+        requirement_id: Uuid,
+    ) -> AppResult<Vec<RequirementCrosswalk>> {
+        let crosswalks = sqlx::query_as::<_, RequirementCrosswalk>(
+            r#"
+            SELECT id, requirement_a_id, requirement_b_id, relationship, confidence, created_at
+            FROM framework_requirement_crosswalks
+            WHERE requirement_a_id = $1 OR requirement_b_id = $1
+            ORDER BY confidence DESC, created_at
+            "#,
+        )
+        .bind(requirement_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(crosswalks)
+    }
+
+    /// Gap analysis that also credits controls mapped to any `Equivalent`
+    /// requirement in another framework, so a control mapped once propagates
+    /// coverage across the frameworks an org is audited against.
+    pub async fn get_gap_analysis_with_crosswalk(
+        &self,
+        org_id: Uuid,
+        framework_id: Uuid,
+    ) -> AppResult<FrameworkGapAnalysis> {
+        let mut analysis = self.get_gap_analysis(org_id, framework_id, false).await?;
+
+        // Controls reachable through an Equivalent crosswalk, per requirement.
+        let equivalent_counts: Vec<(Uuid, i64)> = sqlx::query_as(
+            r#"
+            SELECT fr.id, COUNT(DISTINCT crm.control_id) AS count
+            FROM framework_requirements fr
+            JOIN framework_requirement_crosswalks x
+              ON x.relationship = 'Equivalent'
+             AND (x.requirement_a_id = fr.id OR x.requirement_b_id = fr.id)
+            JOIN framework_requirements other
+              ON other.id = CASE WHEN x.requirement_a_id = fr.id
+                                 THEN x.requirement_b_id ELSE x.requirement_a_id END
+            JOIN control_requirement_mappings crm ON crm.framework_requirement_id = other.id
+            JOIN controls c ON crm.control_id = c.id AND c.organization_id = $1
+            WHERE fr.framework_id = $2
+            GROUP BY fr.id
+            "#,
+        )
+        .bind(org_id)
+        .bind(framework_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        let equivalent_map: HashMap<Uuid, i64> = equivalent_counts.into_iter().collect();
 
+        for req in analysis.requirements.iter_mut() {
+            if let Some(extra) = equivalent_map.get(&req.id) {
+                req.inherited_control_count += extra;
+                req.control_count += extra;
+                req.is_covered = req.control_count > 0;
+            }
+        }
+
+        // Recompute the headline totals now that coverage may have shifted.
+        analysis.covered_requirements =
+            analysis.requirements.iter().filter(|r| r.is_covered).count() as i64;
+        analysis.uncovered_requirements =
+            analysis.total_requirements - analysis.covered_requirements;
+        analysis.coverage_percentage = if analysis.total_requirements > 0 {
+            (analysis.covered_requirements as f64 / analysis.total_requirements as f64) * 100.0
+        } else {
+            0.0
+        };
+
+        Ok(analysis)
+    }
+
+    /// Propose candidate equivalences between two frameworks by fuzzy-matching
+    /// requirement text. Similarity is the Jaccard overlap of normalized token
+    /// sets over `name` + `description`; pairs above `0.35` are returned ranked.
+    pub async fn suggest_crosswalks(
+        &self,
+        framework_a: Uuid,
+        framework_b: Uuid,
+    ) -> AppResult<Vec<CrosswalkSuggestion>> {
+        const THRESHOLD: f64 = 0.35;
+
+        let reqs_a = self.list_requirements(framework_a).await?;
+        let reqs_b = self.list_requirements(framework_b).await?;
+
+        let tokens = |r: &FrameworkRequirement| -> std::collections::HashSet<String> {
+            let mut text = r.name.clone();
+            if let Some(desc) = &r.description {
+                text.push(' ');
+                text.push_str(desc);
+            }
+            text.to_lowercase()
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|t| t.len() > 2)
+                .map(|t| t.to_string())
+                .collect()
+        };
+
+        let b_tokens: Vec<(std::collections::HashSet<String>, &FrameworkRequirement)> =
+            reqs_b.iter().map(|r| (tokens(r), r)).collect();
+
+        let mut suggestions = Vec::new();
+        for a in &reqs_a {
+            let a_tokens = tokens(a);
+            if a_tokens.is_empty() {
+                continue;
+            }
+            for (b_set, b) in &b_tokens {
+                if b_set.is_empty() {
+                    continue;
+                }
+                let intersection = a_tokens.intersection(b_set).count();
+                if intersection == 0 {
+                    continue;
+                }
+                let union = a_tokens.len() + b_set.len() - intersection;
+                let similarity = intersection as f64 / union as f64;
+                if similarity >= THRESHOLD {
+                    suggestions.push(CrosswalkSuggestion {
+                        requirement_a_id: a.id,
+                        requirement_a_code: a.code.clone(),
+                        requirement_b_id: b.id,
+                        requirement_b_code: b.code.clone(),
+                        similarity,
+                    });
+                }
+            }
+        }
+
+        suggestions.sort_by(|x, y| {
+            y.similarity
+                .partial_cmp(&x.similarity)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        Ok(suggestions)
+    }
+
+    // ==================== Revision history ====================
+
+    /// Open a new editgroup that callers can thread through several mutations
+    /// so they commit as one reviewable unit.
+    pub async fn create_editgroup(
+        &self,
+        description: Option<String>,
+        created_by: Option<Uuid>,
+    ) -> AppResult<Uuid> {
+        let id: Uuid = sqlx::query_scalar(
+            "INSERT INTO framework_editgroups (description, created_by) VALUES ($1, $2) RETURNING id",
+        )
+        .bind(description)
+        .bind(created_by)
+        .fetch_one(&self.db)
+        .await?;
+        Ok(id)
+    }
+
+    /// Return the full revision history of a framework, newest first.
+    pub async fn get_framework_history(&self, id: Uuid) -> AppResult<Vec<FrameworkRevision>> {
+        let ident = self.framework_ident(id).await?;
+        let revisions = sqlx::query_as::<_, FrameworkRevision>(
+            r#"
+            SELECT id, ident, editgroup_id, op, is_current, name, version,
+                   description, category, is_system, created_at
+            FROM framework_revisions
+            WHERE ident = $1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(ident)
+        .fetch_all(&self.db)
+        .await?;
+        Ok(revisions)
+    }
+
+    /// Diff the requirement sets current at two framework revisions.
+    ///
+    /// `rev_a` is treated as the "before" state and `rev_b` as the "after":
+    /// requirements present only in `b` are `added`, those only in `a` are
+    /// `removed`, and those in both whose fields differ are `changed`.
+    pub async fn diff_revisions(&self, rev_a: Uuid, rev_b: Uuid) -> AppResult<RevisionDiff> {
+        let before = self.requirements_at_revision(rev_a).await?;
+        let after = self.requirements_at_revision(rev_b).await?;
+
+        let before_by: HashMap<Uuid, RequirementRevision> =
+            before.into_iter().map(|r| (r.ident, r)).collect();
+        let mut after_by: HashMap<Uuid, RequirementRevision> =
+            after.into_iter().map(|r| (r.ident, r)).collect();
+
+        let mut diff = RevisionDiff::default();
+        for (ident, before_rev) in before_by {
+            match after_by.remove(&ident) {
+                None => diff.removed.push(before_rev),
+                Some(after_rev) => {
+                    if Self::requirement_fields_differ(&before_rev, &after_rev) {
+                        diff.changed.push(RequirementChange {
+                            ident,
+                            before: before_rev,
+                            after: after_rev,
+                        });
+                    }
+                }
+            }
+        }
+        // Anything left in `after` was not present in `before`.
+        diff.added.extend(after_by.into_values());
+        Ok(diff)
+    }
+
+    /// Read an ordered page of the change feed after `since_seq`.
+    ///
+    /// Multiple changes to the same row inside the page are coalesced to the
+    /// latest state, and `last_seq` always advances to the newest scanned seq
+    /// so a caller that stores it never re-observes a stale change.
+    pub async fn list_changes(&self, since_seq: i64, limit: usize) -> AppResult<ChangeBatch> {
+        // Bound the rows pulled from the log before coalescing, so a
+        // cold-start or far-behind consumer doesn't pull the entire
+        // remaining backlog into memory regardless of the requested page
+        // size - only the bounded candidate window is collapsed to each
+        // entity's most recent change past the cursor.
+        let candidate_limit = (limit as i64)
+            .saturating_mul(CHANGE_LOG_CANDIDATE_MULTIPLIER)
+            .max(limit as i64);
+
+        let mut coalesced = sqlx::query_as::<_, ChangeEvent>(
+            r#"
+            SELECT DISTINCT ON (entity_type, entity_id)
+                   seq, entity_type, entity_id, op, current_value
+            FROM (
+                SELECT seq, entity_type, entity_id, op, current_value
+                FROM framework_change_log
+                WHERE seq > $1
+                ORDER BY seq ASC
+                LIMIT $2
+            ) candidates
+            ORDER BY entity_type, entity_id, seq DESC
+            "#,
+        )
+        .bind(since_seq)
+        .bind(candidate_limit)
+        .fetch_all(&self.db)
+        .await?;
+
+        coalesced.sort_by_key(|c| c.seq);
+        coalesced.truncate(limit);
+
+        // High-water is the last change returned, or the global max past the
+        // cursor when the page is empty so empty polls still make progress.
+        let last_seq = match coalesced.last() {
+            Some(last) => last.seq,
+            None => sqlx::query_scalar::<_, Option<i64>>(
+                "SELECT MAX(seq) FROM framework_change_log WHERE seq > $1",
+            )
+            .bind(since_seq)
+            .fetch_one(&self.db)
+            .await?
+            .unwrap_or(since_seq),
+        };
+
+        Ok(ChangeBatch {
+            changes: coalesced,
+            last_seq,
+        })
+    }
+
+    /// Append a change-feed entry inside the caller's transaction.
+    async fn append_change<T: serde::Serialize>(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        entity_type: &str,
+        entity_id: Uuid,
+        op: &str,
+        snapshot: &T,
+    ) -> AppResult<()> {
+        let value = serde_json::to_value(snapshot)
+            .map_err(|e| AppError::InternalServerError(format!("failed to snapshot change: {}", e)))?;
+        sqlx::query(
+            "INSERT INTO framework_change_log (entity_type, entity_id, op, current_value) VALUES ($1, $2, $3, $4)",
+        )
+        .bind(entity_type)
+        .bind(entity_id)
+        .bind(op)
+        .bind(value)
+        .execute(&mut **tx)
+        .await?;
         Ok(())
     }
 
-    async fn invalidate_framework_list_cache(&self) -> AppResult<()> {
-        self.cache.delete_pattern(&format!("{}:*", CACHE_PREFIX_FRAMEWORKS_LIST)).await
+    /// Resolve the framework identity behind a live row id.
+    async fn framework_ident(&self, id: Uuid) -> AppResult<Uuid> {
+        sqlx::query_scalar::<_, Uuid>("SELECT ident FROM frameworks WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.db)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Framework {} not found", id)))
     }
 
-    async fn invalidate_requirement_cache(&self, id: Uuid, framework_id: Uuid) -> AppResult<()> {
-        // Delete specific requirement cache
-        let cache_key = cache_key(CACHE_PREFIX_REQUIREMENT, &id.to_string());
-        self.cache.delete(&cache_key).await?;
+    /// The requirement revisions current as of a given framework revision.
+    ///
+    /// A requirement belongs to the snapshot when its latest revision at or
+    /// before the framework revision's timestamp is not a tombstone.
+    async fn requirements_at_revision(
+        &self,
+        framework_rev: Uuid,
+    ) -> AppResult<Vec<RequirementRevision>> {
+        let (framework_ident, at): (Uuid, chrono::DateTime<chrono::Utc>) = sqlx::query_as(
+            "SELECT ident, created_at FROM framework_revisions WHERE id = $1",
+        )
+        .bind(framework_rev)
+        .fetch_optional(&self.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Revision {} not found", framework_rev)))?;
 
-        // Delete list cache
-        self.invalidate_requirement_list_cache(framework_id).await?;
+        let revisions = sqlx::query_as::<_, RequirementRevision>(
+            r#"
+            SELECT DISTINCT ON (ident)
+                   id, ident, framework_ident, editgroup_id, op, is_current,
+                   code, name, description, category, parent_ident, sort_order, created_at
+            FROM framework_requirement_revisions
+            WHERE framework_ident = $1 AND created_at <= $2
+            ORDER BY ident, created_at DESC
+            "#,
+        )
+        .bind(framework_ident)
+        .bind(at)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(revisions
+            .into_iter()
+            .filter(|r| r.op != "deleted")
+            .collect())
+    }
+
+    fn requirement_fields_differ(a: &RequirementRevision, b: &RequirementRevision) -> bool {
+        a.code != b.code
+            || a.name != b.name
+            || a.description != b.description
+            || a.category != b.category
+            || a.parent_ident != b.parent_ident
+            || a.sort_order != b.sort_order
+    }
+
+    /// Resolve an optional caller-supplied editgroup, opening a fresh
+    /// single-mutation one when none was provided.
+    async fn resolve_editgroup(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        editgroup: Option<Uuid>,
+    ) -> AppResult<Uuid> {
+        match editgroup {
+            Some(id) => Ok(id),
+            None => {
+                let id: Uuid = sqlx::query_scalar(
+                    "INSERT INTO framework_editgroups (description) VALUES (NULL) RETURNING id",
+                )
+                .fetch_one(&mut **tx)
+                .await?;
+                Ok(id)
+            }
+        }
+    }
+
+    /// Snapshot the current framework row into a new revision and flip the
+    /// `is_current` pointer, all inside the caller's transaction.
+    async fn commit_framework_revision(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        id: Uuid,
+        editgroup: Uuid,
+        op: &str,
+        deleted: bool,
+    ) -> AppResult<()> {
+        sqlx::query(
+            "UPDATE framework_revisions SET is_current = false WHERE ident = (SELECT ident FROM frameworks WHERE id = $1) AND is_current",
+        )
+        .bind(id)
+        .execute(&mut **tx)
+        .await?;
+
+        let rev_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO framework_revisions
+                (ident, editgroup_id, op, is_current, name, version, description, category, is_system)
+            SELECT ident, $2, $3, NOT $4, name, version, description, category, is_system
+            FROM frameworks WHERE id = $1
+            RETURNING id
+            "#,
+        )
+        .bind(id)
+        .bind(editgroup)
+        .bind(op)
+        .bind(deleted)
+        .fetch_one(&mut **tx)
+        .await?;
+
+        sqlx::query("UPDATE frameworks SET current_rev = $2 WHERE id = $1")
+            .bind(id)
+            .bind(rev_id)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+
+    /// Snapshot the current requirement row into a new revision and flip the
+    /// `is_current` pointer, all inside the caller's transaction.
+    async fn commit_requirement_revision(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        id: Uuid,
+        editgroup: Uuid,
+        op: &str,
+        deleted: bool,
+    ) -> AppResult<()> {
+        sqlx::query(
+            "UPDATE framework_requirement_revisions SET is_current = false WHERE ident = (SELECT ident FROM framework_requirements WHERE id = $1) AND is_current",
+        )
+        .bind(id)
+        .execute(&mut **tx)
+        .await?;
 
-        // Invalidate framework with requirements cache
-        let with_reqs_key = format!("{}:with_reqs:{}", CACHE_PREFIX_FRAMEWORK, framework_id);
-        self.cache.delete(&with_reqs_key).await?;
+        let rev_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO framework_requirement_revisions
+                (ident, framework_ident, editgroup_id, op, is_current, code, name,
+                 description, category, parent_ident, sort_order)
+            SELECT fr.ident,
+                   (SELECT ident FROM frameworks WHERE id = fr.framework_id),
+                   $2, $3, NOT $4, fr.code, fr.name, fr.description, fr.category,
+                   (SELECT ident FROM framework_requirements p WHERE p.id = fr.parent_id),
+                   fr.sort_order
+            FROM framework_requirements fr WHERE fr.id = $1
+            RETURNING id
+            "#,
+        )
+        .bind(id)
+        .bind(editgroup)
+        .bind(op)
+        .bind(deleted)
+        .fetch_one(&mut **tx)
+        .await?;
 
+        sqlx::query("UPDATE framework_requirements SET current_rev = $2 WHERE id = $1")
+            .bind(id)
+            .bind(rev_id)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+
+    // ==================== Cache tags ====================
+
+    /// Tag busting the cross-tenant framework list caches.
+    const TAG_FRAMEWORK_LIST: &'static str = "framework_list";
+
+    /// Tag covering a single framework's row cache.
+    fn tag_framework(id: Uuid) -> String {
+        format!("framework:{}", id)
+    }
+
+    /// Tag covering a framework's requirement-derived caches: the requirement
+    /// list, each requirement row, and the `with_reqs` snapshot. Busting it
+    /// fans out precisely to every entry that depends on the requirement set.
+    fn tag_framework_reqs(framework_id: Uuid) -> String {
+        format!("framework_reqs:{}", framework_id)
+    }
+
+    /// Tag covering a single requirement's row cache.
+    fn tag_requirement(id: Uuid) -> String {
+        format!("framework_req:{}", id)
+    }
+
+    // ==================== Cache Invalidation ====================
+
+    async fn invalidate_framework_cache(&self, id: Uuid) -> AppResult<()> {
+        self.cache.invalidate_tag(&Self::tag_framework(id)).await?;
+        self.cache.invalidate_tag(&Self::tag_framework_reqs(id)).await?;
+        self.invalidate_framework_list_cache().await?;
+        Ok(())
+    }
+
+    async fn invalidate_framework_list_cache(&self) -> AppResult<()> {
+        self.cache.invalidate_tag(Self::TAG_FRAMEWORK_LIST).await
+    }
+
+    async fn invalidate_requirement_cache(&self, id: Uuid, framework_id: Uuid) -> AppResult<()> {
+        self.cache.invalidate_tag(&Self::tag_requirement(id)).await?;
+        // Fans out to the requirement list and `with_reqs` snapshot in one sweep.
+        self.invalidate_requirement_list_cache(framework_id).await?;
         Ok(())
     }
 
     async fn invalidate_requirement_list_cache(&self, framework_id: Uuid) -> AppResult<()> {
-        let cache_key = format!("{}:{}", CACHE_PREFIX_REQUIREMENTS_LIST, framework_id);
-        self.cache.delete(&cache_key).await
+        self.cache.invalidate_tag(&Self::tag_framework_reqs(framework_id)).await
     }
 }
@@ -0,0 +1,294 @@
+use crate::integrations::jira::{JiraClient, JiraConfig};
+use crate::models::IssueTrackingSyncReport;
+use crate::utils::AppResult;
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+/// Pushes OpenGRC findings (failed control tests, overdue questionnaire
+/// assignments, open risks) into Jira as issues and pulls their resolution
+/// back, persisting the record <-> ticket link in `integration_issue_links`.
+///
+/// This sits above the stateless `IntegrationProvider`/`run_sync` path: that
+/// path only talks to the external API and has no database access, while
+/// linking a ticket to a specific OpenGRC record requires one.
+#[derive(Clone)]
+pub struct JiraIssueTrackingService {
+    db: PgPool,
+}
+
+#[derive(Debug, FromRow)]
+struct Finding {
+    entity_type: String,
+    entity_id: Uuid,
+    summary: String,
+    description: String,
+}
+
+#[derive(Debug, FromRow)]
+struct LinkRow {
+    id: Uuid,
+    external_key: String,
+}
+
+impl JiraIssueTrackingService {
+    pub fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+
+    /// Create Jira issues for unresolved findings that don't already have a
+    /// link, then pull the status of previously-linked issues back.
+    pub async fn sync(
+        &self,
+        org_id: Uuid,
+        integration_id: Uuid,
+        client: &JiraClient,
+        config: &JiraConfig,
+    ) -> AppResult<IssueTrackingSyncReport> {
+        let mut report = IssueTrackingSyncReport::default();
+
+        let project_key = match &config.project_key {
+            Some(key) => key.clone(),
+            None => {
+                report
+                    .errors
+                    .push("issue_tracking requires project_key".to_string());
+                return Ok(report);
+            }
+        };
+
+        self.push_findings(org_id, integration_id, client, config, &project_key, &mut report)
+            .await;
+        self.pull_status(org_id, integration_id, client, config, &mut report)
+            .await;
+
+        Ok(report)
+    }
+
+    async fn push_findings(
+        &self,
+        org_id: Uuid,
+        integration_id: Uuid,
+        client: &JiraClient,
+        config: &JiraConfig,
+        project_key: &str,
+        report: &mut IssueTrackingSyncReport,
+    ) {
+        let findings = match self.unlinked_findings(org_id, integration_id).await {
+            Ok(findings) => findings,
+            Err(e) => {
+                report.errors.push(format!("failed to load findings: {}", e));
+                return;
+            }
+        };
+
+        let labels = vec!["opengrc".to_string(), format!("opengrc-{}", org_id)];
+
+        for finding in findings {
+            match client
+                .create_issue(
+                    project_key,
+                    "Task",
+                    &finding.summary,
+                    &finding.description,
+                    &labels,
+                )
+                .await
+            {
+                Ok(issue_key) => {
+                    if let Err(e) = self
+                        .store_link(org_id, integration_id, &finding.entity_type, finding.entity_id, &issue_key)
+                        .await
+                    {
+                        report.errors.push(format!("failed to store link for {}: {}", issue_key, e));
+                        continue;
+                    }
+                    report.issues_created += 1;
+                }
+                Err(e) => report.errors.push(format!(
+                    "failed to create issue for {} {}: {}",
+                    finding.entity_type, finding.entity_id, e
+                )),
+            }
+        }
+
+        let _ = config; // status_mapping only consulted on pull-back
+    }
+
+    async fn pull_status(
+        &self,
+        org_id: Uuid,
+        integration_id: Uuid,
+        client: &JiraClient,
+        config: &JiraConfig,
+        report: &mut IssueTrackingSyncReport,
+    ) {
+        let links = match sqlx::query_as::<_, LinkRow>(
+            r#"
+            SELECT id, external_key
+            FROM integration_issue_links
+            WHERE organization_id = $1 AND integration_id = $2 AND stale = FALSE
+            "#,
+        )
+        .bind(org_id)
+        .bind(integration_id)
+        .fetch_all(&self.db)
+        .await
+        {
+            Ok(links) => links,
+            Err(e) => {
+                report.errors.push(format!("failed to load issue links: {}", e));
+                return;
+            }
+        };
+
+        for link in links {
+            match client.get_issue_status(&link.external_key).await {
+                // Deleted in Jira: mark stale, don't fail the sync.
+                Ok(None) => {
+                    if let Err(e) = sqlx::query(
+                        "UPDATE integration_issue_links SET stale = TRUE, updated_at = NOW() WHERE id = $1",
+                    )
+                    .bind(link.id)
+                    .execute(&self.db)
+                    .await
+                    {
+                        report.errors.push(format!("failed to mark link {} stale: {}", link.id, e));
+                        continue;
+                    }
+                    report.issues_marked_stale += 1;
+                }
+                Ok(Some(info)) => {
+                    let mapped = config.map_status(&info.status, info.resolution.as_deref());
+                    if let Err(e) = sqlx::query(
+                        r#"
+                        UPDATE integration_issue_links
+                        SET external_status = $2, external_resolution = $3, mapped_status = $4,
+                            updated_at = NOW()
+                        WHERE id = $1
+                        "#,
+                    )
+                    .bind(link.id)
+                    .bind(&info.status)
+                    .bind(&info.resolution)
+                    .bind(&mapped)
+                    .execute(&self.db)
+                    .await
+                    {
+                        report.errors.push(format!("failed to update link {}: {}", link.id, e));
+                        continue;
+                    }
+                    report.issues_updated += 1;
+                }
+                Err(e) => report
+                    .errors
+                    .push(format!("failed to fetch status for {}: {}", link.external_key, e)),
+            }
+        }
+    }
+
+    /// Move a linked issue to the named status, e.g. "In Progress" when
+    /// remediation starts on the OpenGRC side.
+    pub async fn transition(
+        &self,
+        org_id: Uuid,
+        link_id: Uuid,
+        to_status: &str,
+        client: &JiraClient,
+    ) -> AppResult<bool> {
+        let link = sqlx::query_as::<_, LinkRow>(
+            "SELECT id, external_key FROM integration_issue_links WHERE id = $1 AND organization_id = $2",
+        )
+        .bind(link_id)
+        .bind(org_id)
+        .fetch_one(&self.db)
+        .await?;
+
+        let moved = client
+            .transition_issue(&link.external_key, to_status)
+            .await
+            .map_err(crate::utils::AppError::InternalServerError)?;
+
+        Ok(moved)
+    }
+
+    async fn unlinked_findings(&self, org_id: Uuid, integration_id: Uuid) -> AppResult<Vec<Finding>> {
+        let findings = sqlx::query_as::<_, Finding>(
+            r#"
+            SELECT 'control_test_result' AS entity_type, r.id AS entity_id,
+                   'Failed control test: ' || c.name AS summary,
+                   COALESCE(r.notes, 'Control test ' || t.name || ' failed for control ' || c.code) AS description
+            FROM control_test_results r
+            JOIN control_tests t ON t.id = r.control_test_id
+            JOIN controls c ON c.id = t.control_id
+            WHERE c.organization_id = $1
+              AND r.status = 'failed'
+              AND NOT EXISTS (
+                  SELECT 1 FROM integration_issue_links l
+                  WHERE l.integration_id = $2 AND l.entity_type = 'control_test_result' AND l.entity_id = r.id
+              )
+
+            UNION ALL
+
+            SELECT 'questionnaire_assignment' AS entity_type, a.id AS entity_id,
+                   'Overdue questionnaire assignment' AS summary,
+                   'Questionnaire assignment ' || a.id || ' is overdue (due ' || a.due_date || ')' AS description
+            FROM questionnaire_assignments a
+            WHERE a.organization_id = $1
+              AND a.due_date IS NOT NULL
+              AND a.due_date < NOW()
+              AND a.submitted_at IS NULL
+              AND NOT EXISTS (
+                  SELECT 1 FROM integration_issue_links l
+                  WHERE l.integration_id = $2 AND l.entity_type = 'questionnaire_assignment' AND l.entity_id = a.id
+              )
+
+            UNION ALL
+
+            SELECT 'risk' AS entity_type, k.id AS entity_id,
+                   'Open risk: ' || k.title AS summary,
+                   COALESCE(k.description, 'Risk ' || k.code || ' requires treatment') AS description
+            FROM risks k
+            WHERE k.organization_id = $1
+              AND k.status NOT IN ('closed', 'mitigated', 'accepted')
+              AND NOT EXISTS (
+                  SELECT 1 FROM integration_issue_links l
+                  WHERE l.integration_id = $2 AND l.entity_type = 'risk' AND l.entity_id = k.id
+              )
+            "#,
+        )
+        .bind(org_id)
+        .bind(integration_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(findings)
+    }
+
+    async fn store_link(
+        &self,
+        org_id: Uuid,
+        integration_id: Uuid,
+        entity_type: &str,
+        entity_id: Uuid,
+        external_key: &str,
+    ) -> AppResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO integration_issue_links
+                (organization_id, integration_id, entity_type, entity_id, external_key)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (integration_id, entity_type, entity_id)
+            DO UPDATE SET external_key = EXCLUDED.external_key, stale = FALSE, updated_at = NOW()
+            "#,
+        )
+        .bind(org_id)
+        .bind(integration_id)
+        .bind(entity_type)
+        .bind(entity_id)
+        .bind(external_key)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+}
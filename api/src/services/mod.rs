@@ -4,20 +4,28 @@ pub mod analytics;
 pub mod asset;
 pub mod audit;
 pub mod aws;
+pub mod chat_delivery;
 pub mod collaboration;
 pub mod control;
+pub mod control_assertion;
 pub mod control_test_automation;
 pub mod enterprise;
 pub mod evidence;
 pub mod evidence_automation;
+pub mod evidence_metrics;
+pub mod export;
 pub mod framework;
 pub mod integration;
+pub mod jira_issue_tracking;
 pub mod notification;
 pub mod pdf;
 pub mod policy;
+pub mod portal_auth;
 pub mod questionnaire;
 pub mod reports;
+pub mod retention;
 pub mod risk;
+pub mod siem_delivery;
 pub mod soc2_parser;
 pub mod task;
 pub mod vendor;
@@ -42,13 +50,17 @@ pub use control_test_automation::ControlTestAutomationService;
 pub use enterprise::EnterpriseService;
 pub use evidence::EvidenceService;
 pub use evidence_automation::EvidenceAutomationService;
+pub use export::ExportService;
 pub use framework::FrameworkService;
 pub use integration::IntegrationService;
+pub use jira_issue_tracking::JiraIssueTrackingService;
 pub use notification::NotificationService;
 pub use pdf::PdfService;
 pub use policy::PolicyService;
+pub use portal_auth::{PortalClaims, PortalTokenService};
 pub use questionnaire::QuestionnaireService;
 pub use reports::ReportsService;
+pub use retention::RetentionService;
 pub use risk::RiskService;
 pub use soc2_parser::Soc2ParserService;
 pub use task::TaskService;
@@ -75,6 +87,7 @@ pub struct AppServices {
     pub integration: IntegrationService,
     pub aws: AwsService,
     pub evidence_automation: EvidenceAutomationService,
+    pub export: ExportService,
     pub control_test_automation: ControlTestAutomationService,
     pub questionnaire: QuestionnaireService,
     pub soc2_parser: Soc2ParserService,
@@ -83,6 +96,9 @@ pub struct AppServices {
     pub analytics: AnalyticsService,
     pub enterprise: EnterpriseService,
     pub collaboration: CollaborationService,
+    pub jira_issue_tracking: JiraIssueTrackingService,
+    pub portal_tokens: PortalTokenService,
+    pub retention: RetentionService,
 }
 
 impl AppServices {
@@ -137,6 +153,9 @@ impl AppServices {
         // Evidence automation service
         let evidence_automation = EvidenceAutomationService::new(db.clone(), cache.clone());
 
+        // Arrow/Parquet bulk export service
+        let export = ExportService::new(db.clone());
+
         // Control test automation service
         let control_test_automation = ControlTestAutomationService::new(db.clone(), cache.clone());
 
@@ -161,6 +180,15 @@ impl AppServices {
         // Collaboration service (comments, mentions, presence, Slack/Teams, digests)
         let collaboration = CollaborationService::new(db.clone(), cache.clone());
 
-        Self { db, cache, storage, search, framework, control, evidence, policy, risk, vendor, asset, audit, task, reports, pdf, notification, integration, aws, evidence_automation, control_test_automation, questionnaire, soc2_parser, access_review, ai, analytics, enterprise, collaboration }
+        // Jira bidirectional issue tracking (push findings, pull resolution)
+        let jira_issue_tracking = JiraIssueTrackingService::new(db.clone());
+
+        // Vendor-portal access tokens (signed, scoped, Redis-revocable)
+        let portal_tokens = PortalTokenService::new(config.vendor_portal.jwt_secret.clone(), cache.clone());
+
+        // Data-retention policies and scheduled purge (activity logs, evidence, comments)
+        let retention = RetentionService::new(db.clone(), enterprise.clone());
+
+        Self { db, cache, storage, search, framework, control, evidence, policy, risk, vendor, asset, audit, task, reports, pdf, notification, integration, aws, evidence_automation, export, control_test_automation, questionnaire, soc2_parser, access_review, ai, analytics, enterprise, collaboration, jira_issue_tracking, portal_tokens, retention }
     }
 }
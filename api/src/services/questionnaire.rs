@@ -8,7 +8,7 @@ use crate::models::{
     SaveQuestionnaireResponse, UpdateQuestionnaireQuestion, UpdateQuestionnaireSection,
     UpdateQuestionnaireTemplate, VendorPortalAccess,
 };
-use crate::utils::{AppError, AppResult};
+use crate::utils::{AppError, AppResult, FieldError};
 use rand::Rng;
 use sqlx::PgPool;
 use std::time::Duration;
@@ -750,6 +750,52 @@ impl QuestionnaireService {
 
     // ==================== Vendor Portal ====================
 
+    /// Resolve the opaque `access_token` behind an assignment so the
+    /// JWT-authenticated portal handlers can reuse the token-keyed lookups
+    /// below without duplicating their query logic.
+    async fn access_token_for(&self, org_id: Uuid, assignment_id: Uuid) -> AppResult<String> {
+        sqlx::query_scalar::<_, String>(
+            "SELECT access_token FROM questionnaire_assignments WHERE id = $1 AND organization_id = $2",
+        )
+        .bind(assignment_id)
+        .bind(org_id)
+        .fetch_optional(&self.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Assignment {} not found", assignment_id)))
+    }
+
+    /// Get vendor portal access, authenticated via a verified `PortalClaims`
+    /// rather than the raw opaque token.
+    pub async fn get_portal_access_by_assignment(
+        &self,
+        org_id: Uuid,
+        assignment_id: Uuid,
+    ) -> AppResult<VendorPortalAccess> {
+        let access_token = self.access_token_for(org_id, assignment_id).await?;
+        self.get_portal_access(&access_token).await
+    }
+
+    /// Save a response, authenticated via a verified `PortalClaims`.
+    pub async fn save_response_by_assignment(
+        &self,
+        org_id: Uuid,
+        assignment_id: Uuid,
+        input: SaveQuestionnaireResponse,
+    ) -> AppResult<QuestionnaireResponse> {
+        let access_token = self.access_token_for(org_id, assignment_id).await?;
+        self.save_response(&access_token, input).await
+    }
+
+    /// Submit the questionnaire, authenticated via a verified `PortalClaims`.
+    pub async fn submit_questionnaire_by_assignment(
+        &self,
+        org_id: Uuid,
+        assignment_id: Uuid,
+    ) -> AppResult<()> {
+        let access_token = self.access_token_for(org_id, assignment_id).await?;
+        self.submit_questionnaire(&access_token).await
+    }
+
     /// Get vendor portal access (by token)
     pub async fn get_portal_access(&self, access_token: &str) -> AppResult<VendorPortalAccess> {
         let assignment = sqlx::query_as::<_, QuestionnaireAssignment>(
@@ -890,9 +936,9 @@ impl QuestionnaireService {
         }
 
         // Check required questions are answered
-        let (unanswered_required,): (i64,) = sqlx::query_as(
+        let unanswered_required: Vec<(Uuid, Option<String>)> = sqlx::query_as(
             r#"
-            SELECT COUNT(*)
+            SELECT q.id, q.question_text
             FROM questionnaire_questions q
             JOIN questionnaire_responses r ON q.id = r.question_id
             WHERE r.assignment_id = $1
@@ -902,14 +948,29 @@ impl QuestionnaireService {
             "#,
         )
         .bind(portal.assignment_id)
-        .fetch_one(&self.db)
+        .fetch_all(&self.db)
         .await?;
 
-        if unanswered_required > 0 {
-            return Err(AppError::ValidationError(format!(
-                "{} required questions have not been answered",
-                unanswered_required
-            )));
+        if !unanswered_required.is_empty() {
+            let details = unanswered_required
+                .iter()
+                .map(|(id, text)| {
+                    FieldError::new(
+                        id.to_string(),
+                        format!(
+                            "{} is required",
+                            text.as_deref().unwrap_or("This question")
+                        ),
+                    )
+                })
+                .collect();
+            return Err(AppError::ValidationFailed(
+                format!(
+                    "{} required questions have not been answered",
+                    unanswered_required.len()
+                ),
+                details,
+            ));
         }
 
         // Calculate score
@@ -0,0 +1,576 @@
+use chrono::Utc;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::enterprise::CreateActivityLog;
+use crate::models::retention::{
+    CreateLegalHold, CreateRetentionPolicyForEntity, DataRetentionPolicy,
+    DataRetentionPolicyForEntity, LegalHold, RetentionPurgeResult, RetentionPurgeSummary,
+    UpdateDataRetentionPolicy, UpdateRetentionPolicyForEntity, RETENTION_ENTITY_TYPES,
+};
+use crate::services::enterprise::EnterpriseService;
+use crate::utils::{AppError, AppResult};
+
+#[derive(Clone)]
+pub struct RetentionService {
+    db: PgPool,
+    enterprise: EnterpriseService,
+}
+
+impl RetentionService {
+    pub fn new(db: PgPool, enterprise: EnterpriseService) -> Self {
+        Self { db, enterprise }
+    }
+
+    // ==================== Policy Configuration ====================
+
+    /// Get the org's retention policy, creating the default (365 days,
+    /// delete) on first access.
+    pub async fn get_policy(&self, org_id: Uuid) -> AppResult<DataRetentionPolicy> {
+        let policy = sqlx::query_as::<_, DataRetentionPolicy>(
+            r#"
+            SELECT id, organization_id, default_retention_days, purge_action, created_at, updated_at
+            FROM data_retention_policies
+            WHERE organization_id = $1
+            "#,
+        )
+        .bind(org_id)
+        .fetch_optional(&self.db)
+        .await?;
+
+        if let Some(p) = policy {
+            return Ok(p);
+        }
+
+        let policy = sqlx::query_as::<_, DataRetentionPolicy>(
+            r#"
+            INSERT INTO data_retention_policies (organization_id)
+            VALUES ($1)
+            RETURNING id, organization_id, default_retention_days, purge_action, created_at, updated_at
+            "#,
+        )
+        .bind(org_id)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(policy)
+    }
+
+    pub async fn update_policy(
+        &self,
+        org_id: Uuid,
+        input: UpdateDataRetentionPolicy,
+    ) -> AppResult<DataRetentionPolicy> {
+        let _ = self.get_policy(org_id).await?;
+
+        let policy = sqlx::query_as::<_, DataRetentionPolicy>(
+            r#"
+            UPDATE data_retention_policies SET
+                default_retention_days = COALESCE($2, default_retention_days),
+                purge_action = COALESCE($3, purge_action),
+                updated_at = NOW()
+            WHERE organization_id = $1
+            RETURNING id, organization_id, default_retention_days, purge_action, created_at, updated_at
+            "#,
+        )
+        .bind(org_id)
+        .bind(input.default_retention_days)
+        .bind(&input.purge_action)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(policy)
+    }
+
+    pub async fn list_entity_policies(
+        &self,
+        org_id: Uuid,
+    ) -> AppResult<Vec<DataRetentionPolicyForEntity>> {
+        let policies = sqlx::query_as::<_, DataRetentionPolicyForEntity>(
+            r#"
+            SELECT id, organization_id, entity_type, retention_days, purge_action, created_at, updated_at
+            FROM data_retention_policy_entities
+            WHERE organization_id = $1
+            ORDER BY entity_type ASC
+            "#,
+        )
+        .bind(org_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(policies)
+    }
+
+    pub async fn upsert_entity_policy(
+        &self,
+        org_id: Uuid,
+        input: CreateRetentionPolicyForEntity,
+    ) -> AppResult<DataRetentionPolicyForEntity> {
+        if !RETENTION_ENTITY_TYPES.contains(&input.entity_type.as_str()) {
+            return Err(AppError::BadRequest(format!(
+                "Unsupported retention entity type: {}. Valid types are: {:?}",
+                input.entity_type, RETENTION_ENTITY_TYPES
+            )));
+        }
+        if input.retention_days < 1 {
+            return Err(AppError::BadRequest(
+                "retention_days must be at least 1".to_string(),
+            ));
+        }
+
+        let policy = sqlx::query_as::<_, DataRetentionPolicyForEntity>(
+            r#"
+            INSERT INTO data_retention_policy_entities (organization_id, entity_type, retention_days, purge_action)
+            VALUES ($1, $2, $3, COALESCE($4, 'delete'))
+            ON CONFLICT (organization_id, entity_type) DO UPDATE SET
+                retention_days = EXCLUDED.retention_days,
+                purge_action = EXCLUDED.purge_action,
+                updated_at = NOW()
+            RETURNING id, organization_id, entity_type, retention_days, purge_action, created_at, updated_at
+            "#,
+        )
+        .bind(org_id)
+        .bind(&input.entity_type)
+        .bind(input.retention_days)
+        .bind(&input.purge_action)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(policy)
+    }
+
+    pub async fn update_entity_policy(
+        &self,
+        org_id: Uuid,
+        entity_type: &str,
+        input: UpdateRetentionPolicyForEntity,
+    ) -> AppResult<DataRetentionPolicyForEntity> {
+        let policy = sqlx::query_as::<_, DataRetentionPolicyForEntity>(
+            r#"
+            UPDATE data_retention_policy_entities SET
+                retention_days = COALESCE($3, retention_days),
+                purge_action = COALESCE($4, purge_action),
+                updated_at = NOW()
+            WHERE organization_id = $1 AND entity_type = $2
+            RETURNING id, organization_id, entity_type, retention_days, purge_action, created_at, updated_at
+            "#,
+        )
+        .bind(org_id)
+        .bind(entity_type)
+        .bind(input.retention_days)
+        .bind(&input.purge_action)
+        .fetch_optional(&self.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("No retention policy for that entity type".to_string()))?;
+
+        Ok(policy)
+    }
+
+    pub async fn delete_entity_policy(&self, org_id: Uuid, entity_type: &str) -> AppResult<()> {
+        let result = sqlx::query(
+            "DELETE FROM data_retention_policy_entities WHERE organization_id = $1 AND entity_type = $2",
+        )
+        .bind(org_id)
+        .bind(entity_type)
+        .execute(&self.db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(
+                "No retention policy for that entity type".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    // ==================== Legal Holds ====================
+
+    pub async fn place_legal_hold(
+        &self,
+        org_id: Uuid,
+        placed_by: Uuid,
+        input: CreateLegalHold,
+    ) -> AppResult<LegalHold> {
+        if !RETENTION_ENTITY_TYPES.contains(&input.entity_type.as_str()) {
+            return Err(AppError::BadRequest(format!(
+                "Unsupported retention entity type: {}. Valid types are: {:?}",
+                input.entity_type, RETENTION_ENTITY_TYPES
+            )));
+        }
+
+        let hold = sqlx::query_as::<_, LegalHold>(
+            r#"
+            INSERT INTO legal_holds (organization_id, entity_type, entity_id, reason, placed_by)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, organization_id, entity_type, entity_id, reason, placed_by, created_at, released_at
+            "#,
+        )
+        .bind(org_id)
+        .bind(&input.entity_type)
+        .bind(input.entity_id)
+        .bind(&input.reason)
+        .bind(placed_by)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(hold)
+    }
+
+    pub async fn release_legal_hold(&self, org_id: Uuid, hold_id: Uuid) -> AppResult<LegalHold> {
+        let hold = sqlx::query_as::<_, LegalHold>(
+            r#"
+            UPDATE legal_holds SET released_at = NOW()
+            WHERE id = $1 AND organization_id = $2 AND released_at IS NULL
+            RETURNING id, organization_id, entity_type, entity_id, reason, placed_by, created_at, released_at
+            "#,
+        )
+        .bind(hold_id)
+        .bind(org_id)
+        .fetch_optional(&self.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("No active legal hold with that id".to_string()))?;
+
+        Ok(hold)
+    }
+
+    pub async fn list_legal_holds(&self, org_id: Uuid) -> AppResult<Vec<LegalHold>> {
+        let holds = sqlx::query_as::<_, LegalHold>(
+            r#"
+            SELECT id, organization_id, entity_type, entity_id, reason, placed_by, created_at, released_at
+            FROM legal_holds
+            WHERE organization_id = $1 AND released_at IS NULL
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(org_id)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(holds)
+    }
+
+    // ==================== Purge ====================
+
+    /// Purge (or, in `dry_run` mode, merely count) rows past their configured
+    /// retention window across every supported entity type, skipping anything
+    /// under an active legal hold. A live run writes a tamper-evident summary
+    /// (counts only, never contents) to the org's `ActivityLog`.
+    pub async fn run_purge(&self, org_id: Uuid, dry_run: bool) -> AppResult<RetentionPurgeSummary> {
+        let default_policy = self.get_policy(org_id).await?;
+        let entity_overrides = self.list_entity_policies(org_id).await?;
+
+        let mut results = Vec::with_capacity(RETENTION_ENTITY_TYPES.len());
+        for entity_type in RETENTION_ENTITY_TYPES {
+            let (retention_days, purge_action) = entity_overrides
+                .iter()
+                .find(|p| p.entity_type == *entity_type)
+                .map(|p| (p.retention_days, p.purge_action.clone()))
+                .unwrap_or((default_policy.default_retention_days, default_policy.purge_action.clone()));
+
+            let cutoff = Utc::now() - chrono::Duration::days(retention_days as i64);
+            let result = self
+                .purge_entity_type(org_id, entity_type, cutoff, &purge_action, dry_run)
+                .await?;
+            results.push(result);
+        }
+
+        let run_at = Utc::now();
+
+        if !dry_run {
+            let summary_json = serde_json::json!({
+                "results": results,
+            });
+            // Route through EnterpriseService so this entry is chained into the
+            // org's tamper-evident hash chain like every other ActivityLog row,
+            // instead of landing with a NULL entry_hash that breaks verify_chain.
+            self.enterprise
+                .create_activity_log(
+                    org_id,
+                    None,
+                    CreateActivityLog {
+                        action: "retention_purge".to_string(),
+                        entity_type: None,
+                        entity_id: None,
+                        old_values: None,
+                        new_values: Some(summary_json),
+                        severity: None,
+                        category: Some("data_retention".to_string()),
+                        outcome: Some("success".to_string()),
+                        duration_ms: None,
+                        resource_name: None,
+                    },
+                    None,
+                    None,
+                    None,
+                )
+                .await?;
+        }
+
+        Ok(RetentionPurgeSummary {
+            dry_run,
+            results,
+            run_at,
+        })
+    }
+
+    /// Purge eligible rows for a single entity type. `entity_type` is always
+    /// one of [`RETENTION_ENTITY_TYPES`], never user input, so it is safe to
+    /// match on directly rather than interpolate into SQL.
+    async fn purge_entity_type(
+        &self,
+        org_id: Uuid,
+        entity_type: &str,
+        cutoff: chrono::DateTime<Utc>,
+        purge_action: &str,
+        dry_run: bool,
+    ) -> AppResult<RetentionPurgeResult> {
+        let (affected_count, held_count) = match entity_type {
+            "activity_log" => {
+                self.purge_rows(
+                    "activity_logs",
+                    "created_at",
+                    "activity_log",
+                    org_id,
+                    cutoff,
+                    dry_run,
+                )
+                .await?
+            }
+            "evidence" => {
+                if purge_action == "archive" {
+                    self.archive_rows("evidence", "collected_at", "evidence", org_id, cutoff, dry_run)
+                        .await?
+                } else {
+                    self.purge_rows("evidence", "collected_at", "evidence", org_id, cutoff, dry_run)
+                        .await?
+                }
+            }
+            "entity_comment" => {
+                // Only comments already soft-deleted are eligible; a live
+                // comment never ages out of retention just for being old.
+                self.purge_deleted_comments(org_id, cutoff, dry_run).await?
+            }
+            other => {
+                return Err(AppError::InternalServerError(format!(
+                    "No purge handler registered for retention entity type: {}",
+                    other
+                )))
+            }
+        };
+
+        Ok(RetentionPurgeResult {
+            entity_type: entity_type.to_string(),
+            retention_days: (Utc::now() - cutoff).num_days() as i32,
+            purge_action: purge_action.to_string(),
+            affected_count,
+            held_count,
+        })
+    }
+
+    /// Delete (or count, in dry-run mode) rows in `table` older than `cutoff`
+    /// on `timestamp_column`, excluding anything under an active legal hold.
+    async fn purge_rows(
+        &self,
+        table: &str,
+        timestamp_column: &str,
+        hold_entity_type: &str,
+        org_id: Uuid,
+        cutoff: chrono::DateTime<Utc>,
+        dry_run: bool,
+    ) -> AppResult<(i64, i64)> {
+        let held_count = self
+            .held_count(table, timestamp_column, hold_entity_type, org_id, cutoff)
+            .await?;
+
+        let affected_count = if dry_run {
+            sqlx::query_scalar::<_, i64>(&format!(
+                r#"
+                SELECT COUNT(*) FROM {table}
+                WHERE organization_id = $1 AND {timestamp_column} < $2
+                  AND id NOT IN (
+                      SELECT entity_id FROM legal_holds
+                      WHERE organization_id = $1 AND entity_type = $3 AND released_at IS NULL
+                  )
+                "#,
+                table = table,
+                timestamp_column = timestamp_column
+            ))
+            .bind(org_id)
+            .bind(cutoff)
+            .bind(hold_entity_type)
+            .fetch_one(&self.db)
+            .await?
+        } else {
+            let result = sqlx::query(&format!(
+                r#"
+                DELETE FROM {table}
+                WHERE organization_id = $1 AND {timestamp_column} < $2
+                  AND id NOT IN (
+                      SELECT entity_id FROM legal_holds
+                      WHERE organization_id = $1 AND entity_type = $3 AND released_at IS NULL
+                  )
+                "#,
+                table = table,
+                timestamp_column = timestamp_column
+            ))
+            .bind(org_id)
+            .bind(cutoff)
+            .bind(hold_entity_type)
+            .execute(&self.db)
+            .await?;
+            result.rows_affected() as i64
+        };
+
+        Ok((affected_count, held_count))
+    }
+
+    /// Archive (flag `archived_at`, never delete) rows in `table` older than
+    /// `cutoff`, excluding anything under an active legal hold.
+    async fn archive_rows(
+        &self,
+        table: &str,
+        timestamp_column: &str,
+        hold_entity_type: &str,
+        org_id: Uuid,
+        cutoff: chrono::DateTime<Utc>,
+        dry_run: bool,
+    ) -> AppResult<(i64, i64)> {
+        let held_count = self
+            .held_count(table, timestamp_column, hold_entity_type, org_id, cutoff)
+            .await?;
+
+        let affected_count = if dry_run {
+            sqlx::query_scalar::<_, i64>(&format!(
+                r#"
+                SELECT COUNT(*) FROM {table}
+                WHERE organization_id = $1 AND {timestamp_column} < $2 AND archived_at IS NULL
+                  AND id NOT IN (
+                      SELECT entity_id FROM legal_holds
+                      WHERE organization_id = $1 AND entity_type = $3 AND released_at IS NULL
+                  )
+                "#,
+                table = table,
+                timestamp_column = timestamp_column
+            ))
+            .bind(org_id)
+            .bind(cutoff)
+            .bind(hold_entity_type)
+            .fetch_one(&self.db)
+            .await?
+        } else {
+            let result = sqlx::query(&format!(
+                r#"
+                UPDATE {table} SET archived_at = NOW()
+                WHERE organization_id = $1 AND {timestamp_column} < $2 AND archived_at IS NULL
+                  AND id NOT IN (
+                      SELECT entity_id FROM legal_holds
+                      WHERE organization_id = $1 AND entity_type = $3 AND released_at IS NULL
+                  )
+                "#,
+                table = table,
+                timestamp_column = timestamp_column
+            ))
+            .bind(org_id)
+            .bind(cutoff)
+            .bind(hold_entity_type)
+            .execute(&self.db)
+            .await?;
+            result.rows_affected() as i64
+        };
+
+        Ok((affected_count, held_count))
+    }
+
+    /// Count rows that are past retention but currently exempted by an active
+    /// legal hold, so purge responses can surface what was skipped and why.
+    async fn held_count(
+        &self,
+        table: &str,
+        timestamp_column: &str,
+        hold_entity_type: &str,
+        org_id: Uuid,
+        cutoff: chrono::DateTime<Utc>,
+    ) -> AppResult<i64> {
+        let count = sqlx::query_scalar::<_, i64>(&format!(
+            r#"
+            SELECT COUNT(*) FROM {table} t
+            WHERE t.organization_id = $1 AND t.{timestamp_column} < $2
+              AND EXISTS (
+                  SELECT 1 FROM legal_holds h
+                  WHERE h.organization_id = $1 AND h.entity_type = $3 AND h.released_at IS NULL
+                    AND h.entity_id = t.id
+              )
+            "#,
+            table = table,
+            timestamp_column = timestamp_column
+        ))
+        .bind(org_id)
+        .bind(cutoff)
+        .bind(hold_entity_type)
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Hard-delete soft-deleted comments whose `deleted_at` is past the
+    /// configured window.
+    async fn purge_deleted_comments(
+        &self,
+        org_id: Uuid,
+        cutoff: chrono::DateTime<Utc>,
+        dry_run: bool,
+    ) -> AppResult<(i64, i64)> {
+        let held_count = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*) FROM entity_comments ec
+            WHERE ec.organization_id = $1 AND ec.deleted_at IS NOT NULL AND ec.deleted_at < $2
+              AND EXISTS (
+                  SELECT 1 FROM legal_holds h
+                  WHERE h.organization_id = $1 AND h.entity_type = 'entity_comment' AND h.released_at IS NULL
+                    AND h.entity_id = ec.id
+              )
+            "#,
+        )
+        .bind(org_id)
+        .bind(cutoff)
+        .fetch_one(&self.db)
+        .await?;
+
+        let affected_count = if dry_run {
+            sqlx::query_scalar::<_, i64>(
+                r#"
+                SELECT COUNT(*) FROM entity_comments
+                WHERE organization_id = $1 AND deleted_at IS NOT NULL AND deleted_at < $2
+                  AND id NOT IN (
+                      SELECT entity_id FROM legal_holds
+                      WHERE organization_id = $1 AND entity_type = 'entity_comment' AND released_at IS NULL
+                  )
+                "#,
+            )
+            .bind(org_id)
+            .bind(cutoff)
+            .fetch_one(&self.db)
+            .await?
+        } else {
+            let result = sqlx::query(
+                r#"
+                DELETE FROM entity_comments
+                WHERE organization_id = $1 AND deleted_at IS NOT NULL AND deleted_at < $2
+                  AND id NOT IN (
+                      SELECT entity_id FROM legal_holds
+                      WHERE organization_id = $1 AND entity_type = 'entity_comment' AND released_at IS NULL
+                  )
+                "#,
+            )
+            .bind(org_id)
+            .bind(cutoff)
+            .execute(&self.db)
+            .await?;
+            result.rows_affected() as i64
+        };
+
+        Ok((affected_count, held_count))
+    }
+}
@@ -0,0 +1,28 @@
+//! Outbound delivery of streamed `ActivityLog` entries (including their hash
+//! chain fields) to a configured SIEM endpoint over webhook-style HTTP.
+//!
+//! Syslog delivery isn't wired up here yet - `export_type` values other than
+//! `"webhook"` are rejected so a misconfigured row fails loudly rather than
+//! silently dropping events.
+
+use crate::utils::{AppError, AppResult};
+
+/// POST a single streamed event to a webhook SIEM endpoint as JSON.
+pub async fn post_siem_event(url: &str, payload: &serde_json::Value) -> AppResult<()> {
+    let client = reqwest::Client::new();
+    let response = client
+        .post(url)
+        .json(payload)
+        .send()
+        .await
+        .map_err(|e| AppError::ExternalServiceError(format!("SIEM stream POST: {}", e)))?;
+
+    if !response.status().is_success() {
+        return Err(AppError::ExternalServiceError(format!(
+            "SIEM stream POST failed: {}",
+            response.status()
+        )));
+    }
+
+    Ok(())
+}
@@ -1,11 +1,13 @@
 use crate::cache::{org_cache_key, CacheClient};
 use crate::models::{
-    AuditFinding, CreateTask, CreateTaskComment, ListTasksQuery, Task, TaskAssigneeCount,
-    TaskComment, TaskCommentWithUser, TaskPriorityCount, TaskRecurrenceHistory, TaskStats,
-    TaskTypeCount, TaskWithAssignee, UpdateTask,
+    AuditFinding, CreateTask, CreateTaskComment, ListTasksQuery, RecurrencePattern, Task,
+    TaskAnalytics, TaskAnalyticsBucket, TaskAnalyticsGroupBy, TaskAnalyticsMetric,
+    TaskAnalyticsQuery, TaskAssigneeCount, TaskComment, TaskCommentWithUser, TaskPriorityCount,
+    TaskRecurrenceHistory, TaskStats, TaskTypeCount, TaskWithAssignee, UpdateTask,
 };
+use crate::utils::rrule::RRule;
 use crate::utils::{AppError, AppResult};
-use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Utc, Weekday};
+use chrono::{DateTime, Duration, Utc};
 use sqlx::PgPool;
 use std::time::Duration as StdDuration;
 use uuid::Uuid;
@@ -55,6 +57,7 @@ impl TaskService {
                        t.is_recurring, t.recurrence_pattern, t.recurrence_interval,
                        t.recurrence_day_of_week, t.recurrence_day_of_month, t.recurrence_month_of_year,
                        t.recurrence_end_at, t.recurrence_count, t.recurrence_occurrences,
+                       t.recurrence_rrule,
                        t.parent_task_id, t.next_occurrence_at, t.last_occurrence_at
                 FROM tasks t
                 WHERE t.organization_id = $1
@@ -106,6 +109,7 @@ impl TaskService {
                        t.is_recurring, t.recurrence_pattern, t.recurrence_interval,
                        t.recurrence_day_of_week, t.recurrence_day_of_month, t.recurrence_month_of_year,
                        t.recurrence_end_at, t.recurrence_count, t.recurrence_occurrences,
+                       t.recurrence_rrule,
                        t.parent_task_id, t.next_occurrence_at, t.last_occurrence_at
                 FROM tasks t
                 WHERE t.organization_id = $1
@@ -213,6 +217,7 @@ impl TaskService {
                    is_recurring, recurrence_pattern, recurrence_interval,
                    recurrence_day_of_week, recurrence_day_of_month, recurrence_month_of_year,
                    recurrence_end_at, recurrence_count, recurrence_occurrences,
+                   recurrence_rrule,
                    parent_task_id, next_occurrence_at, last_occurrence_at
             FROM tasks
             WHERE id = $1 AND organization_id = $2
@@ -281,9 +286,9 @@ impl TaskService {
                               is_recurring, recurrence_pattern, recurrence_interval,
                               recurrence_day_of_week, recurrence_day_of_month, recurrence_month_of_year,
                               recurrence_end_at, recurrence_count, recurrence_occurrences,
-                              next_occurrence_at)
+                              recurrence_rrule, next_occurrence_at)
             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, 'open',
-                    $11, $12, $13, $14, $15, $16, $17, $18, 0, $19)
+                    $11, $12, $13, $14, $15, $16, $17, $18, 0, $19, $20)
             RETURNING id, organization_id, title, description, task_type,
                       related_entity_type, related_entity_id, assignee_id,
                       due_at, completed_at, status, priority,
@@ -291,6 +296,7 @@ impl TaskService {
                       is_recurring, recurrence_pattern, recurrence_interval,
                       recurrence_day_of_week, recurrence_day_of_month, recurrence_month_of_year,
                       recurrence_end_at, recurrence_count, recurrence_occurrences,
+                      recurrence_rrule,
                       parent_task_id, next_occurrence_at, last_occurrence_at
             "#,
         )
@@ -312,6 +318,7 @@ impl TaskService {
         .bind(input.recurrence_month_of_year)
         .bind(input.recurrence_end_at)
         .bind(input.recurrence_count)
+        .bind(&input.recurrence_rrule)
         .bind(next_occurrence_at)
         .fetch_one(&self.db)
         .await?;
@@ -359,6 +366,7 @@ impl TaskService {
                       is_recurring, recurrence_pattern, recurrence_interval,
                       recurrence_day_of_week, recurrence_day_of_month, recurrence_month_of_year,
                       recurrence_end_at, recurrence_count, recurrence_occurrences,
+                      recurrence_rrule,
                       parent_task_id, next_occurrence_at, last_occurrence_at
             "#,
         )
@@ -428,6 +436,7 @@ impl TaskService {
                 recurrence_month_of_year = COALESCE($18, recurrence_month_of_year),
                 recurrence_end_at = COALESCE($19, recurrence_end_at),
                 recurrence_count = COALESCE($20, recurrence_count),
+                recurrence_rrule = COALESCE($21, recurrence_rrule),
                 updated_at = NOW()
             WHERE id = $1 AND organization_id = $2
             RETURNING id, organization_id, title, description, task_type,
@@ -437,6 +446,7 @@ impl TaskService {
                       is_recurring, recurrence_pattern, recurrence_interval,
                       recurrence_day_of_week, recurrence_day_of_month, recurrence_month_of_year,
                       recurrence_end_at, recurrence_count, recurrence_occurrences,
+                      recurrence_rrule,
                       parent_task_id, next_occurrence_at, last_occurrence_at
             "#,
         )
@@ -460,6 +470,7 @@ impl TaskService {
         .bind(input.recurrence_month_of_year)
         .bind(input.recurrence_end_at)
         .bind(input.recurrence_count)
+        .bind(&input.recurrence_rrule)
         .fetch_one(&self.db)
         .await?;
 
@@ -499,6 +510,7 @@ impl TaskService {
                       is_recurring, recurrence_pattern, recurrence_interval,
                       recurrence_day_of_week, recurrence_day_of_month, recurrence_month_of_year,
                       recurrence_end_at, recurrence_count, recurrence_occurrences,
+                      recurrence_rrule,
                       parent_task_id, next_occurrence_at, last_occurrence_at
             "#,
         )
@@ -623,6 +635,79 @@ impl TaskService {
         Ok(stats)
     }
 
+    /// Time-bucketed, filterable task analytics for remediation velocity
+    /// dashboards - completion burndown, overdue accumulation, etc - as
+    /// opposed to the fixed snapshot `get_stats` returns. Not cached since
+    /// the `from`/`to`/`group_by`/`metric` combination is effectively
+    /// unbounded.
+    pub async fn get_analytics(&self, org_id: Uuid, query: TaskAnalyticsQuery) -> AppResult<TaskAnalytics> {
+        let group_expr = match query.group_by {
+            TaskAnalyticsGroupBy::Status => "t.status",
+            TaskAnalyticsGroupBy::Type => "COALESCE(t.task_type, 'unspecified')",
+            TaskAnalyticsGroupBy::Priority => "COALESCE(t.priority, 'unspecified')",
+            TaskAnalyticsGroupBy::Assignee => "COALESCE(u.name, 'Unassigned')",
+            TaskAnalyticsGroupBy::RelatedEntityType => "COALESCE(t.related_entity_type, 'none')",
+            TaskAnalyticsGroupBy::Day => "to_char(date_trunc('day', t.created_at), 'YYYY-MM-DD')",
+            TaskAnalyticsGroupBy::Week => "to_char(date_trunc('week', t.created_at), 'YYYY-MM-DD')",
+            TaskAnalyticsGroupBy::Month => "to_char(date_trunc('month', t.created_at), 'YYYY-MM')",
+        };
+
+        let metric_expr = match query.metric {
+            TaskAnalyticsMetric::Count => "COUNT(*)::float8",
+            TaskAnalyticsMetric::Completed => "COUNT(*) FILTER (WHERE t.status = 'completed')::float8",
+            TaskAnalyticsMetric::Overdue => {
+                "COUNT(*) FILTER (WHERE t.status != 'completed' AND t.due_at IS NOT NULL AND t.due_at < NOW())::float8"
+            }
+            TaskAnalyticsMetric::AvgCompletionHours => {
+                "COALESCE(AVG(EXTRACT(EPOCH FROM (t.completed_at - t.created_at)) / 3600.0) FILTER (WHERE t.completed_at IS NOT NULL), 0)::float8"
+            }
+        };
+
+        let join_clause = if query.group_by == TaskAnalyticsGroupBy::Assignee {
+            "LEFT JOIN users u ON u.id = t.assignee_id"
+        } else {
+            ""
+        };
+
+        let sql = format!(
+            r#"
+            SELECT {group_expr} AS bucket, {metric_expr} AS value
+            FROM tasks t
+            {join_clause}
+            WHERE t.organization_id = $1
+              AND ($2::text IS NULL OR t.status = $2)
+              AND ($3::text IS NULL OR t.task_type = $3)
+              AND ($4::text IS NULL OR t.priority = $4)
+              AND ($5::uuid IS NULL OR t.assignee_id = $5)
+              AND ($6::text IS NULL OR t.related_entity_type = $6)
+              AND ($7::uuid IS NULL OR t.related_entity_id = $7)
+              AND ($8::timestamptz IS NULL OR t.created_at >= $8)
+              AND ($9::timestamptz IS NULL OR t.created_at <= $9)
+            GROUP BY {group_expr}
+            ORDER BY bucket
+            "#,
+        );
+
+        let buckets: Vec<TaskAnalyticsBucket> = sqlx::query_as(&sql)
+            .bind(org_id)
+            .bind(&query.status)
+            .bind(&query.task_type)
+            .bind(&query.priority)
+            .bind(query.assignee_id)
+            .bind(&query.related_entity_type)
+            .bind(query.related_entity_id)
+            .bind(query.from)
+            .bind(query.to)
+            .fetch_all(&self.db)
+            .await?;
+
+        Ok(TaskAnalytics {
+            group_by: query.group_by,
+            metric: query.metric,
+            buckets,
+        })
+    }
+
     // ==================== My Tasks ====================
 
     /// Get tasks assigned to a specific user
@@ -636,6 +721,7 @@ impl TaskService {
                    is_recurring, recurrence_pattern, recurrence_interval,
                    recurrence_day_of_week, recurrence_day_of_month, recurrence_month_of_year,
                    recurrence_end_at, recurrence_count, recurrence_occurrences,
+                   recurrence_rrule,
                    parent_task_id, next_occurrence_at, last_occurrence_at
             FROM tasks
             WHERE organization_id = $1 AND assignee_id = $2 AND status != 'completed'
@@ -668,6 +754,7 @@ impl TaskService {
                    is_recurring, recurrence_pattern, recurrence_interval,
                    recurrence_day_of_week, recurrence_day_of_month, recurrence_month_of_year,
                    recurrence_end_at, recurrence_count, recurrence_occurrences,
+                   recurrence_rrule,
                    parent_task_id, next_occurrence_at, last_occurrence_at
             FROM tasks
             WHERE organization_id = $1
@@ -825,7 +912,9 @@ impl TaskService {
 
     // ==================== Recurring Tasks ====================
 
-    /// Calculate the next occurrence date based on recurrence pattern
+    /// Calculate the next occurrence date based on recurrence pattern. Thin
+    /// wrapper around the pure [`RecurrencePattern::next_after`] so callers
+    /// can keep passing the raw column value stored on `Task`.
     fn calculate_next_occurrence(
         &self,
         current: DateTime<Utc>,
@@ -835,81 +924,32 @@ impl TaskService {
         day_of_month: Option<i32>,
         month_of_year: Option<i32>,
     ) -> Option<DateTime<Utc>> {
-        let interval = interval.max(1) as i64;
-
-        match pattern {
-            "daily" => Some(current + Duration::days(interval)),
-            "weekly" => {
-                // If day_of_week is specified, find next occurrence on that day
-                if let Some(dow) = day_of_week {
-                    let target_weekday = match dow {
-                        0 => Weekday::Sun,
-                        1 => Weekday::Mon,
-                        2 => Weekday::Tue,
-                        3 => Weekday::Wed,
-                        4 => Weekday::Thu,
-                        5 => Weekday::Fri,
-                        6 => Weekday::Sat,
-                        _ => Weekday::Mon,
-                    };
-                    let current_weekday = current.weekday();
-                    let days_until = (target_weekday.num_days_from_sunday() as i64
-                        - current_weekday.num_days_from_sunday() as i64
-                        + 7) % 7;
-                    let days_until = if days_until == 0 { 7 * interval } else { days_until + 7 * (interval - 1) };
-                    Some(current + Duration::days(days_until))
-                } else {
-                    Some(current + Duration::weeks(interval))
-                }
-            }
-            "biweekly" => Some(current + Duration::weeks(2 * interval)),
-            "monthly" => {
-                let dom = day_of_month.unwrap_or(current.day() as i32) as u32;
-                let mut next_month = current.month() + interval as u32;
-                let mut year = current.year();
-                while next_month > 12 {
-                    next_month -= 12;
-                    year += 1;
-                }
-                // Handle months with fewer days
-                let days_in_month = NaiveDate::from_ymd_opt(year, next_month, 1)
-                    .and_then(|d| d.with_month(next_month + 1))
-                    .map(|d| d.pred_opt().unwrap().day())
-                    .unwrap_or(28);
-                let actual_day = dom.min(days_in_month);
-                NaiveDate::from_ymd_opt(year, next_month, actual_day)
-                    .and_then(|d| d.and_time(NaiveTime::from_hms_opt(0, 0, 0)?).and_local_timezone(Utc).single())
-            }
-            "quarterly" => {
-                let dom = day_of_month.unwrap_or(current.day() as i32) as u32;
-                let mut next_month = current.month() + 3 * interval as u32;
-                let mut year = current.year();
-                while next_month > 12 {
-                    next_month -= 12;
-                    year += 1;
-                }
-                let days_in_month = NaiveDate::from_ymd_opt(year, next_month, 1)
-                    .and_then(|d| d.with_month(next_month + 1))
-                    .map(|d| d.pred_opt().unwrap().day())
-                    .unwrap_or(28);
-                let actual_day = dom.min(days_in_month);
-                NaiveDate::from_ymd_opt(year, next_month, actual_day)
-                    .and_then(|d| d.and_time(NaiveTime::from_hms_opt(0, 0, 0)?).and_local_timezone(Utc).single())
-            }
-            "yearly" => {
-                let moy = month_of_year.unwrap_or(current.month() as i32) as u32;
-                let dom = day_of_month.unwrap_or(current.day() as i32) as u32;
-                let next_year = current.year() + interval as i32;
-                let days_in_month = NaiveDate::from_ymd_opt(next_year, moy, 1)
-                    .and_then(|d| d.with_month(moy + 1))
-                    .map(|d| d.pred_opt().unwrap().day())
-                    .unwrap_or(28);
-                let actual_day = dom.min(days_in_month);
-                NaiveDate::from_ymd_opt(next_year, moy, actual_day)
-                    .and_then(|d| d.and_time(NaiveTime::from_hms_opt(0, 0, 0)?).and_local_timezone(Utc).single())
-            }
-            _ => None,
+        RecurrencePattern::from_str(pattern)
+            .map(|p| p.next_after(current, interval, day_of_week, day_of_month, month_of_year))
+    }
+
+    /// Calculate the next occurrence for a recurring task template, preferring
+    /// `recurrence_rrule` when set (validated at create/update time so a
+    /// parse failure here just means no further occurrences are scheduled)
+    /// and otherwise falling back to the legacy pattern columns.
+    fn calculate_next_occurrence_for_task(
+        &self,
+        template: &Task,
+        current: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        if let Some(rrule) = &template.recurrence_rrule {
+            let dtstart = template.due_at.unwrap_or(current);
+            return RRule::parse(rrule).ok()?.next_after(dtstart, current);
         }
+        let pattern = template.recurrence_pattern.as_ref()?;
+        self.calculate_next_occurrence(
+            current,
+            pattern,
+            template.recurrence_interval.unwrap_or(1),
+            template.recurrence_day_of_week,
+            template.recurrence_day_of_month,
+            template.recurrence_month_of_year,
+        )
     }
 
     /// Get all recurring task templates for an organization
@@ -923,6 +963,7 @@ impl TaskService {
                    is_recurring, recurrence_pattern, recurrence_interval,
                    recurrence_day_of_week, recurrence_day_of_month, recurrence_month_of_year,
                    recurrence_end_at, recurrence_count, recurrence_occurrences,
+                   recurrence_rrule,
                    parent_task_id, next_occurrence_at, last_occurrence_at
             FROM tasks
             WHERE organization_id = $1 AND is_recurring = true
@@ -948,6 +989,7 @@ impl TaskService {
                    is_recurring, recurrence_pattern, recurrence_interval,
                    recurrence_day_of_week, recurrence_day_of_month, recurrence_month_of_year,
                    recurrence_end_at, recurrence_count, recurrence_occurrences,
+                   recurrence_rrule,
                    parent_task_id, next_occurrence_at, last_occurrence_at
             FROM tasks
             WHERE organization_id = $1
@@ -971,6 +1013,42 @@ impl TaskService {
         // Calculate the due date for this occurrence (same as next_occurrence_at)
         let due_at = template.next_occurrence_at;
 
+        // Guard against double-materializing the same occurrence if the worker
+        // and a manual `/tasks/recurring/process` call race on the same tick.
+        if let Some(scheduled_at) = due_at {
+            let existing = sqlx::query_scalar::<_, Uuid>(
+                "SELECT created_task_id FROM task_recurrence_history
+                 WHERE task_id = $1 AND scheduled_at = $2 AND created_task_id IS NOT NULL",
+            )
+            .bind(template.id)
+            .bind(scheduled_at)
+            .fetch_optional(&self.db)
+            .await?;
+
+            if let Some(created_task_id) = existing {
+                let existing_task = sqlx::query_as::<_, Task>(
+                    r#"
+                    SELECT id, organization_id, title, description, task_type,
+                           related_entity_type, related_entity_id, assignee_id,
+                           due_at, completed_at, status, priority,
+                           created_by, created_at, updated_at,
+                           is_recurring, recurrence_pattern, recurrence_interval,
+                           recurrence_day_of_week, recurrence_day_of_month, recurrence_month_of_year,
+                           recurrence_end_at, recurrence_count, recurrence_occurrences,
+                           recurrence_rrule,
+                           parent_task_id, next_occurrence_at, last_occurrence_at
+                    FROM tasks
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(created_task_id)
+                .fetch_one(&self.db)
+                .await?;
+
+                return Ok(existing_task);
+            }
+        }
+
         // Create the task occurrence
         let occurrence = sqlx::query_as::<_, Task>(
             r#"
@@ -985,6 +1063,7 @@ impl TaskService {
                       is_recurring, recurrence_pattern, recurrence_interval,
                       recurrence_day_of_week, recurrence_day_of_month, recurrence_month_of_year,
                       recurrence_end_at, recurrence_count, recurrence_occurrences,
+                      recurrence_rrule,
                       parent_task_id, next_occurrence_at, last_occurrence_at
             "#,
         )
@@ -1003,22 +1082,9 @@ impl TaskService {
         .await?;
 
         // Calculate the next occurrence date
-        let next_occurrence = if let Some(pattern) = &template.recurrence_pattern {
-            if let Some(current) = template.next_occurrence_at {
-                self.calculate_next_occurrence(
-                    current,
-                    pattern,
-                    template.recurrence_interval.unwrap_or(1),
-                    template.recurrence_day_of_week,
-                    template.recurrence_day_of_month,
-                    template.recurrence_month_of_year,
-                )
-            } else {
-                None
-            }
-        } else {
-            None
-        };
+        let next_occurrence = template
+            .next_occurrence_at
+            .and_then(|current| self.calculate_next_occurrence_for_task(template, current));
 
         // Update the template with new next_occurrence_at and increment occurrences
         sqlx::query(
@@ -1072,6 +1138,56 @@ impl TaskService {
         Ok(created_count)
     }
 
+    /// Get every recurring task template across all organizations that is due
+    /// for its next occurrence, for the background scheduler.
+    async fn get_all_tasks_needing_occurrence(&self) -> AppResult<Vec<Task>> {
+        let now = Utc::now();
+        let tasks = sqlx::query_as::<_, Task>(
+            r#"
+            SELECT id, organization_id, title, description, task_type,
+                   related_entity_type, related_entity_id, assignee_id,
+                   due_at, completed_at, status, priority,
+                   created_by, created_at, updated_at,
+                   is_recurring, recurrence_pattern, recurrence_interval,
+                   recurrence_day_of_week, recurrence_day_of_month, recurrence_month_of_year,
+                   recurrence_end_at, recurrence_count, recurrence_occurrences,
+                   recurrence_rrule,
+                   parent_task_id, next_occurrence_at, last_occurrence_at
+            FROM tasks
+            WHERE is_recurring = true
+              AND next_occurrence_at IS NOT NULL
+              AND next_occurrence_at <= $1
+              AND (recurrence_end_at IS NULL OR recurrence_end_at > $1)
+              AND (recurrence_count IS NULL OR recurrence_occurrences < recurrence_count)
+            "#,
+        )
+        .bind(now)
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(tasks)
+    }
+
+    /// Materialize the next occurrence for every due recurring task across all
+    /// organizations. Called on a timer by [`crate::workers::TaskRecurrenceWorker`];
+    /// the manual `/tasks/recurring/process` route still exists for an
+    /// administrator to force a single organization's tasks forward.
+    pub async fn process_due_recurring_tasks(&self) -> AppResult<i32> {
+        let tasks = self.get_all_tasks_needing_occurrence().await?;
+        let mut created_count = 0;
+
+        for task in tasks {
+            match self.create_occurrence(&task).await {
+                Ok(_) => created_count += 1,
+                Err(e) => {
+                    tracing::warn!("Failed to create occurrence for task {}: {}", task.id, e);
+                }
+            }
+        }
+
+        Ok(created_count)
+    }
+
     /// Get recurrence history for a recurring task template
     pub async fn get_recurrence_history(
         &self,
@@ -1147,6 +1263,7 @@ impl TaskService {
                    is_recurring, recurrence_pattern, recurrence_interval,
                    recurrence_day_of_week, recurrence_day_of_month, recurrence_month_of_year,
                    recurrence_end_at, recurrence_count, recurrence_occurrences,
+                   recurrence_rrule,
                    parent_task_id, next_occurrence_at, last_occurrence_at
             FROM tasks
             WHERE id = $1 AND organization_id = $2 AND is_recurring = true
@@ -1159,18 +1276,9 @@ impl TaskService {
         .ok_or_else(|| AppError::NotFound("Recurring task not found".to_string()))?;
 
         // Calculate the next occurrence
-        let next_occurrence = if let (Some(pattern), Some(current)) = (&task.recurrence_pattern, task.next_occurrence_at) {
-            self.calculate_next_occurrence(
-                current,
-                pattern,
-                task.recurrence_interval.unwrap_or(1),
-                task.recurrence_day_of_week,
-                task.recurrence_day_of_month,
-                task.recurrence_month_of_year,
-            )
-        } else {
-            None
-        };
+        let next_occurrence = task
+            .next_occurrence_at
+            .and_then(|current| self.calculate_next_occurrence_for_task(&task, current));
 
         // Record the skip in history
         let occurrence_num = task.recurrence_occurrences.unwrap_or(0) + 1;
@@ -1202,6 +1310,7 @@ impl TaskService {
                       is_recurring, recurrence_pattern, recurrence_interval,
                       recurrence_day_of_week, recurrence_day_of_month, recurrence_month_of_year,
                       recurrence_end_at, recurrence_count, recurrence_occurrences,
+                      recurrence_rrule,
                       parent_task_id, next_occurrence_at, last_occurrence_at
             "#,
         )
@@ -1229,6 +1338,7 @@ impl TaskService {
                       is_recurring, recurrence_pattern, recurrence_interval,
                       recurrence_day_of_week, recurrence_day_of_month, recurrence_month_of_year,
                       recurrence_end_at, recurrence_count, recurrence_occurrences,
+                      recurrence_rrule,
                       parent_task_id, next_occurrence_at, last_occurrence_at
             "#,
         )
@@ -1264,6 +1374,7 @@ impl TaskService {
                       is_recurring, recurrence_pattern, recurrence_interval,
                       recurrence_day_of_week, recurrence_day_of_month, recurrence_month_of_year,
                       recurrence_end_at, recurrence_count, recurrence_occurrences,
+                      recurrence_rrule,
                       parent_task_id, next_occurrence_at, last_occurrence_at
             "#,
         )
@@ -0,0 +1,169 @@
+//! Envelope encryption for evidence blobs at rest.
+//!
+//! Each object gets a fresh random 256-bit data key. The payload is sealed with
+//! AES-256-GCM under that data key and a random nonce; the data key itself is
+//! wrapped (also AES-256-GCM) under a master key from config. The wrapped key,
+//! its nonce, and the payload nonce are stored inline in a small self-describing
+//! header so a blob carries everything needed to decrypt it given the master
+//! key:
+//!
+//! ```text
+//! [ magic "OGEV" ][ version:u8 ][ key_nonce:12 ][ wrapped_key_len:u16 ]
+//! [ wrapped_key ][ payload_nonce:12 ][ ciphertext... ]
+//! ```
+
+use aes_gcm::{
+    aead::{Aead, KeyInit},
+    Aes256Gcm, Nonce,
+};
+use rand::RngCore;
+
+use crate::utils::{AppError, AppResult};
+
+const MAGIC: &[u8; 4] = b"OGEV";
+const VERSION: u8 = 1;
+const NONCE_LEN: usize = 12;
+
+/// Transparent evidence cipher built from a 256-bit master key (hex-encoded).
+#[derive(Clone)]
+pub struct EvidenceCipher {
+    master: Aes256Gcm,
+}
+
+impl EvidenceCipher {
+    /// Build a cipher from a hex-encoded 32-byte master key.
+    pub fn new(master_key_hex: &str) -> AppResult<Self> {
+        let bytes = hex_decode(master_key_hex)
+            .map_err(|_| AppError::InternalServerError("Invalid storage master key hex".to_string()))?;
+        if bytes.len() != 32 {
+            return Err(AppError::InternalServerError(format!(
+                "Storage master key must be 32 bytes, got {}",
+                bytes.len()
+            )));
+        }
+        let key = aes_gcm::Key::<Aes256Gcm>::from_slice(&bytes);
+        Ok(Self {
+            master: Aes256Gcm::new(key),
+        })
+    }
+
+    /// Seal a payload into a self-describing encrypted blob.
+    pub fn seal(&self, plaintext: &[u8]) -> AppResult<Vec<u8>> {
+        // Fresh per-object data key.
+        let mut data_key = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut data_key);
+        let data_cipher = Aes256Gcm::new(aes_gcm::Key::<Aes256Gcm>::from_slice(&data_key));
+
+        let payload_nonce = random_nonce();
+        let ciphertext = data_cipher
+            .encrypt(Nonce::from_slice(&payload_nonce), plaintext)
+            .map_err(|_| AppError::InternalServerError("Evidence encryption failed".to_string()))?;
+
+        // Wrap the data key under the master key.
+        let key_nonce = random_nonce();
+        let wrapped_key = self
+            .master
+            .encrypt(Nonce::from_slice(&key_nonce), data_key.as_slice())
+            .map_err(|_| AppError::InternalServerError("Data-key wrap failed".to_string()))?;
+
+        let mut out = Vec::with_capacity(
+            MAGIC.len() + 1 + NONCE_LEN + 2 + wrapped_key.len() + NONCE_LEN + ciphertext.len(),
+        );
+        out.extend_from_slice(MAGIC);
+        out.push(VERSION);
+        out.extend_from_slice(&key_nonce);
+        out.extend_from_slice(&(wrapped_key.len() as u16).to_be_bytes());
+        out.extend_from_slice(&wrapped_key);
+        out.extend_from_slice(&payload_nonce);
+        out.extend_from_slice(&ciphertext);
+        Ok(out)
+    }
+
+    /// Open a blob produced by [`seal`]. Returns an error if the header is
+    /// malformed or authentication fails.
+    pub fn open(&self, blob: &[u8]) -> AppResult<Vec<u8>> {
+        let mut cursor = 0usize;
+        let take = |cursor: &mut usize, n: usize| -> AppResult<&[u8]> {
+            let end = *cursor + n;
+            if end > blob.len() {
+                return Err(AppError::InternalServerError("Truncated evidence blob".to_string()));
+            }
+            let slice = &blob[*cursor..end];
+            *cursor = end;
+            Ok(slice)
+        };
+
+        if take(&mut cursor, MAGIC.len())? != MAGIC {
+            return Err(AppError::InternalServerError("Not an encrypted evidence blob".to_string()));
+        }
+        let version = take(&mut cursor, 1)?[0];
+        if version != VERSION {
+            return Err(AppError::InternalServerError(format!(
+                "Unsupported evidence blob version {}",
+                version
+            )));
+        }
+
+        let key_nonce = take(&mut cursor, NONCE_LEN)?.to_vec();
+        let wrapped_len = {
+            let b = take(&mut cursor, 2)?;
+            u16::from_be_bytes([b[0], b[1]]) as usize
+        };
+        let wrapped_key = take(&mut cursor, wrapped_len)?.to_vec();
+        let payload_nonce = take(&mut cursor, NONCE_LEN)?.to_vec();
+        let ciphertext = &blob[cursor..];
+
+        let data_key = self
+            .master
+            .decrypt(Nonce::from_slice(&key_nonce), wrapped_key.as_slice())
+            .map_err(|_| AppError::InternalServerError("Data-key unwrap failed".to_string()))?;
+
+        let data_cipher = Aes256Gcm::new(aes_gcm::Key::<Aes256Gcm>::from_slice(&data_key));
+        data_cipher
+            .decrypt(Nonce::from_slice(&payload_nonce), ciphertext)
+            .map_err(|_| AppError::InternalServerError("Evidence decryption failed".to_string()))
+    }
+}
+
+fn random_nonce() -> [u8; NONCE_LEN] {
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    nonce
+}
+
+fn hex_decode(hex: &str) -> Result<Vec<u8>, ()> {
+    if hex.len() % 2 != 0 {
+        return Err(());
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| ()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key() -> String {
+        "0".repeat(64)
+    }
+
+    #[test]
+    fn seal_open_roundtrip() {
+        let cipher = EvidenceCipher::new(&key()).unwrap();
+        let plaintext = b"sensitive evidence with PII";
+        let blob = cipher.seal(plaintext).unwrap();
+        assert_ne!(&blob[..], &plaintext[..]);
+        assert_eq!(cipher.open(&blob).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn tampered_blob_fails() {
+        let cipher = EvidenceCipher::new(&key()).unwrap();
+        let mut blob = cipher.seal(b"hello").unwrap();
+        let last = blob.len() - 1;
+        blob[last] ^= 0xff;
+        assert!(cipher.open(&blob).is_err());
+    }
+}
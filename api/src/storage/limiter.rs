@@ -0,0 +1,94 @@
+//! Per-organization egress quota for evidence downloads.
+//!
+//! Modeled on the gitolfs3 `DownloadLimiter`: each org has a running count of
+//! bytes served within a rolling hourly window. A background task resets the
+//! counters every hour. The counters are persisted to a small JSON file so a
+//! restart doesn't silently reset an org's consumed quota mid-window.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::utils::{AppError, AppResult};
+
+/// How often the background task resets the per-org counters.
+const RESET_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Tracks bytes served per organization and enforces a configured ceiling.
+#[derive(Clone)]
+pub struct DownloadLimiter {
+    limit: u64,
+    state: Arc<Mutex<HashMap<Uuid, u64>>>,
+    persist_path: PathBuf,
+}
+
+impl DownloadLimiter {
+    /// Create a limiter capping each org to `limit` bytes per hour. A zero
+    /// limit means unlimited (callers should not construct one in that case).
+    pub fn new(limit: u64, persist_path: PathBuf) -> Self {
+        let state = Self::load(&persist_path);
+        Self {
+            limit,
+            state: Arc::new(Mutex::new(state)),
+            persist_path,
+        }
+    }
+
+    /// Record `bytes` against `org_id`, returning an error (HTTP 429-equivalent)
+    /// when the download would push the org over its quota for the window.
+    pub async fn check_and_add(&self, org_id: Uuid, bytes: u64) -> AppResult<()> {
+        let mut guard = self.state.lock().await;
+        let used = guard.entry(org_id).or_insert(0);
+        if *used + bytes > self.limit {
+            return Err(AppError::QuotaExceeded(format!(
+                "Download quota exceeded: {} of {} bytes used this window",
+                used, self.limit
+            )));
+        }
+        *used += bytes;
+        let snapshot = guard.clone();
+        drop(guard);
+        self.persist(&snapshot);
+        Ok(())
+    }
+
+    /// Reset all counters (invoked hourly by the background task).
+    pub async fn reset(&self) {
+        let mut guard = self.state.lock().await;
+        guard.clear();
+        let snapshot = guard.clone();
+        drop(guard);
+        self.persist(&snapshot);
+    }
+
+    /// Spawn the hourly reset loop, returning immediately.
+    pub fn spawn_reset_task(&self) {
+        let limiter = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(RESET_INTERVAL);
+            ticker.tick().await; // consume the immediate first tick
+            loop {
+                ticker.tick().await;
+                limiter.reset().await;
+            }
+        });
+    }
+
+    fn load(path: &PathBuf) -> HashMap<Uuid, u64> {
+        std::fs::read(path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    fn persist(&self, state: &HashMap<Uuid, u64>) {
+        if let Ok(bytes) = serde_json::to_vec(state) {
+            // Best-effort persistence; a lost write only loosens the quota.
+            let _ = std::fs::write(&self.persist_path, bytes);
+        }
+    }
+}
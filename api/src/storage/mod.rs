@@ -1,18 +1,39 @@
 use std::path::PathBuf;
 use std::time::Duration;
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
 use uuid::Uuid;
 
 use crate::config::StorageConfig;
-use crate::utils::{AppError, AppResult};
+use crate::utils::{hmac_sha256, AppError, AppResult};
+
+mod crypto;
+mod limiter;
+pub use crypto::EvidenceCipher;
+pub use limiter::DownloadLimiter;
+
+/// Minimum S3 multipart part size. S3 requires every part except the last to be
+/// at least 5 MiB; we buffer to 8 MiB so typical evidence exports produce a
+/// small, predictable number of parts.
+const MULTIPART_PART_SIZE: usize = 8 * 1024 * 1024;
 
 /// Storage client that abstracts over local filesystem or S3 storage
 #[derive(Clone)]
 pub struct StorageClient {
     backend: StorageBackend,
+    /// Present when envelope encryption at rest is enabled.
+    cipher: Option<EvidenceCipher>,
+    /// Secret for signing/verifying local-storage download tokens.
+    download_token_secret: Option<String>,
+    /// Present when a per-org egress quota is configured.
+    limiter: Option<DownloadLimiter>,
 }
 
+/// Validity window for a signed local-storage download token.
+const DOWNLOAD_TOKEN_TTL: Duration = Duration::from_secs(3600);
+
 #[derive(Clone)]
 enum StorageBackend {
     Local(LocalStorage),
@@ -73,6 +94,40 @@ impl LocalStorage {
         Ok(key)
     }
 
+    async fn upload_stream<S>(
+        &self,
+        org_id: Uuid,
+        evidence_id: Uuid,
+        filename: &str,
+        _content_type: &str,
+        mut stream: S,
+    ) -> AppResult<String>
+    where
+        S: Stream<Item = AppResult<Bytes>> + Unpin,
+    {
+        let file_path = self.file_path(org_id, evidence_id, filename);
+        self.ensure_dir(&file_path).await?;
+
+        let mut file = fs::File::create(&file_path)
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to create file: {}", e)))?;
+
+        // Stream chunks straight to the open handle so memory stays flat.
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            file.write_all(&chunk)
+                .await
+                .map_err(|e| AppError::InternalServerError(format!("Failed to write file: {}", e)))?;
+        }
+
+        file.flush()
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("Failed to flush file: {}", e)))?;
+
+        let key = format!("orgs/{}/evidence/{}/{}", org_id, evidence_id, filename);
+        Ok(key)
+    }
+
     async fn download(&self, key: &str) -> AppResult<(Vec<u8>, String)> {
         let file_path = self.base_path.join(key);
 
@@ -105,6 +160,47 @@ impl LocalStorage {
         file_path.exists()
     }
 
+    async fn object_size(&self, key: &str) -> AppResult<u64> {
+        let file_path = self.base_path.join(key);
+        let meta = fs::metadata(&file_path)
+            .await
+            .map_err(|e| AppError::NotFound(format!("File not found: {}", e)))?;
+        Ok(meta.len())
+    }
+
+    /// Enumerate every evidence key by walking the `orgs/**/evidence/**` tree.
+    async fn list_evidence_keys(&self) -> AppResult<Vec<String>> {
+        let root = self.base_path.join("orgs");
+        let mut keys = Vec::new();
+        if !root.exists() {
+            return Ok(keys);
+        }
+
+        let mut stack = vec![root];
+        while let Some(dir) = stack.pop() {
+            let mut entries = fs::read_dir(&dir)
+                .await
+                .map_err(|e| AppError::InternalServerError(format!("Failed to read directory: {}", e)))?;
+            while let Some(entry) = entries
+                .next_entry()
+                .await
+                .map_err(|e| AppError::InternalServerError(format!("Failed to read directory entry: {}", e)))?
+            {
+                let path = entry.path();
+                let file_type = entry
+                    .file_type()
+                    .await
+                    .map_err(|e| AppError::InternalServerError(format!("Failed to stat entry: {}", e)))?;
+                if file_type.is_dir() {
+                    stack.push(path);
+                } else if let Ok(rel) = path.strip_prefix(&self.base_path) {
+                    keys.push(rel.to_string_lossy().replace('\\', "/"));
+                }
+            }
+        }
+        Ok(keys)
+    }
+
     fn get_download_url(&self, key: &str) -> AppResult<String> {
         // For local storage, return a path that the API can serve
         // The actual serving is handled by a separate endpoint
@@ -143,36 +239,31 @@ impl S3Storage {
 
         let region = Region::new(config.region.clone());
 
-        let sdk_config = if let (Some(access_key), Some(secret_key)) =
+        // Base loader: explicit static credentials when provided, otherwise the
+        // default provider chain (env, profile, container, IMDS).
+        let mut builder = aws_config::defaults(BehaviorVersion::latest()).region(region.clone());
+
+        if let (Some(access_key), Some(secret_key)) =
             (&config.access_key_id, &config.secret_access_key)
         {
-            let credentials = Credentials::new(
-                access_key,
-                secret_key,
-                None,
-                None,
-                "opengrc",
-            );
-
-            let mut builder = aws_config::defaults(BehaviorVersion::latest())
-                .region(region.clone())
-                .credentials_provider(credentials);
-
-            if let Some(endpoint) = &config.endpoint {
-                builder = builder.endpoint_url(endpoint);
-            }
+            let credentials = Credentials::new(access_key, secret_key, None, None, "opengrc");
+            builder = builder.credentials_provider(credentials);
+        }
 
-            builder.load().await
-        } else {
-            let mut builder = aws_config::defaults(BehaviorVersion::latest())
-                .region(region.clone());
+        if let Some(endpoint) = &config.endpoint {
+            builder = builder.endpoint_url(endpoint);
+        }
 
-            if let Some(endpoint) = &config.endpoint {
-                builder = builder.endpoint_url(endpoint);
-            }
+        // Layer STS AssumeRole / web-identity over the base chain so the client
+        // transparently refreshes short-lived credentials and deployments can
+        // run with no long-lived secrets in config.
+        if let Some(role_arn) = &config.role_arn {
+            let base = builder.clone().load().await;
+            let provider = Self::assume_role_provider(config, role_arn, &base, &region).await;
+            builder = builder.credentials_provider(provider);
+        }
 
-            builder.load().await
-        };
+        let sdk_config = builder.load().await;
 
         let mut s3_config_builder = aws_sdk_s3::config::Builder::from(&sdk_config);
 
@@ -186,6 +277,40 @@ impl S3Storage {
         Ok(Self { client, bucket })
     }
 
+    /// Build an `AssumeRoleProvider` for the configured role. When a
+    /// web-identity token file is present the role is assumed via
+    /// AssumeRoleWithWebIdentity (EKS IRSA / OIDC); otherwise the base
+    /// credential chain signs the AssumeRole call (static keys or instance
+    /// profile for cross-account hops).
+    async fn assume_role_provider(
+        config: &StorageConfig,
+        role_arn: &str,
+        base: &aws_config::SdkConfig,
+        region: &aws_sdk_s3::config::Region,
+    ) -> aws_config::sts::AssumeRoleProvider {
+        use aws_config::sts::AssumeRoleProvider;
+
+        let session_name = config
+            .session_name
+            .clone()
+            .unwrap_or_else(|| "opengrc".to_string());
+
+        let mut provider = AssumeRoleProvider::builder(role_arn)
+            .session_name(session_name)
+            .region(region.clone());
+
+        if let Some(external_id) = &config.external_id {
+            provider = provider.external_id(external_id);
+        }
+
+        match &config.web_identity_token_file {
+            Some(token_file) => provider
+                .build_from_web_identity_token_file(token_file)
+                .await,
+            None => provider.build_from_provider(base.credentials_provider().unwrap()).await,
+        }
+    }
+
     fn evidence_key(org_id: Uuid, evidence_id: Uuid, filename: &str) -> String {
         format!("orgs/{}/evidence/{}/{}", org_id, evidence_id, filename)
     }
@@ -216,6 +341,132 @@ impl S3Storage {
         Ok(key)
     }
 
+    async fn upload_stream<S>(
+        &self,
+        org_id: Uuid,
+        evidence_id: Uuid,
+        filename: &str,
+        content_type: &str,
+        mut stream: S,
+    ) -> AppResult<String>
+    where
+        S: Stream<Item = AppResult<Bytes>> + Unpin,
+    {
+        use aws_sdk_s3::types::CompletedMultipartUpload;
+
+        let key = Self::evidence_key(org_id, evidence_id, filename);
+
+        let create = self
+            .client
+            .create_multipart_upload()
+            .bucket(&self.bucket)
+            .key(&key)
+            .content_type(content_type)
+            .send()
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("S3 multipart init failed: {}", e)))?;
+
+        let upload_id = create
+            .upload_id()
+            .ok_or_else(|| AppError::InternalServerError("S3 did not return an upload id".to_string()))?
+            .to_string();
+
+        // Run the part uploads, aborting on the first error so S3 doesn't keep
+        // billing for orphaned parts.
+        match self
+            .upload_parts(&key, &upload_id, &mut stream)
+            .await
+        {
+            Ok(parts) => {
+                let completed = CompletedMultipartUpload::builder()
+                    .set_parts(Some(parts))
+                    .build();
+
+                self.client
+                    .complete_multipart_upload()
+                    .bucket(&self.bucket)
+                    .key(&key)
+                    .upload_id(&upload_id)
+                    .multipart_upload(completed)
+                    .send()
+                    .await
+                    .map_err(|e| AppError::InternalServerError(format!("S3 multipart complete failed: {}", e)))?;
+
+                Ok(key)
+            }
+            Err(err) => {
+                let _ = self
+                    .client
+                    .abort_multipart_upload()
+                    .bucket(&self.bucket)
+                    .key(&key)
+                    .upload_id(&upload_id)
+                    .send()
+                    .await;
+                Err(err)
+            }
+        }
+    }
+
+    async fn upload_parts<S>(
+        &self,
+        key: &str,
+        upload_id: &str,
+        stream: &mut S,
+    ) -> AppResult<Vec<aws_sdk_s3::types::CompletedPart>>
+    where
+        S: Stream<Item = AppResult<Bytes>> + Unpin,
+    {
+        let mut parts = Vec::new();
+        let mut buffer: Vec<u8> = Vec::with_capacity(MULTIPART_PART_SIZE);
+        let mut part_number: i32 = 1;
+
+        while let Some(chunk) = stream.next().await {
+            buffer.extend_from_slice(&chunk?);
+            if buffer.len() >= MULTIPART_PART_SIZE {
+                let body = std::mem::replace(&mut buffer, Vec::with_capacity(MULTIPART_PART_SIZE));
+                parts.push(self.upload_one_part(key, upload_id, part_number, body).await?);
+                part_number += 1;
+            }
+        }
+
+        // Flush the trailing remainder (always, so a zero-byte object still gets
+        // one part and `complete` succeeds).
+        if !buffer.is_empty() || parts.is_empty() {
+            parts.push(self.upload_one_part(key, upload_id, part_number, buffer).await?);
+        }
+
+        Ok(parts)
+    }
+
+    async fn upload_one_part(
+        &self,
+        key: &str,
+        upload_id: &str,
+        part_number: i32,
+        body: Vec<u8>,
+    ) -> AppResult<aws_sdk_s3::types::CompletedPart> {
+        use aws_sdk_s3::primitives::ByteStream;
+        use aws_sdk_s3::types::CompletedPart;
+
+        let resp = self
+            .client
+            .upload_part()
+            .bucket(&self.bucket)
+            .key(key)
+            .upload_id(upload_id)
+            .part_number(part_number)
+            .body(ByteStream::from(body))
+            .send()
+            .await
+            .map_err(|e| AppError::InternalServerError(format!("S3 upload_part failed: {}", e)))?;
+
+        Ok(CompletedPart::builder()
+            .set_e_tag(resp.e_tag().map(|s| s.to_string()))
+            .part_number(part_number)
+            .build())
+    }
+
     async fn download(&self, key: &str) -> AppResult<(Vec<u8>, String)> {
         let response = self
             .client
@@ -264,6 +515,58 @@ impl S3Storage {
             .is_ok()
     }
 
+    async fn object_size(&self, key: &str) -> AppResult<u64> {
+        let resp = self
+            .client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| AppError::NotFound(format!("File not found: {}", e)))?;
+        Ok(resp.content_length().unwrap_or(0).max(0) as u64)
+    }
+
+    /// Enumerate every evidence key under the `orgs/` prefix, following
+    /// `list_objects_v2` continuation tokens to completion.
+    async fn list_evidence_keys(&self) -> AppResult<Vec<String>> {
+        let mut keys = Vec::new();
+        let mut continuation: Option<String> = None;
+
+        loop {
+            let mut req = self
+                .client
+                .list_objects_v2()
+                .bucket(&self.bucket)
+                .prefix("orgs/");
+            if let Some(token) = &continuation {
+                req = req.continuation_token(token);
+            }
+
+            let resp = req
+                .send()
+                .await
+                .map_err(|e| AppError::InternalServerError(format!("S3 list failed: {}", e)))?;
+
+            for object in resp.contents() {
+                if let Some(key) = object.key() {
+                    keys.push(key.to_string());
+                }
+            }
+
+            if resp.is_truncated().unwrap_or(false) {
+                continuation = resp.next_continuation_token().map(|s| s.to_string());
+                if continuation.is_none() {
+                    break;
+                }
+            } else {
+                break;
+            }
+        }
+
+        Ok(keys)
+    }
+
     async fn get_presigned_download_url(&self, key: &str) -> AppResult<String> {
         use aws_sdk_s3::presigning::PresigningConfig;
 
@@ -336,7 +639,35 @@ impl StorageClient {
             StorageBackend::S3(S3Storage::new(config).await?)
         };
 
-        Ok(Self { backend })
+        let cipher = if config.encrypt_at_rest {
+            let master = config.encryption_master_key.clone().ok_or_else(|| {
+                AppError::InternalServerError(
+                    "STORAGE_ENCRYPTION_KEY must be set when encryption at rest is enabled".to_string(),
+                )
+            })?;
+            Some(EvidenceCipher::new(&master)?)
+        } else {
+            None
+        };
+
+        let limiter = if config.download_limit > 0 {
+            let persist_path = PathBuf::from(
+                config.local_path.clone().unwrap_or_else(|| "./storage".to_string()),
+            )
+            .join("download_quota.json");
+            let limiter = DownloadLimiter::new(config.download_limit, persist_path);
+            limiter.spawn_reset_task();
+            Some(limiter)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            backend,
+            cipher,
+            download_token_secret: config.download_token_secret.clone(),
+            limiter,
+        })
     }
 
     /// Upload a file to storage
@@ -348,6 +679,11 @@ impl StorageClient {
         content_type: &str,
         data: Vec<u8>,
     ) -> AppResult<String> {
+        // Transparently seal the payload when encryption at rest is enabled.
+        let data = match &self.cipher {
+            Some(cipher) => cipher.seal(&data)?,
+            None => data,
+        };
         match &self.backend {
             StorageBackend::Local(storage) => {
                 storage.upload(org_id, evidence_id, filename, content_type, data).await
@@ -358,14 +694,83 @@ impl StorageClient {
         }
     }
 
+    /// Upload a file from a byte stream. For S3 this uses the multipart
+    /// upload protocol (~8 MiB parts); for local storage it streams chunks
+    /// straight to the file handle - in both cases without buffering the
+    /// whole payload in memory, *unless* encryption at rest is enabled. The
+    /// cipher seals a single AEAD payload (there's no streaming AEAD
+    /// construct in this crate), so an encrypted upload must first assemble
+    /// the chunks into one buffer before sealing and handing it to the
+    /// backend's ordinary buffered upload.
+    pub async fn upload_evidence_stream<S>(
+        &self,
+        org_id: Uuid,
+        evidence_id: Uuid,
+        filename: &str,
+        content_type: &str,
+        mut stream: S,
+    ) -> AppResult<String>
+    where
+        S: Stream<Item = AppResult<Bytes>> + Unpin,
+    {
+        if let Some(cipher) = &self.cipher {
+            let mut buffer = Vec::new();
+            while let Some(chunk) = stream.next().await {
+                buffer.extend_from_slice(&chunk?);
+            }
+            let sealed = cipher.seal(&buffer)?;
+            return match &self.backend {
+                StorageBackend::Local(storage) => {
+                    storage.upload(org_id, evidence_id, filename, content_type, sealed).await
+                }
+                StorageBackend::S3(storage) => {
+                    storage.upload(org_id, evidence_id, filename, content_type, sealed).await
+                }
+            };
+        }
+
+        match &self.backend {
+            StorageBackend::Local(storage) => {
+                storage.upload_stream(org_id, evidence_id, filename, content_type, stream).await
+            }
+            StorageBackend::S3(storage) => {
+                storage.upload_stream(org_id, evidence_id, filename, content_type, stream).await
+            }
+        }
+    }
+
     /// Download a file from storage
     pub async fn download_evidence(&self, key: &str) -> AppResult<(Vec<u8>, String)> {
-        match &self.backend {
-            StorageBackend::Local(storage) => storage.download(key).await,
-            StorageBackend::S3(storage) => storage.download(key).await,
+        // Enforce the per-org egress quota before streaming anything, using the
+        // object's declared size rather than the decrypted payload length.
+        if let Some(limiter) = &self.limiter {
+            if let Ok((org_id, _, _)) = parse_evidence_key(key) {
+                let size = match &self.backend {
+                    StorageBackend::Local(storage) => storage.object_size(key).await?,
+                    StorageBackend::S3(storage) => storage.object_size(key).await?,
+                };
+                limiter.check_and_add(org_id, size).await?;
+            }
+        }
+
+        let (data, content_type) = match &self.backend {
+            StorageBackend::Local(storage) => storage.download(key).await?,
+            StorageBackend::S3(storage) => storage.download(key).await?,
+        };
+        match &self.cipher {
+            Some(cipher) => Ok((cipher.open(&data)?, content_type)),
+            None => Ok((data, content_type)),
         }
     }
 
+    /// Returns true when evidence is encrypted at rest. Callers that would
+    /// otherwise hand out a raw S3 presigned URL must instead route the
+    /// download through the API (which decrypts), because the stored object is
+    /// ciphertext.
+    pub fn is_encrypted(&self) -> bool {
+        self.cipher.is_some()
+    }
+
     /// Delete a file from storage
     pub async fn delete_evidence(&self, key: &str) -> AppResult<()> {
         match &self.backend {
@@ -386,12 +791,56 @@ impl StorageClient {
     /// For S3: returns a presigned URL
     /// For local: returns an API path that serves the file
     pub async fn get_presigned_download_url(&self, key: &str) -> AppResult<String> {
+        // When objects are encrypted the S3 copy is ciphertext, so a raw
+        // presigned URL would hand out undecryptable bytes. Route through the
+        // API download endpoint (which decrypts) in that case.
+        if self.is_encrypted() {
+            return Ok(format!("/api/v1/storage/download/{}", key));
+        }
         match &self.backend {
-            StorageBackend::Local(storage) => storage.get_download_url(key),
+            StorageBackend::Local(storage) => {
+                let url = storage.get_download_url(key)?;
+                Ok(self.sign_local_url(key, url))
+            }
             StorageBackend::S3(storage) => storage.get_presigned_download_url(key).await,
         }
     }
 
+    /// Append an HMAC-signed, expiring token to a local download URL. When no
+    /// signing secret is configured the URL is returned unchanged (legacy
+    /// behavior).
+    fn sign_local_url(&self, key: &str, url: String) -> String {
+        match &self.download_token_secret {
+            Some(secret) => {
+                let expires = now_unix() + DOWNLOAD_TOKEN_TTL.as_secs();
+                let sig = download_token_tag(secret, key, expires);
+                format!("{}?expires={}&sig={}", url, expires, sig)
+            }
+            None => url,
+        }
+    }
+
+    /// Verify a signed download token for `key`. Recomputes the HMAC tag in
+    /// constant time and rejects expired tokens. When no signing secret is
+    /// configured this is a no-op (unsigned legacy URLs are accepted).
+    pub fn verify_download_token(&self, key: &str, expires: u64, sig: &str) -> AppResult<()> {
+        let secret = match &self.download_token_secret {
+            Some(secret) => secret,
+            None => return Ok(()),
+        };
+
+        if expires < now_unix() {
+            return Err(AppError::Unauthorized("Download token expired".to_string()));
+        }
+
+        let expected = download_token_tag(secret, key, expires);
+        if constant_time_eq(expected.as_bytes(), sig.as_bytes()) {
+            Ok(())
+        } else {
+            Err(AppError::Unauthorized("Invalid download token".to_string()))
+        }
+    }
+
     /// Get a URL for uploading a file
     /// For S3: returns a presigned URL
     /// For local: returns an API path for upload
@@ -410,6 +859,65 @@ impl StorageClient {
         }
     }
 
+    /// List every evidence key currently held by this backend.
+    pub async fn list_evidence_keys(&self) -> AppResult<Vec<String>> {
+        match &self.backend {
+            StorageBackend::Local(storage) => storage.list_evidence_keys().await,
+            StorageBackend::S3(storage) => storage.list_evidence_keys().await,
+        }
+    }
+
+    /// Migrate all evidence objects from this backend into `target` (e.g. when
+    /// an org switches local disk ⇄ S3). Each object is streamed to the
+    /// destination under the same relative key and verified present before the
+    /// source copy is optionally deleted. The migration is resumable: keys
+    /// already present on the target are skipped, so a re-run after a partial
+    /// failure only copies what is missing.
+    pub async fn migrate_to(
+        &self,
+        target: &StorageConfig,
+        delete_source: bool,
+    ) -> AppResult<MigrationReport> {
+        let destination = StorageClient::new(target).await?;
+        let keys = self.list_evidence_keys().await?;
+
+        let mut report = MigrationReport::default();
+        for key in keys {
+            if destination.file_exists(&key).await {
+                report.skipped += 1;
+                continue;
+            }
+
+            match self.copy_key(&destination, &key).await {
+                Ok(()) if destination.file_exists(&key).await => {
+                    report.copied += 1;
+                    if delete_source {
+                        // Best-effort: a failed source delete leaves a
+                        // harmless duplicate, not data loss.
+                        let _ = self.delete_evidence(&key).await;
+                    }
+                }
+                _ => {
+                    report.failed += 1;
+                    report.failed_keys.push(key);
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Copy a single object between backends, reusing the streaming download and
+    /// re-deriving the evidence coordinates from the canonical key layout.
+    async fn copy_key(&self, destination: &StorageClient, key: &str) -> AppResult<()> {
+        let (org_id, evidence_id, filename) = parse_evidence_key(key)?;
+        let (data, content_type) = self.download_evidence(key).await?;
+        destination
+            .upload_evidence(org_id, evidence_id, &filename, &content_type, data)
+            .await?;
+        Ok(())
+    }
+
     /// Returns true if using local storage
     pub fn is_local(&self) -> bool {
         matches!(&self.backend, StorageBackend::Local(_))
@@ -420,3 +928,90 @@ impl StorageClient {
         matches!(&self.backend, StorageBackend::S3(_))
     }
 }
+
+// ==================== Migration ====================
+
+/// Counts returned by [`StorageClient::migrate_to`].
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct MigrationReport {
+    /// Objects streamed to and verified on the target backend.
+    pub copied: u64,
+    /// Objects already present on the target and left untouched.
+    pub skipped: u64,
+    /// Objects that could not be copied or verified.
+    pub failed: u64,
+    /// Keys that failed, for operator follow-up.
+    pub failed_keys: Vec<String>,
+}
+
+/// Decompose an evidence key (`orgs/{org}/evidence/{evidence}/{filename}`) back
+/// into its components so a copied object can be re-uploaded under the same path.
+fn parse_evidence_key(key: &str) -> AppResult<(Uuid, Uuid, String)> {
+    let parts: Vec<&str> = key.splitn(5, '/').collect();
+    match parts.as_slice() {
+        ["orgs", org, "evidence", evidence, filename] => {
+            let org_id = Uuid::parse_str(org)
+                .map_err(|_| AppError::BadRequest(format!("Invalid org id in key: {}", key)))?;
+            let evidence_id = Uuid::parse_str(evidence)
+                .map_err(|_| AppError::BadRequest(format!("Invalid evidence id in key: {}", key)))?;
+            Ok((org_id, evidence_id, (*filename).to_string()))
+        }
+        _ => Err(AppError::BadRequest(format!("Unrecognized evidence key layout: {}", key))),
+    }
+}
+
+// ==================== Download token signing ====================
+
+/// Current wall-clock time as Unix seconds.
+fn now_unix() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Compute the hex-encoded HMAC-SHA256 claim tag over `(key, expires)`,
+/// modeled on Git LFS servers' HMAC claim tags.
+fn download_token_tag(secret: &str, key: &str, expires: u64) -> String {
+    let message = format!("download:{}:{}", key, expires);
+    hex_encode(&hmac_sha256(secret.as_bytes(), message.as_bytes()))
+}
+
+/// Constant-time byte comparison to avoid leaking the tag via timing.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut diff = 0u8;
+    for (x, y) in a.iter().zip(b.iter()) {
+        diff |= x ^ y;
+    }
+    diff == 0
+}
+
+/// Encode bytes as a lowercase hex string.
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn download_token_roundtrip() {
+        let secret = "server-secret";
+        let key = "orgs/a/evidence/b/report.pdf";
+        let expires = now_unix() + 3600;
+        let sig = download_token_tag(secret, key, expires);
+        assert!(constant_time_eq(
+            download_token_tag(secret, key, expires).as_bytes(),
+            sig.as_bytes()
+        ));
+        // A different key must not verify against the same tag.
+        assert!(!constant_time_eq(
+            download_token_tag(secret, "orgs/a/evidence/b/other.pdf", expires).as_bytes(),
+            sig.as_bytes()
+        ));
+    }
+}
@@ -3,9 +3,36 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
+use serde::Serialize;
 use serde_json::json;
 use std::fmt;
 
+/// One field-level validation failure, e.g. `{ "field": "due_date", "reason": "must be in the future" }`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct FieldError {
+    pub field: String,
+    pub reason: String,
+}
+
+/// Schema-only mirror of the JSON body `AppError::into_response` writes.
+/// Never constructed at runtime - exists so the OpenAPI spec has a shared
+/// error schema to reference from every handler's non-2xx responses.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct ErrorResponse {
+    pub error: String,
+    pub code: String,
+    pub details: Option<Vec<FieldError>>,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            reason: reason.into(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum AppError {
     BadRequest(String),
@@ -18,7 +45,22 @@ pub enum AppError {
     RedisError(redis::RedisError),
     SearchError(String),
     ValidationError(String),
+    /// Validation failure with per-field detail, surfaced as `details` in
+    /// the JSON body so clients (e.g. the vendor portal) can highlight the
+    /// offending fields instead of parsing `error`.
+    ValidationFailed(String, Vec<FieldError>),
     ExternalServiceError(String),
+    /// Device-flow polling hasn't completed yet - the caller should keep
+    /// polling no faster than the `interval` it was given.
+    DeviceAuthorizationPending(String),
+    /// The caller is polling faster than the granted interval.
+    DeviceSlowDown(String),
+    /// The user denied the device authorization request at the IdP.
+    DeviceAccessDenied(String),
+    /// The device code expired before the user completed authorization.
+    DeviceTokenExpired(String),
+    /// A rate/egress quota (e.g. the per-org download limiter) was exceeded.
+    QuotaExceeded(String),
 }
 
 impl fmt::Display for AppError {
@@ -34,27 +76,61 @@ impl fmt::Display for AppError {
             AppError::RedisError(err) => write!(f, "Redis Error: {}", err),
             AppError::SearchError(msg) => write!(f, "Search Error: {}", msg),
             AppError::ValidationError(msg) => write!(f, "Validation Error: {}", msg),
+            AppError::ValidationFailed(msg, _) => write!(f, "Validation Error: {}", msg),
             AppError::ExternalServiceError(msg) => write!(f, "External Service Error: {}", msg),
+            AppError::DeviceAuthorizationPending(msg) => write!(f, "Authorization Pending: {}", msg),
+            AppError::DeviceSlowDown(msg) => write!(f, "Slow Down: {}", msg),
+            AppError::DeviceAccessDenied(msg) => write!(f, "Access Denied: {}", msg),
+            AppError::DeviceTokenExpired(msg) => write!(f, "Device Token Expired: {}", msg),
+            AppError::QuotaExceeded(msg) => write!(f, "Quota Exceeded: {}", msg),
         }
     }
 }
 
 impl std::error::Error for AppError {}
 
+impl AppError {
+    /// Stable, machine-readable code for this error, drawn from a fixed
+    /// catalog. Clients should branch on this instead of string-matching
+    /// `error` (the human-readable message may be reworded at any time).
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::BadRequest(_) => "bad_request",
+            AppError::Unauthorized(_) => "unauthorized",
+            AppError::Forbidden(_) => "forbidden",
+            AppError::NotFound(_) => "not_found",
+            AppError::Conflict(_) => "conflict",
+            AppError::InternalServerError(_) => "internal_error",
+            AppError::DatabaseError(_) => "database_error",
+            AppError::RedisError(_) => "cache_error",
+            AppError::SearchError(_) => "search_error",
+            AppError::ValidationError(_) | AppError::ValidationFailed(_, _) => "validation_failed",
+            AppError::ExternalServiceError(_) => "external_service_error",
+            AppError::DeviceAuthorizationPending(_) => "device_authorization_pending",
+            AppError::DeviceSlowDown(_) => "device_slow_down",
+            AppError::DeviceAccessDenied(_) => "device_access_denied",
+            AppError::DeviceTokenExpired(_) => "device_token_expired",
+            AppError::QuotaExceeded(_) => "quota_exceeded",
+        }
+    }
+}
+
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
-        let (status, error_message) = match self {
-            AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
-            AppError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg),
-            AppError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg),
-            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
-            AppError::Conflict(msg) => (StatusCode::CONFLICT, msg),
-            AppError::InternalServerError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
+        let code = self.code();
+        let (status, error_message, details) = match self {
+            AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg, None),
+            AppError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg, None),
+            AppError::Forbidden(msg) => (StatusCode::FORBIDDEN, msg, None),
+            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg, None),
+            AppError::Conflict(msg) => (StatusCode::CONFLICT, msg, None),
+            AppError::InternalServerError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg, None),
             AppError::DatabaseError(err) => {
                 tracing::error!("Database error: {:?}", err);
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     "Database error occurred".to_string(),
+                    None,
                 )
             }
             AppError::RedisError(err) => {
@@ -62,6 +138,7 @@ impl IntoResponse for AppError {
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     "Cache error occurred".to_string(),
+                    None,
                 )
             }
             AppError::SearchError(err) => {
@@ -69,20 +146,34 @@ impl IntoResponse for AppError {
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     "Search error occurred".to_string(),
+                    None,
                 )
             }
-            AppError::ValidationError(msg) => (StatusCode::BAD_REQUEST, msg),
+            AppError::ValidationError(msg) => (StatusCode::BAD_REQUEST, msg, None),
+            AppError::ValidationFailed(msg, fields) => {
+                (StatusCode::BAD_REQUEST, msg, Some(fields))
+            }
             AppError::ExternalServiceError(msg) => {
                 tracing::error!("External service error: {}", msg);
                 (
                     StatusCode::BAD_GATEWAY,
                     format!("External service error: {}", msg),
+                    None,
                 )
             }
+            // Device flow polling states, given distinct status codes so
+            // callers can branch without parsing the error body.
+            AppError::DeviceAuthorizationPending(msg) => (StatusCode::ACCEPTED, msg, None),
+            AppError::DeviceSlowDown(msg) => (StatusCode::TOO_MANY_REQUESTS, msg, None),
+            AppError::DeviceAccessDenied(msg) => (StatusCode::FORBIDDEN, msg, None),
+            AppError::DeviceTokenExpired(msg) => (StatusCode::GONE, msg, None),
+            AppError::QuotaExceeded(msg) => (StatusCode::TOO_MANY_REQUESTS, msg, None),
         };
 
         let body = Json(json!({
             "error": error_message,
+            "code": code,
+            "details": details,
         }));
 
         (status, body).into_response()
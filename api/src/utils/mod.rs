@@ -1,5 +1,9 @@
+pub mod crypto;
 pub mod encryption;
 pub mod error;
+pub mod rrule;
 
+pub use crypto::hmac_sha256;
 pub use encryption::{EncryptionError, EncryptionService};
-pub use error::{AppError, AppResult};
+pub use error::{AppError, AppResult, ErrorResponse, FieldError};
+pub use rrule::RRule;
@@ -0,0 +1,418 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+
+/// A parsed subset of an RFC 5545 `RRULE` string.
+///
+/// Supports `FREQ=(DAILY|WEEKLY|MONTHLY|YEARLY)`, `INTERVAL`, `COUNT`,
+/// `UNTIL` (UTC timestamp), `BYDAY` (optionally ordinal-prefixed, e.g.
+/// `2TU` or `-1FR`), `BYMONTHDAY`, and `BYMONTH`. Anything outside this
+/// subset is rejected by [`RRule::parse`] rather than silently ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RRule {
+    pub freq: Freq,
+    pub interval: i32,
+    pub count: Option<i32>,
+    pub until: Option<DateTime<Utc>>,
+    pub by_day: Vec<ByDay>,
+    pub by_month_day: Vec<i32>,
+    pub by_month: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Freq {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// A `BYDAY` entry: a weekday, optionally prefixed with an ordinal (e.g.
+/// `2TU` = second Tuesday of the period, `-1FR` = last Friday).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ByDay {
+    pub ordinal: Option<i32>,
+    pub weekday: Weekday,
+}
+
+impl RRule {
+    /// Parse an RRULE string such as `FREQ=MONTHLY;BYDAY=-1FR` or
+    /// `FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE,FR;COUNT=10`.
+    pub fn parse(rrule: &str) -> Result<Self, String> {
+        let mut freq: Option<Freq> = None;
+        let mut interval = 1;
+        let mut count = None;
+        let mut until = None;
+        let mut by_day = Vec::new();
+        let mut by_month_day = Vec::new();
+        let mut by_month = Vec::new();
+
+        for part in rrule.trim().trim_start_matches("RRULE:").split(';') {
+            let part = part.trim();
+            if part.is_empty() {
+                continue;
+            }
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("malformed RRULE component: {part}"))?;
+
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    freq = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Freq::Daily,
+                        "WEEKLY" => Freq::Weekly,
+                        "MONTHLY" => Freq::Monthly,
+                        "YEARLY" => Freq::Yearly,
+                        other => return Err(format!("unsupported FREQ: {other}")),
+                    });
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse::<i32>()
+                        .map_err(|_| format!("invalid INTERVAL: {value}"))?;
+                    if interval < 1 {
+                        return Err("INTERVAL must be at least 1".to_string());
+                    }
+                }
+                "COUNT" => {
+                    count = Some(
+                        value
+                            .parse::<i32>()
+                            .map_err(|_| format!("invalid COUNT: {value}"))?,
+                    );
+                }
+                "UNTIL" => {
+                    until = Some(parse_until(value)?);
+                }
+                "BYDAY" => {
+                    for entry in value.split(',') {
+                        by_day.push(parse_by_day(entry)?);
+                    }
+                }
+                "BYMONTHDAY" => {
+                    for entry in value.split(',') {
+                        let dom = entry
+                            .parse::<i32>()
+                            .map_err(|_| format!("invalid BYMONTHDAY: {entry}"))?;
+                        if !(-31..=31).contains(&dom) || dom == 0 {
+                            return Err(format!("BYMONTHDAY out of range: {dom}"));
+                        }
+                        by_month_day.push(dom);
+                    }
+                }
+                "BYMONTH" => {
+                    for entry in value.split(',') {
+                        let m = entry
+                            .parse::<u32>()
+                            .map_err(|_| format!("invalid BYMONTH: {entry}"))?;
+                        if !(1..=12).contains(&m) {
+                            return Err(format!("BYMONTH out of range: {m}"));
+                        }
+                        by_month.push(m);
+                    }
+                }
+                other => return Err(format!("unsupported RRULE component: {other}")),
+            }
+        }
+
+        if count.is_some() && until.is_some() {
+            return Err("RRULE cannot set both COUNT and UNTIL".to_string());
+        }
+
+        Ok(Self {
+            freq: freq.ok_or_else(|| "RRULE is missing FREQ".to_string())?,
+            interval,
+            count,
+            until,
+            by_day,
+            by_month_day,
+            by_month,
+        })
+    }
+
+    /// Expand occurrences starting from `dtstart`, yielding timestamps
+    /// strictly after `dtstart` in order until `count`/`until` is hit or
+    /// `max_periods` periods (including the one containing `dtstart`) have
+    /// been scanned — a safety bound against rules like
+    /// `FREQ=YEARLY;BYMONTHDAY=31;BYMONTH=2` that never match.
+    pub fn expand(&self, dtstart: DateTime<Utc>, max_periods: usize) -> Vec<DateTime<Utc>> {
+        let mut occurrences = Vec::new();
+        let mut period_start = dtstart;
+
+        for _ in 0..=max_periods {
+            for candidate in self.candidates_in_period(period_start) {
+                if candidate <= dtstart {
+                    continue;
+                }
+                if let Some(until) = self.until {
+                    if candidate > until {
+                        return occurrences;
+                    }
+                }
+                occurrences.push(candidate);
+                if let Some(count) = self.count {
+                    if occurrences.len() as i32 >= count {
+                        return occurrences;
+                    }
+                }
+            }
+            period_start = self.advance_period(period_start);
+        }
+
+        occurrences
+    }
+
+    /// First occurrence strictly after `after`, or `None` if expansion
+    /// exhausts its search window without finding one.
+    pub fn next_after(&self, dtstart: DateTime<Utc>, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.expand(dtstart.max(after), 1000)
+            .into_iter()
+            .find(|occ| *occ > after)
+    }
+
+    fn advance_period(&self, from: DateTime<Utc>) -> DateTime<Utc> {
+        match self.freq {
+            Freq::Daily => from + Duration::days(self.interval as i64),
+            Freq::Weekly => from + Duration::weeks(self.interval as i64),
+            Freq::Monthly => add_months(from, self.interval),
+            Freq::Yearly => add_months(from, self.interval * 12),
+        }
+    }
+
+    /// All matching dates within the period anchored at `period_start`,
+    /// in chronological order.
+    fn candidates_in_period(&self, period_start: DateTime<Utc>) -> Vec<DateTime<Utc>> {
+        if !self.by_day.is_empty() {
+            return self.by_day_candidates(period_start);
+        }
+        if !self.by_month_day.is_empty() {
+            return self.by_month_day_candidates(period_start);
+        }
+        vec![period_start]
+    }
+
+    fn by_day_candidates(&self, period_start: DateTime<Utc>) -> Vec<DateTime<Utc>> {
+        let mut out: Vec<DateTime<Utc>> = match self.freq {
+            Freq::Monthly | Freq::Yearly => {
+                let months = if self.freq == Freq::Yearly && !self.by_month.is_empty() {
+                    self.by_month.clone()
+                } else {
+                    vec![period_start.month()]
+                };
+                months
+                    .into_iter()
+                    .flat_map(|month| {
+                        self.by_day
+                            .iter()
+                            .filter_map(|bd| nth_weekday_of_month(period_start.year(), month, *bd))
+                    })
+                    .collect()
+            }
+            Freq::Weekly | Freq::Daily => self
+                .by_day
+                .iter()
+                .filter_map(|bd| weekday_in_week_of(period_start, bd.weekday))
+                .collect(),
+        };
+        out.sort();
+        out
+    }
+
+    fn by_month_day_candidates(&self, period_start: DateTime<Utc>) -> Vec<DateTime<Utc>> {
+        let months = if !self.by_month.is_empty() {
+            self.by_month.clone()
+        } else {
+            vec![period_start.month()]
+        };
+        let mut out: Vec<DateTime<Utc>> = months
+            .into_iter()
+            .flat_map(|month| {
+                self.by_month_day
+                    .iter()
+                    .filter_map(move |dom| nth_day_of_month(period_start.year(), month, *dom))
+            })
+            .collect();
+        out.sort();
+        out
+    }
+}
+
+fn parse_until(value: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    // Basic RFC 5545 form: YYYYMMDDTHHMMSSZ
+    chrono::NaiveDateTime::parse_from_str(value.trim_end_matches('Z'), "%Y%m%dT%H%M%S")
+        .map(|naive| DateTime::from_naive_utc_and_offset(naive, Utc))
+        .map_err(|_| format!("invalid UNTIL timestamp: {value}"))
+}
+
+fn parse_by_day(entry: &str) -> Result<ByDay, String> {
+    let entry = entry.trim();
+    let split_at = entry
+        .find(|c: char| c.is_ascii_alphabetic())
+        .ok_or_else(|| format!("invalid BYDAY: {entry}"))?;
+    let (ord_part, day_part) = entry.split_at(split_at);
+    let ordinal = if ord_part.is_empty() {
+        None
+    } else {
+        Some(
+            ord_part
+                .parse::<i32>()
+                .map_err(|_| format!("invalid BYDAY ordinal: {entry}"))?,
+        )
+    };
+    let weekday = match day_part.to_ascii_uppercase().as_str() {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        other => return Err(format!("invalid BYDAY weekday: {other}")),
+    };
+    Ok(ByDay { ordinal, weekday })
+}
+
+fn add_months(from: DateTime<Utc>, months: i32) -> DateTime<Utc> {
+    let total_months = from.year() as i64 * 12 + (from.month() as i64 - 1) + months as i64;
+    let year = total_months.div_euclid(12) as i32;
+    let month = total_months.rem_euclid(12) as u32 + 1;
+    day_at(year, month, from.day(), from).unwrap_or(from)
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    let (next_year, next_month) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|d| d.pred_opt())
+        .map(|d| d.day())
+        .unwrap_or(28)
+}
+
+fn day_at(year: i32, month: u32, day: u32, reference: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let actual_day = day.min(days_in_month(year, month)).max(1);
+    NaiveDate::from_ymd_opt(year, month, actual_day)
+        .and_then(|d| d.and_time(reference.time()).and_local_timezone(Utc).single())
+}
+
+/// Resolve a `BYMONTHDAY` value (positive = day-of-month, negative = counted
+/// back from month end) to a concrete date, using `reference`'s time-of-day.
+fn nth_day_of_month(year: i32, month: u32, dom: i32) -> Option<DateTime<Utc>> {
+    let len = days_in_month(year, month) as i32;
+    let day = if dom > 0 { dom } else { len + dom + 1 };
+    if day < 1 || day > len {
+        return None;
+    }
+    NaiveDate::from_ymd_opt(year, month, day as u32)
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .and_then(|d| d.and_local_timezone(Utc).single())
+}
+
+/// Resolve a `BYDAY` ordinal (e.g. `2` for "2nd Tuesday", `-1` for "last
+/// Friday") within `(year, month)` to a concrete date.
+fn nth_weekday_of_month(year: i32, month: u32, by_day: ByDay) -> Option<DateTime<Utc>> {
+    let len = days_in_month(year, month);
+    let matching_days: Vec<u32> = (1..=len)
+        .filter(|&day| {
+            NaiveDate::from_ymd_opt(year, month, day)
+                .map(|d| d.weekday() == by_day.weekday)
+                .unwrap_or(false)
+        })
+        .collect();
+
+    let day = match by_day.ordinal {
+        None => *matching_days.first()?,
+        Some(n) if n > 0 => *matching_days.get(n as usize - 1)?,
+        Some(n) => *matching_days.get(matching_days.len().checked_sub((-n) as usize)?)?,
+    };
+
+    NaiveDate::from_ymd_opt(year, month, day)
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .and_then(|d| d.and_local_timezone(Utc).single())
+}
+
+/// The date within the same Sunday-start week as `period_start` that falls
+/// on `weekday` (used for `FREQ=WEEKLY;BYDAY=...`).
+fn weekday_in_week_of(period_start: DateTime<Utc>, weekday: Weekday) -> Option<DateTime<Utc>> {
+    let days_from_week_start = period_start.weekday().num_days_from_sunday() as i64;
+    let week_start = period_start - Duration::days(days_from_week_start);
+    let offset = weekday.num_days_from_sunday() as i64;
+    Some(week_start + Duration::days(offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn ts(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, 0, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn rejects_missing_freq() {
+        assert!(RRule::parse("INTERVAL=2").is_err());
+    }
+
+    #[test]
+    fn rejects_unsupported_component() {
+        assert!(RRule::parse("FREQ=DAILY;BYSETPOS=1").is_err());
+    }
+
+    #[test]
+    fn rejects_count_and_until_together() {
+        assert!(RRule::parse("FREQ=DAILY;COUNT=5;UNTIL=20270101T000000Z").is_err());
+    }
+
+    #[test]
+    fn daily_with_interval() {
+        let rule = RRule::parse("FREQ=DAILY;INTERVAL=3").unwrap();
+        let occ = rule.expand(ts(2026, 1, 1), 10);
+        assert_eq!(occ[0], ts(2026, 1, 4));
+        assert_eq!(occ[1], ts(2026, 1, 7));
+    }
+
+    #[test]
+    fn monthly_last_friday() {
+        // "last business day" approximated as last Friday of each month.
+        let rule = RRule::parse("FREQ=MONTHLY;BYDAY=-1FR").unwrap();
+        let occ = rule.expand(ts(2026, 1, 1), 3);
+        assert_eq!(occ[0], ts(2026, 1, 30));
+        assert_eq!(occ[1], ts(2026, 2, 27));
+    }
+
+    #[test]
+    fn monthly_second_tuesday() {
+        let rule = RRule::parse("FREQ=MONTHLY;BYDAY=2TU").unwrap();
+        let occ = rule.expand(ts(2026, 1, 1), 2);
+        assert_eq!(occ[0], ts(2026, 1, 13));
+    }
+
+    #[test]
+    fn quarterly_via_interval_and_bymonth() {
+        let rule = RRule::parse("FREQ=YEARLY;INTERVAL=1;BYMONTH=3,6,9,12;BYMONTHDAY=-1").unwrap();
+        let occ = rule.expand(ts(2026, 1, 1), 2);
+        assert_eq!(occ[0], ts(2026, 3, 31));
+        assert_eq!(occ[1], ts(2026, 6, 30));
+    }
+
+    #[test]
+    fn respects_count() {
+        let rule = RRule::parse("FREQ=WEEKLY;BYDAY=MO;COUNT=2").unwrap();
+        let occ = rule.expand(ts(2026, 1, 1), 10);
+        assert_eq!(occ.len(), 2);
+    }
+
+    #[test]
+    fn respects_until() {
+        let rule = RRule::parse("FREQ=DAILY;UNTIL=20260103T000000Z").unwrap();
+        let occ = rule.expand(ts(2026, 1, 1), 10);
+        assert_eq!(occ, vec![ts(2026, 1, 2), ts(2026, 1, 3)]);
+    }
+
+    #[test]
+    fn next_after_skips_to_requested_point() {
+        let rule = RRule::parse("FREQ=DAILY").unwrap();
+        let next = rule.next_after(ts(2026, 1, 1), ts(2026, 1, 5)).unwrap();
+        assert_eq!(next, ts(2026, 1, 6));
+    }
+}
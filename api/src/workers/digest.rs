@@ -0,0 +1,135 @@
+use chrono::{Datelike, Duration, Timelike, Utc};
+use std::sync::Arc;
+use tokio::time::interval;
+
+use crate::models::collaboration::DigestCandidate;
+use crate::services::CollaborationService;
+
+/// Worker that delivers scheduled daily and weekly digests, landing each in the
+/// recipient's local morning rather than at UTC midnight.
+pub struct DigestWorker {
+    collaboration: CollaborationService,
+    check_interval_secs: u64,
+}
+
+impl DigestWorker {
+    pub fn new(collaboration: CollaborationService) -> Self {
+        Self {
+            collaboration,
+            // Quarter-hourly so timezones with sub-hour offsets still line up
+            // with the target send hour.
+            check_interval_secs: 15 * 60,
+        }
+    }
+
+    /// Start the scheduler loop.
+    pub async fn run(self: Arc<Self>) {
+        tracing::info!("Starting digest scheduler");
+
+        let mut interval = interval(std::time::Duration::from_secs(self.check_interval_secs));
+
+        loop {
+            interval.tick().await;
+
+            for digest_type in ["daily", "weekly"] {
+                if let Err(e) = self.process(digest_type).await {
+                    tracing::error!("Error processing {} digests: {}", digest_type, e);
+                }
+            }
+        }
+    }
+
+    /// Emit digests for every candidate whose local clock is currently at their
+    /// configured send hour (and, for weekly digests, send day).
+    async fn process(&self, digest_type: &str) -> crate::utils::AppResult<()> {
+        let candidates = self.collaboration.get_digest_candidates(digest_type).await?;
+        let now = Utc::now();
+
+        // Don't re-emit within the cadence window even though the send hour spans
+        // several ticks.
+        let min_gap = match digest_type {
+            "weekly" => Duration::days(6),
+            _ => Duration::hours(20),
+        };
+        let default_window = match digest_type {
+            "weekly" => Duration::days(7),
+            _ => Duration::days(1),
+        };
+
+        for candidate in candidates {
+            if !self.should_send(digest_type, &candidate, now, min_gap) {
+                continue;
+            }
+
+            let period_start = candidate.last_digest_at.unwrap_or(now - default_window);
+
+            let content = self
+                .collaboration
+                .create_digest_content(
+                    candidate.organization_id,
+                    candidate.user_id,
+                    period_start,
+                    now,
+                )
+                .await?;
+
+            if content.notifications.is_empty()
+                && content.tasks_due.is_empty()
+                && content.tasks_overdue.is_empty()
+                && content.mentions.is_empty()
+                && content.comments.is_empty()
+            {
+                continue;
+            }
+
+            self.collaboration
+                .create_digest(
+                    candidate.organization_id,
+                    candidate.user_id,
+                    digest_type,
+                    period_start,
+                    now,
+                    content,
+                )
+                .await?;
+
+            tracing::info!(
+                "Created {} digest for user {}",
+                digest_type,
+                candidate.user_id
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Whether this candidate's local clock currently crosses their send hour
+    /// and enough time has passed since their last digest.
+    fn should_send(
+        &self,
+        digest_type: &str,
+        candidate: &DigestCandidate,
+        now: chrono::DateTime<Utc>,
+        min_gap: Duration,
+    ) -> bool {
+        let local = now + Duration::seconds(candidate.tz_offset_seconds as i64);
+
+        if local.hour() as i32 != candidate.send_hour {
+            return false;
+        }
+
+        // Weekly digests only fire on the configured local weekday (0 = Sunday,
+        // defaulting to Monday when unset).
+        if digest_type == "weekly" {
+            let send_dow = candidate.send_day_of_week.unwrap_or(1);
+            if local.weekday().num_days_from_sunday() as i32 != send_dow {
+                return false;
+            }
+        }
+
+        match candidate.last_digest_at {
+            Some(last) => now - last >= min_gap,
+            None => true,
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use crate::cache::CacheClient;
+use crate::models::FrameworkWithRequirements;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::sync::Arc;
+use tokio::time::interval;
+use uuid::Uuid;
+
+/// Limits a repair pass to a subset of the data.
+#[derive(Debug, Clone, Copy)]
+pub enum RepairScope {
+    /// Scrub every framework and org.
+    All,
+    /// Only the named framework and its requirements.
+    Framework(Uuid),
+    /// Only data belonging to the named organization.
+    Org(Uuid),
+}
+
+/// Summary of what a repair pass observed and (unless dry-run) fixed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RepairStats {
+    /// Cache entries deleted (stale lists plus count-mismatched snapshots).
+    pub caches_purged: u64,
+    /// Requirements whose `parent_id` pointed at a missing/deleted row.
+    pub orphans_found: u64,
+    /// Orphaned requirements re-parented to the top level.
+    pub orphans_fixed: u64,
+    /// Control mappings referencing a nonexistent requirement.
+    pub mappings_dangling: u64,
+}
+
+/// Background scrubber that reconciles integrity problems the write-through
+/// cache and CRUD guards can miss: stale list caches, orphaned `parent_id`
+/// links, dangling control mappings, and count-drifted framework snapshots.
+pub struct FrameworkMaintenanceWorker {
+    db: PgPool,
+    cache: CacheClient,
+    check_interval_secs: u64,
+}
+
+impl FrameworkMaintenanceWorker {
+    pub fn new(db: PgPool, cache: CacheClient) -> Self {
+        Self {
+            db,
+            cache,
+            check_interval_secs: 3600, // Hourly scrub
+        }
+    }
+
+    /// Start the periodic scrub loop.
+    pub async fn run(self: Arc<Self>) {
+        tracing::info!("Starting framework maintenance worker");
+
+        let mut interval = interval(std::time::Duration::from_secs(self.check_interval_secs));
+
+        loop {
+            interval.tick().await;
+
+            match self.run_repair(RepairScope::All, false).await {
+                Ok(stats) => tracing::info!("Framework scrub complete: {:?}", stats),
+                Err(e) => tracing::error!("Framework scrub failed: {}", e),
+            }
+        }
+    }
+
+    /// Run a single repair pass over `scope`.
+    ///
+    /// When `dry_run` is set the pass only reports problems and leaves the data
+    /// untouched; otherwise orphans are re-parented, dangling mappings removed,
+    /// and drifted cache entries purged.
+    pub async fn run_repair(
+        &self,
+        scope: RepairScope,
+        dry_run: bool,
+    ) -> Result<RepairStats, sqlx::Error> {
+        let mut stats = RepairStats::default();
+
+        self.repair_orphans(scope, dry_run, &mut stats).await?;
+        self.repair_dangling_mappings(scope, dry_run, &mut stats).await?;
+        self.repair_cache_drift(scope, dry_run, &mut stats).await?;
+
+        Ok(stats)
+    }
+
+    /// Re-parent requirements whose `parent_id` references a missing or
+    /// soft-deleted row so the live tree never has a broken edge.
+    async fn repair_orphans(
+        &self,
+        scope: RepairScope,
+        dry_run: bool,
+        stats: &mut RepairStats,
+    ) -> Result<(), sqlx::Error> {
+        let orphans: Vec<(Uuid,)> = sqlx::query_as(
+            r#"
+            SELECT c.id
+            FROM framework_requirements c
+            LEFT JOIN framework_requirements p ON c.parent_id = p.id AND NOT p.deleted
+            WHERE c.parent_id IS NOT NULL
+              AND NOT c.deleted
+              AND p.id IS NULL
+              AND ($1::uuid IS NULL OR c.framework_id = $1)
+            "#,
+        )
+        .bind(Self::framework_filter(scope))
+        .fetch_all(&self.db)
+        .await?;
+
+        stats.orphans_found += orphans.len() as u64;
+
+        if dry_run || orphans.is_empty() {
+            return Ok(());
+        }
+
+        let ids: Vec<Uuid> = orphans.into_iter().map(|(id,)| id).collect();
+        let fixed = sqlx::query(
+            "UPDATE framework_requirements SET parent_id = NULL WHERE id = ANY($1)",
+        )
+        .bind(&ids)
+        .execute(&self.db)
+        .await?
+        .rows_affected();
+        stats.orphans_fixed += fixed;
+
+        Ok(())
+    }
+
+    /// Delete control mappings that point at a requirement which no longer
+    /// exists (a hard-deleted row from before soft-delete landed).
+    async fn repair_dangling_mappings(
+        &self,
+        scope: RepairScope,
+        dry_run: bool,
+        stats: &mut RepairStats,
+    ) -> Result<(), sqlx::Error> {
+        let org = match scope {
+            RepairScope::Org(id) => Some(id),
+            _ => None,
+        };
+
+        let dangling: Vec<(Uuid,)> = sqlx::query_as(
+            r#"
+            SELECT crm.id
+            FROM control_requirement_mappings crm
+            LEFT JOIN framework_requirements fr ON crm.framework_requirement_id = fr.id
+            LEFT JOIN controls c ON crm.control_id = c.id
+            WHERE fr.id IS NULL
+              AND ($1::uuid IS NULL OR c.organization_id = $1)
+            "#,
+        )
+        .bind(org)
+        .fetch_all(&self.db)
+        .await?;
+
+        stats.mappings_dangling += dangling.len() as u64;
+
+        if dry_run || dangling.is_empty() {
+            return Ok(());
+        }
+
+        let ids: Vec<Uuid> = dangling.into_iter().map(|(id,)| id).collect();
+        sqlx::query("DELETE FROM control_requirement_mappings WHERE id = ANY($1)")
+            .bind(&ids)
+            .execute(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Purge list caches and any `FrameworkWithRequirements` snapshot whose
+    /// cached `requirement_count` no longer matches the live row count.
+    async fn repair_cache_drift(
+        &self,
+        scope: RepairScope,
+        dry_run: bool,
+        stats: &mut RepairStats,
+    ) -> Result<(), sqlx::Error> {
+        let frameworks: Vec<(Uuid,)> = sqlx::query_as(
+            "SELECT id FROM frameworks WHERE NOT deleted AND ($1::uuid IS NULL OR id = $1)",
+        )
+        .bind(Self::framework_filter(scope))
+        .fetch_all(&self.db)
+        .await?;
+
+        for (framework_id,) in frameworks {
+            let with_reqs_key = format!("framework:with_reqs:{}", framework_id);
+            let cached: Option<FrameworkWithRequirements> = self
+                .cache
+                .get(&with_reqs_key)
+                .await
+                .unwrap_or(None);
+
+            if let Some(snapshot) = cached {
+                let live_count: (i64,) = sqlx::query_as(
+                    "SELECT COUNT(*) FROM framework_requirements WHERE framework_id = $1 AND NOT deleted",
+                )
+                .bind(framework_id)
+                .fetch_one(&self.db)
+                .await?;
+
+                if snapshot.requirement_count != live_count.0 {
+                    stats.caches_purged += 1;
+                    if !dry_run {
+                        let _ = self.cache.delete(&with_reqs_key).await;
+                    }
+                }
+            }
+        }
+
+        // The shared list cache can go stale after a TTL race; bust it wholesale.
+        if !dry_run {
+            let _ = self.cache.delete_pattern("frameworks:list:*").await;
+            stats.caches_purged += 1;
+        }
+
+        Ok(())
+    }
+
+    /// The framework id a scope restricts to, or `None` for unscoped passes.
+    fn framework_filter(scope: RepairScope) -> Option<Uuid> {
+        match scope {
+            RepairScope::Framework(id) => Some(id),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,15 @@
+pub mod control_testing;
+pub mod digest;
+pub mod framework_maintenance;
+pub mod notification_queue;
+pub mod retention;
+pub mod siem_stream;
+pub mod task_recurrence;
+
+pub use control_testing::ControlTestingWorker;
+pub use digest::DigestWorker;
+pub use framework_maintenance::{FrameworkMaintenanceWorker, RepairScope, RepairStats};
+pub use notification_queue::NotificationQueueWorker;
+pub use retention::RetentionWorker;
+pub use siem_stream::SiemStreamWorker;
+pub use task_recurrence::TaskRecurrenceWorker;
@@ -0,0 +1,285 @@
+use serde_json::Value as JsonValue;
+use sqlx::PgPool;
+use std::sync::Arc;
+use tokio::time::interval;
+use uuid::Uuid;
+
+use crate::services::chat_delivery::{
+    get_teams_token, open_mattermost_dm, open_slack_dm, post_mattermost_message,
+    post_slack_message, post_teams_message,
+};
+
+/// A leased outbound-message row awaiting delivery.
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct QueuedMessage {
+    id: Uuid,
+    organization_id: Uuid,
+    target_kind: String,
+    channel: String,
+    thread_ref: Option<String>,
+    payload: JsonValue,
+    attempts: i32,
+}
+
+/// An active Mattermost server row as needed for delivery.
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct MattermostServerToken {
+    base_url: String,
+    access_token: String,
+    bot_user_id: Option<String>,
+}
+
+/// Worker that drains the durable `notification_queue`, delivering messages to
+/// connected Slack workspaces, Teams tenants, and Mattermost servers with
+/// at-least-once semantics.
+pub struct NotificationQueueWorker {
+    db: PgPool,
+    poll_interval_secs: u64,
+    /// How long a lease is held before a crashed delivery becomes eligible again.
+    lease_secs: i64,
+    /// Messages are dropped once this many delivery attempts have failed.
+    max_attempts: i32,
+    batch_size: i64,
+}
+
+impl NotificationQueueWorker {
+    pub fn new(db: PgPool) -> Self {
+        Self {
+            db,
+            poll_interval_secs: 10,
+            lease_secs: 60,
+            max_attempts: 6,
+            batch_size: 50,
+        }
+    }
+
+    /// Start the worker loop.
+    pub async fn run(self: Arc<Self>) {
+        tracing::info!("Starting notification queue worker");
+
+        let mut interval = interval(std::time::Duration::from_secs(self.poll_interval_secs));
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.drain().await {
+                tracing::error!("Error draining notification queue: {}", e);
+            }
+        }
+    }
+
+    /// Lease and deliver one batch of pending messages.
+    async fn drain(&self) -> Result<(), sqlx::Error> {
+        let leased = self.lease_batch().await?;
+
+        if leased.is_empty() {
+            return Ok(());
+        }
+
+        tracing::debug!("Leased {} queued messages for delivery", leased.len());
+
+        for message in leased {
+            match self.deliver(&message).await {
+                Ok(()) => {
+                    sqlx::query("DELETE FROM notification_queue WHERE id = $1")
+                        .bind(message.id)
+                        .execute(&self.db)
+                        .await?;
+                }
+                Err(e) => {
+                    self.record_failure(&message, &e).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Atomically lease a batch of eligible rows by stamping `leased_at = NOW()`.
+    ///
+    /// A row is eligible when it has never been leased, or its lease has expired
+    /// (for a crashed worker), or its backoff window — encoded as a future
+    /// `leased_at` — has elapsed.
+    async fn lease_batch(&self) -> Result<Vec<QueuedMessage>, sqlx::Error> {
+        sqlx::query_as::<_, QueuedMessage>(
+            r#"
+            UPDATE notification_queue
+            SET leased_at = NOW()
+            WHERE id IN (
+                SELECT id FROM notification_queue
+                WHERE leased_at IS NULL
+                   OR leased_at < NOW() - make_interval(secs => $1)
+                ORDER BY leased_at NULLS FIRST, created_at
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, organization_id, target_kind, channel, thread_ref, payload, attempts
+            "#,
+        )
+        .bind(self.lease_secs as f64)
+        .bind(self.batch_size)
+        .fetch_all(&self.db)
+        .await
+    }
+
+    /// Deliver a single message to its target provider.
+    async fn deliver(
+        &self,
+        message: &QueuedMessage,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let text = message
+            .payload
+            .get("text")
+            .and_then(|v| v.as_str())
+            .ok_or("Queued message payload missing 'text'")?;
+
+        match message.target_kind.as_str() {
+            "slack" => {
+                let token = self
+                    .active_token(message.organization_id, "slack_workspaces")
+                    .await?
+                    .ok_or("No active Slack workspace for organization")?;
+                post_slack_message(&token, &message.channel, text).await?;
+            }
+            "slack_dm" => {
+                // `channel` holds the recipient's Slack user id; open a DM first.
+                let token = self
+                    .active_token(message.organization_id, "slack_workspaces")
+                    .await?
+                    .ok_or("No active Slack workspace for organization")?;
+                let dm_channel = open_slack_dm(&token, &message.channel).await?;
+                post_slack_message(&token, &dm_channel, text).await?;
+            }
+            "teams" => {
+                let token = get_teams_token(&self.db, message.organization_id).await?;
+                let team_id = message
+                    .thread_ref
+                    .as_deref()
+                    .ok_or("Teams message missing team id in thread_ref")?;
+                post_teams_message(&token, team_id, &message.channel, text).await?;
+            }
+            "mattermost" => {
+                let server = self
+                    .active_mattermost_server(message.organization_id)
+                    .await?
+                    .ok_or("No active Mattermost server for organization")?;
+                post_mattermost_message(&server.base_url, &server.access_token, &message.channel, text)
+                    .await?;
+            }
+            "mattermost_dm" => {
+                // `channel` holds the recipient's Mattermost user id; open a DM first.
+                let server = self
+                    .active_mattermost_server(message.organization_id)
+                    .await?
+                    .ok_or("No active Mattermost server for organization")?;
+                let bot_user_id = server
+                    .bot_user_id
+                    .as_deref()
+                    .ok_or("Mattermost server has no bot user id for opening DMs")?;
+                let dm_channel = open_mattermost_dm(
+                    &server.base_url,
+                    &server.access_token,
+                    bot_user_id,
+                    &message.channel,
+                )
+                .await?;
+                post_mattermost_message(&server.base_url, &server.access_token, &dm_channel, text)
+                    .await?;
+            }
+            other => return Err(format!("Unknown notification target kind: {}", other).into()),
+        }
+
+        Ok(())
+    }
+
+    /// Fetch the freshest active bot token for the org from the given table.
+    async fn active_token(
+        &self,
+        org_id: Uuid,
+        table: &str,
+    ) -> Result<Option<String>, sqlx::Error> {
+        // `table` is a fixed internal literal, never user input.
+        let query = format!(
+            "SELECT access_token FROM {} WHERE organization_id = $1 AND status = 'active' \
+             ORDER BY updated_at DESC LIMIT 1",
+            table
+        );
+        sqlx::query_scalar::<_, String>(&query)
+            .bind(org_id)
+            .fetch_optional(&self.db)
+            .await
+    }
+
+    /// Fetch the freshest active Mattermost server for the org. Unlike Slack's
+    /// fixed API host, a self-hosted Mattermost server's `base_url` must be
+    /// looked up alongside its token.
+    async fn active_mattermost_server(
+        &self,
+        org_id: Uuid,
+    ) -> Result<Option<MattermostServerToken>, sqlx::Error> {
+        sqlx::query_as::<_, MattermostServerToken>(
+            r#"
+            SELECT base_url, access_token, bot_user_id
+            FROM mattermost_servers
+            WHERE organization_id = $1 AND status = 'active'
+            ORDER BY updated_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(org_id)
+        .fetch_optional(&self.db)
+        .await
+    }
+
+    /// Increment the attempt counter and either drop the message or defer it
+    /// with exponential backoff.
+    async fn record_failure(
+        &self,
+        message: &QueuedMessage,
+        error: &(dyn std::error::Error + Send + Sync),
+    ) -> Result<(), sqlx::Error> {
+        let attempts = message.attempts + 1;
+
+        if attempts >= self.max_attempts {
+            tracing::error!(
+                "Dropping queued message {} after {} attempts: {}",
+                message.id,
+                attempts,
+                error
+            );
+            sqlx::query("DELETE FROM notification_queue WHERE id = $1")
+                .bind(message.id)
+                .execute(&self.db)
+                .await?;
+            return Ok(());
+        }
+
+        // Defer the next attempt by pushing leased_at into the future. Backoff
+        // doubles each attempt, capped at one hour.
+        let backoff_secs = (self.lease_secs * 2_i64.pow(attempts as u32)).min(3600);
+
+        tracing::warn!(
+            "Delivery of queued message {} failed (attempt {}), retrying in {}s: {}",
+            message.id,
+            attempts,
+            backoff_secs,
+            error
+        );
+
+        sqlx::query(
+            r#"
+            UPDATE notification_queue
+            SET attempts = $2,
+                leased_at = NOW() + make_interval(secs => $3)
+            WHERE id = $1
+            "#,
+        )
+        .bind(message.id)
+        .bind(attempts)
+        .bind(backoff_secs as f64)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+}
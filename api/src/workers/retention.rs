@@ -0,0 +1,67 @@
+use sqlx::PgPool;
+use std::sync::Arc;
+use tokio::time::interval;
+use uuid::Uuid;
+
+use crate::services::RetentionService;
+
+/// Background scheduler that runs the configured data-retention purge for
+/// every organization once a day. The manual purge-preview/purge-run routes
+/// still exist for an administrator to force a single organization on demand.
+pub struct RetentionWorker {
+    db: PgPool,
+    retention: RetentionService,
+    check_interval_secs: u64,
+}
+
+impl RetentionWorker {
+    pub fn new(db: PgPool, retention: RetentionService) -> Self {
+        Self {
+            db,
+            retention,
+            // Purge eligibility is measured in days, so once a day is plenty.
+            check_interval_secs: 24 * 60 * 60,
+        }
+    }
+
+    /// Start the periodic scheduler loop.
+    pub async fn run(self: Arc<Self>) {
+        tracing::info!("Starting data retention purge worker");
+
+        let mut interval = interval(std::time::Duration::from_secs(self.check_interval_secs));
+
+        loop {
+            interval.tick().await;
+
+            let orgs = match self.all_org_ids().await {
+                Ok(orgs) => orgs,
+                Err(e) => {
+                    tracing::error!("Failed to list organizations for retention purge: {}", e);
+                    continue;
+                }
+            };
+
+            for org_id in orgs {
+                match self.retention.run_purge(org_id, false).await {
+                    Ok(summary) => {
+                        let total: i64 = summary.results.iter().map(|r| r.affected_count).sum();
+                        if total > 0 {
+                            tracing::info!(
+                                "Retention purge for org {} removed {} row(s)",
+                                org_id,
+                                total
+                            );
+                        }
+                    }
+                    Err(e) => tracing::error!("Retention purge failed for org {}: {}", org_id, e),
+                }
+            }
+        }
+    }
+
+    async fn all_org_ids(&self) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar::<_, Uuid>("SELECT id FROM organizations")
+            .fetch_all(&self.db)
+            .await
+    }
+}
@@ -0,0 +1,255 @@
+use sqlx::PgPool;
+use std::sync::Arc;
+use tokio::time::interval;
+use uuid::Uuid;
+
+use crate::services::siem_delivery::post_siem_event;
+
+/// A leased outbound SIEM event row awaiting delivery.
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct QueuedSiemEvent {
+    id: Uuid,
+    config_id: Uuid,
+    payload: serde_json::Value,
+    attempts: i32,
+}
+
+/// The export configuration fields needed to deliver and to drive its
+/// circuit breaker.
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct StreamTarget {
+    webhook_url: Option<String>,
+    export_type: String,
+    circuit_breaker_state: String,
+    circuit_breaker_failure_count: i32,
+    circuit_breaker_opened_at: Option<chrono::DateTime<chrono::Utc>>,
+    circuit_breaker_threshold: i32,
+    circuit_breaker_reset_ms: i32,
+}
+
+/// Worker that drains the durable `siem_stream_queue`, delivering each
+/// ActivityLog entry to its configured SIEM endpoint with at-least-once
+/// semantics and a per-configuration circuit breaker (reusing the
+/// open/half-open/closed states from `integration::CircuitBreakerState`) so a
+/// persistently-failing endpoint stops being hammered.
+pub struct SiemStreamWorker {
+    db: PgPool,
+    poll_interval_secs: u64,
+    lease_secs: i64,
+    max_attempts: i32,
+    batch_size: i64,
+}
+
+impl SiemStreamWorker {
+    pub fn new(db: PgPool) -> Self {
+        Self {
+            db,
+            poll_interval_secs: 5,
+            lease_secs: 30,
+            max_attempts: 8,
+            batch_size: 100,
+        }
+    }
+
+    /// Start the worker loop.
+    pub async fn run(self: Arc<Self>) {
+        tracing::info!("Starting SIEM stream worker");
+
+        let mut interval = interval(std::time::Duration::from_secs(self.poll_interval_secs));
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.drain().await {
+                tracing::error!("Error draining SIEM stream queue: {}", e);
+            }
+        }
+    }
+
+    async fn drain(&self) -> Result<(), sqlx::Error> {
+        let leased = self.lease_batch().await?;
+
+        if leased.is_empty() {
+            return Ok(());
+        }
+
+        tracing::debug!("Leased {} SIEM stream event(s) for delivery", leased.len());
+
+        for event in leased {
+            match self.deliver(&event).await {
+                Ok(()) => {
+                    sqlx::query("DELETE FROM siem_stream_queue WHERE id = $1")
+                        .bind(event.id)
+                        .execute(&self.db)
+                        .await?;
+                    self.record_success(event.config_id).await?;
+                }
+                Err(e) => {
+                    tracing::warn!("SIEM stream delivery {} failed: {}", event.id, e);
+                    self.record_failure(&event, &e).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn lease_batch(&self) -> Result<Vec<QueuedSiemEvent>, sqlx::Error> {
+        sqlx::query_as::<_, QueuedSiemEvent>(
+            r#"
+            UPDATE siem_stream_queue
+            SET leased_at = NOW()
+            WHERE id IN (
+                SELECT id FROM siem_stream_queue
+                WHERE leased_at IS NULL
+                   OR leased_at < NOW() - make_interval(secs => $1)
+                ORDER BY leased_at NULLS FIRST, created_at
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, config_id, payload, attempts
+            "#,
+        )
+        .bind(self.lease_secs as f64)
+        .bind(self.batch_size)
+        .fetch_all(&self.db)
+        .await
+    }
+
+    async fn deliver(
+        &self,
+        event: &QueuedSiemEvent,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let target = sqlx::query_as::<_, StreamTarget>(
+            r#"
+            SELECT webhook_url, export_type, circuit_breaker_state, circuit_breaker_failure_count,
+                   circuit_breaker_opened_at, circuit_breaker_threshold, circuit_breaker_reset_ms
+            FROM audit_export_configurations
+            WHERE id = $1
+            "#,
+        )
+        .bind(event.config_id)
+        .fetch_optional(&self.db)
+        .await?
+        .ok_or("SIEM export configuration no longer exists")?;
+
+        if self.circuit_is_open(&target) {
+            return Err("Circuit breaker open for this SIEM endpoint".into());
+        }
+
+        if target.export_type != "webhook" {
+            return Err(format!(
+                "Streaming delivery not supported for export_type '{}'",
+                target.export_type
+            )
+            .into());
+        }
+
+        let url = target
+            .webhook_url
+            .as_deref()
+            .ok_or("SIEM export configuration missing webhook_url")?;
+
+        post_siem_event(url, &event.payload).await?;
+        Ok(())
+    }
+
+    /// Half-open allows a single trial delivery through once the reset window
+    /// has elapsed; open otherwise blocks delivery outright.
+    fn circuit_is_open(&self, target: &StreamTarget) -> bool {
+        if target.circuit_breaker_state != "open" {
+            return false;
+        }
+
+        match target.circuit_breaker_opened_at {
+            Some(opened_at) => {
+                let reset_after = chrono::Duration::milliseconds(target.circuit_breaker_reset_ms as i64);
+                chrono::Utc::now() < opened_at + reset_after
+            }
+            None => false,
+        }
+    }
+
+    async fn record_success(&self, config_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE audit_export_configurations
+            SET circuit_breaker_state = 'closed',
+                circuit_breaker_failure_count = 0,
+                circuit_breaker_opened_at = NULL,
+                total_events_exported = COALESCE(total_events_exported, 0) + 1,
+                last_export_at = NOW(),
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(config_id)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn record_failure(
+        &self,
+        event: &QueuedSiemEvent,
+        error: &(dyn std::error::Error + Send + Sync),
+    ) -> Result<(), sqlx::Error> {
+        let attempts = event.attempts + 1;
+
+        sqlx::query(
+            r#"
+            UPDATE audit_export_configurations
+            SET circuit_breaker_failure_count = circuit_breaker_failure_count + 1,
+                circuit_breaker_state = CASE
+                    WHEN circuit_breaker_failure_count + 1 >= circuit_breaker_threshold THEN 'open'
+                    ELSE circuit_breaker_state
+                END,
+                circuit_breaker_opened_at = CASE
+                    WHEN circuit_breaker_failure_count + 1 >= circuit_breaker_threshold THEN NOW()
+                    ELSE circuit_breaker_opened_at
+                END,
+                total_failures = COALESCE(total_failures, 0) + 1,
+                last_error = $2,
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(event.config_id)
+        .bind(error.to_string())
+        .execute(&self.db)
+        .await?;
+
+        if attempts >= self.max_attempts {
+            tracing::error!(
+                "Dropping SIEM stream event {} after {} attempts: {}",
+                event.id,
+                attempts,
+                error
+            );
+            sqlx::query("DELETE FROM siem_stream_queue WHERE id = $1")
+                .bind(event.id)
+                .execute(&self.db)
+                .await?;
+            return Ok(());
+        }
+
+        let backoff_secs = (self.lease_secs * 2_i64.pow(attempts as u32)).min(3600);
+
+        sqlx::query(
+            r#"
+            UPDATE siem_stream_queue
+            SET attempts = $2,
+                leased_at = NOW() + make_interval(secs => $3)
+            WHERE id = $1
+            "#,
+        )
+        .bind(event.id)
+        .bind(attempts)
+        .bind(backoff_secs as f64)
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+use tokio::time::interval;
+
+use crate::services::TaskService;
+
+/// Background scheduler that materializes the next occurrence of every
+/// recurring task whose `next_occurrence_at` has come due, across all
+/// organizations. The manual `/tasks/recurring/process` route still exists
+/// for an administrator to force a single organization forward on demand.
+pub struct TaskRecurrenceWorker {
+    task: TaskService,
+    check_interval_secs: u64,
+}
+
+impl TaskRecurrenceWorker {
+    pub fn new(task: TaskService) -> Self {
+        Self {
+            task,
+            // Occurrences are due at day/week/month granularity, so a coarse
+            // poll is plenty and keeps this cheap to run continuously.
+            check_interval_secs: 15 * 60,
+        }
+    }
+
+    /// Start the periodic scheduler loop.
+    pub async fn run(self: Arc<Self>) {
+        tracing::info!("Starting task recurrence worker");
+
+        let mut interval = interval(std::time::Duration::from_secs(self.check_interval_secs));
+
+        loop {
+            interval.tick().await;
+
+            match self.task.process_due_recurring_tasks().await {
+                Ok(created_count) if created_count > 0 => {
+                    tracing::info!("Created {} recurring task occurrence(s)", created_count);
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Failed to process recurring tasks: {}", e),
+            }
+        }
+    }
+}